@@ -0,0 +1,206 @@
+//! The pumpfun copy buy from detection to send: decoding the target's instruction and
+//! trade event, arranging accounts for our wallet, quoting the buy and building and
+//! signing the transaction, with a stub sender in place of the network.
+//!
+//! The payload is synthetic but shaped like a geyser update of a pumpfun buy: a
+//! 16-account `buy` instruction and its `TradeEvent` self-CPI.
+
+use carbon_core::{
+    deserialize::{ArrangeAccounts, CarbonDeserialize},
+    instruction::InstructionDecoder,
+};
+use carbon_pumpfun_decoder::{
+    PumpfunDecoder,
+    instructions::{
+        PumpfunInstruction,
+        buy::{Buy, BuyInstructionAccounts},
+        trade_event::TradeEvent,
+    },
+};
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use pumpfun_monitor::{
+    instructions::buy_ix::{BUY_DISCRIMINATOR, BuyExactInInstructionAccountsExt},
+    utils::{
+        PumpfunFees, TradeEventView, associated_token_address_with_program_id,
+        bonding_curve_token_quote, build_and_sign, pumpfun_buy_cost,
+    },
+};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{Signer, keypair::Keypair},
+    system_instruction,
+};
+
+/// A recorded-like pumpfun buy: the raw instruction and the trade event self-CPI data.
+struct Payload {
+    instruction: Instruction,
+    event_cpi: Vec<u8>,
+}
+
+fn payload() -> Payload {
+    let mut data = BUY_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&3_500_000_000_000u64.to_le_bytes());
+    data.extend_from_slice(&105_000_000u64.to_le_bytes());
+
+    let instruction = Instruction {
+        program_id: carbon_pumpfun_decoder::PROGRAM_ID,
+        accounts: (0..16)
+            .map(|i| AccountMeta::new(Pubkey::new_unique(), i == 6))
+            .collect(),
+        data,
+    };
+
+    let mint = instruction.accounts[2].pubkey;
+    let user = instruction.accounts[6].pubkey;
+
+    let mut body = Vec::with_capacity(217);
+    body.extend_from_slice(mint.as_ref());
+    body.extend_from_slice(&100_000_000u64.to_le_bytes()); // sol_amount
+    body.extend_from_slice(&3_500_000_000_000u64.to_le_bytes()); // token_amount
+    body.push(1); // is_buy
+    body.extend_from_slice(user.as_ref());
+    body.extend_from_slice(&1_750_000_000i64.to_le_bytes()); // timestamp
+    body.extend_from_slice(&31_000_000_000u64.to_le_bytes()); // virtual_sol_reserves
+    body.extend_from_slice(&1_038_000_000_000_000u64.to_le_bytes()); // virtual_token_reserves
+    body.extend_from_slice(&1_000_000_000u64.to_le_bytes()); // real_sol_reserves
+    body.extend_from_slice(&758_000_000_000_000u64.to_le_bytes()); // real_token_reserves
+    body.extend_from_slice(Pubkey::new_unique().as_ref()); // fee_recipient
+    body.extend_from_slice(&95u64.to_le_bytes()); // fee_basis_points
+    body.extend_from_slice(&950_000u64.to_le_bytes()); // fee
+    body.extend_from_slice(Pubkey::new_unique().as_ref()); // creator
+    body.extend_from_slice(&5u64.to_le_bytes()); // creator_fee_basis_points
+    body.extend_from_slice(&50_000u64.to_le_bytes()); // creator_fee
+
+    Payload {
+        instruction,
+        event_cpi: [TradeEvent::DISCRIMINATOR, &body].concat(),
+    }
+}
+
+const FEES: PumpfunFees = PumpfunFees {
+    fee_basis_points: 95,
+    creator_fee_basis_points: 5,
+};
+
+/// The target's accounts with our wallet as the buyer.
+fn arrange(instruction: &Instruction, wallet: &Pubkey) -> Option<BuyInstructionAccounts> {
+    let mut arranged = Buy::arrange_accounts(&instruction.accounts)?;
+
+    arranged.user = *wallet;
+    arranged.associated_user =
+        associated_token_address_with_program_id(wallet, &arranged.mint, &arranged.token_program);
+
+    Some(arranged)
+}
+
+/// Quoted buy of `sol_amount` against the event's reserves, with 10% slippage.
+fn quote(event: &TradeEvent, sol_amount: u64) -> Buy {
+    let amount = bonding_curve_token_quote(
+        sol_amount,
+        event.virtual_sol_reserves,
+        event.virtual_token_reserves,
+        true,
+    );
+    let cost = pumpfun_buy_cost(
+        amount,
+        event.virtual_sol_reserves,
+        event.virtual_token_reserves,
+        &FEES,
+    );
+
+    Buy {
+        amount,
+        max_sol_cost: cost + cost / 10,
+    }
+}
+
+/// Compute budget and a sender tip around the buy, like the senders add.
+fn transaction_ixs(
+    arranged: &BuyInstructionAccounts,
+    buy: Buy,
+    payer: &Pubkey,
+) -> Vec<Instruction> {
+    vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(120_000),
+        ComputeBudgetInstruction::set_compute_unit_price(1_000_000),
+        arranged.get_create_idempotent_ata_ix(),
+        arranged.get_buy_ix(buy),
+        system_instruction::transfer(payer, &Pubkey::new_from_array([7; 32]), 1_000_000),
+    ]
+}
+
+/// Stands in for the sender: the payload is built but nothing leaves the process.
+fn stub_send(encoded_tx: String) -> usize {
+    black_box(encoded_tx).len()
+}
+
+fn detection_to_send(c: &mut Criterion) {
+    let payload = payload();
+    let wallet = Keypair::new();
+    let fee_payer = Keypair::new();
+    let blockhash = Hash::new_unique();
+
+    let decoded = PumpfunDecoder
+        .decode_instruction(&payload.instruction)
+        .expect("payload decodes");
+    let PumpfunInstruction::Buy(_) = decoded.data else {
+        panic!("payload is a buy");
+    };
+    let event = TradeEventView::from_cpi(&payload.event_cpi)
+        .expect("payload has a trade event")
+        .to_event();
+    let arranged = arrange(&payload.instruction, &wallet.pubkey()).expect("payload arranges");
+
+    let mut group = c.benchmark_group("detection_to_send");
+
+    group.bench_function("decode_instruction", |b| {
+        b.iter(|| PumpfunDecoder.decode_instruction(black_box(&payload.instruction)))
+    });
+
+    group.bench_function("decode_trade_event", |b| {
+        b.iter(|| {
+            TradeEventView::from_cpi(black_box(&payload.event_cpi)).map(|view| view.to_event())
+        })
+    });
+
+    group.bench_function("arrange", |b| {
+        b.iter(|| arrange(black_box(&payload.instruction), &wallet.pubkey()))
+    });
+
+    group.bench_function("quote", |b| {
+        b.iter(|| quote(black_box(&event), 100_000_000))
+    });
+
+    group.bench_function("build_and_sign", |b| {
+        b.iter(|| {
+            let ixs = transaction_ixs(&arranged, quote(&event, 100_000_000), &fee_payer.pubkey());
+            build_and_sign(ixs, blockhash, None, &[&fee_payer, &wallet])
+        })
+    });
+
+    group.bench_function("end_to_end", |b| {
+        b.iter(|| {
+            let instruction = black_box(&payload.instruction);
+
+            let decoded = PumpfunDecoder.decode_instruction(instruction)?;
+            let PumpfunInstruction::Buy(_) = decoded.data else {
+                return None;
+            };
+            let event = TradeEventView::from_cpi(black_box(&payload.event_cpi))?.to_event();
+            let arranged = arrange(instruction, &wallet.pubkey())?;
+            let ixs = transaction_ixs(&arranged, quote(&event, 100_000_000), &fee_payer.pubkey());
+
+            let encoded_tx = build_and_sign(ixs, blockhash, None, &[&fee_payer, &wallet]).ok()?;
+
+            Some(stub_send(encoded_tx))
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, detection_to_send);
+criterion_main!(benches);
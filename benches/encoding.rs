@@ -0,0 +1,80 @@
+//! Base64 encoding of a signed copy buy, as done on every send and retry, with the
+//! SIMD encoder `build_and_sign` uses against the crates used before it.
+
+use base64_simd::{Out, STANDARD};
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use pumpfun_monitor::utils::encode_transaction;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::{VersionedMessage, v0::Message},
+    pubkey::Pubkey,
+    signer::{Signer, keypair::Keypair},
+    system_instruction,
+    transaction::VersionedTransaction,
+};
+
+/// A transaction the size of a copy buy: compute budget, the buy and a sender tip.
+fn copy_buy_transaction() -> VersionedTransaction {
+    let payer = Keypair::new();
+    let wallet = Keypair::new();
+
+    let mut accounts = vec![AccountMeta::new(wallet.pubkey(), true)];
+    accounts.extend((0..15).map(|_| AccountMeta::new(Pubkey::new_unique(), false)));
+
+    let ixs = [
+        ComputeBudgetInstruction::set_compute_unit_limit(120_000),
+        ComputeBudgetInstruction::set_compute_unit_price(1_000_000),
+        Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts,
+            data: vec![7; 24],
+        },
+        system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000_000),
+    ];
+
+    let message = Message::try_compile(&payer.pubkey(), &ixs, &[], Hash::new_unique()).unwrap();
+
+    VersionedTransaction::try_new(VersionedMessage::V0(message), &[&payer, &wallet]).unwrap()
+}
+
+fn encode_transaction_bytes(c: &mut Criterion) {
+    let serialized = bincode::serialize(&copy_buy_transaction()).unwrap();
+    let mut output = vec![0u8; STANDARD.encoded_length(serialized.len())];
+    let mut group = c.benchmark_group("encode_transaction_bytes");
+
+    group.bench_function("base64", |b| {
+        b.iter(|| base64::encode(black_box(&serialized)))
+    });
+
+    group.bench_function("bs64", |b| b.iter(|| bs64::encode(black_box(&serialized))));
+
+    group.bench_function("base64_simd", |b| {
+        b.iter(|| {
+            STANDARD
+                .encode_as_str(black_box(&serialized), Out::from_slice(&mut output))
+                .len()
+        })
+    });
+
+    group.finish();
+}
+
+fn serialize_and_encode(c: &mut Criterion) {
+    let transaction = copy_buy_transaction();
+    let mut group = c.benchmark_group("serialize_and_encode");
+
+    group.bench_function("allocating", |b| {
+        b.iter(|| bs64::encode(&bincode::serialize(black_box(&transaction)).unwrap()))
+    });
+
+    group.bench_function("encode_transaction", |b| {
+        b.iter(|| encode_transaction(black_box(&transaction)).unwrap())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, encode_transaction_bytes, serialize_and_encode);
+criterion_main!(benches);
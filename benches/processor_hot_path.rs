@@ -0,0 +1,72 @@
+//! Per-event work of the pumpfun processor: arranging the decoded instruction's accounts
+//! and collecting the transaction's account keys, before and after dropping the
+//! instruction clone and pre-allocating the key vector.
+
+use carbon_core::{deserialize::ArrangeAccounts, instruction::DecodedInstruction};
+use carbon_pumpfun_decoder::instructions::{PumpfunInstruction, buy::Buy};
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey};
+
+fn buy_instruction() -> DecodedInstruction<PumpfunInstruction> {
+    DecodedInstruction {
+        program_id: carbon_pumpfun_decoder::PROGRAM_ID,
+        data: PumpfunInstruction::Buy(Buy {
+            amount: 1_000_000_000,
+            max_sol_cost: 100_000_000,
+        }),
+        accounts: (0..16)
+            .map(|_| AccountMeta::new(Pubkey::new_unique(), false))
+            .collect(),
+    }
+}
+
+fn arrange_buy_accounts(c: &mut Criterion) {
+    let instruction = buy_instruction();
+    let mut group = c.benchmark_group("arrange_buy_accounts");
+
+    group.bench_function("cloned_instruction", |b| {
+        b.iter(|| {
+            let instruction_clone = black_box(&instruction).clone();
+            Buy::arrange_accounts(&instruction_clone.accounts)
+        })
+    });
+
+    group.bench_function("borrowed_instruction", |b| {
+        b.iter(|| Buy::arrange_accounts(&black_box(&instruction).accounts))
+    });
+
+    group.finish();
+}
+
+fn collect_account_keys(c: &mut Criterion) {
+    let static_keys: Vec<Pubkey> = (0..24).map(|_| Pubkey::new_unique()).collect();
+    let writable: Vec<Pubkey> = (0..12).map(|_| Pubkey::new_unique()).collect();
+    let readonly: Vec<Pubkey> = (0..12).map(|_| Pubkey::new_unique()).collect();
+    let mut group = c.benchmark_group("collect_account_keys");
+
+    group.bench_function("growing", |b| {
+        b.iter(|| {
+            let mut account_keys: Vec<Pubkey> = vec![];
+            account_keys.extend(black_box(&static_keys));
+            account_keys.extend(black_box(&writable));
+            account_keys.extend(black_box(&readonly));
+            account_keys
+        })
+    });
+
+    group.bench_function("preallocated", |b| {
+        b.iter(|| {
+            let mut account_keys: Vec<Pubkey> =
+                Vec::with_capacity(static_keys.len() + writable.len() + readonly.len());
+            account_keys.extend(black_box(&static_keys));
+            account_keys.extend(black_box(&writable));
+            account_keys.extend(black_box(&readonly));
+            account_keys
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, arrange_buy_accounts, collect_account_keys);
+criterion_main!(benches);
@@ -0,0 +1,15 @@
+fn main() {
+    // Builds protoc from source, so no system install is needed. SAFETY: the build
+    // script runs single-threaded
+    unsafe { std::env::set_var("PROTOC", protobuf_src::protoc()) };
+
+    tonic_build::configure()
+        .build_client(false)
+        .compile_protos(&["proto/control.proto"], &["proto"])
+        .expect("Failed to compile proto/control.proto");
+
+    tonic_build::configure()
+        .build_server(false)
+        .compile_protos(&["proto/signer.proto"], &["proto"])
+        .expect("Failed to compile proto/signer.proto");
+}
@@ -0,0 +1,20 @@
+use std::{fs, path::PathBuf};
+
+use crate::{config::AUDIT_LOG_PATH, utils::verify_audit_log};
+
+/// Checks the hash chain of the audit log at `path`, `AUDIT_LOG_PATH` by default.
+pub fn verify_audit(path: Option<PathBuf>) -> anyhow::Result<()> {
+    let path = path
+        .or_else(|| AUDIT_LOG_PATH.as_ref().map(PathBuf::from))
+        .ok_or_else(|| anyhow::anyhow!("AUDIT_LOG_PATH is not set, pass the log's path"))?;
+
+    let content = fs::read_to_string(&path)?;
+
+    match verify_audit_log(&content) {
+        Ok(entries) => {
+            println!("{}: {} entries, chain intact", path.display(), entries);
+            Ok(())
+        }
+        Err(e) => anyhow::bail!("{}: chain broken, {}", path.display(), e),
+    }
+}
@@ -0,0 +1,154 @@
+use chrono::{NaiveDate, NaiveTime};
+use solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+    signature::Signature, signer::Signer,
+};
+use std::{collections::HashSet, ops::Range, path::Path, str::FromStr};
+
+use crate::{
+    config::PAPER_SOL_BALANCE, datasource::load_replay_signatures, utils::TARGET_METRICS,
+    wallet::WALLET_POOL,
+};
+
+/// Signatures fetched per `getSignaturesForAddress` page
+const SIGNATURE_PAGE: usize = 1_000;
+
+/// Unix seconds from the start of `from` to the end of `to`, both UTC days.
+pub fn backtest_window(from: NaiveDate, to: NaiveDate) -> anyhow::Result<Range<i64>> {
+    anyhow::ensure!(from <= to, "--from {} is after --to {}", from, to);
+
+    let end = to
+        .succ_opt()
+        .ok_or_else(|| anyhow::anyhow!("--to {} is out of range", to))?;
+
+    Ok(from.and_time(NaiveTime::MIN).and_utc().timestamp()
+        ..end.and_time(NaiveTime::MIN).and_utc().timestamp())
+}
+
+/// Target transactions to backtest, oldest first: those of a journal or capture at
+/// `source`, or else every successful transaction of `targets` in `window`, read from
+/// the RPC, which needs to serve that far back, e.g. an archive node.
+pub async fn backtest_signatures(
+    rpc_client: &RpcClient,
+    source: Option<&Path>,
+    targets: &[Pubkey],
+    window: &Range<i64>,
+) -> anyhow::Result<Vec<Signature>> {
+    if let Some(source) = source {
+        return Ok(load_replay_signatures(source)?);
+    }
+
+    let mut transactions = Vec::new();
+
+    for target in targets {
+        transactions.extend(target_signatures(rpc_client, target, window).await?);
+    }
+
+    // Targets trading the same token interleave, keep them in chain order
+    transactions.sort_by_key(|(slot, _)| *slot);
+
+    let mut seen = HashSet::new();
+
+    Ok(transactions
+        .into_iter()
+        .map(|(_, signature)| signature)
+        .filter(|signature| seen.insert(*signature))
+        .collect())
+}
+
+/// Successful transactions of `target` with a block time in `window`, with their slot.
+async fn target_signatures(
+    rpc_client: &RpcClient,
+    target: &Pubkey,
+    window: &Range<i64>,
+) -> anyhow::Result<Vec<(u64, Signature)>> {
+    let mut signatures = Vec::new();
+    let mut before = None;
+
+    // Pages go newest first, back until the start of the window
+    loop {
+        let page = rpc_client
+            .get_signatures_for_address_with_config(
+                target,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until: None,
+                    limit: Some(SIGNATURE_PAGE),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                },
+            )
+            .await?;
+
+        let Some(last) = page.last() else {
+            break;
+        };
+
+        before = Some(Signature::from_str(&last.signature)?);
+        let reached_start = last.block_time.is_some_and(|time| time < window.start);
+        let exhausted = page.len() < SIGNATURE_PAGE;
+
+        signatures.extend(
+            page.iter()
+                .filter(|status| status.err.is_none())
+                .filter(|status| status.block_time.is_some_and(|time| window.contains(&time)))
+                .filter_map(|status| {
+                    Some((status.slot, Signature::from_str(&status.signature).ok()?))
+                }),
+        );
+
+        if reached_start || exhausted {
+            break;
+        }
+    }
+
+    Ok(signatures)
+}
+
+/// Prints what the backtest's paper trades made per target, as `pnl` does, with the
+/// positions still open and the wallets' virtual SOL at the end. Open positions aren't
+/// valued, the SOL they cost counts against the total.
+pub fn print_backtest_report(replayed: usize) {
+    let sol = |lamports: i64| lamports as f64 / LAMPORTS_PER_SOL as f64;
+    let mut total = 0;
+
+    println!("Backtested {} target transactions", replayed);
+    println!();
+    println!(
+        "{:<44}  {:>9}  {:>12}  {:>9}",
+        "target", "detected", "SOL", "filled"
+    );
+
+    for (target, stats) in TARGET_METRICS.all() {
+        total += stats.sol_flow;
+
+        println!(
+            "{:<44}  {:>9}  {:>+12.4}  {:>9}",
+            target.to_string(),
+            stats.detections,
+            sol(stats.sol_flow),
+            format!(
+                "{}/{}",
+                stats.landed,
+                stats.copies_sent + stats.copies_failed
+            )
+        );
+    }
+
+    println!("{:<44}  {:>9}  {:>+12.4}", "total", "", sol(total));
+    println!();
+    println!("Open positions: {}", WALLET_POOL.positions().len());
+
+    for wallet in WALLET_POOL.wallets() {
+        let balance = WALLET_POOL.balance(&wallet.pubkey()).unwrap_or_default();
+
+        println!(
+            "{:<44}  {:>12.4} SOL (started with {})",
+            wallet.pubkey().to_string(),
+            sol(balance as i64),
+            *PAPER_SOL_BALANCE
+        );
+    }
+}
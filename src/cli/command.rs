@@ -0,0 +1,193 @@
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+use solana_sdk::pubkey::Pubkey;
+use std::path::PathBuf;
+
+use crate::cli::{
+    add_target, config_check, create_keystore, init_wallet, list_targets, print_pnl,
+    print_positions, remove_target, rotate_through_bot, sell_through_bot, sweep_atas, verify_audit,
+};
+
+/// Copies the trades of target wallets on pump.fun, PumpSwap, Raydium and Meteora.
+#[derive(Debug, Parser)]
+#[command(name = "pumpfun-monitor", version, about)]
+pub struct Cli {
+    /// Runs the bot when left out
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Runs the copy trading bot
+    Run {
+        /// Shows a live terminal view instead of the console logs
+        #[arg(long)]
+        tui: bool,
+        /// Sells the open pump.fun curve positions before exiting on SIGINT or SIGTERM
+        #[arg(long)]
+        liquidate_on_exit: bool,
+    },
+    /// Feeds the target transactions of a decision journal, or a capture with one
+    /// signature per line, back through the processors with the senders stubbed out.
+    /// Set JOURNAL_PATH to another file to journal the replay's decisions
+    Replay { journal: PathBuf },
+    /// Paper trades the historical transactions of target wallets over a date range with
+    /// the current settings, and reports how they would have done
+    Backtest {
+        /// Wallet to backtest, repeatable; the followed targets when left out
+        #[arg(long = "target")]
+        targets: Vec<Pubkey>,
+        /// First UTC day, e.g. 2025-01-31
+        #[arg(long)]
+        from: NaiveDate,
+        /// Last UTC day, included
+        #[arg(long)]
+        to: NaiveDate,
+        /// Decision journal or capture to take the transactions from; without it they
+        /// are listed over RPC, which then has to serve the whole range
+        #[arg(long)]
+        source: Option<PathBuf>,
+    },
+    /// Lists the token balances of every trading wallet, read from chain
+    Positions,
+    /// Shows the running bot's realized PnL per target, through its control API
+    Pnl,
+    /// Sells a pump.fun curve position from every wallet, through the running bot's
+    /// control API
+    Sell {
+        mint: Pubkey,
+        /// The code the bot answered a sell over MANUAL_CONFIRM_ABOVE_SOL with
+        #[arg(long)]
+        confirm_code: Option<String>,
+    },
+    /// Closes the empty token accounts of the trading wallets to reclaim their rent
+    SweepAtas {
+        /// Lists the accounts without closing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Inspects the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Manages the wallets followed besides TARGET_WALLET, saved in TARGETS_PATH
+    Targets {
+        #[command(subcommand)]
+        command: TargetsCommand,
+    },
+    /// Manages the encrypted keystore that can hold PRIVATE_KEY
+    Keystore {
+        #[command(subcommand)]
+        command: KeystoreCommand,
+    },
+    /// Inspects the audit log of signed transactions and control calls
+    Audit {
+        #[command(subcommand)]
+        command: AuditCommand,
+    },
+    /// Sets up a new wallet, or manages the trading wallets of the running bot through
+    /// its control API
+    Wallet {
+        #[command(subcommand)]
+        command: WalletCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Validates the .env settings, the RPC and the trading wallet balances
+    Check,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum KeystoreCommand {
+    /// Encrypts a private key, typed at the prompt, into a new keystore file
+    Create { path: PathBuf },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AuditCommand {
+    /// Checks that no entry was modified or removed since it was written
+    Verify {
+        /// Log to check, AUDIT_LOG_PATH when left out
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum WalletCommand {
+    /// Generates a wallet, writes its encrypted keystore to a new file and waits for it
+    /// to be funded
+    Init {
+        path: PathBuf,
+        /// SOL to wait for, BUY_SOL_AMOUNT when left out
+        #[arg(long)]
+        min_sol: Option<f64>,
+        /// Opens the wallet's WSOL account once funded; copies open and close it
+        /// themselves, so it's only needed by tools expecting it
+        #[arg(long)]
+        create_wsol_account: bool,
+    },
+    /// Rotates a trading wallet out for a key or signer typed at the prompt, without
+    /// stopping the bot. The old wallet gets no new buys, and once its positions are
+    /// sold its SOL moves to the new one
+    Rotate {
+        wallet: Pubkey,
+        /// Moves the old wallet's tokens to the new one now instead of waiting for the
+        /// targets to exit
+        #[arg(long)]
+        transfer: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TargetsCommand {
+    /// Lists the followed wallets
+    List,
+    /// Follows another wallet from the next start
+    Add { wallet: Pubkey },
+    /// Stops following a wallet from the next start
+    Remove { wallet: Pubkey },
+}
+
+/// Runs a one-off command. `run`, `replay` and `backtest` are started by the binary
+/// itself.
+pub async fn run_command(command: Command) -> anyhow::Result<()> {
+    match command {
+        Command::Run { .. } | Command::Replay { .. } | Command::Backtest { .. } => {
+            unreachable!("the pipeline is started by main")
+        }
+        Command::Positions => print_positions().await,
+        Command::Pnl => print_pnl().await,
+        Command::Sell { mint, confirm_code } => {
+            sell_through_bot(&mint, confirm_code.as_deref()).await
+        }
+        Command::SweepAtas { dry_run } => sweep_atas(dry_run).await,
+        Command::Config {
+            command: ConfigCommand::Check,
+        } => config_check().await,
+        Command::Targets { command } => match command {
+            TargetsCommand::List => list_targets(),
+            TargetsCommand::Add { wallet } => add_target(wallet),
+            TargetsCommand::Remove { wallet } => remove_target(&wallet),
+        },
+        Command::Keystore {
+            command: KeystoreCommand::Create { path },
+        } => create_keystore(&path),
+        Command::Audit {
+            command: AuditCommand::Verify { path },
+        } => verify_audit(path),
+        Command::Wallet { command } => match command {
+            WalletCommand::Init {
+                path,
+                min_sol,
+                create_wsol_account,
+            } => init_wallet(&path, min_sol, create_wsol_account).await,
+            WalletCommand::Rotate { wallet, transfer } => {
+                rotate_through_bot(&wallet, transfer).await
+            }
+        },
+    }
+}
@@ -0,0 +1,307 @@
+use anyhow::bail;
+use solana_sdk::{
+    bs58, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signer::Signer, signer::keypair::Keypair,
+};
+use std::{env, fs, str::FromStr};
+
+use crate::{
+    config::{
+        BUY_SOL_AMOUNT, CONTROL_API_ADDR, CONTROL_API_TOKEN, CONTROL_GRPC_ADDR, FOLLOWED_VENUES,
+        KEYSTORE_PATH, LOG_ROTATION, LOW_BALANCE_HALT_SOL, LOW_BALANCE_WARN_SOL,
+        PROFIT_SWEEP_ADDRESS, PROFIT_SWEEP_FLOOR_SOL, READ_ONLY, RPC_CLIENT, SecretsSource,
+        TARGETS_PATH, TELEGRAM_BOT_TOKEN, TELEGRAM_COMMANDS, TOP_UP_THRESHOLD_SOL, TRADING_WALLETS,
+        keystore_pubkey,
+    },
+    datasource::{VENUES, load_saved_targets},
+    utils::{LogRotation, get_balances},
+    wallet::{KmsKey, REMOTE_SIGNER_SCHEMES, parse_derivation_path},
+};
+
+/// Findings printed as they are made, counted for the summary.
+#[derive(Default)]
+struct Report {
+    errors: usize,
+    warnings: usize,
+}
+
+impl Report {
+    fn ok(&mut self, message: impl AsRef<str>) {
+        println!("[ok]    {}", message.as_ref());
+    }
+
+    fn warn(&mut self, message: impl AsRef<str>) {
+        self.warnings += 1;
+        println!("[warn]  {}", message.as_ref());
+    }
+
+    fn error(&mut self, message: impl AsRef<str>) {
+        self.errors += 1;
+        println!("[error] {}", message.as_ref());
+    }
+}
+
+fn env_value(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.trim().is_empty())
+}
+
+fn is_keypair(key: &str) -> bool {
+    bs58::decode(key.trim())
+        .into_vec()
+        .is_ok_and(|bytes| Keypair::from_bytes(&bytes).is_ok())
+}
+
+/// A base58 key, or a Ledger, remote signer or KMS key as `TRADING_WALLETS` takes one.
+fn is_wallet(key: &str) -> bool {
+    if key.trim().starts_with("aws-kms:") || key.trim().starts_with("gcp-kms:") {
+        return KmsKey::from_entry(key.trim()).is_ok();
+    }
+
+    if let Some(url) = key.trim().strip_prefix("remote:") {
+        return url
+            .split_once("://")
+            .is_some_and(|(scheme, _)| REMOTE_SIGNER_SCHEMES.contains(&scheme));
+    }
+
+    match key.trim().strip_prefix("ledger") {
+        Some(path) => path
+            .strip_prefix(':')
+            .is_none_or(|path| parse_derivation_path(path).is_ok()),
+        None => is_keypair(key),
+    }
+}
+
+/// Checks the keystore at `path` can be read, without decrypting it.
+fn check_keystore(report: &mut Report, path: &str) -> bool {
+    match fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| keystore_pubkey(&content))
+    {
+        Ok(pubkey) => {
+            report.ok(format!(
+                "PRIVATE_KEY of {} is in the keystore {}",
+                pubkey, path
+            ));
+            true
+        }
+        Err(e) => {
+            report.error(format!("KEYSTORE_PATH {} is unusable: {}", path, e));
+            false
+        }
+    }
+}
+
+/// Validates the settings the bot needs to start, reading the raw values so a bad one
+/// is reported rather than panicking, then checks the RPC and the trading wallet
+/// balances. Fails when any error was found.
+pub async fn config_check() -> anyhow::Result<()> {
+    let mut report = Report::default();
+
+    for name in ["RPC_ENDPOINT", "NOZOMI_API_KEY", "ZERO_SLOT_KEY"] {
+        match env_value(name) {
+            Some(_) => report.ok(format!("{} is set", name)),
+            None => report.error(format!("{} is not set", name)),
+        }
+    }
+
+    match env_value("TARGET_WALLET").map(|wallet| Pubkey::from_str(wallet.trim())) {
+        Some(Ok(target)) => report.ok(format!("Following TARGET_WALLET {}", target)),
+        Some(Err(_)) => report.error("TARGET_WALLET is not a valid wallet address"),
+        None => report.error("TARGET_WALLET is not set"),
+    }
+
+    let saved_targets = load_saved_targets();
+    if !saved_targets.is_empty() {
+        report.ok(format!(
+            "Following {} more wallet(s) from {}",
+            saved_targets.len(),
+            *TARGETS_PATH
+        ));
+    }
+
+    match env_value("BUY_SOL_AMOUNT").map(|amount| amount.trim().parse::<f64>()) {
+        Some(Ok(amount)) if amount > 0.0 => report.ok(format!("Buying {} SOL per copy", amount)),
+        Some(_) => report.error("BUY_SOL_AMOUNT must be a positive number of SOL"),
+        None => report.error("BUY_SOL_AMOUNT is not set"),
+    }
+
+    // The same fallback as TRADING_WALLETS, and PRIVATE_KEY's to the keystore
+    let (keys_name, keys) = match env_value("TRADING_WALLETS") {
+        Some(keys) => ("TRADING_WALLETS", Some(keys)),
+        None => ("PRIVATE_KEY", env_value("PRIVATE_KEY")),
+    };
+    let keystore = KEYSTORE_PATH
+        .as_deref()
+        .filter(|_| keys_name == "PRIVATE_KEY");
+    let keys_valid = match (keystore, keys) {
+        _ if *READ_ONLY => {
+            report.ok("READ_ONLY is set, no key is loaded");
+            true
+        }
+        (Some(path), _) => check_keystore(&mut report, path),
+        (None, Some(keys)) => {
+            let invalid = keys
+                .split(',')
+                .filter(|key| !key.trim().is_empty())
+                .filter(|key| !is_wallet(key))
+                .count();
+
+            if invalid > 0 {
+                report.error(format!("{} has {} invalid key(s)", keys_name, invalid));
+            } else {
+                report.ok(format!("{} keys are valid", keys_name));
+            }
+
+            invalid == 0
+        }
+        (None, None) => {
+            report.error("None of TRADING_WALLETS, PRIVATE_KEY or KEYSTORE_PATH is set");
+            false
+        }
+    };
+
+    if let Some(key) = env_value("FEE_PAYER_PRIVATE_KEY")
+        && !is_wallet(&key)
+    {
+        report.error("FEE_PAYER_PRIVATE_KEY is not a valid key");
+    }
+
+    if let Some(key) = env_value("RESERVE_PRIVATE_KEY")
+        && !is_wallet(&key)
+    {
+        report.error("RESERVE_PRIVATE_KEY is not a valid key");
+    }
+
+    if let Some(address) = env_value("RESERVE_ADDRESS")
+        && Pubkey::from_str(address.trim()).is_err()
+    {
+        report.error("RESERVE_ADDRESS is not a valid address");
+    }
+
+    if let Some(source) = env_value("SECRETS_SOURCE") {
+        match SecretsSource::from_entry(source.trim()) {
+            Ok(SecretsSource::Vault { .. })
+                if env_value("VAULT_ADDR").is_none() || env_value("VAULT_TOKEN").is_none() =>
+            {
+                report
+                    .error("SECRETS_SOURCE reads from Vault but VAULT_ADDR or VAULT_TOKEN is unset")
+            }
+            Ok(_) => {}
+            Err(e) => report.error(format!("SECRETS_SOURCE: {}", e)),
+        }
+    }
+
+    if let Some(address) = env_value("PROFIT_SWEEP_ADDRESS")
+        && Pubkey::from_str(address.trim()).is_err()
+    {
+        report.error("PROFIT_SWEEP_ADDRESS is not a valid address");
+    }
+
+    if PROFIT_SWEEP_ADDRESS.is_some()
+        && TOP_UP_THRESHOLD_SOL.is_some_and(|threshold| threshold >= *PROFIT_SWEEP_FLOOR_SOL)
+    {
+        report.warn(
+            "PROFIT_SWEEP_FLOOR_SOL is at or below TOP_UP_THRESHOLD_SOL, wallets would be swept into a top-up",
+        );
+    }
+
+    if let (Some(warn), Some(halt)) = (*LOW_BALANCE_WARN_SOL, *LOW_BALANCE_HALT_SOL)
+        && halt >= warn
+    {
+        report.warn(
+            "LOW_BALANCE_HALT_SOL is at or above LOW_BALANCE_WARN_SOL, buying stops without a warning first",
+        );
+    }
+
+    if let Some(programs) = env_value("EXTRA_ALLOWED_PROGRAMS") {
+        for program_id in programs.split(',').map(str::trim) {
+            if !program_id.is_empty() && Pubkey::from_str(program_id).is_err() {
+                report.error(format!(
+                    "EXTRA_ALLOWED_PROGRAMS: {} is not a valid program ID",
+                    program_id
+                ));
+            }
+        }
+    }
+
+    let datasources: Vec<&str> = [
+        "GEYSER_URL",
+        "LASER_ENDPOINT",
+        "WS_ENDPOINT",
+        "RPC_POLL_INTERVAL_MS",
+    ]
+    .into_iter()
+    .filter(|name| env_value(name).is_some())
+    .collect();
+    if datasources.is_empty() {
+        report.error(
+            "No datasource is configured, set GEYSER_URL, LASER_ENDPOINT, WS_ENDPOINT or \
+             RPC_POLL_INTERVAL_MS",
+        );
+    } else {
+        report.ok(format!("Datasources: {}", datasources.join(", ")));
+    }
+
+    for venue in FOLLOWED_VENUES.iter() {
+        if !VENUES.iter().any(|(name, _)| name == venue) {
+            report.warn(format!("Unknown venue {} in FOLLOWED_VENUES", venue));
+        }
+    }
+
+    if LogRotation::from_config(&LOG_ROTATION).is_none() {
+        report.warn(format!(
+            "Unknown LOG_ROTATION {}, daily is used",
+            *LOG_ROTATION
+        ));
+    }
+
+    if (CONTROL_API_ADDR.is_some() || CONTROL_GRPC_ADDR.is_some()) && CONTROL_API_TOKEN.is_none() {
+        report.warn(
+            "The control API is enabled without CONTROL_API_TOKEN, anyone reaching it can trade",
+        );
+    }
+
+    if *TELEGRAM_COMMANDS && TELEGRAM_BOT_TOKEN.is_none() {
+        report.warn("TELEGRAM_COMMANDS is enabled but TELEGRAM_BOT_TOKEN is not set");
+    }
+
+    // Loading the RPC client or the wallets panics on the errors above
+    if report.errors == 0 && keys_valid {
+        match RPC_CLIENT.get_slot().await {
+            Ok(slot) => report.ok(format!("RPC reachable, at slot {}", slot)),
+            Err(e) => report.error(format!("RPC unreachable: {}", e)),
+        }
+
+        // A read-only run's throwaway wallet holds nothing
+        let wallets: Vec<Pubkey> = if *READ_ONLY {
+            Vec::new()
+        } else {
+            TRADING_WALLETS.iter().map(Signer::pubkey).collect()
+        };
+
+        match get_balances(&RPC_CLIENT, &wallets).await {
+            Ok(balances) => {
+                for (wallet, balance) in wallets.iter().zip(balances) {
+                    let sol = balance as f64 / LAMPORTS_PER_SOL as f64;
+
+                    if balance < *BUY_SOL_AMOUNT {
+                        report.warn(format!(
+                            "Wallet {} holds {:.4} SOL, not enough for a BUY_SOL_AMOUNT buy",
+                            wallet, sol
+                        ));
+                    } else {
+                        report.ok(format!("Wallet {} holds {:.4} SOL", wallet, sol));
+                    }
+                }
+            }
+            Err(e) => report.error(format!("Failed to fetch the wallet balances: {}", e)),
+        }
+    }
+
+    println!("{} error(s), {} warning(s)", report.errors, report.warnings);
+
+    if report.errors > 0 {
+        bail!("the configuration has {} error(s)", report.errors);
+    }
+
+    Ok(())
+}
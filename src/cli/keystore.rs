@@ -0,0 +1,49 @@
+use anyhow::{Context, bail};
+use solana_sdk::{bs58, signer::Signer, signer::keypair::Keypair};
+use std::{fs::OpenOptions, io::Write, path::Path};
+
+use crate::config::{encrypt_keystore, read_passphrase};
+
+/// Encrypts a private key typed at the prompt into a new keystore at `path`, to set as
+/// `KEYSTORE_PATH` in place of `PRIVATE_KEY`. Never overwrites a file.
+pub fn create_keystore(path: &Path) -> anyhow::Result<()> {
+    let private_key = read_passphrase("Private key (base58): ")?;
+    let keypair = bs58::decode(private_key.trim())
+        .into_vec()
+        .ok()
+        .and_then(|bytes| Keypair::from_bytes(&bytes).ok())
+        .context("not a valid base58 private key")?;
+
+    write_keystore(&keypair, path)?;
+
+    println!(
+        "Wrote the keystore of {} to {}, set KEYSTORE_PATH to it and remove PRIVATE_KEY",
+        keypair.pubkey(),
+        path.display()
+    );
+
+    Ok(())
+}
+
+/// Encrypts `keypair` with a passphrase typed at the prompt into a new keystore at
+/// `path`. Never overwrites a file.
+pub fn write_keystore(keypair: &Keypair, path: &Path) -> anyhow::Result<()> {
+    let passphrase = read_passphrase("New passphrase: ")?;
+
+    if passphrase.is_empty() {
+        bail!("the passphrase can't be empty");
+    }
+
+    if read_passphrase("Repeat the passphrase: ")? != passphrase {
+        bail!("the passphrases don't match");
+    }
+
+    let keystore = encrypt_keystore(keypair, &passphrase);
+
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(keystore.as_bytes()))
+        .with_context(|| format!("failed to write {}", path.display()))
+}
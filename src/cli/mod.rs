@@ -0,0 +1,21 @@
+pub mod audit;
+pub mod backtest;
+pub mod command;
+pub mod config_check;
+pub mod keystore;
+pub mod positions;
+pub mod remote;
+pub mod sweep;
+pub mod targets;
+pub mod wallet_init;
+
+pub use audit::*;
+pub use backtest::*;
+pub use command::*;
+pub use config_check::*;
+pub use keystore::*;
+pub use positions::*;
+pub use remote::*;
+pub use sweep::*;
+pub use targets::*;
+pub use wallet_init::*;
@@ -0,0 +1,37 @@
+use solana_sdk::signer::Signer;
+
+use crate::{
+    config::{RPC_CLIENT, TRADING_WALLETS},
+    wallet::fetch_token_accounts,
+};
+
+/// Prints every non-empty token account of the trading wallets.
+pub async fn print_positions() -> anyhow::Result<()> {
+    let wallets: Vec<_> = TRADING_WALLETS.iter().map(Signer::pubkey).collect();
+
+    let mut positions: Vec<_> = fetch_token_accounts(&RPC_CLIENT, &wallets)
+        .await?
+        .into_iter()
+        .filter(|account| account.amount > 0)
+        .collect();
+
+    if positions.is_empty() {
+        println!("No open positions");
+        return Ok(());
+    }
+
+    positions.sort_by_key(|account| (account.mint, account.owner));
+
+    println!("{:<44}  {:<44}  {:>20}", "mint", "wallet", "tokens");
+
+    for account in positions {
+        println!(
+            "{:<44}  {:<44}  {:>20}",
+            account.mint.to_string(),
+            account.owner.to_string(),
+            account.ui_amount()
+        );
+    }
+
+    Ok(())
+}
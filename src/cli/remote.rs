@@ -0,0 +1,181 @@
+use anyhow::{Context, anyhow, bail};
+use reqwest::{Client, Method};
+use serde_json::{Value, json};
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+
+use crate::config::{CONTROL_API_ADDR, CONTROL_API_TOKEN, read_passphrase};
+
+/// Calls the control API of the running bot, which holds the PnL and the positions.
+async fn control_api_request(
+    method: Method,
+    path: &str,
+    body: Option<Value>,
+) -> anyhow::Result<Value> {
+    let addr = CONTROL_API_ADDR.as_deref().ok_or_else(|| {
+        anyhow!(
+            "CONTROL_API_ADDR isn't set, this command goes through the running bot's control API"
+        )
+    })?;
+
+    // A wildcard listen address is reached on the loopback
+    let addr = addr.replace("0.0.0.0", "127.0.0.1");
+
+    let mut request = Client::new().request(method, format!("http://{}{}", addr, path));
+
+    if let Some(token) = CONTROL_API_TOKEN.as_deref() {
+        request = request.bearer_auth(token);
+    }
+
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+
+    let response = request.send().await.with_context(|| {
+        format!(
+            "is the bot running? Can't reach its control API on {}",
+            addr
+        )
+    })?;
+
+    let status = response.status();
+    let body: Value = response.json().await?;
+
+    if !status.is_success() {
+        bail!(
+            "{}: {}",
+            status,
+            body["error"].as_str().unwrap_or("request failed")
+        );
+    }
+
+    Ok(body)
+}
+
+/// Prints the running bot's SOL flow and land rate per target, then the tips and fees
+/// it paid per sender and day, and the total net of them.
+pub async fn print_pnl() -> anyhow::Result<()> {
+    let targets = control_api_request(Method::GET, "/pnl", None).await?;
+    let targets = targets.as_array().cloned().unwrap_or_default();
+
+    if targets.is_empty() {
+        println!("No copies landed yet");
+        return Ok(());
+    }
+
+    let sol = |lamports: i64| lamports as f64 / LAMPORTS_PER_SOL as f64;
+    let mut total = 0;
+
+    println!(
+        "{:<44}  {:>12}  {:>9}  {:>9}",
+        "target", "SOL", "landed", "land rate"
+    );
+
+    for target in &targets {
+        let sol_flow = target["sol_flow_lamports"].as_i64().unwrap_or_default();
+        let landed = target["landed"].as_u64().unwrap_or_default();
+        let not_landed = target["not_landed"].as_u64().unwrap_or_default();
+
+        total += sol_flow;
+
+        println!(
+            "{:<44}  {:>+12.4}  {:>9}  {:>8.0}%",
+            target["target"].as_str().unwrap_or_default(),
+            sol(sol_flow),
+            format!("{}/{}", landed, landed + not_landed),
+            target["land_rate"].as_f64().unwrap_or_default() * 100.0
+        );
+    }
+
+    println!("{:<44}  {:>+12.4}", "total", sol(total));
+
+    let fees = control_api_request(Method::GET, "/fees", None).await?;
+    let lamports = |value: &Value| value.as_u64().unwrap_or_default() as i64;
+
+    println!(
+        "\n{:<12}  {:<12}  {:>6}  {:>10}  {:>10}  {:>10}",
+        "day", "sender", "txs", "tips", "priority", "base fees"
+    );
+
+    for day in fees["days"].as_array().into_iter().flatten() {
+        let totals = &day["totals"];
+
+        println!(
+            "{:<12}  {:<12}  {:>6}  {:>10.4}  {:>10.4}  {:>10.4}",
+            day["day"].as_str().unwrap_or_default(),
+            day["sender"].as_str().unwrap_or_default(),
+            totals["transactions"].as_u64().unwrap_or_default(),
+            sol(lamports(&totals["tip_lamports"])),
+            sol(lamports(&totals["priority_fee_lamports"])),
+            sol(lamports(&totals["base_fee_lamports"]))
+        );
+    }
+
+    for sender in fees["senders"].as_array().into_iter().flatten() {
+        let totals = &sender["totals"];
+
+        println!(
+            "{:<12}  {:<12}  {:>6}  {:>10.4}  {:>10.4}  {:>10.4}",
+            "all days",
+            sender["sender"].as_str().unwrap_or_default(),
+            totals["transactions"].as_u64().unwrap_or_default(),
+            sol(lamports(&totals["tip_lamports"])),
+            sol(lamports(&totals["priority_fee_lamports"])),
+            sol(lamports(&totals["base_fee_lamports"]))
+        );
+    }
+
+    // What the trading wallets paid themselves is already in their SOL flow
+    println!(
+        "\nTips and fees: {:.4} SOL, total net of them: {:+.4} SOL",
+        sol(lamports(&fees["total_lamports"])),
+        sol(total - lamports(&fees["fee_payer_paid_lamports"]))
+    );
+
+    Ok(())
+}
+
+/// Has the running bot sell `mint` from every wallet holding it, with the code it asked
+/// for a sell over `MANUAL_CONFIRM_ABOVE_SOL`.
+pub async fn sell_through_bot(mint: &Pubkey, confirm_code: Option<&str>) -> anyhow::Result<()> {
+    let reply = control_api_request(
+        Method::POST,
+        "/sell",
+        Some(json!({ "mint": mint.to_string(), "confirm_code": confirm_code })),
+    )
+    .await?;
+
+    println!(
+        "Selling {} from {} wallet(s)",
+        mint,
+        reply["wallets"].as_u64().unwrap_or_default()
+    );
+
+    Ok(())
+}
+
+/// Has the running bot rotate `wallet` out for a key or signer entry read from the
+/// prompt, so it never lands in the shell history.
+pub async fn rotate_through_bot(wallet: &Pubkey, transfer: bool) -> anyhow::Result<()> {
+    let new_wallet = read_passphrase("New wallet (base58 key, ledger, remote: or KMS key): ")?;
+    let mode = if transfer { "transfer" } else { "drain" };
+
+    let reply = control_api_request(
+        Method::POST,
+        "/wallets/rotate",
+        Some(json!({
+            "wallet": wallet.to_string(),
+            "new_wallet": new_wallet.trim(),
+            "mode": mode,
+        })),
+    )
+    .await?;
+
+    println!(
+        "Rotating {} out for {}, buys now go to the new wallet. Replace it in \
+         TRADING_WALLETS before the next restart",
+        wallet,
+        reply["new_wallet"].as_str().unwrap_or_default()
+    );
+
+    Ok(())
+}
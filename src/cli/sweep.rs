@@ -0,0 +1,98 @@
+use solana_sdk::{
+    instruction::Instruction, native_token::LAMPORTS_PER_SOL, signer::Signer,
+    transaction::Transaction,
+};
+
+use crate::{
+    config::{RPC_CLIENT, TRADING_WALLETS},
+    instructions::token_account::close_token_account_ix,
+    utils::check_instructions,
+    wallet::{TokenAccount, fetch_token_accounts},
+};
+
+/// Close instructions per transaction, well within the transaction size limit
+const CLOSES_PER_TX: usize = 20;
+
+/// Closes every empty token account of the trading wallets, each wallet paying for and
+/// receiving the rent of its own. Best run while the bot is stopped, so it doesn't close
+/// an account a buy is about to fill.
+pub async fn sweep_atas(dry_run: bool) -> anyhow::Result<()> {
+    let mut closed = 0;
+    let mut reclaimed = 0;
+
+    for wallet in TRADING_WALLETS.iter() {
+        let empty: Vec<TokenAccount> = fetch_token_accounts(&RPC_CLIENT, &[wallet.pubkey()])
+            .await?
+            .into_iter()
+            .filter(|account| account.amount == 0)
+            .collect();
+
+        if dry_run {
+            for account in &empty {
+                println!(
+                    "Would close {} ({}) of {}",
+                    account.address,
+                    account.mint,
+                    wallet.pubkey()
+                );
+            }
+
+            closed += empty.len();
+            reclaimed += empty.iter().map(|account| account.lamports).sum::<u64>();
+            continue;
+        }
+
+        for chunk in empty.chunks(CLOSES_PER_TX) {
+            let ixs: Vec<Instruction> = chunk
+                .iter()
+                .map(|account| {
+                    close_token_account_ix(
+                        &account.token_program,
+                        &account.address,
+                        &wallet.pubkey(),
+                    )
+                })
+                .collect();
+
+            check_instructions(&ixs, &[wallet.pubkey()]).map_err(anyhow::Error::msg)?;
+
+            let blockhash = RPC_CLIENT.get_latest_blockhash().await?;
+            let transaction = Transaction::new_signed_with_payer(
+                &ixs,
+                Some(&wallet.pubkey()),
+                &[wallet],
+                blockhash,
+            );
+
+            // Some accounts can't be closed, e.g. Token-2022 ones holding withheld fees
+            match RPC_CLIENT.send_and_confirm_transaction(&transaction).await {
+                Ok(signature) => {
+                    println!(
+                        "Closed {} account(s) of {}: {}",
+                        chunk.len(),
+                        wallet.pubkey(),
+                        signature
+                    );
+
+                    closed += chunk.len();
+                    reclaimed += chunk.iter().map(|account| account.lamports).sum::<u64>();
+                }
+                Err(e) => println!(
+                    "Failed to close {} account(s) of {}: {}",
+                    chunk.len(),
+                    wallet.pubkey(),
+                    e
+                ),
+            }
+        }
+    }
+
+    println!(
+        "{} {} empty token account(s), {:.6} SOL of rent",
+        if dry_run { "Found" } else { "Closed" },
+        closed,
+        reclaimed as f64 / LAMPORTS_PER_SOL as f64
+    );
+
+    Ok(())
+}
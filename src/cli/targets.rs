@@ -0,0 +1,60 @@
+use anyhow::{Context, bail};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    config::{TARGET_WALLET, TARGETS_PATH},
+    datasource::{load_saved_targets, save_targets},
+};
+
+pub fn list_targets() -> anyhow::Result<()> {
+    println!("{} (TARGET_WALLET)", *TARGET_WALLET);
+
+    for target in load_saved_targets() {
+        println!("{}", target);
+    }
+
+    Ok(())
+}
+
+/// Saves `wallet` to `TARGETS_PATH`, so the bot follows it from its next start.
+pub fn add_target(wallet: Pubkey) -> anyhow::Result<()> {
+    if wallet == *TARGET_WALLET {
+        println!("{} is TARGET_WALLET, it's always followed", wallet);
+        return Ok(());
+    }
+
+    let mut targets = load_saved_targets();
+
+    if targets.contains(&wallet) {
+        println!("Already following {}", wallet);
+        return Ok(());
+    }
+
+    targets.push(wallet);
+    save_targets(&targets).with_context(|| format!("failed to write {}", *TARGETS_PATH))?;
+
+    println!("Following {} from the next start", wallet);
+
+    Ok(())
+}
+
+/// Drops `wallet` from `TARGETS_PATH`, so the bot stops following it from its next start.
+pub fn remove_target(wallet: &Pubkey) -> anyhow::Result<()> {
+    if *wallet == *TARGET_WALLET {
+        bail!("{} is TARGET_WALLET, change it in .env instead", wallet);
+    }
+
+    let mut targets = load_saved_targets();
+
+    if !targets.contains(wallet) {
+        println!("{} isn't followed", wallet);
+        return Ok(());
+    }
+
+    targets.retain(|target| target != wallet);
+    save_targets(&targets).with_context(|| format!("failed to write {}", *TARGETS_PATH))?;
+
+    println!("No longer following {} from the next start", wallet);
+
+    Ok(())
+}
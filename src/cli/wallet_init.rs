@@ -0,0 +1,85 @@
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signer::Signer, signer::keypair::Keypair};
+use std::path::Path;
+use tokio::time::{Duration, sleep};
+
+use crate::{
+    cli::write_keystore,
+    config::{BUY_SOL_AMOUNT, RPC_CLIENT},
+    instructions::wsol::create_wsol_account_ix,
+    utils::associated_token_address,
+    wallet::{WalletSigner, send_instructions},
+};
+
+/// Seconds between balance checks while waiting for the deposit
+const DEPOSIT_POLL_SEC: u64 = 5;
+
+/// Sets up a new trading wallet: generates a key and writes its keystore to `path` right
+/// away, so a deposit is never made to a key that isn't saved, then waits for at least
+/// `min_sol` (`BUY_SOL_AMOUNT` by default) to arrive. With `create_wsol_account` it then
+/// opens the wallet's WSOL account, which copies otherwise open and close themselves.
+pub async fn init_wallet(
+    path: &Path,
+    min_sol: Option<f64>,
+    create_wsol_account: bool,
+) -> anyhow::Result<()> {
+    let keypair = Keypair::new();
+    let wallet = keypair.pubkey();
+
+    println!("Generated wallet {}", wallet);
+
+    write_keystore(&keypair, path)?;
+
+    println!("Wrote its keystore to {}", path.display());
+
+    let min_lamports = min_sol.map_or(*BUY_SOL_AMOUNT, |sol| {
+        (sol * LAMPORTS_PER_SOL as f64) as u64
+    });
+
+    println!(
+        "\nFund it by sending at least {} SOL to {}, plus what the tips and fees of the \
+         trades take. Waiting for the deposit, Ctrl-C stops waiting and keeps the keystore",
+        min_lamports as f64 / LAMPORTS_PER_SOL as f64,
+        wallet
+    );
+
+    let balance = loop {
+        match RPC_CLIENT.get_balance(&wallet).await {
+            Ok(balance) if balance >= min_lamports => break balance,
+            Ok(_) => {}
+            Err(e) => println!("Failed to fetch the balance, retrying: {}", e),
+        }
+
+        sleep(Duration::from_secs(DEPOSIT_POLL_SEC)).await;
+    };
+
+    println!(
+        "Received, {} holds {} SOL",
+        wallet,
+        balance as f64 / LAMPORTS_PER_SOL as f64
+    );
+
+    if create_wsol_account {
+        let wsol_account = associated_token_address(&wallet, &spl_token::native_mint::ID);
+
+        if RPC_CLIENT.get_account(&wsol_account).await.is_ok() {
+            println!("WSOL account {} already exists", wsol_account);
+        } else {
+            let signature = send_instructions(
+                &RPC_CLIENT,
+                &WalletSigner::Keypair(keypair),
+                &[create_wsol_account_ix(&wallet)],
+            )
+            .await
+            .map_err(anyhow::Error::msg)?;
+
+            println!("Opened WSOL account {}: {}", wsol_account, signature);
+        }
+    }
+
+    println!(
+        "\nSet KEYSTORE_PATH={} and remove PRIVATE_KEY to trade with it",
+        path.display()
+    );
+
+    Ok(())
+}
@@ -2,7 +2,10 @@ use dotenvy::dotenv;
 use std::env;
 use tokio::sync::OnceCell;
 
-use crate::service::{Jito, Nozomi, ZeroSlot};
+use crate::{
+    config::SENDER_KEEP_WARM_SEC,
+    service::{Jito, Nozomi, ZeroSlot},
+};
 
 pub static NOZOMI_CLIENT: OnceCell<Nozomi> = OnceCell::const_new();
 pub static ZSLOT_CLIENT: OnceCell<ZeroSlot> = OnceCell::const_new();
@@ -15,6 +18,7 @@ pub async fn init_nozomi() {
 
     let nozomi = Nozomi::new_auto(nozomi_api_key).await;
     nozomi.health_check(50);
+    nozomi.keep_warm(*SENDER_KEEP_WARM_SEC);
     NOZOMI_CLIENT.set(nozomi).unwrap();
 }
 
@@ -24,11 +28,14 @@ pub async fn init_zslot() {
     let zslot_api_key = env::var("ZERO_SLOT_KEY").expect("ZERO_SLOT_KEY not set in .env");
 
     let zslot = ZeroSlot::new_auto(zslot_api_key).await;
+    zslot.health_check(50);
+    zslot.keep_warm(*SENDER_KEEP_WARM_SEC);
     ZSLOT_CLIENT.set(zslot).unwrap();
 }
 
 pub async fn init_jito() {
     let jito = Jito::new_auto(None).await;
+    jito.health_check(50);
+    jito.keep_warm(*SENDER_KEEP_WARM_SEC);
     JITO_CLIENT.set(jito).unwrap();
 }
-
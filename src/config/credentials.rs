@@ -8,23 +8,158 @@ use solana_sdk::{
 };
 use std::{env, sync::Arc};
 
+use crate::{
+    config::{READ_ONLY, load_keystore},
+    wallet::WalletSigner,
+};
+
+pub static KEYSTORE_PATH: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Encrypted keystore holding the PRIVATE_KEY wallet, written by `keystore create`
+    env::var("KEYSTORE_PATH")
+        .ok()
+        .filter(|path| !path.is_empty())
+});
+
+pub static KEYSTORE_PASSPHRASE: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Passphrase of KEYSTORE_PATH; prompted for on the terminal when unset
+    env::var("KEYSTORE_PASSPHRASE")
+        .ok()
+        .filter(|passphrase| !passphrase.is_empty())
+});
+
 pub static PRIVATE_KEY: Lazy<Keypair> = Lazy::new(|| {
     dotenv().ok();
 
-    let private_key = env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
+    // Generated for the run, it never holds funds
+    if *READ_ONLY {
+        return Keypair::new();
+    }
+
+    // The keystore takes the place of a raw key in .env
+    if let Some(keystore_path) = KEYSTORE_PATH.as_deref() {
+        return load_keystore(keystore_path);
+    }
+
+    let private_key = env::var("PRIVATE_KEY").expect("PRIVATE_KEY or KEYSTORE_PATH must be set");
+
+    Keypair::from_base58_string(private_key.as_str())
+});
+pub static PUBKEY: Lazy<Pubkey> = Lazy::new(|| PRIVATE_KEY.pubkey());
+
+pub static TRADING_WALLETS: Lazy<Vec<WalletSigner>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Comma separated base58 keys, "ledger[:path]", "remote:<url>" or a KMS key; PRIVATE_KEY's wallet when unset
+    let Some(private_keys) = env::var("TRADING_WALLETS")
+        .ok()
+        .filter(|keys| !keys.trim().is_empty() && !*READ_ONLY)
+    else {
+        return vec![WalletSigner::Keypair(PRIVATE_KEY.insecure_clone())];
+    };
+
+    private_keys
+        .split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(WalletSigner::from_entry)
+        .collect()
+});
+
+pub static FEE_PAYER: Lazy<WalletSigner> = Lazy::new(|| {
+    dotenv().ok();
+
+    // The trading wallet pays its own fees unless a dedicated fee payer is configured
+    match env::var("FEE_PAYER_PRIVATE_KEY")
+        .ok()
+        .filter(|key| !key.is_empty() && !*READ_ONLY)
+    {
+        Some(private_key) => WalletSigner::from_entry(private_key.trim()),
+        None => WalletSigner::Keypair(PRIVATE_KEY.insecure_clone()),
+    }
+});
+pub static FEE_PAYER_PUBKEY: Lazy<Pubkey> = Lazy::new(|| FEE_PAYER.pubkey());
+
+pub static RESERVE_PRIVATE_KEY: Lazy<Option<WalletSigner>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Wallet topping up the fee payer and trading wallets, any TRADING_WALLETS entry
+    env::var("RESERVE_PRIVATE_KEY")
+        .ok()
+        .filter(|key| !key.trim().is_empty() && !*READ_ONLY)
+        .map(|key| WalletSigner::from_entry(key.trim()))
+});
+
+pub static REMOTE_SIGNER_TOKEN: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Bearer token sent to "remote:<url>" signers; unset sends none
+    env::var("REMOTE_SIGNER_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty())
+});
+
+pub static AWS_ACCESS_KEY_ID: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Credentials of the "aws-kms:<key ARN>" wallets and an "aws-secrets:<ARN>" SECRETS_SOURCE
+    env::var("AWS_ACCESS_KEY_ID")
+        .ok()
+        .filter(|key| !key.is_empty())
+});
+
+pub static AWS_SECRET_ACCESS_KEY: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("AWS_SECRET_ACCESS_KEY")
+        .ok()
+        .filter(|key| !key.is_empty())
+});
 
-    let payer: Keypair = Keypair::from_base58_string(private_key.as_str());
+pub static AWS_SESSION_TOKEN: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
 
-    payer
+    // Set along with temporary credentials, e.g. of an assumed role
+    env::var("AWS_SESSION_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty())
 });
-pub static PUBKEY: Lazy<Pubkey> = Lazy::new(|| {
+
+pub static GCP_ACCESS_TOKEN: Lazy<Option<String>> = Lazy::new(|| {
     dotenv().ok();
 
-    let private_key = env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
+    // OAuth token of the "gcp-kms:<key version>" wallets; the metadata server's when unset
+    env::var("GCP_ACCESS_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty())
+});
 
-    let payer: Keypair = Keypair::from_base58_string(private_key.as_str());
+pub static SECRETS_SOURCE: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
 
-    payer.pubkey()
+    // "vault:<path>" or "aws-secrets:<secret ARN>" to read secrets from at startup; unset reads none
+    env::var("SECRETS_SOURCE")
+        .ok()
+        .map(|source| source.trim().to_string())
+        .filter(|source| !source.is_empty())
+});
+
+pub static VAULT_ADDR: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("VAULT_ADDR").ok().filter(|addr| !addr.is_empty())
+});
+
+pub static VAULT_TOKEN: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Token SECRETS_SOURCE is read with, renewed while the bot runs
+    env::var("VAULT_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty())
 });
 
 pub static TARGET_WALLET: Lazy<Pubkey> = Lazy::new(|| {
@@ -32,17 +167,13 @@ pub static TARGET_WALLET: Lazy<Pubkey> = Lazy::new(|| {
 
     let target_wallet = env::var("TARGET_WALLET").expect("TARGET_WALLET must be set");
 
-    let target_wallet = Pubkey::from_str_const(&target_wallet);
-
-    target_wallet
+    Pubkey::from_str_const(&target_wallet)
 });
 
 pub static RPC_ENDPOINT: Lazy<String> = Lazy::new(|| {
     dotenv().ok();
 
-    let rpc_endpoint = env::var("RPC_ENDPOINT").expect("RPC_ENDPOINT must be set");
-
-    rpc_endpoint
+    env::var("RPC_ENDPOINT").expect("RPC_ENDPOINT must be set")
 });
 
 pub static RPC_CLIENT: Lazy<Arc<RpcClient>> = Lazy::new(|| {
@@ -59,31 +190,132 @@ pub static RPC_CLIENT: Lazy<Arc<RpcClient>> = Lazy::new(|| {
 pub static LASER_ENDPOINT: Lazy<String> = Lazy::new(|| {
     dotenv().ok();
 
-    let laser_endpoint = env::var("LASER_ENDPOINT").expect("LASER_ENDPOINT must be set");
-
-    laser_endpoint
+    env::var("LASER_ENDPOINT").expect("LASER_ENDPOINT must be set")
 });
 
 pub static LASER_TOKEN_KEY: Lazy<String> = Lazy::new(|| {
     dotenv().ok();
 
-    let laser_token_key = env::var("LASER_TOKEN_KEY").expect("LASER_TOKEN_KEY must be set");
-
-    laser_token_key
+    env::var("LASER_TOKEN_KEY").expect("LASER_TOKEN_KEY must be set")
 });
 
 pub static GRPC_ENDPOINT: Lazy<String> = Lazy::new(|| {
     dotenv().ok();
 
-    let grpc_endpoint = env::var("GRPC_ENDPOINT").expect("GRPC_ENDPOINT must be set");
-
-    grpc_endpoint
+    env::var("GRPC_ENDPOINT").expect("GRPC_ENDPOINT must be set")
 });
 
 pub static GRPC_TOKEN: Lazy<String> = Lazy::new(|| {
     dotenv().ok();
 
-    let grpc_token = env::var("GRPC_TOKEN").expect("GRPC_TOKEN must be set");
+    env::var("GRPC_TOKEN").expect("GRPC_TOKEN must be set")
+});
+
+pub static SIMULATION_RPC_ENDPOINT: Lazy<String> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Falls back to the main RPC when no dedicated simulation RPC is configured
+    env::var("SIMULATION_RPC_ENDPOINT").unwrap_or_else(|_| RPC_ENDPOINT.clone())
+});
+
+pub static WS_ENDPOINT: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Optional WebSocket RPC used as a fallback / redundant datasource
+    env::var("WS_ENDPOINT")
+        .ok()
+        .filter(|endpoint| !endpoint.is_empty())
+});
+
+pub static TELEGRAM_BOT_TOKEN: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Bot posting fills, exits and errors to TELEGRAM_CHAT_ID; unset disables notifications
+    env::var("TELEGRAM_BOT_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty())
+});
+
+pub static TELEGRAM_CHAT_ID: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("TELEGRAM_CHAT_ID")
+        .ok()
+        .filter(|chat_id| !chat_id.is_empty())
+});
+
+pub static CONTROL_API_TOKEN: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Bearer token every control API request must carry; unset leaves the API open
+    env::var("CONTROL_API_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty())
+});
+
+pub static CONTROL_API_READ_TOKEN: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Bearer token that may only read the bot, not pause, trade or change targets
+    env::var("CONTROL_API_READ_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty())
+});
+
+pub static TELEGRAM_ALLOWED_CHAT_IDS: Lazy<Vec<i64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Chats allowed to send bot commands, separated by commas; defaults to TELEGRAM_CHAT_ID
+    env::var("TELEGRAM_ALLOWED_CHAT_IDS")
+        .ok()
+        .filter(|chat_ids| !chat_ids.is_empty())
+        .or_else(|| TELEGRAM_CHAT_ID.clone())
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|chat_id| chat_id.trim().parse::<i64>().ok())
+        .collect()
+});
+
+pub static TELEGRAM_READ_CHAT_IDS: Lazy<Vec<i64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Chats only allowed /positions and /pnl, separated by commas
+    env::var("TELEGRAM_READ_CHAT_IDS")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|chat_id| chat_id.trim().parse::<i64>().ok())
+        .collect()
+});
+
+pub static SLACK_WEBHOOK_URL: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Slack incoming webhook posting fills, exits and errors; unset disables it
+    env::var("SLACK_WEBHOOK_URL")
+        .ok()
+        .filter(|url| !url.is_empty())
+});
+
+pub static WEBHOOK_SECRET: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Key webhook payloads are HMAC-SHA256 signed with; unset sends them unsigned
+    env::var("WEBHOOK_SECRET")
+        .ok()
+        .filter(|secret| !secret.is_empty())
+});
+
+pub static INFLUXDB_URL: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // InfluxDB 2.x server metrics are written to when METRICS_SINK is influxdb
+    env::var("INFLUXDB_URL").ok().filter(|url| !url.is_empty())
+});
+
+pub static INFLUXDB_TOKEN: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
 
-    grpc_token
+    env::var("INFLUXDB_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty())
 });
@@ -0,0 +1,189 @@
+use aes_gcm_siv::{
+    Aes256GcmSiv, Nonce,
+    aead::{Aead, KeyInit},
+};
+use hmac::Hmac;
+use once_cell::sync::Lazy;
+use rand::RngCore;
+use ratatui::crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    terminal,
+};
+use serde_json::{Value, json};
+use sha2::Sha256;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signer::{Signer, keypair::Keypair},
+};
+use std::{
+    fs,
+    io::{self, IsTerminal, Write},
+    str::FromStr,
+};
+
+use crate::config::{KEYSTORE_PASSPHRASE, KEYSTORE_PATH, PRIVATE_KEY};
+
+/// PBKDF2-HMAC-SHA256 rounds deriving the encryption key from the passphrase
+const KEYSTORE_ROUNDS: u32 = 600_000;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+fn derive_key(passphrase: &str, salt: &[u8], rounds: u32) -> [u8; 32] {
+    let mut key = [0; 32];
+    pbkdf2::pbkdf2::<Hmac<Sha256>>(passphrase.as_bytes(), salt, rounds, &mut key);
+
+    key
+}
+
+/// Encrypts `keypair` with a key derived from `passphrase`, as the JSON keystore
+/// `decrypt_keystore` reads. The public key is kept in the clear to tell keystores
+/// apart.
+pub fn encrypt_keystore(keypair: &Keypair, passphrase: &str) -> String {
+    let mut salt = [0; SALT_LEN];
+    let mut nonce = [0; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let key = derive_key(passphrase, &salt, KEYSTORE_ROUNDS);
+    let ciphertext = Aes256GcmSiv::new(&key.into())
+        .encrypt(Nonce::from_slice(&nonce), keypair.to_bytes().as_slice())
+        .expect("Failed to encrypt the keypair");
+
+    json!({
+        "version": 1,
+        "pubkey": keypair.pubkey().to_string(),
+        "kdf": "pbkdf2-hmac-sha256",
+        "rounds": KEYSTORE_ROUNDS,
+        "salt": base64::encode(salt),
+        "cipher": "aes-256-gcm-siv",
+        "nonce": base64::encode(nonce),
+        "ciphertext": base64::encode(ciphertext),
+    })
+    .to_string()
+}
+
+/// Public key a keystore says it holds, without decrypting it.
+pub fn keystore_pubkey(content: &str) -> Result<Pubkey, String> {
+    let keystore: Value =
+        serde_json::from_str(content).map_err(|e| format!("not a keystore: {}", e))?;
+
+    keystore["pubkey"]
+        .as_str()
+        .and_then(|pubkey| Pubkey::from_str(pubkey).ok())
+        .ok_or_else(|| "the keystore has no valid pubkey".to_string())
+}
+
+/// Decrypts a keystore written by `encrypt_keystore`. Fails on a wrong passphrase or
+/// a modified keystore.
+pub fn decrypt_keystore(content: &str, passphrase: &str) -> Result<Keypair, String> {
+    let keystore: Value =
+        serde_json::from_str(content).map_err(|e| format!("not a keystore: {}", e))?;
+    let field = |name: &str| {
+        keystore[name]
+            .as_str()
+            .and_then(|value| base64::decode(value).ok())
+            .ok_or_else(|| format!("the keystore has no valid {}", name))
+    };
+
+    let salt = field("salt")?;
+    let nonce = field("nonce")?;
+    let ciphertext = field("ciphertext")?;
+    let rounds = keystore["rounds"]
+        .as_u64()
+        .and_then(|rounds| u32::try_from(rounds).ok())
+        .ok_or("the keystore has no valid rounds")?;
+
+    if nonce.len() != NONCE_LEN {
+        return Err("the keystore has no valid nonce".to_string());
+    }
+
+    let key = derive_key(passphrase, &salt, rounds);
+    let bytes = Aes256GcmSiv::new(&key.into())
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| "wrong passphrase".to_string())?;
+
+    let keypair = Keypair::from_bytes(&bytes).map_err(|e| format!("not a keypair: {}", e))?;
+
+    if keystore_pubkey(content)? != keypair.pubkey() {
+        return Err("the keypair doesn't match the keystore's pubkey".to_string());
+    }
+
+    Ok(keypair)
+}
+
+/// Decrypts the keystore at `path` with `KEYSTORE_PASSPHRASE`, or a passphrase
+/// prompted for on the terminal when it's unset. Panics when it can't, like a missing
+/// `PRIVATE_KEY` does.
+pub fn load_keystore(path: &str) -> Keypair {
+    let content = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Failed to read the keystore {}: {}", path, e));
+
+    let passphrase = match KEYSTORE_PASSPHRASE.clone() {
+        Some(passphrase) => passphrase,
+        None => read_passphrase(&format!("Passphrase for {}: ", path))
+            .unwrap_or_else(|e| panic!("Failed to read the keystore passphrase: {}", e)),
+    };
+
+    decrypt_keystore(&content, &passphrase)
+        .unwrap_or_else(|e| panic!("Failed to decrypt the keystore {}: {}", path, e))
+}
+
+/// Decrypts the keystore, when `KEYSTORE_PATH` is set, before anything logs over the
+/// passphrase prompt.
+pub fn unlock_keystore() {
+    if KEYSTORE_PATH.is_some() {
+        Lazy::force(&PRIVATE_KEY);
+    }
+}
+
+/// Prompts for a passphrase on stderr and reads it without echo. When stdin isn't a
+/// terminal, the first line of it is read instead.
+pub fn read_passphrase(prompt: &str) -> io::Result<String> {
+    eprint!("{}", prompt);
+    io::stderr().flush()?;
+
+    if !io::stdin().is_terminal() {
+        let mut line = String::new();
+        io::stdin().read_line(&mut line)?;
+
+        return Ok(line.trim_end_matches(['\r', '\n']).to_string());
+    }
+
+    terminal::enable_raw_mode()?;
+    let passphrase = read_hidden_line();
+    terminal::disable_raw_mode()?;
+    eprintln!();
+
+    passphrase
+}
+
+/// Keys typed up to Enter, in raw mode so none are echoed.
+fn read_hidden_line() -> io::Result<String> {
+    let mut line = String::new();
+
+    loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Enter => return Ok(line),
+            KeyCode::Backspace => {
+                line.pop();
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Interrupted,
+                    "passphrase prompt interrupted",
+                ));
+            }
+            KeyCode::Char(c) => line.push(c),
+            _ => {}
+        }
+    }
+}
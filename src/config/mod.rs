@@ -1,7 +1,11 @@
 pub mod clients;
 pub mod credentials;
+pub mod keystore;
+pub mod secrets;
 pub mod trade_setting;
 
 pub use clients::*;
 pub use credentials::*;
+pub use keystore::*;
+pub use secrets::*;
 pub use trade_setting::*;
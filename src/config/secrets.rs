@@ -0,0 +1,270 @@
+use once_cell::sync::{Lazy, OnceCell};
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::{collections::HashMap, env};
+use tokio::time::{Duration, sleep};
+use tracing::{info, warn};
+
+use crate::{
+    config::{SECRETS_SOURCE, VAULT_ADDR, VAULT_TOKEN},
+    utils::notify_error,
+    wallet::aws_json_request,
+};
+
+/// Shortest wait between two lease renewals
+const MIN_RENEW_WAIT: Duration = Duration::from_secs(5);
+/// Wait before retrying a failed renewal
+const RENEW_RETRY: Duration = Duration::from_secs(30);
+
+static SECRETS_CLIENT: Lazy<Client> = Lazy::new(Client::new);
+
+/// Lease of the secret read from Vault, renewed by `secrets_lease_handler`
+static VAULT_LEASE: OnceCell<Option<String>> = OnceCell::new();
+
+/// Where the secrets the bot reads from its environment are kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretsSource {
+    /// Vault secret at `path`, e.g. `secret/data/pumpfun` for a KV v2 mount
+    Vault { path: String },
+    /// AWS Secrets Manager secret by ARN, its `SecretString` a JSON object
+    Aws { region: String, secret_id: String },
+}
+
+impl SecretsSource {
+    /// Parses a `SECRETS_SOURCE` entry, `vault:<path>` or `aws-secrets:<secret ARN>`.
+    pub fn from_entry(entry: &str) -> Result<Self, String> {
+        if let Some(path) = entry.strip_prefix("vault:") {
+            let path = path.trim_matches('/');
+
+            if path.is_empty() {
+                return Err("vault: needs a secret path".to_string());
+            }
+
+            return Ok(Self::Vault {
+                path: path.to_string(),
+            });
+        }
+
+        if let Some(arn) = entry.strip_prefix("aws-secrets:") {
+            // arn:aws:secretsmanager:<region>:<account>:secret:<name>
+            let parts: Vec<&str> = arn.split(':').collect();
+
+            return match parts.as_slice() {
+                ["arn", _, "secretsmanager", region, _, "secret", name]
+                    if !region.is_empty() && !name.is_empty() =>
+                {
+                    Ok(Self::Aws {
+                        region: region.to_string(),
+                        secret_id: arn.to_string(),
+                    })
+                }
+                _ => Err(format!("{} isn't a Secrets Manager secret ARN", arn)),
+            };
+        }
+
+        Err(format!(
+            "{} isn't a vault:<path> or aws-secrets:<ARN> source",
+            entry
+        ))
+    }
+}
+
+/// Reads the secrets of `SECRETS_SOURCE`, when set, into the environment before any
+/// setting is read, in place of a `.env` on disk. They take precedence over what the
+/// environment and `.env` hold. The Vault or AWS credentials to read them with come
+/// from the environment. Panics when they can't be read, like a missing `PRIVATE_KEY`
+/// does.
+pub async fn load_secrets() {
+    let Some(source) = SECRETS_SOURCE.as_deref() else {
+        return;
+    };
+
+    let source = SecretsSource::from_entry(source)
+        .unwrap_or_else(|e| panic!("Invalid SECRETS_SOURCE: {}", e));
+
+    let (secrets, lease_id) = match &source {
+        SecretsSource::Vault { path } => {
+            let (addr, token) = vault_credentials().unwrap_or_else(|e| panic!("{}", e));
+
+            fetch_vault_secrets(&addr, &token, path).await
+        }
+        SecretsSource::Aws { region, secret_id } => fetch_aws_secrets(region, secret_id)
+            .await
+            .map(|secrets| (secrets, None)),
+    }
+    .unwrap_or_else(|e| panic!("Failed to read secrets from {:?}: {}", source, e));
+
+    for (name, value) in &secrets {
+        // SAFETY: runs first thing in `main`, before anything else reads or writes
+        // the environment
+        unsafe { env::set_var(name, value) };
+    }
+
+    let _ = VAULT_LEASE.set(lease_id);
+
+    info!("Loaded {} secrets from {:?}", secrets.len(), source);
+}
+
+/// Keeps the Vault token `load_secrets` used, and the lease of the secret it read,
+/// from expiring: each is renewed at half its TTL. Alerts when a renewal fails, the
+/// bot keeps the secrets it already read either way.
+pub async fn secrets_lease_handler() {
+    // Only set once secrets were read from Vault
+    let Some(lease_id) = VAULT_LEASE.get() else {
+        return;
+    };
+
+    let Ok((addr, token)) = vault_credentials() else {
+        return;
+    };
+
+    loop {
+        match renew_vault_leases(&addr, &token, lease_id.as_deref()).await {
+            Ok(Some(ttl)) => sleep((ttl / 2).max(MIN_RENEW_WAIT)).await,
+            Ok(None) => {
+                info!("The Vault token and secret don't expire, nothing to renew");
+                return;
+            }
+            Err(e) => {
+                let message = format!("Failed to renew the Vault lease: {}", e);
+                warn!("ALERT: {}", message);
+                notify_error(message);
+
+                sleep(RENEW_RETRY).await;
+            }
+        }
+    }
+}
+
+fn vault_credentials() -> Result<(String, String), String> {
+    let addr = VAULT_ADDR
+        .clone()
+        .ok_or("VAULT_ADDR isn't set for a vault: SECRETS_SOURCE")?;
+    let token = VAULT_TOKEN
+        .clone()
+        .ok_or("VAULT_TOKEN isn't set for a vault: SECRETS_SOURCE")?;
+
+    Ok((addr.trim_end_matches('/').to_string(), token))
+}
+
+/// Reads the Vault secret at `path`, with its lease ID if it has one. A KV v2 secret's
+/// fields are under `data.data`, those of any other engine under `data`.
+pub async fn fetch_vault_secrets(
+    addr: &str,
+    token: &str,
+    path: &str,
+) -> Result<(HashMap<String, String>, Option<String>), String> {
+    let response =
+        vault_request(SECRETS_CLIENT.get(format!("{}/v1/{}", addr, path)), token).await?;
+
+    let data = match &response["data"]["data"] {
+        Value::Object(_) => &response["data"]["data"],
+        _ => &response["data"],
+    };
+
+    let lease_id = response["lease_id"]
+        .as_str()
+        .filter(|lease_id| !lease_id.is_empty() && response["renewable"] == true)
+        .map(str::to_string);
+
+    Ok((secret_fields(data)?, lease_id))
+}
+
+/// Renews the Vault token and the secret's lease, each when renewable. Returns the
+/// shortest TTL left, `None` when neither expires.
+async fn renew_vault_leases(
+    addr: &str,
+    token: &str,
+    lease_id: Option<&str>,
+) -> Result<Option<Duration>, String> {
+    let mut ttls = Vec::new();
+
+    let lookup = vault_request(
+        SECRETS_CLIENT.get(format!("{}/v1/auth/token/lookup-self", addr)),
+        token,
+    )
+    .await?;
+
+    if lookup["data"]["renewable"] == true {
+        let renewed = vault_request(
+            SECRETS_CLIENT.post(format!("{}/v1/auth/token/renew-self", addr)),
+            token,
+        )
+        .await?;
+
+        ttls.extend(renewed["auth"]["lease_duration"].as_u64());
+    }
+
+    if let Some(lease_id) = lease_id {
+        let renewed = vault_request(
+            SECRETS_CLIENT
+                .put(format!("{}/v1/sys/leases/renew", addr))
+                .json(&json!({ "lease_id": lease_id })),
+            token,
+        )
+        .await?;
+
+        ttls.extend(renewed["lease_duration"].as_u64());
+    }
+
+    Ok(ttls
+        .into_iter()
+        .filter(|ttl| *ttl > 0)
+        .min()
+        .map(Duration::from_secs))
+}
+
+async fn vault_request(request: reqwest::RequestBuilder, token: &str) -> Result<Value, String> {
+    let response = request
+        .header("X-Vault-Token", token)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let status = response.status();
+    let response: Value = response.json().await.map_err(|e| e.to_string())?;
+
+    if !status.is_success() {
+        return Err(format!("Vault answered {} {}", status, response));
+    }
+
+    Ok(response)
+}
+
+/// Reads the AWS Secrets Manager secret `secret_id`, its `SecretString` a JSON object.
+async fn fetch_aws_secrets(
+    region: &str,
+    secret_id: &str,
+) -> Result<HashMap<String, String>, String> {
+    let response = aws_json_request(
+        region,
+        "secretsmanager",
+        "secretsmanager.GetSecretValue",
+        json!({ "SecretId": secret_id }),
+    )
+    .await?;
+
+    let secret_string = response["SecretString"]
+        .as_str()
+        .ok_or("the secret has no SecretString")?;
+    let data: Value = serde_json::from_str(secret_string)
+        .map_err(|e| format!("the SecretString isn't JSON: {}", e))?;
+
+    secret_fields(&data)
+}
+
+/// Fields of a secret as environment variables, non-string values as their JSON.
+fn secret_fields(data: &Value) -> Result<HashMap<String, String>, String> {
+    let fields = data.as_object().ok_or("the secret isn't a JSON object")?;
+
+    Ok(fields
+        .iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+
+            (name.clone(), value)
+        })
+        .collect())
+}
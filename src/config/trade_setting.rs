@@ -1,10 +1,8 @@
 use dotenvy::dotenv;
 use once_cell::sync::Lazy;
-use std::env;
-use std::fs::{File, OpenOptions};
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-use std::sync::{Arc, Mutex};
+use solana_sdk::pubkey::Pubkey;
+use std::{collections::HashMap, env, str::FromStr};
+use tracing::warn;
 
 pub static CONFIRM_SERVICE: Lazy<String> =
     Lazy::new(|| env::var("CONFIRM_SERVICE").expect("CONFIRM_SERVICE must be set"));
@@ -36,13 +34,24 @@ pub static BUY_SOL_AMOUNT: Lazy<u64> = Lazy::new(|| {
     let val = env::var("BUY_SOL_AMOUNT").expect("Missing env var: BUY_SOL_AMOUNT");
 
     let buy_sol_amount = val.parse::<f64>().unwrap_or_else(|e| {
-        eprintln!("Invalid BUY_SOL_AMOUNT '{}': {}", val, e);
+        warn!("Invalid BUY_SOL_AMOUNT '{}': {}", val, e);
         std::process::exit(1);
     });
 
     (buy_sol_amount * 10_f64.powf(9.0)) as u64
 });
 
+pub static BUY_SPLIT_THRESHOLD: Lazy<Option<u64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Buys larger than this many SOL are split into transactions no larger than it
+    env::var("BUY_SPLIT_THRESHOLD_SOL")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+        .filter(|threshold| *threshold > 0.0)
+        .map(|threshold| (threshold * 10_f64.powf(9.0)) as u64)
+});
+
 pub static SLIPPAGE: Lazy<f64> = Lazy::new(|| {
     dotenv().ok(); // load .env if available
 
@@ -50,3 +59,1043 @@ pub static SLIPPAGE: Lazy<f64> = Lazy::new(|| {
     let parsed: f64 = raw.parse().expect("Failed to parse SLIPPAGE");
     parsed / 100.0 // convert percent to decimal (e.g., 1.0 -> 0.01)
 });
+
+pub static BUY_SLIPPAGE: Lazy<f64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Slippage in percent on entries, SLIPPAGE when unset
+    env::var("BUY_SLIPPAGE")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+        .map(|percent| percent / 100.0)
+        .unwrap_or(*SLIPPAGE) // fallback if missing or invalid
+});
+
+pub static SELL_SLIPPAGE: Lazy<f64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Slippage in percent on exits, SLIPPAGE when unset
+    env::var("SELL_SLIPPAGE")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+        .map(|percent| percent / 100.0)
+        .unwrap_or(*SLIPPAGE) // fallback if missing or invalid
+});
+
+/// Buy and sell slippage overriding the global settings for one target
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SlippageOverride {
+    pub buy: Option<f64>,
+    pub sell: Option<f64>,
+}
+
+pub static TARGET_SLIPPAGE: Lazy<HashMap<Pubkey, SlippageOverride>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Per-target buy and sell slippage in percent, as "Wallet:buy:sell,Wallet:buy:sell";
+    // either side may be left empty to use the global setting
+    env::var("TARGET_SLIPPAGE")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.trim().split(':');
+            let wallet = Pubkey::from_str(parts.next()?.trim()).ok()?;
+            let mut percent = || {
+                parts
+                    .next()
+                    .and_then(|val| val.trim().parse::<f64>().ok())
+                    .map(|percent| percent / 100.0)
+            };
+            let buy = percent();
+            let sell = percent();
+
+            Some((wallet, SlippageOverride { buy, sell }))
+        })
+        .collect()
+});
+
+/// Buy slippage when copying `target`, its override or `BUY_SLIPPAGE`.
+pub fn buy_slippage(target: &Pubkey) -> f64 {
+    TARGET_SLIPPAGE
+        .get(target)
+        .and_then(|slippage| slippage.buy)
+        .unwrap_or(*BUY_SLIPPAGE)
+}
+
+/// Sell slippage when copying `target`, its override or `SELL_SLIPPAGE`.
+pub fn sell_slippage(target: &Pubkey) -> f64 {
+    TARGET_SLIPPAGE
+        .get(target)
+        .and_then(|slippage| slippage.sell)
+        .unwrap_or(*SELL_SLIPPAGE)
+}
+
+pub static SIMULATE_BEFORE_SEND: Lazy<bool> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("SIMULATE_BEFORE_SEND")
+        .ok()
+        .and_then(|val| val.parse::<bool>().ok())
+        .unwrap_or(false) // simulation gating is opt-in
+});
+
+pub static SHADOW_MODE: Lazy<bool> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Build, sign and simulate every order but never submit it, recording the fill at its quote
+    env::var("SHADOW_MODE")
+        .ok()
+        .and_then(|val| val.parse::<bool>().ok())
+        .unwrap_or(false) // fallback if missing or invalid
+});
+
+pub static SIMULATION_SLIPPAGE_BUMP: Lazy<Option<f64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Extra slippage in percent applied once when simulation fails on a slippage error
+    env::var("SIMULATION_SLIPPAGE_BUMP")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+        .map(|bump| bump / 100.0)
+});
+
+pub static WALLET_BALANCE_REFRESH_SEC: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("WALLET_BALANCE_REFRESH_SEC")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(5) // fallback if missing or invalid
+});
+
+pub static WALLET_MAX_PENDING: Lazy<usize> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Transactions a trading wallet may have in flight before buys skip it; 0 for no limit
+    env::var("WALLET_MAX_PENDING")
+        .ok()
+        .and_then(|val| val.parse::<usize>().ok())
+        .unwrap_or(0) // fallback if missing or invalid
+});
+
+pub static LEDGER_DEVICE: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // hidraw device of the Ledger, e.g. /dev/hidraw0; found by its vendor id when unset
+    env::var("LEDGER_DEVICE")
+        .ok()
+        .filter(|device| !device.is_empty())
+});
+
+pub static LEDGER_SIGN_TIMEOUT_SEC: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Seconds to wait for a transaction to be approved on the Ledger
+    env::var("LEDGER_SIGN_TIMEOUT_SEC")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(60) // fallback if missing or invalid
+});
+
+pub static LEDGER_MAX_SIGNATURES_PER_HOUR: Lazy<usize> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Signatures the Ledger is asked for per rolling hour, beyond which trades are refused
+    env::var("LEDGER_MAX_SIGNATURES_PER_HOUR")
+        .ok()
+        .and_then(|val| val.parse::<usize>().ok())
+        .unwrap_or(60) // fallback if missing or invalid
+});
+
+pub static LEDGER_MAX_TRADE_SOL: Lazy<Option<f64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Largest buy in SOL a Ledger wallet signs; unset leaves it uncapped
+    env::var("LEDGER_MAX_TRADE_SOL")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+});
+
+pub static REMOTE_SIGNER_TIMEOUT_MS: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Milliseconds to wait for a remote signer to sign or report its key
+    env::var("REMOTE_SIGNER_TIMEOUT_MS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(2000) // fallback if missing or invalid
+});
+
+pub static LOW_BALANCE_WARN_SOL: Lazy<Option<f64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // SOL of the fee payer it's alerted on below; unset disables the warning
+    env::var("LOW_BALANCE_WARN_SOL")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+});
+
+pub static LOW_BALANCE_HALT_SOL: Lazy<Option<f64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // SOL of the fee payer buying stops below, leaving it for exits; unset disables the halt
+    env::var("LOW_BALANCE_HALT_SOL")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+});
+
+pub static TOP_UP_THRESHOLD_SOL: Lazy<Option<f64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // SOL the fee payer and trading wallets are topped up below; unset disables top-ups
+    env::var("TOP_UP_THRESHOLD_SOL")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+});
+
+pub static TOP_UP_AMOUNT_SOL: Lazy<f64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // SOL transferred from the reserve per top-up
+    env::var("TOP_UP_AMOUNT_SOL")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+        .unwrap_or(0.5) // fallback if missing or invalid
+});
+
+pub static TOP_UP_MAX_PER_DAY: Lazy<usize> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Top-ups a wallet gets per rolling 24 hours, so a leak can't drain the reserve
+    env::var("TOP_UP_MAX_PER_DAY")
+        .ok()
+        .and_then(|val| val.parse::<usize>().ok())
+        .unwrap_or(3) // fallback if missing or invalid
+});
+
+pub static TOP_UP_CHECK_SEC: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("TOP_UP_CHECK_SEC")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(30) // fallback if missing or invalid
+});
+
+pub static RESERVE_ADDRESS: Lazy<Option<Pubkey>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Cold reserve wallet low balance alerts point to when no RESERVE_PRIVATE_KEY is set
+    env::var("RESERVE_ADDRESS")
+        .ok()
+        .and_then(|address| Pubkey::from_str(address.trim()).ok())
+});
+
+pub static PROFIT_SWEEP_ADDRESS: Lazy<Option<Pubkey>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Cold wallet profits above PROFIT_SWEEP_FLOOR_SOL are swept to; unset disables sweeps
+    env::var("PROFIT_SWEEP_ADDRESS")
+        .ok()
+        .and_then(|address| Pubkey::from_str(address.trim()).ok())
+});
+
+pub static PROFIT_SWEEP_FLOOR_SOL: Lazy<f64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Working capital each trading wallet keeps after a sweep
+    env::var("PROFIT_SWEEP_FLOOR_SOL")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+        .unwrap_or(1.0) // fallback if missing or invalid
+});
+
+pub static PROFIT_SWEEP_MIN_SOL: Lazy<f64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Least SOL above the floor worth sweeping
+    env::var("PROFIT_SWEEP_MIN_SOL")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+        .unwrap_or(0.1) // fallback if missing or invalid
+});
+
+pub static PROFIT_SWEEP_CHECK_SEC: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("PROFIT_SWEEP_CHECK_SEC")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(300) // fallback if missing or invalid
+});
+
+pub static MAX_SPEND_PER_TRADE_SOL: Lazy<Option<f64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Most SOL a single buy may spend; unset for no limit
+    env::var("MAX_SPEND_PER_TRADE_SOL")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+});
+
+pub static MAX_SPEND_PER_HOUR_SOL: Lazy<Option<f64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Most SOL buys may spend over a rolling hour; unset for no limit
+    env::var("MAX_SPEND_PER_HOUR_SOL")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+});
+
+pub static MAX_SPEND_PER_DAY_SOL: Lazy<Option<f64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Most SOL buys may spend over a rolling 24 hours; unset for no limit
+    env::var("MAX_SPEND_PER_DAY_SOL")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+});
+
+pub static EXTRA_ALLOWED_PROGRAMS: Lazy<Vec<Pubkey>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Programs outgoing transactions may call on top of the built-in allow-list
+    env::var("EXTRA_ALLOWED_PROGRAMS")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|program_id| Pubkey::from_str(program_id.trim()).ok())
+        .collect()
+});
+
+pub static MANUAL_CONFIRM_ABOVE_SOL: Lazy<Option<f64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Manual buys and sells worth more SOL need a confirmation code; unset for none
+    env::var("MANUAL_CONFIRM_ABOVE_SOL")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+});
+
+pub static MANUAL_CONFIRM_TTL_SEC: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Seconds a manual trade's confirmation code stays valid
+    env::var("MANUAL_CONFIRM_TTL_SEC")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(60) // fallback if missing or invalid
+});
+
+pub static PAPER_TRADING: Lazy<bool> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Fill orders against virtual balances at their quote instead of sending them
+    env::var("PAPER_TRADING")
+        .ok()
+        .and_then(|val| val.parse::<bool>().ok())
+        .unwrap_or(false) // fallback if missing or invalid
+});
+
+pub static READ_ONLY: Lazy<bool> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Paper trade with a throwaway wallet in place of the configured keys, none is loaded
+    env::var("READ_ONLY")
+        .ok()
+        .and_then(|val| val.parse::<bool>().ok())
+        .unwrap_or(false) // fallback if missing or invalid
+});
+
+pub static PAPER_SOL_BALANCE: Lazy<f64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Virtual SOL each trading wallet starts paper trading with
+    env::var("PAPER_SOL_BALANCE")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+        .filter(|sol| *sol >= 0.0)
+        .unwrap_or(10.0) // fallback if missing or invalid
+});
+
+pub static PAPER_LANDING_MS: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Modeled time from submission to landing, added to the detection latency of a paper fill
+    env::var("PAPER_LANDING_MS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(400) // fallback if missing or invalid
+});
+
+pub static PAPER_SLIPPAGE_BPS_PER_100MS: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Adverse price move a paper fill gets per 100ms between the target's trade and ours
+    env::var("PAPER_SLIPPAGE_BPS_PER_100MS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(10) // fallback if missing or invalid
+});
+
+pub static SENDER_FAILOVER: Lazy<Vec<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Senders tried in order after CONFIRM_SERVICE, e.g. "JITO,ZERO_SLOT"
+    env::var("SENDER_FAILOVER")
+        .unwrap_or_default()
+        .split(',')
+        .map(|service| service.trim().to_uppercase())
+        .filter(|service| !service.is_empty() && *service != *CONFIRM_SERVICE)
+        .collect()
+});
+
+pub static SENDER_MAX_FAILURES: Lazy<u32> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("SENDER_MAX_FAILURES")
+        .ok()
+        .and_then(|val| val.parse::<u32>().ok())
+        .unwrap_or(3) // fallback if missing or invalid
+});
+
+pub static SENDER_SLOW_MS: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("SENDER_SLOW_MS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(1_000) // fallback if missing or invalid
+});
+
+pub static STALE_EVENT_DEADLINE_MS: Lazy<Option<u64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Buys detected later than this are skipped; unset disables the deadline
+    env::var("STALE_EVENT_DEADLINE_MS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+});
+
+pub static SENDER_TIMEOUT_MS: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("SENDER_TIMEOUT_MS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(3_000) // fallback if missing or invalid
+});
+
+pub static SENDER_CIRCUIT_COOLDOWN_SEC: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("SENDER_CIRCUIT_COOLDOWN_SEC")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(30) // fallback if missing or invalid
+});
+
+pub static SENDER_KEEP_WARM_SEC: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // 0 disables the keep-warm requests
+    env::var("SENDER_KEEP_WARM_SEC")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(15) // fallback if missing or invalid
+});
+
+pub static LAND_TIMEOUT_SEC: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // How long a sent transaction is watched before counting it as not landed
+    env::var("LAND_TIMEOUT_SEC")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(30) // fallback if missing or invalid
+});
+
+pub static SHUTDOWN_TIMEOUT_SEC: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Longest a shutdown waits for in-flight orders to go out and confirm
+    env::var("SHUTDOWN_TIMEOUT_SEC")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(60) // fallback if missing or invalid
+});
+
+pub static WATCHDOG_STALL_SEC: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Seconds without progress before a stalled pipeline is rebuilt, 0 disables
+    env::var("WATCHDOG_STALL_SEC")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(120) // fallback if missing or invalid
+});
+
+pub static RPC_POLL_INTERVAL_MS: Lazy<Option<u64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Enables the getSignaturesForAddress polling datasource; unset disables it
+    env::var("RPC_POLL_INTERVAL_MS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .filter(|interval| *interval > 0)
+});
+
+pub static GRPC_RECONNECT_BASE_MS: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // First delay before reconnecting a dropped gRPC stream, doubled on every failed attempt
+    env::var("GRPC_RECONNECT_BASE_MS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(500) // fallback if missing or invalid
+});
+
+pub static GRPC_RECONNECT_MAX_MS: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("GRPC_RECONNECT_MAX_MS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(30_000) // fallback if missing or invalid
+});
+
+pub static DEDUP_TTL_SEC: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // How long a handled target transaction is remembered to drop duplicate deliveries
+    env::var("DEDUP_TTL_SEC")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(60) // fallback if missing or invalid
+});
+
+pub static EXTRA_ROUTER_PROGRAMS: Lazy<Vec<(String, Pubkey)>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Extra router programs that CPI into pumpfun, as "Name:ProgramId,Name:ProgramId"
+    env::var("EXTRA_ROUTER_PROGRAMS")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|entry| {
+            let (name, program_id) = entry.trim().split_once(':')?;
+            let program_id = Pubkey::from_str(program_id.trim()).ok()?;
+
+            Some((name.trim().to_string(), program_id))
+        })
+        .collect()
+});
+
+pub static LAUNCHPAD_PLATFORMS: Lazy<Vec<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // LaunchLab platforms to copy, e.g. "bonk.fun"; empty copies every platform
+    env::var("LAUNCHPAD_PLATFORMS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|platform| platform.trim().to_lowercase())
+        .filter(|platform| !platform.is_empty())
+        .collect()
+});
+
+pub static SNIPE_MODE: Lazy<bool> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("SNIPE_MODE")
+        .ok()
+        .and_then(|val| val.parse::<bool>().ok())
+        .unwrap_or(false) // sniping the target's launches is opt-in
+});
+
+pub static SNIPE_SOL_AMOUNT: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Size of a buy into a token the target just created
+    env::var("SNIPE_SOL_AMOUNT")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+        .filter(|amount| *amount > 0.0)
+        .map(|amount| (amount * 10_f64.powf(9.0)) as u64)
+        .unwrap_or(*BUY_SOL_AMOUNT / 2) // fallback if missing or invalid
+});
+
+pub static SNIPE_MAX_HOLD_SEC: Lazy<Option<u64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Sniped positions still held after this many seconds are sold
+    env::var("SNIPE_MAX_HOLD_SEC")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+});
+
+pub static LAUNCH_BUNDLE_SOL_AMOUNT: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Size of the bundled copy of a create + dev buy transaction
+    env::var("LAUNCH_BUNDLE_SOL_AMOUNT")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+        .filter(|amount| *amount > 0.0)
+        .map(|amount| (amount * 10_f64.powf(9.0)) as u64)
+        .unwrap_or(*BUY_SOL_AMOUNT) // fallback if missing or invalid
+});
+
+pub static LAUNCH_BUNDLE_TIP_SOL: Lazy<Option<f64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Jito tip of launch bundles, the regular tip of PRIORITY_FEE when unset
+    env::var("LAUNCH_BUNDLE_TIP_SOL")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+        .filter(|tip| *tip > 0.0)
+});
+
+pub static PUMPFUN_GLOBAL_REFRESH_SEC: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("PUMPFUN_GLOBAL_REFRESH_SEC")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(60) // fallback if missing or invalid
+});
+
+pub static WATCH_HOT_CURVES: Lazy<bool> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("WATCH_HOT_CURVES")
+        .ok()
+        .and_then(|val| val.parse::<bool>().ok())
+        .unwrap_or(false) // only held tokens are followed by default
+});
+
+pub static HOT_CURVE_LIMIT: Lazy<usize> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Most recent bonding curves of tokens the target traded to keep following
+    env::var("HOT_CURVE_LIMIT")
+        .ok()
+        .and_then(|val| val.parse::<usize>().ok())
+        .unwrap_or(20) // fallback if missing or invalid
+});
+
+pub static SLOT_LAG_ALERT_SLOTS: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Slots a datasource may trail the chain tip before it's reported as lagging
+    env::var("SLOT_LAG_ALERT_SLOTS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(8) // fallback if missing or invalid
+});
+
+pub static PAUSE_ON_SLOT_LAG: Lazy<bool> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("PAUSE_ON_SLOT_LAG")
+        .ok()
+        .and_then(|val| val.parse::<bool>().ok())
+        .unwrap_or(false) // lag is only reported by default
+});
+
+pub static EXECUTION_BREAKER_FAILURES: Lazy<u32> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Consecutive unlanded copies, or failed simulations, that pause buying; 0 disables it
+    env::var("EXECUTION_BREAKER_FAILURES")
+        .ok()
+        .and_then(|val| val.parse::<u32>().ok())
+        .unwrap_or(10) // fallback if missing or invalid
+});
+
+pub static FOLLOWED_VENUES: Lazy<Vec<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Venues the target is followed on, e.g. "pumpfun,pump_swap"; empty follows all of them
+    env::var("FOLLOWED_VENUES")
+        .unwrap_or_default()
+        .split(',')
+        .map(|venue| venue.trim().to_lowercase())
+        .filter(|venue| !venue.is_empty())
+        .collect()
+});
+
+pub static ARRIVAL_STATS_INTERVAL_SEC: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // How often to report which datasource delivers the target's transactions first
+    env::var("ARRIVAL_STATS_INTERVAL_SEC")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(300) // fallback if missing or invalid
+});
+
+pub static PENDING_ACTION_TTL_SEC: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // How long a copy of a target transaction blocks further copies of the same trade
+    env::var("PENDING_ACTION_TTL_SEC")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(15) // fallback if missing or invalid
+});
+
+pub static GRPC_REPLAY_MAX_SLOTS: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Furthest back a LaserStream reconnect replays missed transactions from
+    env::var("GRPC_REPLAY_MAX_SLOTS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(3000) // fallback if missing or invalid
+});
+
+pub static TRADE_LOG_PATH: Lazy<String> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("TRADE_LOG_PATH").unwrap_or_else(|_| "trade_log.jsonl".to_string())
+});
+
+pub static POSITIONS_PATH: Lazy<String> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Open positions are saved here on shutdown and restored on the next start
+    env::var("POSITIONS_PATH").unwrap_or_else(|_| "positions.json".to_string())
+});
+
+pub static CONTROL_RATE_LIMIT_PER_MIN: Lazy<u32> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Control calls each token or Telegram chat may make per minute
+    env::var("CONTROL_RATE_LIMIT_PER_MIN")
+        .ok()
+        .and_then(|val| val.parse::<u32>().ok())
+        .unwrap_or(120) // fallback if missing or invalid
+});
+
+pub static AUDIT_LOG_PATH: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Hash-chained log of every transaction the bot signs and control call; unset disables it
+    env::var("AUDIT_LOG_PATH")
+        .ok()
+        .filter(|path| !path.is_empty())
+});
+
+pub static TARGETS_PATH: Lazy<String> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Wallets followed besides TARGET_WALLET, managed with `targets add` and `targets remove`
+    env::var("TARGETS_PATH").unwrap_or_else(|_| "targets.txt".to_string())
+});
+
+pub static BACKFILL_ON_STARTUP: Lazy<bool> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("BACKFILL_ON_STARTUP")
+        .ok()
+        .and_then(|val| val.parse::<bool>().ok())
+        .unwrap_or(true) // fallback if missing or invalid
+});
+
+pub static BACKFILL_SIGNATURE_LIMIT: Lazy<usize> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Most recent target transactions checked for trades missed during downtime
+    env::var("BACKFILL_SIGNATURE_LIMIT")
+        .ok()
+        .and_then(|val| val.parse::<usize>().ok())
+        .unwrap_or(100) // fallback if missing or invalid
+});
+
+pub static DYNAMIC_SLIPPAGE: Lazy<bool> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("DYNAMIC_SLIPPAGE")
+        .ok()
+        .and_then(|val| val.parse::<bool>().ok())
+        .unwrap_or(false) // static slippage by default
+});
+
+pub static SLIPPAGE_IMPACT_MULTIPLIER: Lazy<f64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Dynamic slippage as a multiple of the trade's own price impact on the curve
+    env::var("SLIPPAGE_IMPACT_MULTIPLIER")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+        .filter(|multiplier| *multiplier > 0.0)
+        .unwrap_or(2.0) // fallback if missing or invalid
+});
+
+pub static MIN_SLIPPAGE: Lazy<f64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Bounds of the dynamic slippage in percent
+    env::var("MIN_SLIPPAGE")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+        .map(|percent| percent / 100.0)
+        .unwrap_or(0.005) // fallback if missing or invalid
+});
+
+pub static MAX_SLIPPAGE: Lazy<f64> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("MAX_SLIPPAGE")
+        .ok()
+        .and_then(|val| val.parse::<f64>().ok())
+        .map(|percent| percent / 100.0)
+        .unwrap_or(0.3) // fallback if missing or invalid
+});
+
+pub static IN_FLIGHT_BUY_TTL_MS: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // How long our own sent buys are applied on top of the observed curve reserves
+    env::var("IN_FLIGHT_BUY_TTL_MS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(2000) // fallback if missing or invalid
+});
+
+pub static EXECUTION_WORKERS: Lazy<usize> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Copy orders are sharded by mint across this many workers
+    env::var("EXECUTION_WORKERS")
+        .ok()
+        .and_then(|val| val.parse::<usize>().ok())
+        .filter(|workers| *workers > 0)
+        .unwrap_or(4) // fallback if missing or invalid
+});
+
+pub static EXECUTION_QUEUE_CAPACITY: Lazy<usize> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Copy jobs each execution worker may have waiting before the drop policy kicks in
+    env::var("EXECUTION_QUEUE_CAPACITY")
+        .ok()
+        .and_then(|val| val.parse::<usize>().ok())
+        .filter(|capacity| *capacity > 0)
+        .unwrap_or(32) // fallback if missing or invalid
+});
+
+pub static EXECUTION_DROP_POLICY: Lazy<String> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Which waiting job gives way to a new one when a worker queue is full
+    env::var("EXECUTION_DROP_POLICY")
+        .map(|val| val.trim().to_lowercase())
+        .unwrap_or_else(|_| "drop_oldest".to_string())
+});
+
+pub static EXECUTION_MAX_QUEUE_MS: Lazy<Option<u64>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Buys that waited longer than this in a worker queue are dropped as stale
+    env::var("EXECUTION_MAX_QUEUE_MS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+});
+
+pub static BLOCKHASH_REFRESH_SLOTS: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // The blockhash is refetched once the chain tip is this many slots past it
+    env::var("BLOCKHASH_REFRESH_SLOTS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .filter(|slots| *slots > 0)
+        .unwrap_or(4) // fallback if missing or invalid
+});
+
+pub static BLOCKHASH_POLL_MS: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Without slot updates for this long, the slot and blockhash are polled from the RPC
+    env::var("BLOCKHASH_POLL_MS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(2000) // fallback if missing or invalid
+});
+
+pub static BLOCKHASH_MAX_AGE_MS: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Sends are refused while the latest blockhash is older than this
+    env::var("BLOCKHASH_MAX_AGE_MS")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(20_000) // fallback if missing or invalid
+});
+
+pub static EXECUTION_RUNTIME_THREADS: Lazy<usize> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Threads of the dedicated runtime the execution workers run on
+    env::var("EXECUTION_RUNTIME_THREADS")
+        .ok()
+        .and_then(|val| val.parse::<usize>().ok())
+        .filter(|threads| *threads > 0)
+        .unwrap_or(2) // fallback if missing or invalid
+});
+
+pub static EXECUTION_CORES: Lazy<Vec<usize>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // CPU cores the execution runtime threads are pinned to, e.g. "2,3"; empty pins nothing
+    env::var("EXECUTION_CORES")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|core| core.trim().parse::<usize>().ok())
+        .collect()
+});
+
+pub static LOG_DIR: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Directory JSON logs are written to next to stdout; unset logs to stdout only
+    env::var("LOG_DIR").ok().filter(|dir| !dir.is_empty())
+});
+
+pub static LOG_ROTATION: Lazy<String> = Lazy::new(|| {
+    dotenv().ok();
+
+    // When log files are rotated: "daily", or "size" once they reach LOG_MAX_FILE_MB
+    env::var("LOG_ROTATION").unwrap_or_else(|_| "daily".to_string())
+});
+
+pub static LOG_MAX_FILE_MB: Lazy<u64> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("LOG_MAX_FILE_MB")
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .filter(|size| *size > 0)
+        .unwrap_or(100) // fallback if missing or invalid
+});
+
+pub static LOG_MAX_FILES: Lazy<usize> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Rotated log files kept, older ones are deleted
+    env::var("LOG_MAX_FILES")
+        .ok()
+        .and_then(|val| val.parse::<usize>().ok())
+        .unwrap_or(14) // fallback if missing or invalid
+});
+
+pub static JOURNAL_PATH: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // File every detection, skip decision, quote and send outcome is appended to; unset disables it
+    env::var("JOURNAL_PATH")
+        .ok()
+        .filter(|path| !path.is_empty())
+});
+
+pub static HEALTH_ADDR: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Address the /healthz and /readyz endpoints listen on, e.g. "0.0.0.0:8080"; unset disables them
+    env::var("HEALTH_ADDR").ok().filter(|addr| !addr.is_empty())
+});
+
+pub static CONTROL_API_ADDR: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Address the REST control API listens on, e.g. "127.0.0.1:8090"; unset disables it
+    env::var("CONTROL_API_ADDR")
+        .ok()
+        .filter(|addr| !addr.is_empty())
+});
+
+pub static CONTROL_GRPC_ADDR: Lazy<Option<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Address the gRPC control service listens on, e.g. "127.0.0.1:50051"; unset disables it
+    env::var("CONTROL_GRPC_ADDR")
+        .ok()
+        .filter(|addr| !addr.is_empty())
+});
+
+pub static TELEGRAM_EVENTS: Lazy<Vec<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Telegram notifications to send, separated by commas: "copy", "exit" and "error"
+    env::var("TELEGRAM_EVENTS")
+        .unwrap_or_else(|_| "copy,exit,error".to_string())
+        .split(',')
+        .map(|event| event.trim().to_lowercase())
+        .filter(|event| !event.is_empty())
+        .collect()
+});
+
+pub static TELEGRAM_COMMANDS: Lazy<bool> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("TELEGRAM_COMMANDS")
+        .ok()
+        .and_then(|val| val.parse::<bool>().ok())
+        .unwrap_or(false) // the bot only sends notifications by default
+});
+
+pub static SLACK_EVENTS: Lazy<Vec<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // Slack notifications to send, separated by commas: "copy", "exit" and "error"
+    env::var("SLACK_EVENTS")
+        .unwrap_or_else(|_| "copy,exit,error".to_string())
+        .split(',')
+        .map(|event| event.trim().to_lowercase())
+        .filter(|event| !event.is_empty())
+        .collect()
+});
+
+pub static WEBHOOK_URLS: Lazy<Vec<String>> = Lazy::new(|| {
+    dotenv().ok();
+
+    // URLs every trade and position event is POSTed to as JSON, separated by commas
+    env::var("WEBHOOK_URLS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+        .collect()
+});
+
+pub static METRICS_SINK: Lazy<String> = Lazy::new(|| {
+    dotenv().ok();
+
+    // External metrics backend next to the log metrics: "statsd", "influxdb" or empty for none
+    env::var("METRICS_SINK").unwrap_or_default()
+});
+
+pub static STATSD_ADDR: Lazy<String> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("STATSD_ADDR").unwrap_or_else(|_| "127.0.0.1:8125".to_string())
+});
+
+pub static STATSD_PREFIX: Lazy<String> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("STATSD_PREFIX").unwrap_or_else(|_| "pumpfun_monitor".to_string())
+});
+
+pub static INFLUXDB_ORG: Lazy<String> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("INFLUXDB_ORG").unwrap_or_default()
+});
+
+pub static INFLUXDB_BUCKET: Lazy<String> = Lazy::new(|| {
+    dotenv().ok();
+
+    env::var("INFLUXDB_BUCKET").unwrap_or_else(|_| "pumpfun_monitor".to_string())
+});
@@ -0,0 +1,149 @@
+use carbon_core::metrics::MetricsCollection;
+use once_cell::sync::Lazy;
+use solana_sdk::signature::Signature;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tokio::time::sleep;
+use tracing::info;
+
+use crate::config::{ARRIVAL_STATS_INTERVAL_SEC, DEDUP_TTL_SEC};
+
+/// How often a datasource delivered a transaction first, and how far behind the first
+/// source it was when it didn't.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ArrivalStats {
+    pub first: u64,
+    pub late: u64,
+    pub total_delay_ms: u64,
+}
+
+impl ArrivalStats {
+    pub fn win_rate(&self) -> f64 {
+        self.first as f64 / (self.first + self.late).max(1) as f64
+    }
+
+    pub fn avg_delay_ms(&self) -> u64 {
+        self.total_delay_ms / self.late.max(1)
+    }
+}
+
+/// Tracks which datasource delivers each target transaction first. The processors
+/// already act on the first arrival only, so this is purely to tell which source wins.
+pub struct ArrivalTracker {
+    ttl: Duration,
+    first_arrivals: Mutex<HashMap<Signature, Instant>>,
+    stats: Mutex<HashMap<String, ArrivalStats>>,
+}
+
+pub static ARRIVALS: Lazy<ArrivalTracker> =
+    Lazy::new(|| ArrivalTracker::new(Duration::from_secs(*DEDUP_TTL_SEC)));
+
+impl ArrivalTracker {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            first_arrivals: Mutex::new(HashMap::new()),
+            stats: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records `datasource` delivering `signature` and returns how long after the first
+    /// arrival it came, `None` when it was first.
+    pub fn record(&self, datasource: &str, signature: Signature) -> Option<Duration> {
+        let now = Instant::now();
+
+        let delay = {
+            let mut first_arrivals = self.first_arrivals.lock().unwrap();
+
+            first_arrivals.retain(|_, arrived_at| now.duration_since(*arrived_at) < self.ttl);
+
+            match first_arrivals.get(&signature) {
+                Some(arrived_at) => Some(now.duration_since(*arrived_at)),
+                None => {
+                    first_arrivals.insert(signature, now);
+                    None
+                }
+            }
+        };
+
+        let mut stats = self.stats.lock().unwrap();
+        let stats = stats.entry(datasource.to_string()).or_default();
+
+        match delay {
+            Some(delay) => {
+                stats.late += 1;
+                stats.total_delay_ms += delay.as_millis() as u64;
+            }
+            None => stats.first += 1,
+        }
+
+        delay
+    }
+
+    /// When `signature` first arrived from any datasource.
+    pub fn first_arrival(&self, signature: &Signature) -> Option<Instant> {
+        self.first_arrivals.lock().unwrap().get(signature).copied()
+    }
+
+    pub fn stats(&self) -> Vec<(String, ArrivalStats)> {
+        let mut stats: Vec<(String, ArrivalStats)> = self
+            .stats
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, stats)| (name.clone(), *stats))
+            .collect();
+
+        stats.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.first));
+
+        stats
+    }
+}
+
+/// Records a transaction arrival for `datasource` in `ARRIVALS` and the metrics.
+pub async fn record_arrival(metrics: &MetricsCollection, datasource: &str, signature: Signature) {
+    match ARRIVALS.record(datasource, signature) {
+        Some(delay) => {
+            metrics
+                .record_histogram(
+                    &format!("{}_arrival_delay_ms", datasource),
+                    delay.as_millis() as f64,
+                )
+                .await
+                .ok();
+        }
+        None => {
+            metrics
+                .increment_counter(&format!("{}_first_arrivals", datasource), 1)
+                .await
+                .ok();
+        }
+    }
+}
+
+/// Periodically prints which datasource delivers the target's transactions first, once
+/// more than one of them is connected.
+pub async fn arrival_stats_handler() {
+    loop {
+        sleep(Duration::from_secs(*ARRIVAL_STATS_INTERVAL_SEC)).await;
+
+        let stats = ARRIVALS.stats();
+
+        if stats.len() < 2 {
+            continue;
+        }
+
+        for (name, stats) in stats {
+            info!(
+                "Datasource {} : first in {:.1}% of {} transactions, {}ms behind on average otherwise",
+                name,
+                stats.win_rate() * 100.0,
+                stats.first + stats.late,
+                stats.avg_delay_ms()
+            );
+        }
+    }
+}
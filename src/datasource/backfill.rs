@@ -0,0 +1,128 @@
+use async_trait::async_trait;
+use carbon_core::{
+    datasource::{Datasource, DatasourceId, Update, UpdateType},
+    error::CarbonResult,
+    metrics::MetricsCollection,
+};
+use solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use std::{str::FromStr, sync::Arc};
+use tokio::sync::mpsc::Sender;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::{
+    config::BACKFILL_SIGNATURE_LIMIT,
+    datasource::fetch_transaction_update,
+    utils::{REPLAYED_TRANSACTIONS, load_trade_log},
+};
+
+/// One-shot datasource run at startup: the followed wallets' transactions since the
+/// newest trade log entry that aren't in the log were missed while the bot was down.
+/// They are fed through the processors as replayed, so missed sells are copied and
+/// positions closed, while missed buys are never bought late.
+pub struct BackfillDatasource {
+    pub rpc_client: Arc<RpcClient>,
+    pub wallets: Vec<Pubkey>,
+}
+
+impl BackfillDatasource {
+    pub fn new(rpc_client: Arc<RpcClient>, wallets: Vec<Pubkey>) -> Self {
+        Self {
+            rpc_client,
+            wallets,
+        }
+    }
+}
+
+#[async_trait]
+impl Datasource for BackfillDatasource {
+    async fn consume(
+        &self,
+        id: DatasourceId,
+        sender: Sender<(Update, DatasourceId)>,
+        cancellation_token: CancellationToken,
+        _metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        // Without a trade log there is nothing to tell missed transactions apart
+        let Some((logged, last_timestamp)) = load_trade_log() else {
+            info!("No trade log yet, skipping backfill");
+            return Ok(());
+        };
+
+        for wallet in &self.wallets {
+            let missed = match missed_signatures(&self.rpc_client, wallet, last_timestamp).await {
+                Ok(signatures) => signatures
+                    .into_iter()
+                    .filter(|signature| !logged.contains(signature))
+                    .collect::<Vec<_>>(),
+                Err(e) => {
+                    warn!("Backfill for {} failed: {}", wallet, e);
+                    continue;
+                }
+            };
+
+            info!(
+                "Backfilling {} transactions of {} missed since the last run",
+                missed.len(),
+                wallet
+            );
+
+            // Oldest first so trades replay in the order they happened
+            for signature in missed.iter().rev() {
+                if cancellation_token.is_cancelled() {
+                    return Ok(());
+                }
+
+                let Some(update) = fetch_transaction_update(&self.rpc_client, signature).await
+                else {
+                    continue;
+                };
+
+                REPLAYED_TRANSACTIONS.mark(*signature);
+
+                if sender
+                    .send((Update::Transaction(Box::new(update)), id.clone()))
+                    .await
+                    .is_err()
+                {
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn update_types(&self) -> Vec<UpdateType> {
+        vec![UpdateType::Transaction]
+    }
+}
+
+/// Successful transactions of `wallet` since `since` (unix seconds), newest first.
+async fn missed_signatures(
+    rpc_client: &RpcClient,
+    wallet: &Pubkey,
+    since: i64,
+) -> anyhow::Result<Vec<Signature>> {
+    let statuses = rpc_client
+        .get_signatures_for_address_with_config(
+            wallet,
+            GetConfirmedSignaturesForAddress2Config {
+                before: None,
+                until: None,
+                limit: Some(*BACKFILL_SIGNATURE_LIMIT),
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )
+        .await?;
+
+    Ok(statuses
+        .iter()
+        .filter(|status| status.err.is_none())
+        .filter(|status| status.block_time.is_some_and(|time| time >= since))
+        .filter_map(|status| Signature::from_str(&status.signature).ok())
+        .collect())
+}
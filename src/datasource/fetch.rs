@@ -0,0 +1,44 @@
+use carbon_core::{
+    datasource::TransactionUpdate, transformers::transaction_metadata_from_original_meta,
+};
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcTransactionConfig};
+use solana_sdk::{commitment_config::CommitmentConfig, signature::Signature};
+use solana_transaction_status_client_types::UiTransactionEncoding;
+use tracing::warn;
+
+/// Fetches a confirmed transaction over RPC and converts it into the update type the
+/// carbon pipeline consumes. Used by the datasources that only learn signatures.
+pub async fn fetch_transaction_update(
+    rpc_client: &RpcClient,
+    signature: &Signature,
+) -> Option<TransactionUpdate> {
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+
+    let confirmed = match rpc_client
+        .get_transaction_with_config(signature, config)
+        .await
+    {
+        Ok(confirmed) => confirmed,
+        Err(e) => {
+            warn!("Failed to fetch transaction {}: {}", signature, e);
+            return None;
+        }
+    };
+
+    let transaction = confirmed.transaction.transaction.decode()?;
+    let meta = transaction_metadata_from_original_meta(confirmed.transaction.meta?).ok()?;
+
+    Some(TransactionUpdate {
+        signature: *signature,
+        transaction,
+        meta,
+        is_vote: false,
+        slot: confirmed.slot,
+        block_time: confirmed.block_time,
+        block_hash: None,
+    })
+}
@@ -0,0 +1,65 @@
+use carbon_pump_swap_decoder::PROGRAM_ID as PUMP_SWAP_PROGRAM_ID;
+use carbon_pumpfun_decoder::PROGRAM_ID as PUMPFUN_PROGRAM_ID;
+use carbon_raydium_amm_v4_decoder::PROGRAM_ID as RAYDIUM_AMM_PROGRAM_ID;
+use carbon_raydium_cpmm_decoder::PROGRAM_ID as RAYDIUM_CPMM_PROGRAM_ID;
+use carbon_raydium_launchpad_decoder::PROGRAM_ID as RAYDIUM_LAUNCHPAD_PROGRAM_ID;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use tracing::warn;
+use yellowstone_grpc_proto::geyser::SubscribeRequestFilterTransactions;
+
+use crate::{config::FOLLOWED_VENUES, decoder::METEORA_DBC_PROGRAM_ID};
+
+/// Venues the target can be followed on, by `FOLLOWED_VENUES` name
+pub const VENUES: [(&str, Pubkey); 6] = [
+    ("pumpfun", PUMPFUN_PROGRAM_ID),
+    ("pump_swap", PUMP_SWAP_PROGRAM_ID),
+    ("raydium_amm", RAYDIUM_AMM_PROGRAM_ID),
+    ("raydium_cpmm", RAYDIUM_CPMM_PROGRAM_ID),
+    ("raydium_launchpad", RAYDIUM_LAUNCHPAD_PROGRAM_ID),
+    ("meteora_dbc", METEORA_DBC_PROGRAM_ID),
+];
+
+/// Whether the venue is followed, every venue is when `FOLLOWED_VENUES` is empty.
+pub fn is_followed_venue(name: &str) -> bool {
+    FOLLOWED_VENUES.is_empty() || FOLLOWED_VENUES.iter().any(|venue| venue == name)
+}
+
+/// Program IDs of the followed venues.
+pub fn followed_programs() -> Vec<Pubkey> {
+    for venue in FOLLOWED_VENUES.iter() {
+        if !VENUES.iter().any(|(name, _)| name == venue) {
+            warn!("Unknown venue in FOLLOWED_VENUES, ignoring: {}", venue);
+        }
+    }
+
+    VENUES
+        .iter()
+        .filter(|(name, _)| is_followed_venue(name))
+        .map(|(_, program_id)| *program_id)
+        .collect()
+}
+
+/// Transaction filters matching transactions of any of `targets` that touch any of the
+/// followed venues. Required accounts must all be present while included ones need only
+/// one match, so each target is required by its own filter and the programs included.
+pub fn target_transaction_filters(
+    targets: &[Pubkey],
+) -> HashMap<String, SubscribeRequestFilterTransactions> {
+    targets
+        .iter()
+        .map(|target| {
+            (
+                format!("target_transaction_filter_{}", target),
+                SubscribeRequestFilterTransactions {
+                    vote: Some(false),
+                    failed: Some(false),
+                    account_include: followed_programs().iter().map(Pubkey::to_string).collect(),
+                    account_exclude: vec![],
+                    account_required: vec![target.to_string()],
+                    signature: None,
+                },
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,393 @@
+use async_trait::async_trait;
+use carbon_core::{
+    datasource::{AccountUpdate, Datasource, DatasourceId, TransactionUpdate, Update, UpdateType},
+    error::CarbonResult,
+    metrics::MetricsCollection,
+};
+use futures::{SinkExt, StreamExt};
+use solana_sdk::{account::Account, pubkey::Pubkey, signature::Signature};
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tokio::{
+    sync::mpsc::Sender,
+    time::{Duration, sleep},
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+use yellowstone_grpc_client::GeyserGrpcClient;
+use yellowstone_grpc_proto::{
+    convert_from::{create_tx_meta, create_tx_versioned},
+    geyser::{
+        CommitmentLevel, SubscribeRequest, SubscribeRequestFilterAccounts,
+        SubscribeRequestFilterSlots, SubscribeRequestFilterTransactions, SubscribeRequestPing,
+        SubscribeUpdateAccountInfo, SubscribeUpdateTransactionInfo, subscribe_update::UpdateOneof,
+    },
+    tonic::transport::ClientTlsConfig,
+};
+
+use crate::{
+    config::{GRPC_RECONNECT_BASE_MS, GRPC_RECONNECT_MAX_MS, GRPC_REPLAY_MAX_SLOTS},
+    datasource::{DATASOURCE_STATUS, FOLLOWED_TARGETS, record_arrival, target_transaction_filters},
+    utils::{CURVE_STATES, PUMPFUN_GLOBAL, REPLAYED_TRANSACTIONS, SLOT_LAG, current_slot},
+};
+
+/// Yellowstone gRPC transaction datasource that survives dropped connections: whenever
+/// the connection or the stream fails, it reconnects with exponential backoff and
+/// re-applies the transaction filters, instead of ending the subscription for good.
+/// With `with_curve_accounts`, it also streams the bonding curves in `CURVE_STATES`,
+/// resubscribing whenever that set changes. Slot updates are streamed as well to
+/// measure how far behind the chain tip the datasource is. With `with_replay`, a
+/// reconnect resumes from the last slot seen so missed transactions are backfilled.
+/// With `with_followed_targets`, the transaction filters follow `FOLLOWED_TARGETS`.
+pub struct ReconnectingGrpcDatasource {
+    pub name: String,
+    pub endpoint: String,
+    pub x_token: Option<String>,
+    pub commitment: Option<CommitmentLevel>,
+    pub transaction_filters: HashMap<String, SubscribeRequestFilterTransactions>,
+    pub watch_curves: bool,
+    pub replay_on_reconnect: bool,
+    pub follow_target: bool,
+    last_slot: AtomicU64,
+}
+
+impl ReconnectingGrpcDatasource {
+    pub fn new(
+        name: &str,
+        endpoint: String,
+        x_token: Option<String>,
+        commitment: Option<CommitmentLevel>,
+        transaction_filters: HashMap<String, SubscribeRequestFilterTransactions>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            endpoint,
+            x_token,
+            commitment,
+            transaction_filters,
+            watch_curves: false,
+            replay_on_reconnect: false,
+            follow_target: false,
+            last_slot: AtomicU64::new(0),
+        }
+    }
+
+    /// Also subscribes to the accounts of the bonding curves watched in `CURVE_STATES`.
+    pub fn with_curve_accounts(mut self) -> Self {
+        self.watch_curves = true;
+        self
+    }
+
+    /// Replays the transactions missed while disconnected on reconnect (LaserStream).
+    pub fn with_replay(mut self) -> Self {
+        self.replay_on_reconnect = true;
+        self
+    }
+
+    /// Filters transactions of `FOLLOWED_TARGETS` instead of the given filters, and
+    /// resubscribes when they change.
+    pub fn with_followed_targets(mut self) -> Self {
+        self.follow_target = true;
+        self
+    }
+
+    /// Slot to resume from after a reconnect, bounded by `GRPC_REPLAY_MAX_SLOTS`.
+    fn replay_from_slot(&self) -> Option<u64> {
+        let last_slot = self.last_slot.load(Ordering::Relaxed);
+
+        (self.replay_on_reconnect && last_slot > 0)
+            .then(|| last_slot.max(current_slot().saturating_sub(*GRPC_REPLAY_MAX_SLOTS)))
+    }
+
+    fn subscribe_request(&self, from_slot: Option<u64>) -> SubscribeRequest {
+        let mut accounts = HashMap::new();
+
+        // An account filter without any account would stream every account update
+        let curves = CURVE_STATES.watched_curves();
+
+        if self.watch_curves && !curves.is_empty() {
+            accounts.insert(
+                "bonding_curves".to_string(),
+                SubscribeRequestFilterAccounts {
+                    account: curves.iter().map(Pubkey::to_string).collect(),
+                    ..Default::default()
+                },
+            );
+        }
+
+        // Fee changes apply as soon as they land rather than at the next refresh
+        if self.watch_curves {
+            accounts.insert(
+                "pumpfun_global".to_string(),
+                SubscribeRequestFilterAccounts {
+                    account: vec![PUMPFUN_GLOBAL.to_string()],
+                    ..Default::default()
+                },
+            );
+        }
+
+        SubscribeRequest {
+            accounts,
+            slots: HashMap::from([(
+                "slots".to_string(),
+                SubscribeRequestFilterSlots {
+                    filter_by_commitment: Some(false),
+                    interslot_updates: Some(false),
+                },
+            )]),
+            transactions: if self.follow_target {
+                target_transaction_filters(&FOLLOWED_TARGETS.all())
+            } else {
+                self.transaction_filters.clone()
+            },
+            commitment: self.commitment.map(|commitment| commitment as i32),
+            from_slot,
+            ..Default::default()
+        }
+    }
+
+    /// Connects, subscribes and forwards updates until the stream ends or fails.
+    /// `received` is set once any update arrives, so the caller can reset its backoff.
+    async fn stream_once(
+        &self,
+        id: &DatasourceId,
+        sender: &Sender<(Update, DatasourceId)>,
+        cancellation_token: &CancellationToken,
+        metrics: &MetricsCollection,
+        received: &mut bool,
+    ) -> anyhow::Result<()> {
+        let mut geyser_client = GeyserGrpcClient::build_from_shared(self.endpoint.clone())?
+            .x_token(self.x_token.clone())?
+            .connect_timeout(Duration::from_secs(15))
+            .timeout(Duration::from_secs(15))
+            .tls_config(ClientTlsConfig::new().with_enabled_roots())?
+            .connect()
+            .await?;
+
+        let mut curve_changes = CURVE_STATES.subscribe_changes();
+        let mut target_changes = FOLLOWED_TARGETS.subscribe_changes();
+
+        // Transactions before the tip at reconnect time are replayed, not live
+        let from_slot = self.replay_from_slot();
+        let live_from_slot = current_slot();
+
+        let (mut subscribe_tx, mut stream) = geyser_client
+            .subscribe_with_request(Some(self.subscribe_request(from_slot)))
+            .await?;
+
+        if let Some(from_slot) = from_slot {
+            info!(
+                "{} replaying missed transactions from slot {}",
+                self.name, from_slot
+            );
+        }
+
+        info!("{} gRPC subscription started", self.name);
+
+        DATASOURCE_STATUS.set_connected(&self.name, true);
+
+        loop {
+            let message = tokio::select! {
+                _ = cancellation_token.cancelled() => return Ok(()),
+                message = stream.next() => match message {
+                    Some(message) => message?,
+                    None => anyhow::bail!("stream closed by the server"),
+                },
+                Ok(()) = curve_changes.changed(), if self.watch_curves => {
+                    // A new request replaces all filters of the subscription
+                    subscribe_tx.send(self.subscribe_request(None)).await?;
+                    continue;
+                }
+                Ok(()) = target_changes.changed(), if self.follow_target => {
+                    info!("{} following {} target(s)", self.name, FOLLOWED_TARGETS.all().len());
+                    subscribe_tx.send(self.subscribe_request(None)).await?;
+                    continue;
+                }
+            };
+
+            *received = true;
+
+            match message.update_oneof {
+                Some(UpdateOneof::Slot(slot_update)) => {
+                    self.last_slot
+                        .fetch_max(slot_update.slot, Ordering::Relaxed);
+
+                    if from_slot.is_some() && slot_update.slot < live_from_slot {
+                        continue;
+                    }
+
+                    let lag = SLOT_LAG.observe(&self.name, slot_update.slot);
+
+                    let _ = metrics
+                        .update_gauge(&format!("{}_slot_lag", self.name), lag as f64)
+                        .await;
+                }
+                Some(UpdateOneof::Transaction(transaction_update)) => {
+                    self.last_slot
+                        .fetch_max(transaction_update.slot, Ordering::Relaxed);
+
+                    let signature = transaction_update
+                        .transaction
+                        .as_ref()
+                        .and_then(|info| Signature::try_from(info.signature.as_slice()).ok());
+
+                    if from_slot.is_some() && transaction_update.slot < live_from_slot {
+                        if let Some(signature) = signature {
+                            REPLAYED_TRANSACTIONS.mark(signature);
+                        }
+                    } else {
+                        SLOT_LAG.observe(&self.name, transaction_update.slot);
+
+                        if let Some(signature) = signature {
+                            record_arrival(metrics, &self.name, signature).await;
+                        }
+                    }
+
+                    let Some(update) = transaction_info_to_update(
+                        transaction_update.transaction,
+                        transaction_update.slot,
+                    ) else {
+                        continue;
+                    };
+
+                    if sender
+                        .send((Update::Transaction(Box::new(update)), id.clone()))
+                        .await
+                        .is_err()
+                    {
+                        return Ok(());
+                    }
+                }
+                Some(UpdateOneof::Account(account_update)) => {
+                    let Some(update) =
+                        account_info_to_update(account_update.account, account_update.slot)
+                    else {
+                        continue;
+                    };
+
+                    if sender
+                        .send((Update::Account(update), id.clone()))
+                        .await
+                        .is_err()
+                    {
+                        return Ok(());
+                    }
+                }
+                Some(UpdateOneof::Ping(_)) => {
+                    subscribe_tx
+                        .send(SubscribeRequest {
+                            ping: Some(SubscribeRequestPing { id: 1 }),
+                            ..Default::default()
+                        })
+                        .await?;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Datasource for ReconnectingGrpcDatasource {
+    async fn consume(
+        &self,
+        id: DatasourceId,
+        sender: Sender<(Update, DatasourceId)>,
+        cancellation_token: CancellationToken,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let mut backoff_ms = *GRPC_RECONNECT_BASE_MS;
+
+        while !cancellation_token.is_cancelled() {
+            let mut received = false;
+
+            let result = self
+                .stream_once(&id, &sender, &cancellation_token, &metrics, &mut received)
+                .await;
+
+            SLOT_LAG.forget(&self.name);
+            DATASOURCE_STATUS.set_connected(&self.name, false);
+
+            if cancellation_token.is_cancelled() || sender.is_closed() {
+                break;
+            }
+
+            if let Err(e) = result {
+                warn!("{} gRPC stream failed: {}", self.name, e);
+            }
+
+            // A connection that delivered updates was healthy, so start the backoff over
+            if received {
+                backoff_ms = *GRPC_RECONNECT_BASE_MS;
+            }
+
+            let _ = metrics
+                .increment_counter(&format!("{}_grpc_reconnects", self.name), 1)
+                .await;
+
+            info!("Reconnecting {} gRPC in {}ms", self.name, backoff_ms);
+
+            tokio::select! {
+                _ = cancellation_token.cancelled() => break,
+                _ = sleep(Duration::from_millis(backoff_ms)) => {}
+            }
+
+            backoff_ms = (backoff_ms * 2).min(*GRPC_RECONNECT_MAX_MS);
+        }
+
+        Ok(())
+    }
+
+    fn update_types(&self) -> Vec<UpdateType> {
+        if self.watch_curves {
+            vec![UpdateType::Transaction, UpdateType::AccountUpdate]
+        } else {
+            vec![UpdateType::Transaction]
+        }
+    }
+}
+
+fn transaction_info_to_update(
+    transaction_info: Option<SubscribeUpdateTransactionInfo>,
+    slot: u64,
+) -> Option<TransactionUpdate> {
+    let transaction_info = transaction_info?;
+
+    let signature = Signature::try_from(transaction_info.signature).ok()?;
+    let transaction = create_tx_versioned(transaction_info.transaction?).ok()?;
+    let meta = create_tx_meta(transaction_info.meta?).ok()?;
+
+    Some(TransactionUpdate {
+        signature,
+        transaction,
+        meta,
+        is_vote: transaction_info.is_vote,
+        slot,
+        block_time: None,
+        block_hash: None,
+    })
+}
+
+fn account_info_to_update(
+    account_info: Option<SubscribeUpdateAccountInfo>,
+    slot: u64,
+) -> Option<AccountUpdate> {
+    let account_info = account_info?;
+
+    Some(AccountUpdate {
+        pubkey: Pubkey::try_from(account_info.pubkey).ok()?,
+        account: Account {
+            lamports: account_info.lamports,
+            data: account_info.data,
+            owner: Pubkey::try_from(account_info.owner).ok()?,
+            executable: account_info.executable,
+            rent_epoch: account_info.rent_epoch,
+        },
+        slot,
+    })
+}
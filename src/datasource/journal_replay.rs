@@ -0,0 +1,132 @@
+use async_trait::async_trait;
+use carbon_core::{
+    datasource::{Datasource, DatasourceId, Update, UpdateType},
+    error::CarbonResult,
+    metrics::MetricsCollection,
+};
+use serde_json::Value;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::signature::Signature;
+use std::{
+    collections::HashSet, fs, io, ops::Range, path::Path, str::FromStr, sync::Arc, time::Duration,
+};
+use tokio::{sync::mpsc::Sender, time::sleep};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::{datasource::fetch_transaction_update, processor::ORDER_EXECUTOR};
+
+/// One-shot datasource feeding recorded target transactions back through the
+/// processors, fetched by signature in the order they were detected. It returns once
+/// the copies of the last one are submitted, which stops the pipeline.
+///
+/// Transactions are replayed as they landed, but pool and curve state the processors
+/// read over RPC is today's.
+pub struct JournalReplayDatasource {
+    pub rpc_client: Arc<RpcClient>,
+    pub signatures: Vec<Signature>,
+    /// Unix seconds the block time of a replayed transaction must fall in, end excluded
+    pub block_time_range: Option<Range<i64>>,
+}
+
+impl JournalReplayDatasource {
+    pub fn new(rpc_client: Arc<RpcClient>, signatures: Vec<Signature>) -> Self {
+        Self {
+            rpc_client,
+            signatures,
+            block_time_range: None,
+        }
+    }
+
+    /// Only replays the transactions with a block time in `range`, unix seconds.
+    pub fn with_block_time_range(mut self, range: Range<i64>) -> Self {
+        self.block_time_range = Some(range);
+        self
+    }
+}
+
+#[async_trait]
+impl Datasource for JournalReplayDatasource {
+    async fn consume(
+        &self,
+        id: DatasourceId,
+        sender: Sender<(Update, DatasourceId)>,
+        cancellation_token: CancellationToken,
+        _metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        for (index, signature) in self.signatures.iter().enumerate() {
+            if cancellation_token.is_cancelled() {
+                return Ok(());
+            }
+
+            let Some(update) = fetch_transaction_update(&self.rpc_client, signature).await else {
+                warn!(
+                    "Skipping {}, the transaction couldn't be fetched",
+                    signature
+                );
+                continue;
+            };
+
+            if let Some(range) = &self.block_time_range
+                && !update
+                    .block_time
+                    .is_some_and(|block_time| range.contains(&block_time))
+            {
+                continue;
+            }
+
+            info!(
+                "Replaying {} ({}/{})",
+                signature,
+                index + 1,
+                self.signatures.len()
+            );
+
+            if sender
+                .send((Update::Transaction(Box::new(update)), id.clone()))
+                .await
+                .is_err()
+            {
+                return Ok(());
+            }
+        }
+
+        // Orders are submitted off the pipeline, keep it up until they are through
+        while !ORDER_EXECUTOR.is_idle() && !cancellation_token.is_cancelled() {
+            sleep(Duration::from_millis(100)).await;
+        }
+
+        info!("Replayed {} transactions", self.signatures.len());
+
+        Ok(())
+    }
+
+    fn update_types(&self) -> Vec<UpdateType> {
+        vec![UpdateType::Transaction]
+    }
+}
+
+/// Target signatures to replay from `path`, once each in the order they first appear.
+/// Reads the `detection` entries of a decision journal, or a capture with one
+/// signature per line.
+pub fn load_replay_signatures(path: &Path) -> io::Result<Vec<Signature>> {
+    let content = fs::read_to_string(path)?;
+
+    let mut seen = HashSet::new();
+
+    Ok(content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+
+            match serde_json::from_str::<Value>(line) {
+                Ok(entry) if entry["event"] == "detection" => {
+                    Signature::from_str(entry["signature"].as_str()?).ok()
+                }
+                Ok(_) => None,
+                Err(_) => Signature::from_str(line).ok(),
+            }
+        })
+        .filter(|signature| seen.insert(*signature))
+        .collect())
+}
@@ -0,0 +1,21 @@
+pub mod arrival;
+pub mod backfill;
+pub mod fetch;
+pub mod filters;
+pub mod grpc;
+pub mod journal_replay;
+pub mod polling;
+pub mod status;
+pub mod target;
+pub mod websocket;
+
+pub use arrival::*;
+pub use backfill::*;
+pub use fetch::*;
+pub use filters::*;
+pub use grpc::*;
+pub use journal_replay::*;
+pub use polling::*;
+pub use status::*;
+pub use target::*;
+pub use websocket::*;
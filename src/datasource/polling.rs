@@ -0,0 +1,154 @@
+use async_trait::async_trait;
+use carbon_core::{
+    datasource::{Datasource, DatasourceId, Update, UpdateType},
+    error::CarbonResult,
+    metrics::MetricsCollection,
+};
+use solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use std::{str::FromStr, sync::Arc};
+use tokio::{
+    sync::mpsc::Sender,
+    time::{Duration, sleep},
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::datasource::{DATASOURCE_STATUS, fetch_transaction_update, record_arrival};
+
+/// Signatures requested per poll; more than this between two polls are dropped
+const POLL_SIGNATURE_LIMIT: usize = 50;
+
+/// Last-resort datasource that polls `getSignaturesForAddress` for the followed wallets
+/// and fetches every new transaction with `getTransaction`. Latency is bounded by the
+/// poll interval, but it only needs a plain HTTP RPC endpoint.
+pub struct RpcPollingDatasource {
+    pub rpc_client: Arc<RpcClient>,
+    pub wallets: Vec<Pubkey>,
+    pub poll_interval: Duration,
+}
+
+impl RpcPollingDatasource {
+    pub fn new(rpc_client: Arc<RpcClient>, wallets: Vec<Pubkey>, poll_interval: Duration) -> Self {
+        Self {
+            rpc_client,
+            wallets,
+            poll_interval,
+        }
+    }
+}
+
+#[async_trait]
+impl Datasource for RpcPollingDatasource {
+    async fn consume(
+        &self,
+        id: DatasourceId,
+        sender: Sender<(Update, DatasourceId)>,
+        cancellation_token: CancellationToken,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let mut handles = Vec::with_capacity(self.wallets.len());
+
+        for wallet in self.wallets.clone() {
+            let rpc_client = self.rpc_client.clone();
+            let poll_interval = self.poll_interval;
+            let sender = sender.clone();
+            let id = id.clone();
+            let cancellation_token = cancellation_token.clone();
+            let metrics = metrics.clone();
+
+            handles.push(tokio::spawn(async move {
+                info!("RPC polling started for {}", wallet);
+
+                let status_name = format!("rpc_polling {}", wallet);
+
+                // The newest signature seen so far; history before startup is never copied
+                let mut last_seen: Option<Signature> = None;
+                let mut initialized = false;
+
+                while !cancellation_token.is_cancelled() {
+                    match poll_new_signatures(&rpc_client, &wallet, last_seen).await {
+                        Ok((newest, signatures)) => {
+                            DATASOURCE_STATUS.set_connected(&status_name, true);
+                            last_seen = newest.or(last_seen);
+
+                            if initialized {
+                                // Oldest first so trades replay in the order they happened
+                                for signature in signatures.iter().rev() {
+                                    record_arrival(&metrics, "rpc_polling", *signature).await;
+
+                                    let Some(update) =
+                                        fetch_transaction_update(&rpc_client, signature).await
+                                    else {
+                                        continue;
+                                    };
+
+                                    if sender
+                                        .send((Update::Transaction(Box::new(update)), id.clone()))
+                                        .await
+                                        .is_err()
+                                    {
+                                        return;
+                                    }
+                                }
+                            }
+
+                            initialized = true;
+                        }
+                        Err(e) => {
+                            DATASOURCE_STATUS.set_connected(&status_name, false);
+                            warn!("RPC polling for {} failed: {}", wallet, e);
+                        }
+                    }
+
+                    tokio::select! {
+                        _ = cancellation_token.cancelled() => break,
+                        _ = sleep(poll_interval) => {}
+                    }
+                }
+            }));
+        }
+
+        futures::future::join_all(handles).await;
+
+        Ok(())
+    }
+
+    fn update_types(&self) -> Vec<UpdateType> {
+        vec![UpdateType::Transaction]
+    }
+}
+
+/// The newest signature after `until`, and the successful transactions among those
+/// signatures, newest first.
+async fn poll_new_signatures(
+    rpc_client: &RpcClient,
+    wallet: &Pubkey,
+    until: Option<Signature>,
+) -> anyhow::Result<(Option<Signature>, Vec<Signature>)> {
+    let statuses = rpc_client
+        .get_signatures_for_address_with_config(
+            wallet,
+            GetConfirmedSignaturesForAddress2Config {
+                before: None,
+                until,
+                limit: Some(POLL_SIGNATURE_LIMIT),
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )
+        .await?;
+
+    let newest = statuses
+        .first()
+        .and_then(|status| Signature::from_str(&status.signature).ok());
+
+    let signatures = statuses
+        .iter()
+        .filter(|status| status.err.is_none())
+        .filter_map(|status| Signature::from_str(&status.signature).ok())
+        .collect();
+
+    Ok((newest, signatures))
+}
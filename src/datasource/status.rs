@@ -0,0 +1,42 @@
+use once_cell::sync::Lazy;
+use std::{collections::HashSet, sync::RwLock};
+
+/// Streaming datasources that are currently connected, by name, e.g. `yellowstone` or
+/// `websocket <wallet>`. Reported by the health endpoint.
+pub struct DatasourceStatus {
+    connected: RwLock<HashSet<String>>,
+}
+
+pub static DATASOURCE_STATUS: Lazy<DatasourceStatus> = Lazy::new(DatasourceStatus::new);
+
+impl Default for DatasourceStatus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DatasourceStatus {
+    pub fn new() -> Self {
+        Self {
+            connected: RwLock::new(HashSet::new()),
+        }
+    }
+
+    pub fn set_connected(&self, datasource: &str, connected: bool) {
+        let mut datasources = self.connected.write().unwrap();
+
+        if connected {
+            datasources.insert(datasource.to_string());
+        } else {
+            datasources.remove(datasource);
+        }
+    }
+
+    /// Names of the connected datasources, sorted.
+    pub fn connected(&self) -> Vec<String> {
+        let mut connected: Vec<String> = self.connected.read().unwrap().iter().cloned().collect();
+
+        connected.sort();
+        connected
+    }
+}
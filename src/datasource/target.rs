@@ -0,0 +1,132 @@
+use once_cell::sync::Lazy;
+use solana_sdk::pubkey::Pubkey;
+use std::{fs, io, str::FromStr, sync::RwLock};
+use tokio::sync::watch;
+use tracing::warn;
+
+use crate::config::{TARGET_WALLET, TARGETS_PATH};
+
+/// The wallets being followed: `TARGET_WALLET` and those saved in `TARGETS_PATH` by
+/// `targets add`, until changed at runtime, e.g. by the Telegram `/settarget` command.
+/// gRPC datasources built `with_followed_targets` resubscribe whenever they change.
+pub struct FollowedTargets {
+    targets: RwLock<Vec<Pubkey>>,
+    changes: watch::Sender<u64>,
+}
+
+pub static FOLLOWED_TARGETS: Lazy<FollowedTargets> = Lazy::new(|| {
+    let mut targets = vec![*TARGET_WALLET];
+
+    for target in load_saved_targets() {
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+
+    FollowedTargets::new(targets)
+});
+
+impl FollowedTargets {
+    pub fn new(targets: Vec<Pubkey>) -> Self {
+        Self {
+            targets: RwLock::new(targets),
+            changes: watch::channel(0).0,
+        }
+    }
+
+    pub fn all(&self) -> Vec<Pubkey> {
+        self.targets.read().unwrap().clone()
+    }
+
+    /// The first followed wallet, that manual trades are attributed to.
+    pub fn primary(&self) -> Pubkey {
+        self.targets.read().unwrap()[0]
+    }
+
+    pub fn contains(&self, target: &Pubkey) -> bool {
+        self.targets.read().unwrap().contains(target)
+    }
+
+    /// Follows only `target` from now on. Returns whether that changed anything.
+    pub fn set(&self, target: Pubkey) -> bool {
+        self.update(|targets| {
+            if targets[..] == [target] {
+                return false;
+            }
+
+            *targets = vec![target];
+            true
+        })
+    }
+
+    /// Follows `target` too. Returns whether it wasn't followed yet.
+    pub fn add(&self, target: Pubkey) -> bool {
+        self.update(|targets| {
+            if targets.contains(&target) {
+                return false;
+            }
+
+            targets.push(target);
+            true
+        })
+    }
+
+    /// Stops following `target`, unless it's the last one. Returns whether it was
+    /// removed.
+    pub fn remove(&self, target: &Pubkey) -> bool {
+        self.update(|targets| {
+            if targets.len() == 1 || !targets.contains(target) {
+                return false;
+            }
+
+            targets.retain(|followed| followed != target);
+            true
+        })
+    }
+
+    fn update(&self, update: impl FnOnce(&mut Vec<Pubkey>) -> bool) -> bool {
+        let changed = update(&mut self.targets.write().unwrap());
+
+        if changed {
+            self.changes.send_modify(|version| *version += 1);
+        }
+
+        changed
+    }
+
+    /// Receiver notified whenever the followed targets change.
+    pub fn subscribe_changes(&self) -> watch::Receiver<u64> {
+        self.changes.subscribe()
+    }
+}
+
+/// Wallets saved in `TARGETS_PATH`, one base58 address per line, skipping blank lines
+/// and `#` comments. Empty when there is no such file.
+pub fn load_saved_targets() -> Vec<Pubkey> {
+    let Ok(content) = fs::read_to_string(TARGETS_PATH.as_str()) else {
+        return Vec::new();
+    };
+
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| match Pubkey::from_str(line) {
+            Ok(target) => Some(target),
+            Err(_) => {
+                warn!("Ignoring invalid wallet {} in {}", line, *TARGETS_PATH);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Overwrites `TARGETS_PATH` with `targets`.
+pub fn save_targets(targets: &[Pubkey]) -> io::Result<()> {
+    let content: String = targets
+        .iter()
+        .map(|target| format!("{}\n", target))
+        .collect();
+
+    fs::write(TARGETS_PATH.as_str(), content)
+}
@@ -0,0 +1,170 @@
+use async_trait::async_trait;
+use carbon_core::{
+    datasource::{Datasource, DatasourceId, Update, UpdateType},
+    error::CarbonResult,
+    metrics::MetricsCollection,
+};
+use futures::StreamExt;
+use solana_client::{
+    nonblocking::{pubsub_client::PubsubClient, rpc_client::RpcClient},
+    rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use std::{str::FromStr, sync::Arc};
+use tokio::{
+    sync::mpsc::Sender,
+    time::{Duration, sleep},
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::datasource::{ARRIVALS, DATASOURCE_STATUS, fetch_transaction_update};
+
+/// Datasource built on the standard Solana WebSocket API: `logsSubscribe` for
+/// transactions mentioning the followed wallets, then `getTransaction` for the full
+/// transaction. Slower than gRPC, but works with any RPC provider.
+pub struct WebsocketLogsDatasource {
+    pub ws_endpoint: String,
+    pub rpc_client: Arc<RpcClient>,
+    pub wallets: Vec<Pubkey>,
+    /// Only transactions whose logs mention one of these programs are fetched
+    pub programs: Vec<Pubkey>,
+}
+
+impl WebsocketLogsDatasource {
+    pub fn new(
+        ws_endpoint: String,
+        rpc_client: Arc<RpcClient>,
+        wallets: Vec<Pubkey>,
+        programs: Vec<Pubkey>,
+    ) -> Self {
+        Self {
+            ws_endpoint,
+            rpc_client,
+            wallets,
+            programs,
+        }
+    }
+}
+
+#[async_trait]
+impl Datasource for WebsocketLogsDatasource {
+    async fn consume(
+        &self,
+        id: DatasourceId,
+        sender: Sender<(Update, DatasourceId)>,
+        cancellation_token: CancellationToken,
+        _metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let mut handles = Vec::with_capacity(self.wallets.len());
+
+        // logsSubscribe only accepts a single address, so run one subscription per wallet
+        for wallet in self.wallets.clone() {
+            let ws_endpoint = self.ws_endpoint.clone();
+            let rpc_client = self.rpc_client.clone();
+            let programs: Vec<String> = self.programs.iter().map(Pubkey::to_string).collect();
+            let sender = sender.clone();
+            let id = id.clone();
+            let cancellation_token = cancellation_token.clone();
+
+            handles.push(tokio::spawn(async move {
+                while !cancellation_token.is_cancelled() {
+                    if let Err(e) = subscribe_wallet_logs(
+                        &ws_endpoint,
+                        &rpc_client,
+                        wallet,
+                        &programs,
+                        &sender,
+                        &id,
+                        &cancellation_token,
+                    )
+                    .await
+                    {
+                        warn!("WebSocket logs subscription for {} failed: {}", wallet, e);
+                    }
+
+                    sleep(Duration::from_secs(1)).await;
+                }
+            }));
+        }
+
+        futures::future::join_all(handles).await;
+
+        Ok(())
+    }
+
+    fn update_types(&self) -> Vec<UpdateType> {
+        vec![UpdateType::Transaction]
+    }
+}
+
+async fn subscribe_wallet_logs(
+    ws_endpoint: &str,
+    rpc_client: &RpcClient,
+    wallet: Pubkey,
+    programs: &[String],
+    sender: &Sender<(Update, DatasourceId)>,
+    id: &DatasourceId,
+    cancellation_token: &CancellationToken,
+) -> anyhow::Result<()> {
+    let pubsub = PubsubClient::new(ws_endpoint).await?;
+
+    let (mut stream, unsubscribe) = pubsub
+        .logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![wallet.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )
+        .await?;
+
+    info!("WebSocket logs subscription started for {}", wallet);
+
+    let status_name = format!("websocket {}", wallet);
+    DATASOURCE_STATUS.set_connected(&status_name, true);
+
+    loop {
+        let response = tokio::select! {
+            _ = cancellation_token.cancelled() => break,
+            response = stream.next() => match response {
+                Some(response) => response,
+                None => break,
+            },
+        };
+
+        let logs = response.value;
+
+        if logs.err.is_some()
+            || !logs.logs.iter().any(|line| {
+                programs
+                    .iter()
+                    .any(|program| line.contains(program.as_str()))
+            })
+        {
+            continue;
+        }
+
+        let Ok(signature) = Signature::from_str(&logs.signature) else {
+            continue;
+        };
+
+        ARRIVALS.record("websocket", signature);
+
+        let Some(update) = fetch_transaction_update(rpc_client, &signature).await else {
+            continue;
+        };
+
+        if sender
+            .send((Update::Transaction(Box::new(update)), id.clone()))
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+
+    DATASOURCE_STATUS.set_connected(&status_name, false);
+    unsubscribe().await;
+
+    Ok(())
+}
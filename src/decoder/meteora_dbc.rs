@@ -0,0 +1,257 @@
+use carbon_core::{
+    deserialize::ArrangeAccounts,
+    instruction::{DecodedInstruction, InstructionDecoder},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+};
+
+/// Meteora dynamic bonding curve program. No carbon decoder release matches our
+/// carbon version, so the few instructions, events and accounts the copy trader
+/// needs are decoded by hand here.
+pub const METEORA_DBC_PROGRAM_ID: Pubkey = pubkey!("dbcij3LWUppWqq96dh6gJWwBifmcGfLSB5D4DuSMaqN");
+
+pub const DBC_SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+pub const DBC_SWAP2_DISCRIMINATOR: [u8; 8] = [65, 75, 63, 76, 235, 91, 91, 136];
+
+/// Anchor event CPI tag followed by the `EvtSwap` / `EvtSwap2` discriminators
+pub const DBC_EVT_SWAP_DISCRIMINATOR: [u8; 16] = [
+    228, 69, 165, 46, 81, 203, 154, 29, 27, 60, 21, 213, 138, 170, 187, 147,
+];
+pub const DBC_EVT_SWAP2_DISCRIMINATOR: [u8; 16] = [
+    228, 69, 165, 46, 81, 203, 154, 29, 189, 66, 51, 168, 38, 80, 117, 153,
+];
+
+/// `trade_direction` of a swap event selling base tokens for quote
+pub const DBC_BASE_TO_QUOTE: u8 = 0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MeteoraDbcInstruction {
+    Swap {
+        amount_in: u64,
+        minimum_amount_out: u64,
+    },
+    Swap2 {
+        amount_0: u64,
+        amount_1: u64,
+        swap_mode: u8,
+    },
+}
+
+pub struct MeteoraDbcDecoder;
+
+impl InstructionDecoder<'_> for MeteoraDbcDecoder {
+    type InstructionType = MeteoraDbcInstruction;
+
+    fn decode_instruction(
+        &self,
+        instruction: &Instruction,
+    ) -> Option<DecodedInstruction<Self::InstructionType>> {
+        if instruction.program_id != METEORA_DBC_PROGRAM_ID {
+            return None;
+        }
+
+        let (discriminator, args) = instruction.data.split_first_chunk::<8>()?;
+
+        let data = match *discriminator {
+            DBC_SWAP_DISCRIMINATOR => MeteoraDbcInstruction::Swap {
+                amount_in: read_u64(args, 0)?,
+                minimum_amount_out: read_u64(args, 8)?,
+            },
+            DBC_SWAP2_DISCRIMINATOR => MeteoraDbcInstruction::Swap2 {
+                amount_0: read_u64(args, 0)?,
+                amount_1: read_u64(args, 8)?,
+                swap_mode: *args.get(16)?,
+            },
+            _ => return None,
+        };
+
+        Some(DecodedInstruction {
+            program_id: instruction.program_id,
+            data,
+            accounts: instruction.accounts.clone(),
+        })
+    }
+}
+
+/// Accounts of `swap` and `swap2`, which share one layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbcSwapInstructionAccounts {
+    pub pool_authority: Pubkey,
+    pub config: Pubkey,
+    pub pool: Pubkey,
+    pub input_token_account: Pubkey,
+    pub output_token_account: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub payer: Pubkey,
+    pub token_base_program: Pubkey,
+    pub token_quote_program: Pubkey,
+    /// The program id itself when the trade has no referral
+    pub referral_token_account: Pubkey,
+    pub event_authority: Pubkey,
+    pub program: Pubkey,
+}
+
+impl ArrangeAccounts for MeteoraDbcInstruction {
+    type ArrangedAccounts = DbcSwapInstructionAccounts;
+
+    fn arrange_accounts(accounts: &[AccountMeta]) -> Option<Self::ArrangedAccounts> {
+        let [
+            pool_authority,
+            config,
+            pool,
+            input_token_account,
+            output_token_account,
+            base_vault,
+            quote_vault,
+            base_mint,
+            quote_mint,
+            payer,
+            token_base_program,
+            token_quote_program,
+            referral_token_account,
+            event_authority,
+            program,
+            ..,
+        ] = accounts
+        else {
+            return None;
+        };
+
+        Some(DbcSwapInstructionAccounts {
+            pool_authority: pool_authority.pubkey,
+            config: config.pubkey,
+            pool: pool.pubkey,
+            input_token_account: input_token_account.pubkey,
+            output_token_account: output_token_account.pubkey,
+            base_vault: base_vault.pubkey,
+            quote_vault: quote_vault.pubkey,
+            base_mint: base_mint.pubkey,
+            quote_mint: quote_mint.pubkey,
+            payer: payer.pubkey,
+            token_base_program: token_base_program.pubkey,
+            token_quote_program: token_quote_program.pubkey,
+            referral_token_account: referral_token_account.pubkey,
+            event_authority: event_authority.pubkey,
+            program: program.pubkey,
+        })
+    }
+}
+
+/// The parts of an `EvtSwap` / `EvtSwap2` event needed to quote a copy trade.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbcSwapEvent {
+    pub pool: Pubkey,
+    pub config: Pubkey,
+    pub trade_direction: u8,
+    /// Input amount including fees
+    pub amount_in: u64,
+    pub output_amount: u64,
+    /// Pool sqrt price (Q64.64) after the trade
+    pub next_sqrt_price: u128,
+    /// Trading, protocol and referral fees combined
+    pub total_fee: u64,
+    pub timestamp: u64,
+}
+
+impl DbcSwapEvent {
+    /// Parses the event CPI data, discriminator included.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let (discriminator, body) = data.split_first_chunk::<16>()?;
+
+        let pool = read_pubkey(body, 0)?;
+        let config = read_pubkey(body, 32)?;
+        let trade_direction = *body.get(64)?;
+
+        match *discriminator {
+            // params (amount_in, minimum_amount_out) then SwapResult, amount_in, timestamp
+            DBC_EVT_SWAP_DISCRIMINATOR => Some(Self {
+                pool,
+                config,
+                trade_direction,
+                amount_in: read_u64(body, 66)?,
+                output_amount: read_u64(body, 90)?,
+                next_sqrt_price: read_u128(body, 98)?,
+                total_fee: read_u64(body, 114)? + read_u64(body, 122)? + read_u64(body, 130)?,
+                timestamp: read_u64(body, 146)?,
+            }),
+            // params (amount_0, amount_1, swap_mode) then SwapResult2, reserves, timestamp
+            DBC_EVT_SWAP2_DISCRIMINATOR => Some(Self {
+                pool,
+                config,
+                trade_direction,
+                amount_in: read_u64(body, 83)?,
+                output_amount: read_u64(body, 107)?,
+                next_sqrt_price: read_u128(body, 115)?,
+                total_fee: read_u64(body, 131)? + read_u64(body, 139)? + read_u64(body, 147)?,
+                timestamp: read_u64(body, 171)?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// The parts of a DBC `PoolConfig` account used for quoting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbcPoolConfig {
+    pub quote_mint: Pubkey,
+    /// 0 collects fees in the quote token, 1 in the output token
+    pub collect_fee_mode: u8,
+    pub migration_sqrt_price: u128,
+    pub sqrt_start_price: u128,
+    /// Liquidity distribution as (upper sqrt price, liquidity), unused points dropped
+    pub curve: Vec<(u128, u128)>,
+}
+
+impl DbcPoolConfig {
+    const QUOTE_MINT_OFFSET: usize = 8;
+    const COLLECT_FEE_MODE_OFFSET: usize = 232;
+    const MIGRATION_SQRT_PRICE_OFFSET: usize = 280;
+    const SQRT_START_PRICE_OFFSET: usize = 392;
+    const CURVE_OFFSET: usize = 408;
+    const CURVE_POINTS: usize = 20;
+
+    /// Parses the raw account data, discriminator included.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let curve = (0..Self::CURVE_POINTS)
+            .map(|point| {
+                let offset = Self::CURVE_OFFSET + point * 32;
+                Some((read_u128(data, offset)?, read_u128(data, offset + 16)?))
+            })
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .take_while(|(sqrt_price, _)| *sqrt_price != 0)
+            .collect();
+
+        Some(Self {
+            quote_mint: read_pubkey(data, Self::QUOTE_MINT_OFFSET)?,
+            collect_fee_mode: *data.get(Self::COLLECT_FEE_MODE_OFFSET)?,
+            migration_sqrt_price: read_u128(data, Self::MIGRATION_SQRT_PRICE_OFFSET)?,
+            sqrt_start_price: read_u128(data, Self::SQRT_START_PRICE_OFFSET)?,
+            curve,
+        })
+    }
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(
+        data.get(offset..offset + 8)?.try_into().ok()?,
+    ))
+}
+
+fn read_u128(data: &[u8], offset: usize) -> Option<u128> {
+    Some(u128::from_le_bytes(
+        data.get(offset..offset + 16)?.try_into().ok()?,
+    ))
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Option<Pubkey> {
+    Some(Pubkey::new_from_array(
+        data.get(offset..offset + 32)?.try_into().ok()?,
+    ))
+}
@@ -0,0 +1,3 @@
+pub mod meteora_dbc;
+
+pub use meteora_dbc::*;
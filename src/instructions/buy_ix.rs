@@ -1,7 +1,10 @@
 use carbon_pumpfun_decoder::instructions::buy::{Buy, BuyInstructionAccounts};
 use solana_sdk::{instruction::{AccountMeta, Instruction}, pubkey::Pubkey};
 
+use crate::utils::find_pda;
+
 pub const EVENT_DISCRIMINATOR: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+pub const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
 
 pub trait BuyExactInInstructionAccountsExt {
     fn get_buy_ix(&self, buy_exact_in_param: Buy) -> Instruction;
@@ -13,34 +16,27 @@ pub trait BuyExactInInstructionAccountsExt {
 
 impl BuyExactInInstructionAccountsExt for BuyInstructionAccounts {
     fn get_create_ata_ix(&self) -> Instruction {
-        let create_ata_ix =
-            spl_associated_token_account::instruction::create_associated_token_account(
-                &self.user,
-                &self.user,
-                &self.mint,
-                &self.token_program,
-            );
-
-        create_ata_ix
+        spl_associated_token_account::instruction::create_associated_token_account(
+            &self.user,
+            &self.user,
+            &self.mint,
+            &self.token_program,
+        )
     }
 
     fn get_create_idempotent_ata_ix(&self) -> Instruction {
-        let create_ata_base_ix =
-            spl_associated_token_account::instruction::create_associated_token_account_idempotent(
-                &self.user,
-                &self.user,
-                &self.mint,
-                &self.token_program,
-            );
-
-        create_ata_base_ix
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            &self.user,
+            &self.user,
+            &self.mint,
+            &self.token_program,
+        )
     }
 
     fn get_buy_ix(&self, buy_exact_in_param: Buy) -> Instruction {
-        let discriminator = [102, 6, 61, 18, 1, 218, 235, 234];
-        let mut data = Vec::new();
+        let mut data = Vec::with_capacity(24);
 
-        data.extend_from_slice(&discriminator);
+        data.extend_from_slice(&BUY_DISCRIMINATOR);
         data.extend_from_slice(&buy_exact_in_param.amount.to_le_bytes());
         data.extend_from_slice(&buy_exact_in_param.max_sol_cost.to_le_bytes());
 
@@ -75,18 +71,16 @@ impl BuyExactInInstructionAccountsExt for BuyInstructionAccounts {
 
     // Pump program
     fn global_volume_accumulator_pda() -> Pubkey {
-        let (global_volume_accumulator, _bump) = Pubkey::find_program_address(
+        find_pda(
             &[b"global_volume_accumulator"],
             &Pubkey::from_str_const("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P"),
-        );
-        global_volume_accumulator
+        )
     }
 
     fn user_volume_accumulator_pda(user: &Pubkey) -> Pubkey {
-        let (user_volume_accumulator, _bump) = Pubkey::find_program_address(
+        find_pda(
             &[b"user_volume_accumulator", user.as_ref()],
             &Pubkey::from_str_const("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P"),
-        );
-        user_volume_accumulator
+        )
     }
 }
@@ -0,0 +1,42 @@
+use solana_sdk::instruction::{AccountMeta, Instruction};
+
+use crate::decoder::{DBC_SWAP_DISCRIMINATOR, DbcSwapInstructionAccounts};
+
+pub trait MeteoraDbcSwapInstructionAccountsExt {
+    fn get_swap_ix(&self, amount_in: u64, minimum_amount_out: u64) -> Instruction;
+}
+
+impl MeteoraDbcSwapInstructionAccountsExt for DbcSwapInstructionAccounts {
+    /// Exact-in `swap`; the direction follows from which of the payer's token accounts
+    /// are passed as input and output.
+    fn get_swap_ix(&self, amount_in: u64, minimum_amount_out: u64) -> Instruction {
+        let mut data = Vec::with_capacity(24);
+
+        data.extend_from_slice(&DBC_SWAP_DISCRIMINATOR);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+
+        Instruction {
+            program_id: self.program,
+            accounts: vec![
+                AccountMeta::new_readonly(self.pool_authority, false),
+                AccountMeta::new_readonly(self.config, false),
+                AccountMeta::new(self.pool, false),
+                AccountMeta::new(self.input_token_account, false),
+                AccountMeta::new(self.output_token_account, false),
+                AccountMeta::new(self.base_vault, false),
+                AccountMeta::new(self.quote_vault, false),
+                AccountMeta::new_readonly(self.base_mint, false),
+                AccountMeta::new_readonly(self.quote_mint, false),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.token_base_program, false),
+                AccountMeta::new_readonly(self.token_quote_program, false),
+                // Optional referral account left empty
+                AccountMeta::new_readonly(self.program, false),
+                AccountMeta::new_readonly(self.event_authority, false),
+                AccountMeta::new_readonly(self.program, false),
+            ],
+            data,
+        }
+    }
+}
@@ -1,2 +1,10 @@
 pub mod buy_ix;
-pub mod sell_ix;
\ No newline at end of file
+pub mod meteora_dbc_ix;
+pub mod pump_swap_buy_ix;
+pub mod pump_swap_sell_ix;
+pub mod raydium_amm_ix;
+pub mod raydium_cpmm_ix;
+pub mod raydium_launchpad_ix;
+pub mod sell_ix;
+pub mod token_account;
+pub mod wsol;
@@ -0,0 +1,83 @@
+use carbon_pump_swap_decoder::instructions::buy::{Buy, BuyInstructionAccounts};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+use crate::utils::find_pda;
+
+pub const PUMP_SWAP_BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+
+pub trait PumpSwapBuyInstructionAccountsExt {
+    fn get_buy_ix(&self, buy_param: Buy) -> Instruction;
+    fn get_create_idempotent_ata_ix(&self) -> Instruction;
+    fn global_volume_accumulator_pda() -> Pubkey;
+    fn user_volume_accumulator_pda(user: &Pubkey) -> Pubkey;
+}
+
+impl PumpSwapBuyInstructionAccountsExt for BuyInstructionAccounts {
+    fn get_create_idempotent_ata_ix(&self) -> Instruction {
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            &self.user,
+            &self.user,
+            &self.base_mint,
+            &self.base_token_program,
+        )
+    }
+
+    fn get_buy_ix(&self, buy_param: Buy) -> Instruction {
+        let mut data = Vec::with_capacity(24);
+
+        data.extend_from_slice(&PUMP_SWAP_BUY_DISCRIMINATOR);
+        data.extend_from_slice(&buy_param.base_amount_out.to_le_bytes());
+        data.extend_from_slice(&buy_param.max_quote_amount_in.to_le_bytes());
+
+        let global_volume_accumulator = Self::global_volume_accumulator_pda();
+        let user_volume_accumulator = Self::user_volume_accumulator_pda(&self.user);
+
+        let accounts = vec![
+            AccountMeta::new(self.pool, false),
+            AccountMeta::new(self.user, true),
+            AccountMeta::new_readonly(self.global_config, false),
+            AccountMeta::new_readonly(self.base_mint, false),
+            AccountMeta::new_readonly(self.quote_mint, false),
+            AccountMeta::new(self.user_base_token_account, false),
+            AccountMeta::new(self.user_quote_token_account, false),
+            AccountMeta::new(self.pool_base_token_account, false),
+            AccountMeta::new(self.pool_quote_token_account, false),
+            AccountMeta::new_readonly(self.protocol_fee_recipient, false),
+            AccountMeta::new(self.protocol_fee_recipient_token_account, false),
+            AccountMeta::new_readonly(self.base_token_program, false),
+            AccountMeta::new_readonly(self.quote_token_program, false),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new_readonly(self.associated_token_program, false),
+            AccountMeta::new_readonly(self.event_authority, false),
+            AccountMeta::new_readonly(self.program, false),
+            AccountMeta::new(self.coin_creator_vault_ata, false),
+            AccountMeta::new_readonly(self.coin_creator_vault_authority, false),
+            AccountMeta::new(global_volume_accumulator, false),
+            AccountMeta::new(user_volume_accumulator, false),
+        ];
+
+        Instruction {
+            program_id: self.program,
+            accounts,
+            data,
+        }
+    }
+
+    // PumpSwap program
+    fn global_volume_accumulator_pda() -> Pubkey {
+        find_pda(
+            &[b"global_volume_accumulator"],
+            &carbon_pump_swap_decoder::PROGRAM_ID,
+        )
+    }
+
+    fn user_volume_accumulator_pda(user: &Pubkey) -> Pubkey {
+        find_pda(
+            &[b"user_volume_accumulator", user.as_ref()],
+            &carbon_pump_swap_decoder::PROGRAM_ID,
+        )
+    }
+}
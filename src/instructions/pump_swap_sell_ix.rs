@@ -0,0 +1,57 @@
+use carbon_pump_swap_decoder::instructions::sell::{Sell, SellInstructionAccounts};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+
+use crate::instructions::token_account::close_token_account_ix;
+
+pub const PUMP_SWAP_SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+
+pub trait PumpSwapSellInstructionAccountsExt {
+    fn get_sell_ix(&self, sell_param: Sell) -> Instruction;
+    fn get_close_ata_ix(&self) -> Instruction;
+}
+
+impl PumpSwapSellInstructionAccountsExt for SellInstructionAccounts {
+    fn get_close_ata_ix(&self) -> Instruction {
+        close_token_account_ix(
+            &self.base_token_program,
+            &self.user_base_token_account,
+            &self.user,
+        )
+    }
+
+    fn get_sell_ix(&self, sell_param: Sell) -> Instruction {
+        let mut data = Vec::with_capacity(24);
+
+        data.extend_from_slice(&PUMP_SWAP_SELL_DISCRIMINATOR);
+        data.extend_from_slice(&sell_param.base_amount_in.to_le_bytes());
+        data.extend_from_slice(&sell_param.min_quote_amount_out.to_le_bytes());
+
+        let accounts = vec![
+            AccountMeta::new(self.pool, false),
+            AccountMeta::new(self.user, true),
+            AccountMeta::new_readonly(self.global_config, false),
+            AccountMeta::new_readonly(self.base_mint, false),
+            AccountMeta::new_readonly(self.quote_mint, false),
+            AccountMeta::new(self.user_base_token_account, false),
+            AccountMeta::new(self.user_quote_token_account, false),
+            AccountMeta::new(self.pool_base_token_account, false),
+            AccountMeta::new(self.pool_quote_token_account, false),
+            AccountMeta::new_readonly(self.protocol_fee_recipient, false),
+            AccountMeta::new(self.protocol_fee_recipient_token_account, false),
+            AccountMeta::new_readonly(self.base_token_program, false),
+            AccountMeta::new_readonly(self.quote_token_program, false),
+            AccountMeta::new_readonly(self.system_program, false),
+            AccountMeta::new_readonly(self.associated_token_program, false),
+            AccountMeta::new_readonly(self.event_authority, false),
+            AccountMeta::new_readonly(self.program, false),
+            AccountMeta::new(self.coin_creator_vault_ata, false),
+            AccountMeta::new_readonly(self.coin_creator_vault_authority, false),
+        ];
+
+        Instruction {
+            program_id: self.program,
+            accounts,
+            data,
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use carbon_raydium_amm_v4_decoder::instructions::{
+    swap_base_in::{SwapBaseIn, SwapBaseInInstructionAccounts},
+    swap_base_out::SwapBaseOutInstructionAccounts,
+};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+
+pub const SWAP_BASE_IN_DISCRIMINATOR: u8 = 9;
+
+pub trait RaydiumAmmSwapInstructionAccountsExt {
+    fn get_swap_base_in_ix(&self, swap_param: SwapBaseIn) -> Instruction;
+}
+
+impl RaydiumAmmSwapInstructionAccountsExt for SwapBaseInInstructionAccounts {
+    fn get_swap_base_in_ix(&self, swap_param: SwapBaseIn) -> Instruction {
+        let mut data = Vec::with_capacity(17);
+
+        data.push(SWAP_BASE_IN_DISCRIMINATOR);
+        data.extend_from_slice(&swap_param.amount_in.to_le_bytes());
+        data.extend_from_slice(&swap_param.minimum_amount_out.to_le_bytes());
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.token_program, false),
+            AccountMeta::new(self.amm, false),
+            AccountMeta::new_readonly(self.amm_authority, false),
+            AccountMeta::new(self.amm_open_orders, false),
+        ];
+
+        // Older pools still expect the target orders account
+        if let Some(amm_target_orders) = self.amm_target_orders {
+            accounts.push(AccountMeta::new(amm_target_orders, false));
+        }
+
+        accounts.extend([
+            AccountMeta::new(self.pool_coin_token_account, false),
+            AccountMeta::new(self.pool_pc_token_account, false),
+            AccountMeta::new_readonly(self.serum_program, false),
+            AccountMeta::new(self.serum_market, false),
+            AccountMeta::new(self.serum_bids, false),
+            AccountMeta::new(self.serum_asks, false),
+            AccountMeta::new(self.serum_event_queue, false),
+            AccountMeta::new(self.serum_coin_vault_account, false),
+            AccountMeta::new(self.serum_pc_vault_account, false),
+            AccountMeta::new_readonly(self.serum_vault_signer, false),
+            AccountMeta::new(self.user_source_token_account, false),
+            AccountMeta::new(self.user_destination_token_account, false),
+            AccountMeta::new(self.user_source_owner, true),
+        ]);
+
+        Instruction {
+            program_id: carbon_raydium_amm_v4_decoder::PROGRAM_ID,
+            accounts,
+            data,
+        }
+    }
+}
+
+/// The target's exact-out swaps are copied as exact-in swaps over the same accounts.
+pub fn swap_base_in_accounts(
+    accounts: SwapBaseOutInstructionAccounts,
+) -> SwapBaseInInstructionAccounts {
+    SwapBaseInInstructionAccounts {
+        token_program: accounts.token_program,
+        amm: accounts.amm,
+        amm_authority: accounts.amm_authority,
+        amm_open_orders: accounts.amm_open_orders,
+        amm_target_orders: accounts.amm_target_orders,
+        pool_coin_token_account: accounts.pool_coin_token_account,
+        pool_pc_token_account: accounts.pool_pc_token_account,
+        serum_program: accounts.serum_program,
+        serum_market: accounts.serum_market,
+        serum_bids: accounts.serum_bids,
+        serum_asks: accounts.serum_asks,
+        serum_event_queue: accounts.serum_event_queue,
+        serum_coin_vault_account: accounts.serum_coin_vault_account,
+        serum_pc_vault_account: accounts.serum_pc_vault_account,
+        serum_vault_signer: accounts.serum_vault_signer,
+        user_source_token_account: accounts.user_source_token_account,
+        user_destination_token_account: accounts.user_destination_token_account,
+        user_source_owner: accounts.user_source_owner,
+    }
+}
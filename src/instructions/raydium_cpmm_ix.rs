@@ -0,0 +1,64 @@
+use carbon_raydium_cpmm_decoder::instructions::{
+    swap_base_input::{SwapBaseInput, SwapBaseInputInstructionAccounts},
+    swap_base_output::SwapBaseOutputInstructionAccounts,
+};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+
+pub const SWAP_BASE_INPUT_DISCRIMINATOR: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222];
+
+pub trait RaydiumCpmmSwapInstructionAccountsExt {
+    fn get_swap_base_input_ix(&self, swap_param: SwapBaseInput) -> Instruction;
+}
+
+impl RaydiumCpmmSwapInstructionAccountsExt for SwapBaseInputInstructionAccounts {
+    fn get_swap_base_input_ix(&self, swap_param: SwapBaseInput) -> Instruction {
+        let mut data = Vec::with_capacity(24);
+
+        data.extend_from_slice(&SWAP_BASE_INPUT_DISCRIMINATOR);
+        data.extend_from_slice(&swap_param.amount_in.to_le_bytes());
+        data.extend_from_slice(&swap_param.minimum_amount_out.to_le_bytes());
+
+        let accounts = vec![
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.authority, false),
+            AccountMeta::new_readonly(self.amm_config, false),
+            AccountMeta::new(self.pool_state, false),
+            AccountMeta::new(self.input_token_account, false),
+            AccountMeta::new(self.output_token_account, false),
+            AccountMeta::new(self.input_vault, false),
+            AccountMeta::new(self.output_vault, false),
+            AccountMeta::new_readonly(self.input_token_program, false),
+            AccountMeta::new_readonly(self.output_token_program, false),
+            AccountMeta::new_readonly(self.input_token_mint, false),
+            AccountMeta::new_readonly(self.output_token_mint, false),
+            AccountMeta::new(self.observation_state, false),
+        ];
+
+        Instruction {
+            program_id: carbon_raydium_cpmm_decoder::PROGRAM_ID,
+            accounts,
+            data,
+        }
+    }
+}
+
+/// The target's exact-out swaps are copied as exact-in swaps over the same accounts.
+pub fn swap_base_input_accounts(
+    accounts: SwapBaseOutputInstructionAccounts,
+) -> SwapBaseInputInstructionAccounts {
+    SwapBaseInputInstructionAccounts {
+        payer: accounts.payer,
+        authority: accounts.authority,
+        amm_config: accounts.amm_config,
+        pool_state: accounts.pool_state,
+        input_token_account: accounts.input_token_account,
+        output_token_account: accounts.output_token_account,
+        input_vault: accounts.input_vault,
+        output_vault: accounts.output_vault,
+        input_token_program: accounts.input_token_program,
+        output_token_program: accounts.output_token_program,
+        input_token_mint: accounts.input_token_mint,
+        output_token_mint: accounts.output_token_mint,
+        observation_state: accounts.observation_state,
+    }
+}
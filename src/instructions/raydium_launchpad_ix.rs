@@ -0,0 +1,97 @@
+use carbon_raydium_launchpad_decoder::instructions::buy_exact_in::BuyExactInInstructionAccounts;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+
+pub const BUY_EXACT_IN_DISCRIMINATOR: [u8; 8] = [250, 234, 13, 123, 213, 156, 19, 236];
+pub const SELL_EXACT_IN_DISCRIMINATOR: [u8; 8] = [149, 39, 222, 155, 211, 124, 152, 26];
+
+/// Buy and sell instructions of Raydium LaunchLab. Every swap instruction of the program
+/// shares one account layout, so the buy accounts are used for both directions.
+pub trait RaydiumLaunchpadInstructionAccountsExt {
+    fn get_buy_exact_in_ix(
+        &self,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        remaining_accounts: &[AccountMeta],
+    ) -> Instruction;
+    fn get_sell_exact_in_ix(
+        &self,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        remaining_accounts: &[AccountMeta],
+    ) -> Instruction;
+}
+
+impl RaydiumLaunchpadInstructionAccountsExt for BuyExactInInstructionAccounts {
+    fn get_buy_exact_in_ix(
+        &self,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        remaining_accounts: &[AccountMeta],
+    ) -> Instruction {
+        swap_ix(
+            self,
+            BUY_EXACT_IN_DISCRIMINATOR,
+            amount_in,
+            minimum_amount_out,
+            remaining_accounts,
+        )
+    }
+
+    fn get_sell_exact_in_ix(
+        &self,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        remaining_accounts: &[AccountMeta],
+    ) -> Instruction {
+        swap_ix(
+            self,
+            SELL_EXACT_IN_DISCRIMINATOR,
+            amount_in,
+            minimum_amount_out,
+            remaining_accounts,
+        )
+    }
+}
+
+fn swap_ix(
+    accounts: &BuyExactInInstructionAccounts,
+    discriminator: [u8; 8],
+    amount_in: u64,
+    minimum_amount_out: u64,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut data = Vec::with_capacity(32);
+
+    data.extend_from_slice(&discriminator);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+    // No share fee, nobody referred this trade
+    data.extend_from_slice(&0u64.to_le_bytes());
+
+    let mut metas = vec![
+        AccountMeta::new(accounts.payer, true),
+        AccountMeta::new_readonly(accounts.authority, false),
+        AccountMeta::new_readonly(accounts.global_config, false),
+        AccountMeta::new_readonly(accounts.platform_config, false),
+        AccountMeta::new(accounts.pool_state, false),
+        AccountMeta::new(accounts.user_base_token, false),
+        AccountMeta::new(accounts.user_quote_token, false),
+        AccountMeta::new(accounts.base_vault, false),
+        AccountMeta::new(accounts.quote_vault, false),
+        AccountMeta::new_readonly(accounts.base_token_mint, false),
+        AccountMeta::new_readonly(accounts.quote_token_mint, false),
+        AccountMeta::new_readonly(accounts.base_token_program, false),
+        AccountMeta::new_readonly(accounts.quote_token_program, false),
+        AccountMeta::new_readonly(accounts.event_authority, false),
+        AccountMeta::new_readonly(accounts.program, false),
+    ];
+
+    // Pool level accounts newer program versions append (fee vaults etc.) are reused as-is
+    metas.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: accounts.program,
+        accounts: metas,
+        data,
+    }
+}
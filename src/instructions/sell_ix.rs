@@ -2,6 +2,7 @@ use carbon_pumpfun_decoder::instructions::sell::{Sell, SellInstructionAccounts};
 use solana_sdk::instruction::{AccountMeta, Instruction};
 
 pub const EVENT_DISCRIMINATOR: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+pub const SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
 
 pub trait SellExactInInstructionAccountsExt {
     fn get_sell_ix(&self, sell_param: Sell) -> Instruction;
@@ -10,23 +11,20 @@ pub trait SellExactInInstructionAccountsExt {
 
 impl SellExactInInstructionAccountsExt for SellInstructionAccounts {
     fn get_close_ata_ix(&self) -> Instruction {
-        let close_ata_ix = spl_token::instruction::close_account(
+        spl_token::instruction::close_account(
             &self.token_program,
             &self.associated_user,
             &self.user,
             &self.user,
             &[&self.user],
         )
-        .unwrap();
-
-        close_ata_ix
+        .unwrap()
     }
 
     fn get_sell_ix(&self, sell_param: Sell) -> Instruction {
-        let discriminator = [51, 230, 133, 164, 1, 127, 131, 173];
-        let mut data = Vec::new();
+        let mut data = Vec::with_capacity(24);
 
-        data.extend_from_slice(&discriminator);
+        data.extend_from_slice(&SELL_DISCRIMINATOR);
         data.extend_from_slice(&sell_param.amount.to_le_bytes());
         data.extend_from_slice(&sell_param.min_sol_output.to_le_bytes());
 
@@ -0,0 +1,57 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// SPL token `CloseAccount` instruction tag, identical in Token and Token-2022
+pub const CLOSE_ACCOUNT_TAG: u8 = 9;
+/// SPL token `TransferChecked` instruction tag, identical in Token and Token-2022
+pub const TRANSFER_CHECKED_TAG: u8 = 12;
+
+/// Closes `account` back to `owner` under either token program. `spl_token`'s own
+/// builder refuses any program id but its own, which breaks Token-2022 mints.
+pub fn close_token_account_ix(
+    token_program: &Pubkey,
+    account: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::new(*account, false),
+            AccountMeta::new(*owner, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data: vec![CLOSE_ACCOUNT_TAG],
+    }
+}
+
+/// Moves `amount` tokens of `mint` from `source` to `destination`, both accounts of the
+/// same token program, signed by `owner`.
+pub fn transfer_checked_ix(
+    token_program: &Pubkey,
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Instruction {
+    let mut data = vec![TRANSFER_CHECKED_TAG];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data,
+    }
+}
@@ -0,0 +1,37 @@
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, system_instruction};
+
+use crate::utils::associated_token_address;
+
+/// Instructions that create `owner`'s wrapped SOL account if needed and fund it with `lamports`.
+pub fn wrap_sol_ixs(owner: &Pubkey, lamports: u64) -> Vec<Instruction> {
+    let wsol_account = associated_token_address(owner, &spl_token::native_mint::id());
+
+    vec![
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            owner,
+            owner,
+            &spl_token::native_mint::id(),
+            &spl_token::id(),
+        ),
+        system_instruction::transfer(owner, &wsol_account, lamports),
+        spl_token::instruction::sync_native(&spl_token::id(), &wsol_account).unwrap(),
+    ]
+}
+
+/// Creates `owner`'s wrapped SOL account if needed, without funding it.
+pub fn create_wsol_account_ix(owner: &Pubkey) -> Instruction {
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+        owner,
+        owner,
+        &spl_token::native_mint::id(),
+        &spl_token::id(),
+    )
+}
+
+/// Closes `owner`'s wrapped SOL account, unwrapping whatever it holds back to SOL.
+pub fn close_wsol_account_ix(owner: &Pubkey) -> Instruction {
+    let wsol_account = associated_token_address(owner, &spl_token::native_mint::id());
+
+    spl_token::instruction::close_account(&spl_token::id(), &wsol_account, owner, owner, &[owner])
+        .unwrap()
+}
@@ -1,5 +1,11 @@
+pub mod cli;
 pub mod config;
+pub mod datasource;
+pub mod decoder;
 pub mod error;
 pub mod instructions;
+pub mod metrics;
+pub mod processor;
 pub mod service;
 pub mod utils;
+pub mod wallet;
@@ -1,451 +1,379 @@
 use {
-    async_trait::async_trait, borsh::BorshDeserialize, carbon_core::{
-        deserialize::{ArrangeAccounts, CarbonDeserialize},
-        error::CarbonResult,
-        instruction::{
-            DecodedInstruction, InstructionMetadata, InstructionProcessorInputType,
-            NestedInstructions,
-        },
-        metrics::MetricsCollection,
-        processor::Processor,
-    }, carbon_log_metrics::LogMetrics, carbon_pumpfun_decoder::{
-        instructions::{buy::Buy, sell::Sell, trade_event::TradeEvent, PumpfunInstruction}, PumpfunDecoder, PROGRAM_ID as PUMPFUN_PROGRAM_ID
-    }, carbon_yellowstone_grpc_datasource::YellowstoneGrpcGeyserClient, chrono::Utc, once_cell::sync::Lazy, pumpfun_monitor::{
+    carbon_core::{error::{CarbonResult, Error as CarbonError}, pipeline::PipelineBuilder}, carbon_log_metrics::LogMetrics, carbon_pump_swap_decoder::PumpSwapDecoder,
+    carbon_pumpfun_decoder::PumpfunDecoder, carbon_raydium_amm_v4_decoder::RaydiumAmmV4Decoder,
+    carbon_raydium_cpmm_decoder::RaydiumCpmmDecoder, carbon_raydium_launchpad_decoder::RaydiumLaunchpadDecoder,
+    pumpfun_monitor::{
+        cli::{backtest_signatures, backtest_window, print_backtest_report, run_command, Cli, Command},
         config::{
-            init_jito, init_nozomi, init_zslot, BUY_SOL_AMOUNT, CONFIRM_SERVICE, JITO_CLIENT, NOZOMI_CLIENT, PRIORITY_FEE, PUBKEY, RPC_CLIENT, SLIPPAGE, TARGET_WALLET, ZSLOT_CLIENT
-        },
-        instructions::{
-            buy_ix::BuyExactInInstructionAccountsExt, sell_ix::SellExactInInstructionAccountsExt,
+            init_jito, init_nozomi, init_zslot, load_secrets, secrets_lease_handler, unlock_keystore, BACKFILL_ON_STARTUP, READ_ONLY, CONTROL_API_ADDR, CONTROL_GRPC_ADDR, FEE_PAYER_PUBKEY, HEALTH_ADDR, JOURNAL_PATH, POSITIONS_PATH, RPC_CLIENT, SHADOW_MODE, RPC_POLL_INTERVAL_MS, TELEGRAM_COMMANDS, WS_ENDPOINT
         },
-        service::Tips,
-        utils::{
-            blockhash::{get_slot, recent_blockhash_handler}, build_and_sign, sol_token_quote, token_sol_quote, TRADE_EVENT_DISC
+        datasource::{
+            arrival_stats_handler, followed_programs, BackfillDatasource, FOLLOWED_TARGETS, is_followed_venue, load_replay_signatures, target_transaction_filters, JournalReplayDatasource, ReconnectingGrpcDatasource,
+            RpcPollingDatasource, WebsocketLogsDatasource,
         },
-    }, serde_json::json, solana_sdk::commitment_config::CommitmentConfig, solana_transaction_status_client_types::InnerInstruction, spl_associated_token_account::{
-        get_associated_token_address, instruction::create_associated_token_account_idempotent,
-    }, std::{
-        collections::{HashMap, HashSet},
-        env,
+        decoder::MeteoraDbcDecoder,
+        service::stub_senders,
+        metrics::{metrics_sink, PipelineHeartbeat},
+        processor::{start_order_workers, AllocationProfiled, BondingCurveProcess, MeteoraDbcProcess, PumpSwapProcess, PumpfunProcess, RaydiumAmmProcess, RaydiumCpmmProcess, RaydiumLaunchpadProcess, Traced},
+        utils::{blockhash_handler, control_api, control_grpc, graceful_shutdown, health_server, init_tracing, pipeline_watchdog, pumpfun_global_handler, sigterm_handler, spawn_background, start_tui, telegram_bot, BaseAllocator, BASE_ALLOCATOR},
+        wallet::{enable_paper_trading, load_positions, paper_trading, profit_sweep_handler, seed_paper_balances, top_up_handler, wallet_balance_handler, low_balance_handler, WALLET_POOL},
+    }, chrono::NaiveDate, clap::Parser, solana_sdk::{pubkey::Pubkey, signer::Signer}, std::{
+        env, path::{Path, PathBuf}, process,
         sync::Arc, time::Duration,
-    }, tokio::{sync::RwLock, time::sleep}, yellowstone_grpc_proto::geyser::{
-        CommitmentLevel, SubscribeRequestFilterAccounts, SubscribeRequestFilterTransactions,
-    }
+    }, tokio_util::sync::CancellationToken, tracing::{info, warn}, yellowstone_grpc_proto::geyser::CommitmentLevel
 };
 
+#[cfg(feature = "alloc-profiling")]
+#[global_allocator]
+static GLOBAL: pumpfun_monitor::utils::CountingAllocator<BaseAllocator> =
+    pumpfun_monitor::utils::CountingAllocator::new(BASE_ALLOCATOR);
+
+#[cfg(not(feature = "alloc-profiling"))]
+#[global_allocator]
+static GLOBAL: BaseAllocator = BASE_ALLOCATOR;
+
 #[tokio::main]
 pub async fn main() -> CarbonResult<()> {
-    env_logger::init();
     dotenv::dotenv().ok();
 
+    let command = Cli::parse().command.unwrap_or(Command::Run { tui: false, liquidate_on_exit: false });
+    // `run --tui` swaps the console logs for a live terminal view, logs then only go to LOG_DIR
+    let tui = matches!(command, Command::Run { tui: true, .. });
+    // Flushes the file logs on exit
+    let log_guard = init_tracing(!tui);
+
+    // Secrets are in the environment before any setting is read
+    load_secrets().await;
+
+    // The keystore passphrase is prompted for before the bot logs anything
+    if matches!(command, Command::Run { .. } | Command::Replay { .. } | Command::Backtest { .. }) && !*READ_ONLY {
+        unlock_keystore();
+    }
+
+    if let Command::Run { tui, liquidate_on_exit } = command {
+        return run(tui, liquidate_on_exit).await;
+    }
+
+    if let Command::Replay { journal } = command {
+        return replay(journal).await;
+    }
+
+    if let Command::Backtest { targets, from, to, source } = command {
+        return backtest(targets, from, to, source).await;
+    }
+
+    if let Err(e) = run_command(command).await {
+        drop(log_guard);
+        eprintln!("Error: {:#}", e);
+        process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Runs the copy trading bot until its datasources stop, then winds it down.
+async fn run(tui: bool, liquidate_on_exit: bool) -> CarbonResult<()> {
+    let shutdown = CancellationToken::new();
+
+    // SIGINT stops the pipeline on its own
+    spawn_background(sigterm_handler(shutdown.clone()));
+
+    // Virtual positions of paper and shadow runs aren't saved, so there's nothing to restore
+    if !paper_trading() && !*SHADOW_MODE {
+        match load_positions(&POSITIONS_PATH) {
+            Ok(restored) => info!("Restored {} open position(s) from {}", restored, *POSITIONS_PATH),
+            Err(e) => warn!("Failed to restore positions from {}: {}", *POSITIONS_PATH, e),
+        }
+    }
+
+    // Up before the senders connect, so supervisors see the bot starting
+    if let Some(health_addr) = HEALTH_ADDR.clone() {
+        spawn_background(health_server(health_addr));
+    }
+
+    if let Some(control_api_addr) = CONTROL_API_ADDR.clone() {
+        spawn_background(control_api(control_api_addr));
+    }
+
+    if let Some(control_grpc_addr) = CONTROL_GRPC_ADDR.clone() {
+        spawn_background(control_grpc(control_grpc_addr));
+    }
+
+    if *TELEGRAM_COMMANDS {
+        spawn_background(telegram_bot());
+    }
+
     init_nozomi().await;
     init_zslot().await;
     init_jito().await;
 
-    tokio::spawn({
-        async move {
-            loop {
-                recent_blockhash_handler(RPC_CLIENT.clone()).await;
+    spawn_background(blockhash_handler(RPC_CLIENT.clone()));
+
+    spawn_background(wallet_balance_handler(RPC_CLIENT.clone()));
+
+    spawn_background(secrets_lease_handler());
+
+    spawn_background(top_up_handler(RPC_CLIENT.clone()));
+
+    spawn_background(low_balance_handler());
+
+    spawn_background(profit_sweep_handler(RPC_CLIENT.clone()));
+
+    spawn_background(pumpfun_global_handler(RPC_CLIENT.clone()));
+
+    spawn_background(arrival_stats_handler());
+
+    start_order_workers();
+
+    for target in FOLLOWED_TARGETS.all() {
+        info!("Following target: {}", target);
+    }
+
+    // NOTE: Workaround, that solving issue https://github.com/rustls/rustls/issues/1877
+    rustls::crypto::aws_lc_rs::default_provider()
+        .install_default()
+        .expect("Can't set crypto provider to aws_lc_rs");
+
+    if *READ_ONLY {
+        info!("Read-only: no key is loaded, orders are paper traded with a throwaway wallet");
+    } else {
+        for wallet in WALLET_POOL.wallets() {
+            info!("Using wallet: {}", wallet.pubkey());
+        }
+        info!("Using fee payer: {}", *FEE_PAYER_PUBKEY);
+    }
+
+    if *SHADOW_MODE {
+        info!("Shadow mode: orders are signed and simulated but never submitted");
+    }
+
+    if paper_trading() && !*READ_ONLY {
+        info!("Paper trading: orders fill against virtual balances and are never submitted");
+    }
+
+    info!("Starting PUMPFUN Monitor...");
+
+    if tui {
+        start_tui(shutdown.clone());
+    }
+
+    // Torn down and built again whenever the watchdog finds it stalled
+    loop {
+        let pipeline_shutdown = shutdown.child_token();
+        let mut pipeline = with_processors(with_datasources(carbon_core::pipeline::Pipeline::builder()));
+
+        if let Some(sink) = metrics_sink() {
+            pipeline = pipeline.metrics(sink);
+        }
+
+        let mut pipeline = pipeline
+            .metrics(Arc::new(LogMetrics::new()))
+            .metrics(Arc::new(PipelineHeartbeat))
+            .metrics_flush_interval(3)
+            .shutdown_strategy(carbon_core::pipeline::ShutdownStrategy::Immediate)
+            .datasource_cancellation_token(pipeline_shutdown.clone())
+            .build()?;
+
+        let stalled = tokio::select! {
+            result = pipeline.run() => {
+                result?;
+                false
             }
+            _ = pipeline_watchdog() => true,
+        };
+
+        // Stops the datasources of a stalled pipeline
+        pipeline_shutdown.cancel();
+
+        if !stalled || shutdown.is_cancelled() {
+            break;
         }
-    });
 
-    println!("TARGET_WALLET : {}", TARGET_WALLET.to_string());
+        warn!("Rebuilding the pipeline...");
+    }
+
+    graceful_shutdown(liquidate_on_exit).await;
+
+    info!("PUMPFUN Monitor has stopped.");
+
+    Ok(())
+}
+
+/// Adds a datasource for every configured endpoint, following the targets.
+fn with_datasources(mut pipeline: PipelineBuilder) -> PipelineBuilder {
+    // One filter per target follows it on every configured venue
+    let transaction_filters = target_transaction_filters(&FOLLOWED_TARGETS.all());
+
+    // Each datasource is only added when its endpoint is configured
+    if let Some(geyser_url) = env::var("GEYSER_URL").ok().filter(|url| !url.is_empty()) {
+        let yellowstone_grpc = ReconnectingGrpcDatasource::new(
+            "yellowstone",
+            geyser_url,
+            env::var("X_TOKEN").ok(),
+            Some(CommitmentLevel::Processed),
+            transaction_filters.clone(),
+        )
+        .with_curve_accounts()
+        .with_followed_targets();
+
+        pipeline = pipeline.datasource(yellowstone_grpc);
+    }
+
+    if let Some(laser_endpoint) = env::var("LASER_ENDPOINT").ok().filter(|url| !url.is_empty()) {
+        let helius_laserstream = ReconnectingGrpcDatasource::new(
+            "laserstream",
+            laser_endpoint,
+            env::var("LASER_TOKEN_KEY").ok(),
+            Some(CommitmentLevel::Processed),
+            transaction_filters.clone(),
+        )
+        .with_curve_accounts()
+        .with_followed_targets()
+        .with_replay();
+
+        pipeline = pipeline.datasource(helius_laserstream);
+    }
+
+    if let Some(ws_endpoint) = WS_ENDPOINT.clone() {
+        let websocket_logs = WebsocketLogsDatasource::new(
+            ws_endpoint,
+            RPC_CLIENT.clone(),
+            FOLLOWED_TARGETS.all(),
+            followed_programs(),
+        );
+
+        pipeline = pipeline.datasource(websocket_logs);
+    }
+
+    if let Some(poll_interval_ms) = *RPC_POLL_INTERVAL_MS {
+        let rpc_polling = RpcPollingDatasource::new(
+            RPC_CLIENT.clone(),
+            FOLLOWED_TARGETS.all(),
+            Duration::from_millis(poll_interval_ms),
+        );
+
+        pipeline = pipeline.datasource(rpc_polling);
+    }
+
+    // Copies the sells missed while the bot was down
+    if *BACKFILL_ON_STARTUP {
+        let backfill = BackfillDatasource::new(RPC_CLIENT.clone(), FOLLOWED_TARGETS.all());
+
+        pipeline = pipeline.datasource(backfill);
+    }
+
+    pipeline
+}
+
+/// Adds the decoder and processors of every followed venue, only those get their decoder in the pipeline.
+fn with_processors(mut pipeline: PipelineBuilder) -> PipelineBuilder {
+    if is_followed_venue("pumpfun") {
+        pipeline = pipeline
+            .instruction(PumpfunDecoder, Traced::new("pumpfun", AllocationProfiled::new("pumpfun", PumpfunProcess)))
+            .account(PumpfunDecoder, BondingCurveProcess);
+    }
+
+    if is_followed_venue("pump_swap") {
+        pipeline = pipeline.instruction(PumpSwapDecoder, Traced::new("pump_swap", AllocationProfiled::new("pump_swap", PumpSwapProcess)));
+    }
+
+    if is_followed_venue("raydium_amm") {
+        pipeline = pipeline.instruction(RaydiumAmmV4Decoder, Traced::new("raydium_amm", AllocationProfiled::new("raydium_amm", RaydiumAmmProcess)));
+    }
+
+    if is_followed_venue("raydium_cpmm") {
+        pipeline = pipeline.instruction(RaydiumCpmmDecoder, Traced::new("raydium_cpmm", AllocationProfiled::new("raydium_cpmm", RaydiumCpmmProcess)));
+    }
+
+    if is_followed_venue("raydium_launchpad") {
+        pipeline = pipeline.instruction(RaydiumLaunchpadDecoder, Traced::new("raydium_launchpad", AllocationProfiled::new("raydium_launchpad", RaydiumLaunchpadProcess)));
+    }
+
+    if is_followed_venue("meteora_dbc") {
+        pipeline = pipeline.instruction(MeteoraDbcDecoder, Traced::new("meteora_dbc", AllocationProfiled::new("meteora_dbc", MeteoraDbcProcess)));
+    }
+
+    pipeline
+}
+
+/// Replays the target transactions recorded in `journal` through the processors, with the senders stubbed out.
+async fn replay(journal: PathBuf) -> CarbonResult<()> {
+    if JOURNAL_PATH.as_deref().is_some_and(|path| Path::new(path) == journal) {
+        return Err(CarbonError::Custom("JOURNAL_PATH is the journal being replayed, point it at another file".to_string()));
+    }
+
+    let signatures = load_replay_signatures(&journal)
+        .map_err(|e| CarbonError::Custom(format!("Failed to read {}: {}", journal.display(), e)))?;
+
+    info!("Replaying {} target transactions from {}", signatures.len(), journal.display());
+
+    stub_senders();
+
+    spawn_background(blockhash_handler(RPC_CLIENT.clone()));
+
+    spawn_background(wallet_balance_handler(RPC_CLIENT.clone()));
+
+    spawn_background(pumpfun_global_handler(RPC_CLIENT.clone()));
+
+    start_order_workers();
 
-    // NOTE: Workaround, that solving issue https://github.com/rustls/rustls/issues/1877
     rustls::crypto::aws_lc_rs::default_provider()
         .install_default()
         .expect("Can't set crypto provider to aws_lc_rs");
 
-    let transaction_filter = SubscribeRequestFilterTransactions {
-        vote: Some(false),
-        failed: Some(false),
-        // account_include: vec![],
-        account_include: vec![TARGET_WALLET.to_string()],
-        account_exclude: vec![],
-        account_required: vec![PUMPFUN_PROGRAM_ID.to_string().clone()],
-        signature: None,
-    };
-
-    println!("Using payer: {}", *PUBKEY);
-
-    let mut transaction_filters: HashMap<String, SubscribeRequestFilterTransactions> =
-        HashMap::new();
-
-    transaction_filters.insert(
-        "jupiter_swap_transaction_filter".to_string(),
-        transaction_filter,
-    );
-
-    let yellowstone_grpc = YellowstoneGrpcGeyserClient::new(
-        env::var("GEYSER_URL").unwrap_or_default(),
-        env::var("X_TOKEN").ok(),
-        Some(CommitmentLevel::Processed),
-        HashMap::new(),
-        transaction_filters.clone(),
-        Default::default(),
-        Arc::new(RwLock::new(HashSet::new())),
-    );
-
-    let helius_laserstream = YellowstoneGrpcGeyserClient::new(
-        env::var("LASER_ENDPOINT").unwrap_or_default(),
-        env::var("LASER_TOKEN_KEY").ok(),
-        Some(CommitmentLevel::Processed),
-        HashMap::new(),
-        transaction_filters.clone(),
-        Default::default(),
-        Arc::new(RwLock::new(HashSet::new())),
-    );
-
-    println!("Starting PUMPFUN Monitor...");
-
-    carbon_core::pipeline::Pipeline::builder()
-        .datasource(yellowstone_grpc)
-        .datasource(helius_laserstream)
+    let pipeline = carbon_core::pipeline::Pipeline::builder()
+        .datasource(JournalReplayDatasource::new(RPC_CLIENT.clone(), signatures));
+
+    with_processors(pipeline)
         .metrics(Arc::new(LogMetrics::new()))
-        .metrics_flush_interval(3)
-        .instruction(PumpfunDecoder, PumpfunProcess)
         .shutdown_strategy(carbon_core::pipeline::ShutdownStrategy::Immediate)
         .build()?
         .run()
-        .await?;
-
-    println!("PUMPFUN Monitor has stopped.");
-
-    Ok(())
+        .await
 }
 
-pub struct PumpfunProcess;
-
-pub static SIGNATURES: Lazy<RwLock<HashSet<String>>> = Lazy::new(|| RwLock::new(HashSet::new()));
-
-#[async_trait]
-impl Processor for PumpfunProcess {
-    type InputType = InstructionProcessorInputType<PumpfunInstruction>;
-
-    async fn process(
-        &mut self,
-        (metadata, instruction, nested_instructions, instructions): Self::InputType,
-        _metrics: Arc<MetricsCollection>,
-    ) -> CarbonResult<()> {
-        let signature = metadata.transaction_metadata.signature;
-
-        let static_account_keys = metadata.transaction_metadata.message.static_account_keys();
-        let writable_account_keys = &metadata.transaction_metadata.meta.loaded_addresses.writable;
-        let readonly_account_keys = &metadata.transaction_metadata.meta.loaded_addresses.readonly;
-
-        let mut account_keys: Vec<solana_sdk::pubkey::Pubkey> = vec![];
-
-        account_keys.extend(static_account_keys);
-        account_keys.extend(writable_account_keys);
-        account_keys.extend(readonly_account_keys);
-
-        let instruction_clone: DecodedInstruction<PumpfunInstruction> = instruction.clone();
-        
-        let start = std::time::Instant::now();
-
-        let raw_instructions = match instruction.data {
-            PumpfunInstruction::Buy(buy_data) => {
-                // Check checking signature period
-                if SIGNATURES.read().await.contains(&signature.to_string()) {
-                    println!("Signature {} already processed, skipping...", signature);
-                    return Ok(());
-                } else {
-                    SIGNATURES.write().await.insert(signature.to_string());
-                }
-                // Spawn a task to remove the signature after 3 seconds
-                tokio::spawn(async move {
-                    sleep(Duration::from_secs(3)).await;
-                    SIGNATURES.write().await.remove(&signature.to_string());
-                });
-                // Print siganure with timestamp
-                println!("Received target's signature : {:#?}\nCurrent time : {:#?}", signature, Utc::now());
-
-                if let Some(mut arranged) = Buy::arrange_accounts(&instruction_clone.accounts) {
-                    arranged.user = *PUBKEY;
-                    arranged.associated_user =
-                        get_associated_token_address(&PUBKEY, &arranged.mint);
-
-                    let inner_ixs: Vec<&InnerInstruction> = metadata
-                        .transaction_metadata
-                        .meta
-                        .inner_instructions
-                        .as_ref()
-                        .expect("missing inner_instructions")
-                        .iter()
-                        .flat_map(|ix_group| ix_group.instructions.iter())
-                        .filter(|inner_ix| {
-                            let program_id_index = inner_ix.instruction.program_id_index as usize;
-                            let program_id = account_keys
-                                .get(program_id_index)
-                                .expect("program id index out of bounds");
-
-                            // Get the first account index
-                            let first_account_index_opt = inner_ix.instruction.accounts.first();
-
-                            if let Some(&first_account_index) = first_account_index_opt {
-                                let first_account = account_keys
-                                    .get(first_account_index as usize)
-                                    .expect("account index out of bounds");
-
-                                // Check the conditions
-                                *program_id == PUMPFUN_PROGRAM_ID
-                                    && *first_account == arranged.event_authority
-                            } else {
-                                false // no first account, so filter out
-                            }
-                        })
-                        .collect();
-
-                    let Some(swap_cpi_ix) = inner_ixs.first() else {
-                        return Ok(()); // or Err(...) depending on your logic
-                    };
-
-                    if swap_cpi_ix
-                        .instruction
-                        .data
-                        .starts_with(&TradeEvent::DISCRIMINATOR)
-                    {
-                        let trade_event =
-                            TradeEvent::try_from_slice(&swap_cpi_ix.instruction.data[16..])
-                                .expect("Failed to parse TradeEvent");
-
-                        println!("Target bought {} tokens by {} SOL",
-                                 trade_event.token_amount as f64 / 10f64.powf(6_f64),
-                                 trade_event.sol_amount as f64 / 10f64.powf(9_f64));
-
-                        let required_token_amount = sol_token_quote(
-                            *BUY_SOL_AMOUNT,
-                            trade_event.virtual_sol_reserves,
-                            trade_event.virtual_token_reserves,
-                            true,
-                        );
-
-                        let lamports_with_slippage =
-                            (*BUY_SOL_AMOUNT as f64 * 1.011 * (1.0 + *SLIPPAGE)) as u64;
-
-                        let create_ata_ix = arranged.get_create_idempotent_ata_ix();
-
-                        let buy_ix = arranged.get_buy_ix(Buy {
-                            amount: required_token_amount,
-                            max_sol_cost: lamports_with_slippage,
-                        });
-
-                        vec![create_ata_ix, buy_ix]
-                    } else {
-                        vec![]
-                    }
-                } else {
-                    println!("Failed to arrange accounts");
-
-                    vec![]
-                }
-            }
-            PumpfunInstruction::Sell(sell_data) => {
-                
-                if SIGNATURES.read().await.contains(&signature.to_string()) {
-                    println!("Signature {} already processed, skipping...", signature);
-                    return Ok(());
-                } else {
-                    SIGNATURES.write().await.insert(signature.to_string());
-                }
-                // Spawn a task to remove the signature after 3 seconds
-                tokio::spawn(async move {
-                    sleep(Duration::from_secs(3)).await;
-                    SIGNATURES.write().await.remove(&signature.to_string());
-                });
-                // Print siganure with timestamp
-                println!("Received target's signature : {:#?}\nCurrent time : {:#?}", signature, Utc::now());
-
-                if let Some(mut arranged) = Sell::arrange_accounts(&instruction_clone.accounts) {
-                    arranged.user = *PUBKEY;
-                    arranged.associated_user =
-                        get_associated_token_address(&PUBKEY, &arranged.mint);
-
-                    let inner_ixs: Vec<&InnerInstruction> = metadata
-                        .transaction_metadata
-                        .meta
-                        .inner_instructions
-                        .as_ref()
-                        .expect("missing inner_instructions")
-                        .iter()
-                        .flat_map(|ix_group| ix_group.instructions.iter())
-                        .filter(|inner_ix| {
-                            let program_id_index = inner_ix.instruction.program_id_index as usize;
-                            let program_id = account_keys
-                                .get(program_id_index)
-                                .expect("program id index out of bounds");
-
-                            // Get the first account index
-                            let first_account_index_opt = inner_ix.instruction.accounts.first();
-
-                            if let Some(&first_account_index) = first_account_index_opt {
-                                let first_account = account_keys
-                                    .get(first_account_index as usize)
-                                    .expect("account index out of bounds");
-
-                                // Check the conditions
-                                *program_id == PUMPFUN_PROGRAM_ID
-                                    && *first_account == arranged.event_authority
-                            } else {
-                                false // no first account, so filter out
-                            }
-                        })
-                        .collect();
-
-                    let Some(swap_cpi_ix) = inner_ixs.first() else {
-                        return Ok(()); // or Err(...) depending on your logic
-                    };
-
-                    if swap_cpi_ix
-                        .instruction
-                        .data
-                        .starts_with(&TradeEvent::DISCRIMINATOR)
-                    {
-                        let trade_event =
-                            TradeEvent::try_from_slice(&swap_cpi_ix.instruction.data[16..])
-                                .expect("Failed to parse TradeEvent");
-
-                        println!("Target sold {} tokens for {} SOL",
-                                 trade_event.token_amount as f64 / 10f64.powf(6_f64),
-                                 trade_event.sol_amount as f64 / 10f64.powf(9_f64));
-
-                        let token_balance = match RPC_CLIENT
-                            .get_token_account_balance_with_commitment(
-                                &arranged.associated_user,
-                                CommitmentConfig::confirmed(),
-                            )
-                            .await
-                        {
-                            Ok(response) => response.value.amount,
-                            Err(e) => {
-                                eprintln!("Failed to get token balance: {:?}", e);
-                                return Ok(());
-                            }
-                        };
-
-                        let token_amount = match token_balance.parse::<u64>() {
-                            Ok(amount) => amount,
-                            Err(e) => {
-                                return Ok(());
-                            }
-                        };
-
-                        let min_sol_amount_out = token_sol_quote(
-                            token_amount,
-                            trade_event.virtual_sol_reserves,
-                            trade_event.virtual_token_reserves,
-                            false,
-                        );
-
-                        let lamports_with_slippage =
-                            (*BUY_SOL_AMOUNT as f64 * 1.011 * (1.0 - *SLIPPAGE)) as u64;
-
-                        let sell_ix = arranged.get_sell_ix(Sell {
-                            amount: token_amount,
-                            min_sol_output: lamports_with_slippage,
-                        });
-
-                        let close_ata_ix = arranged.get_close_ata_ix();
-
-                        vec![sell_ix, close_ata_ix]
-                    } else {
-                        vec![]
-                    }
-                } else {
-                    println!("Failed to arrange accounts");
-
-                    vec![]
-                }
-            }
-            _ => {
-                vec![]
-            }
-        };
+/// Paper trades the transactions of `targets` between the `from` and `to` days through the processors, with the senders stubbed out, and prints the results.
+async fn backtest(targets: Vec<Pubkey>, from: NaiveDate, to: NaiveDate, source: Option<PathBuf>) -> CarbonResult<()> {
+    let window = backtest_window(from, to).map_err(|e| CarbonError::Custom(e.to_string()))?;
 
-        if !raw_instructions.is_empty() {
-            let (cu, priority_fee_micro_lamport, third_party_fee) = *PRIORITY_FEE;
-
-            // Print current timestamp and consumed time from start
-            println!("Submitting tx --> Current time: {:#?}\nPeriod from start: {:?}", Utc::now(), start.elapsed());
-
-            let results = match CONFIRM_SERVICE.as_str() {
-                "NOZOMI" => {
-                    let nozomi = NOZOMI_CLIENT.get().expect("Nozomi client not initialized");
-
-                    let ixs = nozomi.add_tip_ix(Tips {
-                        cu: Some(cu),
-                        priority_fee_micro_lamport: Some(priority_fee_micro_lamport),
-                        payer: *PUBKEY,
-                        pure_ix: raw_instructions.clone(),
-                        tip_addr_idx: 1,
-                        tip_sol_amount: third_party_fee,
-                    });
-
-                    let recent_blockhash = get_slot();
-
-                    let encoded_tx = build_and_sign(ixs, recent_blockhash, None);
-
-                    match nozomi.send_transaction(&encoded_tx).await {
-                        Ok(data) => json!({ "result": data }),
-                        Err(err) => {
-                            json!({ "result": "error", "message": err.to_string() })
-                        }
-                    }
-                }
-                "ZERO_SLOT" => {
-                    let zero_slot = ZSLOT_CLIENT.get().expect("ZSlot client not initialized");
-
-                    let ixs = zero_slot.add_tip_ix(Tips {
-                        cu: Some(cu),
-                        priority_fee_micro_lamport: Some(priority_fee_micro_lamport),
-                        payer: *PUBKEY,
-                        pure_ix: raw_instructions,
-                        tip_addr_idx: 1,
-                        tip_sol_amount: third_party_fee,
-                    });
-
-                    let recent_blockhash = get_slot();
-
-                    let encoded_tx = build_and_sign(ixs, recent_blockhash, None);
-
-                    match zero_slot.send_transaction(&encoded_tx).await {
-                        Ok(data) => json!({ "result": data }),
-                        Err(err) => {
-                            json!({ "result": "error", "message": err.to_string() })
-                        }
-                    }
-                }
-                "JITO" => {
-                    let jito = JITO_CLIENT.get().expect("Jito client not initialized");
-
-                    let ixs = jito.add_tip_ix(Tips {
-                        cu: Some(cu),
-                        priority_fee_micro_lamport: Some(priority_fee_micro_lamport),
-                        payer: *PUBKEY,
-                        pure_ix: raw_instructions,
-                        tip_addr_idx: 1,
-                        tip_sol_amount: third_party_fee,
-                    });
-
-                    let recent_blockhash = get_slot();
-
-                    let encoded_tx = build_and_sign(ixs, recent_blockhash, None);
-
-                    match jito.send_transaction(&encoded_tx).await {
-                        Ok(data) => json!({ "result": data }),
-                        Err(err) => {
-                            json!({ "result": "error", "message": err.to_string() })
-                        }
-                    }
-                }
-                _ => {
-                    json!({ "result": "error", "message": "unknown confirmation service" })
-                }
-            };
-
-            println!("Transaction confirmed --> : {:#?}\nCurrent time: {:#?}\nPeriod from start: {:?}", results, Utc::now(), start.elapsed());
-        };
+    // Only the backtested wallets are copied, nothing is saved to TARGETS_PATH
+    if let Some((first, rest)) = targets.split_first() {
+        FOLLOWED_TARGETS.set(*first);
 
-        Ok(())
+        for target in rest {
+            FOLLOWED_TARGETS.add(*target);
+        }
     }
+
+    let signatures = backtest_signatures(&RPC_CLIENT, source.as_deref(), &FOLLOWED_TARGETS.all(), &window)
+        .await
+        .map_err(|e| CarbonError::Custom(format!("Failed to collect the transactions to backtest: {:#}", e)))?;
+    let replayed = signatures.len();
+
+    info!("Backtesting {} target transactions from {} to {}", replayed, from, to);
+
+    stub_senders();
+    enable_paper_trading();
+    seed_paper_balances();
+
+    spawn_background(pumpfun_global_handler(RPC_CLIENT.clone()));
+
+    start_order_workers();
+
+    rustls::crypto::aws_lc_rs::default_provider()
+        .install_default()
+        .expect("Can't set crypto provider to aws_lc_rs");
+
+    let pipeline = carbon_core::pipeline::Pipeline::builder()
+        .datasource(JournalReplayDatasource::new(RPC_CLIENT.clone(), signatures).with_block_time_range(window));
+
+    with_processors(pipeline)
+        .shutdown_strategy(carbon_core::pipeline::ShutdownStrategy::Immediate)
+        .build()?
+        .run()
+        .await?;
+
+    print_backtest_report(replayed);
+
+    Ok(())
 }
@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use carbon_core::{error::CarbonResult, metrics::Metrics};
+
+use crate::utils::record_pipeline_heartbeat;
+
+/// Stamps the pipeline heartbeat the watchdog checks. The pipeline calls its metrics for
+/// every update it processes and on every metrics flush, so the stamps stop only when
+/// its loop does.
+pub struct PipelineHeartbeat;
+
+#[async_trait]
+impl Metrics for PipelineHeartbeat {
+    async fn initialize(&self) -> CarbonResult<()> {
+        record_pipeline_heartbeat();
+        Ok(())
+    }
+
+    async fn flush(&self) -> CarbonResult<()> {
+        record_pipeline_heartbeat();
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn update_gauge(&self, _name: &str, _value: f64) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn increment_counter(&self, _name: &str, _value: u64) -> CarbonResult<()> {
+        record_pipeline_heartbeat();
+        Ok(())
+    }
+
+    async fn record_histogram(&self, _name: &str, _value: f64) -> CarbonResult<()> {
+        Ok(())
+    }
+}
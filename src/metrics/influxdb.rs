@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use carbon_core::{error::CarbonResult, metrics::Metrics};
+use chrono::Utc;
+use reqwest::Client;
+use std::{sync::Mutex, time::Duration};
+use tracing::warn;
+
+/// Points kept between flushes, beyond that new ones are dropped
+const MAX_PENDING_POINTS: usize = 50_000;
+
+/// Ships measurements to InfluxDB 2.x through its `/api/v2/write` endpoint, as line
+/// protocol. Points are buffered with the time they were recorded and written on the
+/// pipeline's metrics flush. Each metric is its own measurement with a `kind` tag
+/// (`counter`, `gauge` or `histogram`) and a single `value` field.
+pub struct InfluxDbMetrics {
+    client: Client,
+    write_url: String,
+    token: Option<String>,
+    pending: Mutex<Vec<String>>,
+}
+
+impl InfluxDbMetrics {
+    /// Writes to `bucket` of `org` on the server at `url`, e.g. `http://localhost:8086`.
+    pub fn new(url: &str, org: &str, bucket: &str, token: Option<String>) -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(5))
+                .build()
+                .expect("Failed to build HTTP client"),
+            write_url: format!(
+                "{}/api/v2/write?org={}&bucket={}&precision=ns",
+                url.trim_end_matches('/'),
+                org,
+                bucket
+            ),
+            token,
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn push(&self, name: &str, kind: &str, value: String) {
+        let mut pending = self.pending.lock().unwrap();
+
+        if pending.len() < MAX_PENDING_POINTS {
+            pending.push(influx_line(
+                name,
+                kind,
+                &value,
+                Utc::now().timestamp_nanos_opt().unwrap_or_default(),
+            ));
+        }
+    }
+}
+
+/// One line protocol point, with the measurement name escaped.
+pub fn influx_line(name: &str, kind: &str, value: &str, timestamp_ns: i64) -> String {
+    let name = name.replace(',', "\\,").replace(' ', "\\ ");
+
+    format!("{},kind={} value={} {}", name, kind, value, timestamp_ns)
+}
+
+#[async_trait]
+impl Metrics for InfluxDbMetrics {
+    async fn initialize(&self) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    /// A failed write only loses measurements, so it is logged and the points dropped.
+    async fn flush(&self) -> CarbonResult<()> {
+        let lines = std::mem::take(&mut *self.pending.lock().unwrap());
+
+        if lines.is_empty() {
+            return Ok(());
+        }
+
+        let mut request = self
+            .client
+            .post(&self.write_url)
+            .header("Content-Type", "text/plain; charset=utf-8")
+            .body(lines.join("\n"));
+
+        if let Some(token) = &self.token {
+            request = request.header("Authorization", format!("Token {}", token));
+        }
+
+        match request.send().await {
+            Ok(response) if response.status().is_success() => {}
+            Ok(response) => warn!("InfluxDB rejected metrics: {}", response.status()),
+            Err(e) => warn!("Failed to write metrics to InfluxDB: {}", e),
+        }
+
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> CarbonResult<()> {
+        self.flush().await
+    }
+
+    async fn update_gauge(&self, name: &str, value: f64) -> CarbonResult<()> {
+        self.push(name, "gauge", value.to_string());
+        Ok(())
+    }
+
+    async fn increment_counter(&self, name: &str, value: u64) -> CarbonResult<()> {
+        self.push(name, "counter", format!("{}i", value));
+        Ok(())
+    }
+
+    async fn record_histogram(&self, name: &str, value: f64) -> CarbonResult<()> {
+        self.push(name, "histogram", value.to_string());
+        Ok(())
+    }
+}
@@ -0,0 +1,9 @@
+pub mod heartbeat;
+pub mod influxdb;
+pub mod sink;
+pub mod statsd;
+
+pub use heartbeat::*;
+pub use influxdb::*;
+pub use sink::*;
+pub use statsd::*;
@@ -0,0 +1,48 @@
+use carbon_core::metrics::Metrics;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::{
+    config::{
+        INFLUXDB_BUCKET, INFLUXDB_ORG, INFLUXDB_TOKEN, INFLUXDB_URL, METRICS_SINK, STATSD_ADDR,
+        STATSD_PREFIX,
+    },
+    metrics::{InfluxDbMetrics, StatsdMetrics},
+};
+
+/// The external metrics backend picked by `METRICS_SINK`, added next to the log metrics.
+/// `None` when it is unset or can't be set up.
+pub fn metrics_sink() -> Option<Arc<dyn Metrics>> {
+    match METRICS_SINK.as_str() {
+        "" => None,
+        "statsd" => match StatsdMetrics::new(&STATSD_ADDR, &STATSD_PREFIX) {
+            Ok(statsd) => {
+                info!("Shipping metrics to StatsD at {}", *STATSD_ADDR);
+                Some(Arc::new(statsd))
+            }
+            Err(e) => {
+                warn!("Failed to set up StatsD metrics at {}: {}", *STATSD_ADDR, e);
+                None
+            }
+        },
+        "influxdb" => {
+            let Some(url) = INFLUXDB_URL.as_deref() else {
+                warn!("METRICS_SINK is influxdb but INFLUXDB_URL is not set");
+                return None;
+            };
+
+            info!("Shipping metrics to InfluxDB at {}", url);
+
+            Some(Arc::new(InfluxDbMetrics::new(
+                url,
+                &INFLUXDB_ORG,
+                &INFLUXDB_BUCKET,
+                INFLUXDB_TOKEN.clone(),
+            )))
+        }
+        other => {
+            warn!("Unknown METRICS_SINK {}, ignoring", other);
+            None
+        }
+    }
+}
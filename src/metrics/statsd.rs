@@ -0,0 +1,119 @@
+use async_trait::async_trait;
+use carbon_core::{error::CarbonResult, metrics::Metrics};
+use std::{io, net::UdpSocket, sync::Mutex};
+use tracing::warn;
+
+/// Largest datagram sent, safe for the usual 1500 byte MTU
+const MAX_PACKET_BYTES: usize = 1432;
+
+/// Measurements kept between flushes, beyond that new ones are dropped
+const MAX_PENDING_LINES: usize = 50_000;
+
+/// Ships measurements to a StatsD daemon over UDP. Lines are buffered and sent on the
+/// pipeline's metrics flush, so recording from the hot path never touches the socket.
+/// Histograms go out as timers (`|ms`), which StatsD aggregates into percentiles.
+pub struct StatsdMetrics {
+    socket: UdpSocket,
+    prefix: String,
+    pending: Mutex<Vec<String>>,
+}
+
+impl StatsdMetrics {
+    /// Connects to the daemon at `addr`, e.g. `127.0.0.1:8125`, prefixing every name
+    /// with `prefix` and a dot.
+    pub fn new(addr: &str, prefix: &str) -> io::Result<Self> {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        socket.connect(addr)?;
+
+        Ok(Self {
+            socket,
+            prefix: prefix.to_string(),
+            pending: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn push(&self, name: &str, value: String, kind: &str) {
+        let mut pending = self.pending.lock().unwrap();
+
+        if pending.len() < MAX_PENDING_LINES {
+            pending.push(statsd_line(&self.prefix, name, &value, kind));
+        }
+    }
+
+    /// A lost datagram only loses measurements, so failures are logged and dropped.
+    fn send(&self, packet: &str) {
+        if let Err(e) = self.socket.send(packet.as_bytes()) {
+            warn!("Failed to send StatsD metrics: {}", e);
+        }
+    }
+}
+
+/// One StatsD line, with the characters the protocol reserves replaced in the name.
+pub fn statsd_line(prefix: &str, name: &str, value: &str, kind: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if matches!(c, ':' | '|' | '@' | ' ') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    if prefix.is_empty() {
+        format!("{}:{}|{}", name, value, kind)
+    } else {
+        format!("{}.{}:{}|{}", prefix, name, value, kind)
+    }
+}
+
+#[async_trait]
+impl Metrics for StatsdMetrics {
+    async fn initialize(&self) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn flush(&self) -> CarbonResult<()> {
+        let lines = std::mem::take(&mut *self.pending.lock().unwrap());
+
+        let mut packet = String::with_capacity(MAX_PACKET_BYTES);
+
+        for line in lines {
+            if !packet.is_empty() && packet.len() + line.len() + 1 > MAX_PACKET_BYTES {
+                self.send(&packet);
+                packet.clear();
+            }
+
+            if !packet.is_empty() {
+                packet.push('\n');
+            }
+            packet.push_str(&line);
+        }
+
+        if !packet.is_empty() {
+            self.send(&packet);
+        }
+
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> CarbonResult<()> {
+        self.flush().await
+    }
+
+    async fn update_gauge(&self, name: &str, value: f64) -> CarbonResult<()> {
+        self.push(name, value.to_string(), "g");
+        Ok(())
+    }
+
+    async fn increment_counter(&self, name: &str, value: u64) -> CarbonResult<()> {
+        self.push(name, value.to_string(), "c");
+        Ok(())
+    }
+
+    async fn record_histogram(&self, name: &str, value: f64) -> CarbonResult<()> {
+        self.push(name, value.to_string(), "ms");
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use carbon_core::{error::CarbonResult, metrics::MetricsCollection, processor::Processor};
+use std::sync::Arc;
+
+#[cfg(feature = "alloc-profiling")]
+use crate::utils::AllocationStats;
+
+/// Wraps a processor to record the allocations each call makes as the
+/// `{name}_allocations` and `{name}_allocated_bytes` histograms. Without the
+/// `alloc-profiling` feature it only forwards to the processor.
+///
+/// Counts are process-wide, so work running concurrently with the call (order
+/// workers, other datasources) is included; they are meant for comparing builds
+/// rather than attributing single allocations.
+pub struct AllocationProfiled<P> {
+    name: &'static str,
+    inner: P,
+}
+
+impl<P> AllocationProfiled<P> {
+    pub fn new(name: &'static str, inner: P) -> Self {
+        Self { name, inner }
+    }
+}
+
+#[async_trait]
+impl<P> Processor for AllocationProfiled<P>
+where
+    P: Processor + Send + Sync,
+    P::InputType: Send,
+{
+    type InputType = P::InputType;
+
+    async fn process(
+        &mut self,
+        data: Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        #[cfg(feature = "alloc-profiling")]
+        {
+            let before = AllocationStats::now();
+
+            let result = self.inner.process(data, metrics.clone()).await;
+
+            let made = AllocationStats::now().since(&before);
+
+            metrics
+                .record_histogram(
+                    &format!("{}_allocations", self.name),
+                    made.allocations as f64,
+                )
+                .await
+                .ok();
+            metrics
+                .record_histogram(&format!("{}_allocated_bytes", self.name), made.bytes as f64)
+                .await
+                .ok();
+
+            result
+        }
+
+        #[cfg(not(feature = "alloc-profiling"))]
+        {
+            let _ = self.name;
+
+            self.inner.process(data, metrics).await
+        }
+    }
+}
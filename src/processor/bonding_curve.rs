@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use carbon_core::{
+    account::AccountProcessorInputType, error::CarbonResult, metrics::MetricsCollection,
+    processor::Processor,
+};
+use carbon_pumpfun_decoder::accounts::PumpfunAccount;
+use std::sync::Arc;
+
+use crate::utils::{CURVE_STATES, CurveReserves, apply_pumpfun_global};
+
+/// Keeps `CURVE_STATES` in sync with the bonding curve accounts streamed by the datasources,
+/// and the cached pumpfun Global config with the Global account.
+pub struct BondingCurveProcess;
+
+#[async_trait]
+impl Processor for BondingCurveProcess {
+    type InputType = AccountProcessorInputType<PumpfunAccount>;
+
+    async fn process(
+        &mut self,
+        (metadata, account, _raw_account): Self::InputType,
+        _metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        match account.data {
+            PumpfunAccount::BondingCurve(bonding_curve) => {
+                CURVE_STATES.update(
+                    metadata.pubkey,
+                    CurveReserves {
+                        virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
+                        virtual_token_reserves: bonding_curve.virtual_token_reserves,
+                        complete: bonding_curve.complete,
+                        slot: metadata.slot,
+                    },
+                );
+            }
+            PumpfunAccount::Global(global) => apply_pumpfun_global(&global),
+        }
+
+        Ok(())
+    }
+}
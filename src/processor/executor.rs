@@ -0,0 +1,265 @@
+use carbon_core::metrics::MetricsCollection;
+use once_cell::sync::Lazy;
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+use tokio::sync::Notify;
+use tracing::{Instrument, Span, info, warn};
+
+use crate::{
+    config::{
+        EXECUTION_DROP_POLICY, EXECUTION_MAX_QUEUE_MS, EXECUTION_QUEUE_CAPACITY, EXECUTION_WORKERS,
+    },
+    processor::{CopyOrder, submit_orders},
+    utils::{EXECUTION_RUNTIME, JOURNAL_SKIP, LatencyTrace},
+};
+
+/// Copy orders of one mint, submitted together by a worker.
+pub struct OrderJob {
+    pub mint: Pubkey,
+    pub orders: Vec<CopyOrder>,
+    pub metrics: Arc<MetricsCollection>,
+    pub start: LatencyTrace,
+    /// Trade span the orders were built in, their sends are logged under it
+    pub span: Span,
+}
+
+impl OrderJob {
+    fn has_buys(&self) -> bool {
+        self.orders
+            .iter()
+            .any(|order| order.bought_tokens.is_some())
+    }
+}
+
+/// Which waiting job gives way when a worker queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropPolicy {
+    /// Drop the job that has waited longest
+    DropOldest,
+    /// Drop the oldest job with buys, so exits aren't held back by a burst of entries
+    DropBuysFirst,
+}
+
+impl DropPolicy {
+    pub fn from_config(value: &str) -> Self {
+        match value {
+            "drop_buys_first" => DropPolicy::DropBuysFirst,
+            "drop_oldest" => DropPolicy::DropOldest,
+            other => {
+                warn!("Unknown EXECUTION_DROP_POLICY {}, using drop_oldest", other);
+                DropPolicy::DropOldest
+            }
+        }
+    }
+}
+
+struct Shard {
+    queue: Mutex<VecDeque<OrderJob>>,
+    notify: Notify,
+}
+
+/// Worker pool that builds and sends copy orders off the processors. Orders are sharded
+/// by mint, so trades on different tokens go out concurrently while the buys and sells
+/// of a single token are submitted in the order they were detected. Each worker queue
+/// holds at most `capacity` jobs, beyond that `policy` decides which one is dropped.
+/// Under load, sells are taken ahead of buys waiting on other mints.
+pub struct OrderExecutor {
+    shards: Vec<Shard>,
+    capacity: usize,
+    policy: DropPolicy,
+    max_queue_time: Option<Duration>,
+    /// Jobs taken off a queue and not yet submitted
+    in_flight: AtomicUsize,
+}
+
+pub static ORDER_EXECUTOR: Lazy<OrderExecutor> = Lazy::new(|| {
+    OrderExecutor::new(
+        *EXECUTION_WORKERS,
+        *EXECUTION_QUEUE_CAPACITY,
+        DropPolicy::from_config(&EXECUTION_DROP_POLICY),
+        EXECUTION_MAX_QUEUE_MS.map(Duration::from_millis),
+    )
+});
+
+impl OrderExecutor {
+    pub fn new(
+        workers: usize,
+        capacity: usize,
+        policy: DropPolicy,
+        max_queue_time: Option<Duration>,
+    ) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            policy,
+            max_queue_time,
+            in_flight: AtomicUsize::new(0),
+            shards: (0..workers.max(1))
+                .map(|_| Shard {
+                    queue: Mutex::new(VecDeque::new()),
+                    notify: Notify::new(),
+                })
+                .collect(),
+        }
+    }
+
+    fn shard(&self, mint: &Pubkey) -> usize {
+        let bytes = mint.to_bytes();
+        let key = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+
+        (key % self.shards.len() as u64) as usize
+    }
+
+    /// Queues `orders` for the workers, one job per mint.
+    pub fn dispatch(
+        &self,
+        orders: Vec<CopyOrder>,
+        metrics: &Arc<MetricsCollection>,
+        start: LatencyTrace,
+    ) {
+        let mut by_mint: HashMap<Pubkey, Vec<CopyOrder>> = HashMap::new();
+
+        for order in orders {
+            by_mint.entry(order.mint).or_default().push(order);
+        }
+
+        for (mint, orders) in by_mint {
+            let shard = &self.shards[self.shard(&mint)];
+
+            let mut queue = shard.queue.lock().unwrap();
+
+            if queue.len() >= self.capacity {
+                let dropped = self
+                    .drop_index(&queue)
+                    .and_then(|index| queue.remove(index));
+
+                if let Some(dropped) = dropped {
+                    let _span = dropped.span.enter();
+
+                    info!(
+                        target: JOURNAL_SKIP,
+                        "Execution queue full, dropping {} order(s) of {} detected {:?} ago",
+                        dropped.orders.len(),
+                        dropped.mint,
+                        dropped.start.elapsed()
+                    );
+                }
+            }
+
+            queue.push_back(OrderJob {
+                mint,
+                orders,
+                metrics: metrics.clone(),
+                start: start.built(),
+                span: Span::current(),
+            });
+            drop(queue);
+
+            shard.notify.notify_one();
+        }
+    }
+
+    fn drop_index(&self, queue: &VecDeque<OrderJob>) -> Option<usize> {
+        match self.policy {
+            DropPolicy::DropOldest => (!queue.is_empty()).then_some(0),
+            DropPolicy::DropBuysFirst => queue
+                .iter()
+                .position(OrderJob::has_buys)
+                .or((!queue.is_empty()).then_some(0)),
+        }
+    }
+
+    /// Drops the buys of a job that waited past `max_queue_time`, it's too late to follow them.
+    fn drop_stale_buys(&self, job: &mut OrderJob) {
+        let Some(max_queue_time) = self.max_queue_time else {
+            return;
+        };
+
+        if job.has_buys() && job.start.elapsed() > max_queue_time {
+            let _span = job.span.enter();
+
+            info!(target: JOURNAL_SKIP,
+                "Buy waited {:?} for execution, past the queue deadline, skipping...",
+                job.start.elapsed()
+            );
+            job.orders.retain(|order| order.bought_tokens.is_none());
+        }
+    }
+
+    /// Whether every queued job has been submitted.
+    pub fn is_idle(&self) -> bool {
+        self.in_flight.load(Ordering::SeqCst) == 0
+            && self
+                .shards
+                .iter()
+                .all(|shard| shard.queue.lock().unwrap().is_empty())
+    }
+
+    /// Submits the jobs of shard `index` one after another, for as long as the bot runs.
+    async fn run_worker(&self, index: usize) {
+        let shard = &self.shards[index];
+
+        loop {
+            let job = {
+                let mut queue = shard.queue.lock().unwrap();
+                let job = next_job(&mut queue);
+
+                // Counted under the lock, so the job is never missing from `is_idle`
+                if job.is_some() {
+                    self.in_flight.fetch_add(1, Ordering::SeqCst);
+                }
+
+                job
+            };
+
+            match job {
+                Some(mut job) => {
+                    self.drop_stale_buys(&mut job);
+
+                    if !job.orders.is_empty() {
+                        job.start.record_build(&job.metrics).await;
+                        job.start.record_queue(&job.metrics).await;
+
+                        submit_orders(job.orders, &job.metrics, job.start)
+                            .instrument(job.span)
+                            .await;
+                    }
+
+                    self.in_flight.fetch_sub(1, Ordering::SeqCst);
+                }
+                None => shard.notify.notified().await,
+            }
+        }
+    }
+}
+
+/// Takes the next job to submit. Sells protecting held positions go ahead of waiting
+/// buys, but never ahead of an earlier job for the same mint, so each token's trades
+/// still go out in order.
+fn next_job(queue: &mut VecDeque<OrderJob>) -> Option<OrderJob> {
+    let sell = queue.iter().enumerate().position(|(index, job)| {
+        !job.has_buys()
+            && queue
+                .iter()
+                .take(index)
+                .all(|earlier| earlier.mint != job.mint)
+    });
+
+    match sell {
+        Some(index) => queue.remove(index),
+        None => queue.pop_front(),
+    }
+}
+
+/// Starts one worker per `EXECUTION_WORKERS` shard on the dedicated execution runtime.
+pub fn start_order_workers() {
+    for index in 0..ORDER_EXECUTOR.shards.len() {
+        EXECUTION_RUNTIME.spawn(ORDER_EXECUTOR.run_worker(index));
+    }
+}
@@ -0,0 +1,250 @@
+use carbon_core::{deserialize::CarbonDeserialize, metrics::MetricsCollection};
+use carbon_pumpfun_decoder::{
+    PROGRAM_ID as PUMPFUN_PROGRAM_ID,
+    accounts::bonding_curve::BondingCurve,
+    instructions::{buy::Buy, buy::BuyInstructionAccounts, sell::SellInstructionAccounts},
+};
+use solana_sdk::{pubkey::Pubkey, signer::Signer, system_program};
+use std::sync::Arc;
+use tracing::info;
+
+use crate::{
+    config::{RPC_CLIENT, buy_slippage},
+    datasource::FOLLOWED_TARGETS,
+    instructions::buy_ix::BuyExactInInstructionAccountsExt,
+    processor::{CopyOrder, ORDER_EXECUTOR, creator_vault_pda, curve_sell_ixs, sell_positions},
+    utils::{
+        CURVE_STATES, CurveReserves, LatencyTrace, MANUAL_CONFIRMATIONS, ManualTrade,
+        PUMPFUN_GLOBAL, associated_token_address_with_program_id, bonding_curve_token_quote,
+        curve_slippage, find_pda, pumpfun_buy_cost, pumpfun_fee_rate, pumpfun_fee_recipient,
+        pumpfun_fees,
+    },
+    wallet::WALLET_POOL,
+};
+
+/// Offset of the creator in bonding curve account data, after the discriminator, five
+/// u64 reserves and the `complete` flag
+const CURVE_CREATOR_OFFSET: usize = 49;
+
+/// The live state of a pump.fun bonding curve needed to trade on it by hand.
+struct LiveCurve {
+    bonding_curve: Pubkey,
+    creator: Pubkey,
+    token_program: Pubkey,
+    reserves: CurveReserves,
+}
+
+impl LiveCurve {
+    /// Fetches the bonding curve of `mint` and its mint's token program over RPC.
+    async fn fetch(mint: &Pubkey) -> Result<Self, String> {
+        let bonding_curve = find_pda(&[b"bonding-curve", mint.as_ref()], &PUMPFUN_PROGRAM_ID);
+
+        let accounts = RPC_CLIENT
+            .get_multiple_accounts(&[bonding_curve, *mint])
+            .await
+            .map_err(|e| format!("failed to fetch the bonding curve: {}", e))?;
+
+        let (Some(curve_account), Some(mint_account)) = (&accounts[0], &accounts[1]) else {
+            return Err("not a pump.fun bonding curve token".to_string());
+        };
+
+        let curve = <BondingCurve as CarbonDeserialize>::deserialize(&curve_account.data)
+            .ok_or("bonding curve account can't be decoded")?;
+
+        if curve.complete {
+            return Err("bonding curve is complete, the token migrated".to_string());
+        }
+
+        let creator = curve_account
+            .data
+            .get(CURVE_CREATOR_OFFSET..CURVE_CREATOR_OFFSET + 32)
+            .and_then(|bytes| Pubkey::try_from(bytes).ok())
+            .ok_or("bonding curve has no creator")?;
+
+        Ok(Self {
+            bonding_curve,
+            creator,
+            token_program: mint_account.owner,
+            reserves: CurveReserves {
+                virtual_sol_reserves: curve.virtual_sol_reserves,
+                virtual_token_reserves: curve.virtual_token_reserves,
+                complete: curve.complete,
+                slot: 0,
+            },
+        })
+    }
+
+    fn token_account(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        associated_token_address_with_program_id(owner, mint, &self.token_program)
+    }
+}
+
+/// Buys `sol_amount` lamports of `mint` on its pump.fun bonding curve with the next pool
+/// wallet that can afford it, outside of any target trade. A buy over
+/// `MANUAL_CONFIRM_ABOVE_SOL` needs its `confirm_code`. Returns the buying wallet, or
+/// why nothing could be bought.
+pub async fn buy_mint(
+    mint: Pubkey,
+    sol_amount: u64,
+    confirm_code: Option<&str>,
+    metrics: &Arc<MetricsCollection>,
+) -> Result<Pubkey, String> {
+    MANUAL_CONFIRMATIONS.check(
+        ManualTrade::Buy { mint, sol_amount },
+        sol_amount,
+        confirm_code,
+    )?;
+
+    let curve = LiveCurve::fetch(&mint).await?;
+    let target = FOLLOWED_TARGETS.primary();
+    let slippage = buy_slippage(&target);
+
+    let required_lamports =
+        (sol_amount as f64 * (1.0 + pumpfun_fee_rate()) * (1.0 + slippage)) as u64;
+
+    let wallet = WALLET_POOL
+        .next_buy_wallet(required_lamports)
+        .ok_or("no trading wallet has enough SOL")?;
+
+    let (virtual_sol_reserves, virtual_token_reserves) = CURVE_STATES.with_own_buys(
+        &mint,
+        curve.reserves.virtual_sol_reserves,
+        curve.reserves.virtual_token_reserves,
+    );
+
+    let token_amount = bonding_curve_token_quote(
+        sol_amount,
+        virtual_sol_reserves,
+        virtual_token_reserves,
+        true,
+    );
+
+    let sol_cost = pumpfun_buy_cost(
+        token_amount,
+        virtual_sol_reserves,
+        virtual_token_reserves,
+        &pumpfun_fees(),
+    );
+
+    let max_sol_cost = (sol_cost as f64
+        * (1.0 + curve_slippage(slippage, sol_amount, virtual_sol_reserves, true)))
+        as u64;
+
+    let buy_accounts = BuyInstructionAccounts {
+        global: PUMPFUN_GLOBAL,
+        fee_recipient: pumpfun_fee_recipient(),
+        mint,
+        bonding_curve: curve.bonding_curve,
+        associated_bonding_curve: curve.token_account(&curve.bonding_curve, &mint),
+        associated_user: curve.token_account(&wallet.pubkey(), &mint),
+        user: wallet.pubkey(),
+        system_program: system_program::ID,
+        token_program: curve.token_program,
+        creator_vault: creator_vault_pda(&curve.creator),
+        event_authority: find_pda(&[b"__event_authority"], &PUMPFUN_PROGRAM_ID),
+        program: PUMPFUN_PROGRAM_ID,
+    };
+
+    let order = CopyOrder {
+        target,
+        wallet,
+        mint,
+        bought_tokens: Some(token_amount),
+        quoted_sol: sol_cost,
+        ixs: vec![
+            buy_accounts.get_create_idempotent_ata_ix(),
+            buy_accounts.get_buy_ix(Buy {
+                amount: token_amount,
+                max_sol_cost,
+            }),
+        ],
+    };
+
+    info!(
+        "Buying {} with {} lamports from {} by hand...",
+        mint,
+        sol_amount,
+        wallet.pubkey()
+    );
+
+    CURVE_STATES.watch(mint, curve.bonding_curve);
+    ORDER_EXECUTOR.dispatch(vec![order], metrics, LatencyTrace::now());
+
+    Ok(wallet.pubkey())
+}
+
+/// Sells every position in `mint` on its pump.fun bonding curve, outside of any target
+/// trade. Returns the number of wallets sold from, or why nothing could be sold.
+pub async fn sell_mint(mint: Pubkey, metrics: &Arc<MetricsCollection>) -> Result<usize, String> {
+    let orders = sell_orders(mint).await?;
+
+    Ok(dispatch_sells(mint, orders, metrics))
+}
+
+/// `sell_mint` for a sell made by hand, which needs its `confirm_code` when the positions
+/// are quoted at more than `MANUAL_CONFIRM_ABOVE_SOL`.
+pub async fn manual_sell_mint(
+    mint: Pubkey,
+    confirm_code: Option<&str>,
+    metrics: &Arc<MetricsCollection>,
+) -> Result<usize, String> {
+    let orders = sell_orders(mint).await?;
+    let quoted_sol = orders.iter().map(|order| order.quoted_sol).sum();
+
+    MANUAL_CONFIRMATIONS.check(ManualTrade::Sell { mint }, quoted_sol, confirm_code)?;
+
+    Ok(dispatch_sells(mint, orders, metrics))
+}
+
+async fn sell_orders(mint: Pubkey) -> Result<Vec<CopyOrder>, String> {
+    let curve = LiveCurve::fetch(&mint).await?;
+
+    let positions = sell_positions(&mint, |wallet| curve.token_account(wallet, &mint)).await;
+
+    if positions.is_empty() {
+        return Err("no wallet holds it".to_string());
+    }
+
+    let target = FOLLOWED_TARGETS.primary();
+
+    Ok(positions
+        .into_iter()
+        .map(|(wallet, token_amount)| {
+            let sell_accounts = SellInstructionAccounts {
+                global: PUMPFUN_GLOBAL,
+                fee_recipient: pumpfun_fee_recipient(),
+                mint,
+                bonding_curve: curve.bonding_curve,
+                associated_bonding_curve: curve.token_account(&curve.bonding_curve, &mint),
+                associated_user: curve.token_account(&wallet.pubkey(), &mint),
+                user: wallet.pubkey(),
+                system_program: system_program::ID,
+                creator_vault: creator_vault_pda(&curve.creator),
+                token_program: curve.token_program,
+                event_authority: find_pda(&[b"__event_authority"], &PUMPFUN_PROGRAM_ID),
+                program: PUMPFUN_PROGRAM_ID,
+            };
+
+            let (ixs, quoted_sol) = curve_sell_ixs(&sell_accounts, token_amount, &curve.reserves);
+
+            CopyOrder {
+                target,
+                wallet,
+                mint,
+                bought_tokens: None,
+                quoted_sol,
+                ixs,
+            }
+        })
+        .collect())
+}
+
+fn dispatch_sells(mint: Pubkey, orders: Vec<CopyOrder>, metrics: &Arc<MetricsCollection>) -> usize {
+    let wallets = orders.len();
+
+    info!("Selling {} from {} wallet(s) by hand...", mint, wallets);
+
+    ORDER_EXECUTOR.dispatch(orders, metrics, LatencyTrace::now());
+
+    wallets
+}
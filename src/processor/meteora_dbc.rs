@@ -0,0 +1,321 @@
+use async_trait::async_trait;
+use carbon_core::{
+    deserialize::ArrangeAccounts, error::CarbonResult, instruction::InstructionProcessorInputType,
+    metrics::MetricsCollection, processor::Processor,
+};
+use once_cell::sync::Lazy;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use tracing::{debug, info, warn};
+
+use crate::{
+    config::{BUY_SOL_AMOUNT, BUY_SPLIT_THRESHOLD, RPC_CLIENT, buy_slippage, sell_slippage},
+    decoder::{
+        DBC_BASE_TO_QUOTE, DBC_EVT_SWAP_DISCRIMINATOR, DBC_EVT_SWAP2_DISCRIMINATOR, DbcPoolConfig,
+        DbcSwapEvent, METEORA_DBC_PROGRAM_ID, MeteoraDbcInstruction,
+    },
+    instructions::{
+        meteora_dbc_ix::MeteoraDbcSwapInstructionAccountsExt,
+        token_account::close_token_account_ix,
+        wsol::{close_wsol_account_ix, create_wsol_account_ix, wrap_sol_ixs},
+    },
+    processor::{
+        CopyOrder, ORDER_EXECUTOR, claim_orders, sell_positions, target_wallet,
+        transaction_account_keys,
+    },
+    utils::{
+        JOURNAL_SKIP, LatencyTrace, SEEN_INSTRUCTIONS, associated_token_address_with_program_id,
+        find_event_cpi, meteora_dbc_buy_quote, meteora_dbc_sell_quote, observe_slot,
+        record_target_trade, split_amount, stale_event_latency,
+    },
+    wallet::WALLET_POOL,
+};
+
+/// Pool configs never change once created, so each one is fetched only once
+static POOL_CONFIGS: Lazy<RwLock<HashMap<Pubkey, Arc<DbcPoolConfig>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+async fn pool_config(config: &Pubkey) -> Option<Arc<DbcPoolConfig>> {
+    if let Some(pool_config) = POOL_CONFIGS.read().unwrap().get(config) {
+        return Some(pool_config.clone());
+    }
+
+    let data = match RPC_CLIENT.get_account_data(config).await {
+        Ok(data) => data,
+        Err(e) => {
+            warn!("Failed to fetch DBC pool config {}: {:?}", config, e);
+            return None;
+        }
+    };
+
+    let pool_config = Arc::new(DbcPoolConfig::parse(&data)?);
+
+    POOL_CONFIGS
+        .write()
+        .unwrap()
+        .insert(*config, pool_config.clone());
+
+    Some(pool_config)
+}
+
+/// Copies the target's trades on Meteora dynamic bonding curve pools quoted in SOL.
+pub struct MeteoraDbcProcess;
+
+#[async_trait]
+impl Processor for MeteoraDbcProcess {
+    type InputType = InstructionProcessorInputType<MeteoraDbcInstruction>;
+
+    async fn process(
+        &mut self,
+        (metadata, instruction, _nested_instructions, _instructions): Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let Some(mut accounts) = MeteoraDbcInstruction::arrange_accounts(&instruction.accounts)
+        else {
+            warn!("Failed to arrange accounts");
+            return Ok(());
+        };
+
+        let signature = metadata.transaction_metadata.signature;
+        let target = target_wallet(&metadata);
+
+        observe_slot(metadata.transaction_metadata.slot);
+
+        if !SEEN_INSTRUCTIONS.first_seen(signature, &metadata.absolute_path) {
+            debug!("Signature {} already processed, skipping...", signature);
+            return Ok(());
+        }
+
+        info!("Received target's Meteora DBC signature");
+
+        let native_mint = spl_token::native_mint::id();
+
+        // Only SOL-quoted pools are copied
+        if accounts.quote_mint != native_mint {
+            return Ok(());
+        }
+
+        let account_keys = transaction_account_keys(&metadata);
+        let inner_instructions = metadata
+            .transaction_metadata
+            .meta
+            .inner_instructions
+            .as_deref()
+            .unwrap_or_default();
+
+        let Some(swap_event) = [DBC_EVT_SWAP_DISCRIMINATOR, DBC_EVT_SWAP2_DISCRIMINATOR]
+            .iter()
+            .find_map(|discriminator| {
+                find_event_cpi(
+                    inner_instructions,
+                    &account_keys,
+                    metadata.index,
+                    &metadata.absolute_path,
+                    &METEORA_DBC_PROGRAM_ID,
+                    &accounts.event_authority,
+                    discriminator,
+                )
+            })
+            .and_then(|event_ix| DbcSwapEvent::parse(&event_ix.instruction.data))
+        else {
+            info!(target: JOURNAL_SKIP, "No swap event found for {}, skipping...", signature);
+            return Ok(());
+        };
+
+        let Some(pool_config) = pool_config(&accounts.config).await else {
+            return Ok(());
+        };
+
+        let start = LatencyTrace::start(&signature);
+
+        let orders = if swap_event.trade_direction != DBC_BASE_TO_QUOTE {
+            record_target_trade(
+                &accounts.base_mint,
+                true,
+                swap_event.amount_in,
+                swap_event.output_amount,
+            );
+
+            info!(
+                "Target bought {} tokens by {} SOL on Meteora DBC",
+                swap_event.output_amount as f64 / 10f64.powf(6_f64),
+                swap_event.amount_in as f64 / 10f64.powf(9_f64)
+            );
+
+            if swap_event.next_sqrt_price >= pool_config.migration_sqrt_price {
+                info!(target: JOURNAL_SKIP, "Meteora DBC curve is complete, skipping...");
+                return Ok(());
+            }
+
+            if let Some(latency) = stale_event_latency(
+                metadata.transaction_metadata.slot,
+                swap_event.timestamp as i64,
+            ) {
+                info!(target: JOURNAL_SKIP,
+                    "Event is {}ms old, past the stale deadline, skipping...",
+                    latency
+                );
+                return Ok(());
+            }
+
+            let fee_rate = if pool_config.collect_fee_mode == 0 {
+                swap_event.total_fee as f64 / swap_event.amount_in.max(1) as f64
+            } else {
+                swap_event.total_fee as f64
+                    / (swap_event.output_amount + swap_event.total_fee).max(1) as f64
+            };
+
+            let required_lamports = (*BUY_SOL_AMOUNT as f64
+                * (1.0 + buy_slippage(&metadata.transaction_metadata.fee_payer)))
+                as u64;
+
+            let Some(wallet) = WALLET_POOL.next_buy_wallet(required_lamports) else {
+                info!(target: JOURNAL_SKIP, "No trading wallet has enough SOL for this buy, skipping...");
+                return Ok(());
+            };
+
+            accounts.payer = wallet.pubkey();
+            accounts.input_token_account = associated_token_address_with_program_id(
+                &wallet.pubkey(),
+                &native_mint,
+                &accounts.token_quote_program,
+            );
+            accounts.output_token_account = associated_token_address_with_program_id(
+                &wallet.pubkey(),
+                &accounts.base_mint,
+                &accounts.token_base_program,
+            );
+
+            let chunks = match *BUY_SPLIT_THRESHOLD {
+                Some(max_chunk) => split_amount(*BUY_SOL_AMOUNT, max_chunk),
+                None => vec![*BUY_SOL_AMOUNT],
+            };
+
+            // Earlier chunks move the price, so later ones are quoted against the full size
+            let mut quoted_sol = 0;
+
+            chunks
+                .into_iter()
+                .map(|sol_amount| {
+                    let amount_out = meteora_dbc_buy_quote(
+                        quoted_sol + sol_amount,
+                        swap_event.next_sqrt_price,
+                        &pool_config,
+                        fee_rate,
+                    ) - meteora_dbc_buy_quote(
+                        quoted_sol,
+                        swap_event.next_sqrt_price,
+                        &pool_config,
+                        fee_rate,
+                    );
+
+                    quoted_sol += sol_amount;
+
+                    let mut ixs = wrap_sol_ixs(&wallet.pubkey(), sol_amount);
+                    ixs.push(
+                        spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                            &wallet.pubkey(),
+                            &wallet.pubkey(),
+                            &accounts.base_mint,
+                            &accounts.token_base_program,
+                        ),
+                    );
+                    ixs.push(accounts.get_swap_ix(
+                        sol_amount,
+                        (amount_out as f64 * (1.0 - sell_slippage(&metadata.transaction_metadata.fee_payer))) as u64,
+                    ));
+                    ixs.push(close_wsol_account_ix(&wallet.pubkey()));
+
+                    CopyOrder {
+                        target,
+                        wallet,
+                        mint: accounts.base_mint,
+                        bought_tokens: Some(amount_out),
+                        quoted_sol: sol_amount,
+                        ixs,
+                    }
+                })
+                .collect()
+        } else {
+            record_target_trade(
+                &accounts.base_mint,
+                false,
+                swap_event.output_amount,
+                swap_event.amount_in,
+            );
+
+            info!(
+                "Target sold {} tokens for {} SOL on Meteora DBC",
+                swap_event.amount_in as f64 / 10f64.powf(6_f64),
+                swap_event.output_amount as f64 / 10f64.powf(9_f64)
+            );
+
+            // Sell fees are always taken from the quote paid out
+            let fee_rate = swap_event.total_fee as f64
+                / (swap_event.output_amount + swap_event.total_fee).max(1) as f64;
+
+            let positions = sell_positions(&accounts.base_mint, |wallet| {
+                associated_token_address_with_program_id(
+                    wallet,
+                    &accounts.base_mint,
+                    &accounts.token_base_program,
+                )
+            })
+            .await;
+            let mut orders = Vec::with_capacity(positions.len());
+
+            for (wallet, token_amount) in positions {
+                accounts.payer = wallet.pubkey();
+                accounts.input_token_account = associated_token_address_with_program_id(
+                    &wallet.pubkey(),
+                    &accounts.base_mint,
+                    &accounts.token_base_program,
+                );
+                accounts.output_token_account = associated_token_address_with_program_id(
+                    &wallet.pubkey(),
+                    &native_mint,
+                    &accounts.token_quote_program,
+                );
+
+                let sol_out = meteora_dbc_sell_quote(
+                    token_amount,
+                    swap_event.next_sqrt_price,
+                    &pool_config,
+                    fee_rate,
+                );
+
+                orders.push(CopyOrder {
+                    target,
+                    wallet,
+                    mint: accounts.base_mint,
+                    bought_tokens: None,
+                    quoted_sol: sol_out,
+                    ixs: vec![
+                        create_wsol_account_ix(&wallet.pubkey()),
+                        accounts.get_swap_ix(
+                            token_amount,
+                            (sol_out as f64
+                                * (1.0 - sell_slippage(&metadata.transaction_metadata.fee_payer)))
+                                as u64,
+                        ),
+                        close_wsol_account_ix(&wallet.pubkey()),
+                        close_token_account_ix(
+                            &accounts.token_base_program,
+                            &accounts.input_token_account,
+                            &wallet.pubkey(),
+                        ),
+                    ],
+                });
+            }
+
+            orders
+        };
+
+        ORDER_EXECUTOR.dispatch(claim_orders(signature, orders), &metrics, start);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+pub mod alloc_profile;
+pub mod bonding_curve;
+pub mod executor;
+pub mod manual_trade;
+pub mod meteora_dbc;
+pub mod order;
+pub mod pump_swap;
+pub mod pumpfun;
+pub mod raydium_amm;
+pub mod raydium_cpmm;
+pub mod raydium_launchpad;
+pub mod snipe;
+pub mod traced;
+
+pub use alloc_profile::*;
+pub use bonding_curve::*;
+pub use executor::*;
+pub use manual_trade::*;
+pub use meteora_dbc::*;
+pub use order::*;
+pub use pump_swap::*;
+pub use pumpfun::*;
+pub use raydium_amm::*;
+pub use raydium_cpmm::*;
+pub use raydium_launchpad::*;
+pub use snipe::*;
+pub use traced::*;
@@ -0,0 +1,820 @@
+use carbon_core::{instruction::InstructionMetadata, metrics::MetricsCollection};
+use futures::future::join_all;
+use solana_sdk::{
+    commitment_config::CommitmentConfig, instruction::Instruction, pubkey::Pubkey,
+    signature::Signature, signer::Signer,
+};
+use solana_transaction_status_client_types::TransactionTokenBalance;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::time::sleep;
+use tracing::{Instrument, info, warn};
+
+use crate::{
+    config::{LAND_TIMEOUT_SEC, RPC_CLIENT, SHADOW_MODE, SIMULATE_BEFORE_SEND},
+    datasource::{FOLLOWED_TARGETS, fetch_transaction_update},
+    service::{SendErrorKind, send_bundle_with_jito, send_with_service},
+    utils::{
+        CURVE_STATES, EXECUTION_BREAKER, JOURNAL_LANDING, JOURNAL_QUOTE, JOURNAL_SEND,
+        JOURNAL_SKIP, LatencyTrace, PENDING_ACTIONS, REPLAYED_TRANSACTIONS, SLOT_LAG, SPEND_POLICY,
+        append_trade_log, get_token_account_balances, notify_error, notify_exit, notify_fill,
+        record_copy, record_stage, record_target_landing, record_trade_outcome, shutting_down,
+        track_confirmation, tx_link,
+    },
+    wallet::{LOW_BALANCE, WALLET_POOL, WalletSigner, paper_fill, paper_trading},
+};
+
+/// A copy trade ready to be submitted by one of the pool wallets.
+pub struct CopyOrder {
+    /// Followed wallet whose trade this copies
+    pub target: Pubkey,
+    pub wallet: &'static WalletSigner,
+    pub mint: Pubkey,
+    /// Tokens bought by a buy order, `None` for a sell closing the position
+    pub bought_tokens: Option<u64>,
+    /// Lamports a buy is quoted to spend, or a sell to pay out, fees included
+    pub quoted_sol: u64,
+    pub ixs: Vec<Instruction>,
+}
+
+/// Lamports `order` spends, its quote for a buy and nothing for a sell.
+fn order_spend(order: &CopyOrder) -> u64 {
+    order.bought_tokens.map_or(0, |_| order.quoted_sol)
+}
+
+/// Whether `order` is a buy larger than its wallet may sign, e.g. one over
+/// `LEDGER_MAX_TRADE_SOL` for a Ledger wallet. Sells are never capped.
+fn exceeds_trade_cap(order: &CopyOrder) -> bool {
+    if order.bought_tokens.is_none() || !order.wallet.exceeds_trade_cap(order.quoted_sol) {
+        return false;
+    }
+
+    info!(target: JOURNAL_SKIP,
+        wallet = %order.wallet.pubkey(),
+        mint = %order.mint,
+        "Buy of {} lamports is over the wallet's trade cap, skipping...",
+        order.quoted_sol
+    );
+
+    true
+}
+
+/// Whether `SPEND_POLICY` lets `order` spend what it's quoted, recording the spend.
+/// Sells always go through.
+fn authorize_spend(order: &CopyOrder) -> bool {
+    if order.bought_tokens.is_none() {
+        return true;
+    }
+
+    let Err(violation) = SPEND_POLICY.authorize(order.quoted_sol) else {
+        return true;
+    };
+
+    info!(target: JOURNAL_SKIP,
+        wallet = %order.wallet.pubkey(),
+        mint = %order.mint,
+        "{}, skipping buy...",
+        violation
+    );
+
+    SPEND_POLICY.alert(&violation);
+
+    false
+}
+
+/// Keeps the orders whose trade isn't already being copied for target transaction
+/// `signature`. Split buys share a mint, so each mint and side is claimed once per call.
+/// Buys of a replayed transaction are dropped, it's too late to follow them.
+pub fn claim_orders(signature: Signature, mut orders: Vec<CopyOrder>) -> Vec<CopyOrder> {
+    if REPLAYED_TRANSACTIONS.is_replayed(&signature)
+        && orders.iter().any(|order| order.bought_tokens.is_some())
+    {
+        info!(target: JOURNAL_SKIP,
+            "{} was replayed after a reconnect, skipping buys...",
+            signature
+        );
+        orders.retain(|order| order.bought_tokens.is_none());
+    }
+
+    let mut claimed: HashMap<(Pubkey, bool), bool> = HashMap::new();
+
+    orders
+        .into_iter()
+        .filter(|order| {
+            let is_buy = order.bought_tokens.is_some();
+
+            *claimed.entry((order.mint, is_buy)).or_insert_with(|| {
+                let claimed = PENDING_ACTIONS.claim(signature, order.mint, is_buy);
+
+                if claimed {
+                    append_trade_log(&signature, &order.mint, is_buy);
+                } else {
+                    info!(target: JOURNAL_SKIP,
+                        "{} of {} is already being copied, skipping...",
+                        signature, order.mint
+                    );
+                }
+
+                claimed
+            })
+        })
+        .collect()
+}
+
+/// Submits copy orders (split buys, multi-wallet sells) concurrently and records the
+/// resulting positions in the wallet pool. Sent buys are applied to the local curve
+/// reserves until they show up on chain. Buys are held back while a datasource lags
+/// and `PAUSE_ON_SLOT_LAG` is enabled, while the execution breaker is tripped, while the
+/// fee payer is under `LOW_BALANCE_HALT_SOL`, once the bot shuts down, or when over a
+/// spend limit; sells always go through.
+pub async fn submit_orders(
+    mut orders: Vec<CopyOrder>,
+    metrics: &Arc<MetricsCollection>,
+    start: LatencyTrace,
+) {
+    if SLOT_LAG.is_trading_paused() && orders.iter().any(|order| order.bought_tokens.is_some()) {
+        info!(target: JOURNAL_SKIP, "Datasource is lagging behind the chain tip, skipping buys...");
+        orders.retain(|order| order.bought_tokens.is_none());
+    }
+
+    if EXECUTION_BREAKER.is_tripped() && orders.iter().any(|order| order.bought_tokens.is_some()) {
+        info!(target: JOURNAL_SKIP, "Execution breaker is tripped, skipping buys...");
+        orders.retain(|order| order.bought_tokens.is_none());
+    }
+
+    if LOW_BALANCE.is_halted() && orders.iter().any(|order| order.bought_tokens.is_some()) {
+        info!(target: JOURNAL_SKIP, "Fee payer balance is critically low, skipping buys...");
+        orders.retain(|order| order.bought_tokens.is_none());
+    }
+
+    if shutting_down() && orders.iter().any(|order| order.bought_tokens.is_some()) {
+        info!(target: JOURNAL_SKIP, "Shutting down, skipping buys...");
+        orders.retain(|order| order.bought_tokens.is_none());
+    }
+
+    orders.retain(|order| !exceeds_trade_cap(order) && authorize_spend(order));
+
+    let sent = join_all(orders.into_iter().map(|order| async move {
+        // Print current timestamp and consumed time from start
+        info!(
+            target: JOURNAL_QUOTE,
+            wallet = %order.wallet.pubkey(),
+            mint = %order.mint,
+            quoted_tokens = order.bought_tokens,
+            "Submitting tx, {:?} since detection",
+            start.elapsed()
+        );
+
+        if let Some(tokens) = order.bought_tokens {
+            CURVE_STATES.record_own_buy(order.mint, order.wallet.pubkey(), tokens);
+        }
+
+        let paper_order = PaperOrder::from(&order);
+        let pending = WALLET_POOL.track_pending(order.wallet.pubkey(), order_spend(&order));
+        let outcome = send_with_service(order.ixs, order.wallet, metrics).await;
+
+        record_stage(metrics, "total", start.elapsed()).await;
+        record_copy(metrics, &order.target, outcome.is_success()).await;
+        record_simulation(metrics, &outcome.error).await;
+
+        if !outcome.is_success() {
+            notify_error(format!(
+                "{} of {} failed to send: {}",
+                if order.bought_tokens.is_some() {
+                    "Buy"
+                } else {
+                    "Sell"
+                },
+                order.mint,
+                outcome
+            ));
+        }
+
+        if !outcome.is_success()
+            && let Some(tokens) = order.bought_tokens
+        {
+            CURVE_STATES.release_own_buy(&order.mint, &order.wallet.pubkey(), tokens);
+
+            // Never left the bot, it spent nothing
+            if outcome.is_local_failure() {
+                SPEND_POLICY.refund(order.quoted_sol);
+            }
+        }
+
+        if outcome.is_success() {
+            match order.bought_tokens {
+                Some(tokens) => {
+                    WALLET_POOL.record_buy(&order.wallet.pubkey(), &order.mint, tokens);
+                }
+                None => {
+                    WALLET_POOL.record_sell(&order.wallet.pubkey(), &order.mint);
+
+                    // Nothing left to exit, stop following the curve
+                    if WALLET_POOL.holders(&order.mint).is_empty() {
+                        CURVE_STATES.unwatch(&order.mint);
+                    }
+                }
+            }
+
+            if paper_trading() {
+                fill_paper_order(metrics, paper_order, outcome.signature, start.elapsed()).await;
+            } else if *SHADOW_MODE {
+                record_shadow_fill(order.wallet.pubkey(), order.mint, order.bought_tokens);
+            } else if let Some(signature) = outcome.signature {
+                let confirmation = confirm_order(
+                    metrics.clone(),
+                    order.target,
+                    order.wallet.pubkey(),
+                    order.mint,
+                    order.bought_tokens,
+                    signature,
+                );
+
+                // In flight until it lands or times out
+                tokio::spawn(
+                    track_confirmation(async move {
+                        confirmation.await;
+                        drop(pending);
+                    })
+                    .in_current_span(),
+                );
+            }
+        }
+
+        info!(
+            target: JOURNAL_SEND,
+            wallet = %order.wallet.pubkey(),
+            mint = %order.mint,
+            success = outcome.is_success(),
+            "Transaction sent --> {}, {:?} since detection",
+            outcome,
+            start.elapsed()
+        );
+
+        outcome.is_success()
+    }))
+    .await;
+
+    record_trade_outcome(&format!(
+        "{}/{} sent",
+        sent.iter().filter(|success| **success).count(),
+        sent.len()
+    ));
+}
+
+/// Submits a launch copy as a Jito bundle and records the resulting position.
+pub async fn submit_bundle_order(
+    order: CopyOrder,
+    metrics: &Arc<MetricsCollection>,
+    start: LatencyTrace,
+) {
+    if SLOT_LAG.is_trading_paused() {
+        info!(target: JOURNAL_SKIP, "Datasource is lagging behind the chain tip, skipping launch bundle...");
+        return;
+    }
+
+    if EXECUTION_BREAKER.is_tripped() {
+        info!(target: JOURNAL_SKIP, "Execution breaker is tripped, skipping launch bundle...");
+        return;
+    }
+
+    if LOW_BALANCE.is_halted() {
+        info!(target: JOURNAL_SKIP, "Fee payer balance is critically low, skipping launch bundle...");
+        return;
+    }
+
+    if shutting_down() {
+        info!(target: JOURNAL_SKIP, "Shutting down, skipping launch bundle...");
+        return;
+    }
+
+    if exceeds_trade_cap(&order) || !authorize_spend(&order) {
+        return;
+    }
+
+    info!(
+        target: JOURNAL_QUOTE,
+        wallet = %order.wallet.pubkey(),
+        mint = %order.mint,
+        quoted_tokens = order.bought_tokens,
+        "Submitting bundle, {:?} since detection",
+        start.elapsed()
+    );
+
+    if let Some(tokens) = order.bought_tokens {
+        CURVE_STATES.record_own_buy(order.mint, order.wallet.pubkey(), tokens);
+    }
+
+    start.record_build(metrics).await;
+
+    let paper_order = PaperOrder::from(&order);
+    let pending = WALLET_POOL.track_pending(order.wallet.pubkey(), order_spend(&order));
+    let outcome = send_bundle_with_jito(order.ixs, order.wallet, metrics).await;
+
+    record_stage(metrics, "total", start.elapsed()).await;
+    record_copy(metrics, &order.target, outcome.is_success()).await;
+    record_simulation(metrics, &outcome.error).await;
+
+    if !outcome.is_success() {
+        notify_error(format!(
+            "Launch bundle for {} failed to send: {}",
+            order.mint, outcome
+        ));
+    }
+
+    if let Some(tokens) = order.bought_tokens {
+        if outcome.is_success() {
+            WALLET_POOL.record_buy(&order.wallet.pubkey(), &order.mint, tokens);
+        } else {
+            CURVE_STATES.release_own_buy(&order.mint, &order.wallet.pubkey(), tokens);
+
+            if outcome.is_local_failure() {
+                SPEND_POLICY.refund(order.quoted_sol);
+            }
+        }
+    }
+
+    if outcome.is_success() && paper_trading() {
+        fill_paper_order(metrics, paper_order, outcome.signature, start.elapsed()).await;
+    } else if outcome.is_success() && *SHADOW_MODE {
+        record_shadow_fill(order.wallet.pubkey(), order.mint, order.bought_tokens);
+    } else if outcome.is_success()
+        && let Some(signature) = outcome.signature
+    {
+        let confirmation = confirm_order(
+            metrics.clone(),
+            order.target,
+            order.wallet.pubkey(),
+            order.mint,
+            order.bought_tokens,
+            signature,
+        );
+
+        tokio::spawn(
+            track_confirmation(async move {
+                confirmation.await;
+                drop(pending);
+            })
+            .in_current_span(),
+        );
+    }
+
+    info!(
+        target: JOURNAL_SEND,
+        wallet = %order.wallet.pubkey(),
+        mint = %order.mint,
+        success = outcome.is_success(),
+        "Bundle sent --> {}, {:?} since detection",
+        outcome,
+        start.elapsed()
+    );
+
+    record_trade_outcome(if outcome.is_success() {
+        "bundle sent"
+    } else {
+        "bundle failed"
+    });
+}
+
+/// Feeds the execution breaker's simulation streak: a send that got past signing
+/// passed its simulation, if one ran.
+async fn record_simulation(metrics: &MetricsCollection, error: &Option<SendErrorKind>) {
+    match error {
+        Some(SendErrorKind::SimulationFailed) => {
+            EXECUTION_BREAKER.record_simulation(metrics, false).await
+        }
+        None if *SIMULATE_BEFORE_SEND || *SHADOW_MODE => {
+            EXECUTION_BREAKER.record_simulation(metrics, true).await
+        }
+        _ => {}
+    }
+}
+
+/// Journals the fill a shadowed order would have got, at its quote. Its position was
+/// already recorded from the quote when the send was accepted, nothing lands to
+/// confirm it.
+fn record_shadow_fill(wallet: Pubkey, mint: Pubkey, bought_tokens: Option<u64>) {
+    info!(
+        target: JOURNAL_LANDING,
+        %wallet,
+        %mint,
+        landed = true,
+        shadow = true,
+        quoted_tokens = bought_tokens,
+        "Shadow {} of {} filled at its quote",
+        if bought_tokens.is_some() { "buy" } else { "sell" },
+        mint
+    );
+}
+
+/// What a landed order did to its wallet, `None` fields when the confirmed transaction
+/// couldn't be fetched.
+#[derive(Debug, Clone, Copy, Default)]
+struct Landing {
+    /// Lamports the wallet gained, negative when it spent them
+    sol_flow: Option<i64>,
+    /// Tokens of the mint the wallet held after the transaction
+    post_tokens: Option<u64>,
+    /// Tokens of the mint the wallet gained, in UI units, negative when it sold them
+    token_flow: Option<f64>,
+}
+
+/// Waits for a sent order to confirm and records whether it landed, see `record_landing`.
+async fn confirm_order(
+    metrics: Arc<MetricsCollection>,
+    target: Pubkey,
+    wallet: Pubkey,
+    mint: Pubkey,
+    bought_tokens: Option<u64>,
+    signature: Signature,
+) {
+    let landing = wait_for_landing(wallet, mint, signature).await;
+
+    record_landing(
+        &metrics,
+        target,
+        wallet,
+        mint,
+        bought_tokens,
+        signature,
+        landing,
+    )
+    .await;
+}
+
+/// Fills a paper order against the virtual balances, see `paper_fill`, and records it
+/// like a live one that landed. A backtest's stubbed sends have no signature, their
+/// fills carry the default one.
+async fn fill_paper_order(
+    metrics: &MetricsCollection,
+    order: PaperOrder,
+    signature: Option<Signature>,
+    elapsed: Duration,
+) {
+    let landing = paper_fill(
+        &order.wallet,
+        &order.mint,
+        order.bought_tokens,
+        order.quoted_sol,
+        elapsed,
+    )
+    .map(|fill| Landing {
+        sol_flow: Some(fill.sol_flow),
+        post_tokens: Some(fill.position),
+        token_flow: None,
+    });
+
+    record_landing(
+        metrics,
+        order.target,
+        order.wallet,
+        order.mint,
+        order.bought_tokens,
+        signature.unwrap_or_default(),
+        landing,
+    )
+    .await;
+}
+
+/// The parts of a `CopyOrder` a paper fill needs, once its instructions are sent off.
+struct PaperOrder {
+    target: Pubkey,
+    wallet: Pubkey,
+    mint: Pubkey,
+    bought_tokens: Option<u64>,
+    quoted_sol: u64,
+}
+
+impl From<&CopyOrder> for PaperOrder {
+    fn from(order: &CopyOrder) -> Self {
+        Self {
+            target: order.target,
+            wallet: order.wallet.pubkey(),
+            mint: order.mint,
+            bought_tokens: order.bought_tokens,
+            quoted_sol: order.quoted_sol,
+        }
+    }
+}
+
+/// Records whether an order landed for its target, with the SOL it moved in or out of
+/// the wallet, and notifies the fill or exit. A landed buy sets the wallet's position
+/// to the token balance the transaction left it with, one that fails or never lands is
+/// taken back out.
+async fn record_landing(
+    metrics: &MetricsCollection,
+    target: Pubkey,
+    wallet: Pubkey,
+    mint: Pubkey,
+    bought_tokens: Option<u64>,
+    signature: Signature,
+    landing: Option<Landing>,
+) {
+    info!(
+        target: JOURNAL_LANDING,
+        %wallet,
+        %mint,
+        %signature,
+        landed = landing.is_some(),
+        sol_flow = landing.and_then(|landing| landing.sol_flow),
+        "{} {}",
+        signature,
+        if landing.is_some() { "landed" } else { "didn't land" }
+    );
+
+    record_target_landing(
+        metrics,
+        &target,
+        landing.is_some(),
+        landing.and_then(|landing| landing.sol_flow),
+    )
+    .await;
+    EXECUTION_BREAKER
+        .record_landing(metrics, landing.is_some())
+        .await;
+
+    let Some(landing) = landing else {
+        let side = if bought_tokens.is_some() {
+            "Buy"
+        } else {
+            "Sell"
+        };
+        notify_error(format!(
+            "{} of {} didn't land\n{}",
+            side,
+            mint,
+            tx_link(&signature)
+        ));
+
+        if let Some(bought_tokens) = bought_tokens {
+            info!(
+                "Buy {} of {} didn't land, reverting position",
+                signature, mint
+            );
+            WALLET_POOL.revert_buy(&wallet, &mint, bought_tokens);
+        }
+
+        return;
+    };
+
+    let tokens = landing.token_flow.map(f64::abs);
+
+    if bought_tokens.is_some() {
+        if let Some(balance) = landing.post_tokens {
+            WALLET_POOL.set_position(&wallet, &mint, balance);
+        }
+
+        let sol_spent = landing.sol_flow.map(|flow| (-flow).max(0) as u64);
+        notify_fill(&wallet, &mint, sol_spent, tokens, &signature);
+    } else {
+        let sol_received = landing.sol_flow.map(|flow| flow.max(0) as u64);
+        notify_exit(&wallet, &mint, sol_received, tokens, &signature);
+    }
+}
+
+/// Polls `signature` until it confirms, fails or `LAND_TIMEOUT_SEC` passes. `None`
+/// when it didn't land, otherwise the wallet's SOL and `mint` balance changes.
+async fn wait_for_landing(wallet: Pubkey, mint: Pubkey, signature: Signature) -> Option<Landing> {
+    let deadline = Instant::now() + Duration::from_secs(*LAND_TIMEOUT_SEC);
+
+    while Instant::now() < deadline {
+        let status = RPC_CLIENT
+            .get_signature_statuses(&[signature])
+            .await
+            .ok()
+            .and_then(|response| response.value.into_iter().next().flatten());
+
+        match status {
+            Some(status) if status.err.is_some() => return None,
+            Some(status) if status.satisfies_commitment(CommitmentConfig::confirmed()) => {
+                let Some(update) = fetch_transaction_update(&RPC_CLIENT, &signature).await else {
+                    return Some(Landing::default());
+                };
+
+                let sol_flow = update
+                    .transaction
+                    .message
+                    .static_account_keys()
+                    .iter()
+                    .position(|key| *key == wallet)
+                    .and_then(|index| {
+                        let pre = *update.meta.pre_balances.get(index)? as i64;
+                        let post = *update.meta.post_balances.get(index)? as i64;
+
+                        Some(post - pre)
+                    });
+
+                let wallet_tokens = |balances: Option<&[TransactionTokenBalance]>| {
+                    balances
+                        .unwrap_or_default()
+                        .iter()
+                        .find(|balance| {
+                            balance.owner == wallet.to_string() && balance.mint == mint.to_string()
+                        })
+                        .and_then(|balance| {
+                            let amount = balance.ui_token_amount.amount.parse::<u64>().ok()?;
+                            let ui_amount =
+                                amount as f64 / 10f64.powi(balance.ui_token_amount.decimals as i32);
+
+                            Some((amount, ui_amount))
+                        })
+                };
+
+                let pre_tokens = wallet_tokens(update.meta.pre_token_balances.as_deref());
+                let post_tokens = wallet_tokens(update.meta.post_token_balances.as_deref());
+
+                // A missing balance is an account the transaction created or closed
+                let token_flow = (pre_tokens.is_some() || post_tokens.is_some()).then(|| {
+                    post_tokens.map_or(0.0, |(_, ui)| ui) - pre_tokens.map_or(0.0, |(_, ui)| ui)
+                });
+
+                return Some(Landing {
+                    sol_flow,
+                    post_tokens: post_tokens.map(|(amount, _)| amount),
+                    token_flow,
+                });
+            }
+            _ => sleep(Duration::from_millis(400)).await,
+        }
+    }
+
+    None
+}
+
+/// Wallets to sell `mint` from: every wallet holding it, or all of them when no
+/// position is tracked (e.g. after a restart).
+pub fn sell_wallets(mint: &Pubkey) -> Vec<&'static WalletSigner> {
+    let holders = WALLET_POOL.holders(mint);
+
+    if holders.is_empty() {
+        WALLET_POOL.wallets()
+    } else {
+        holders.into_iter().map(|(wallet, _)| wallet).collect()
+    }
+}
+
+/// Sell wallets of `mint` with the tokens each can sell, `token_account` giving a
+/// wallet's account for the mint. Untracked positions are read over RPC in a single
+/// batched request; wallets with nothing to sell are left out.
+pub async fn sell_positions(
+    mint: &Pubkey,
+    token_account: impl Fn(&Pubkey) -> Pubkey,
+) -> Vec<(&'static WalletSigner, u64)> {
+    let wallets = sell_wallets(mint);
+
+    let positions: Vec<Option<u64>> = wallets
+        .iter()
+        .map(|wallet| WALLET_POOL.position(&wallet.pubkey(), mint))
+        .collect();
+
+    let untracked: Vec<Pubkey> = wallets
+        .iter()
+        .zip(&positions)
+        .filter(|(_, position)| position.is_none())
+        .map(|(wallet, _)| token_account(&wallet.pubkey()))
+        .collect();
+
+    let mut fetched = if untracked.is_empty() {
+        Vec::new()
+    } else {
+        get_token_account_balances(&RPC_CLIENT, &untracked)
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Failed to get token balances for {}: {:?}", mint, e);
+                vec![None; untracked.len()]
+            })
+    }
+    .into_iter();
+
+    wallets
+        .into_iter()
+        .zip(positions)
+        .filter_map(|(wallet, position)| {
+            position
+                .or_else(|| fetched.next().flatten())
+                .filter(|amount| *amount > 0)
+                .map(|amount| (wallet, amount))
+        })
+        .collect()
+}
+
+/// The followed wallet behind a target transaction: the first of `FOLLOWED_TARGETS`
+/// the transaction includes, otherwise its fee payer.
+pub fn target_wallet(metadata: &InstructionMetadata) -> Pubkey {
+    let transaction = &metadata.transaction_metadata;
+
+    transaction
+        .message
+        .static_account_keys()
+        .iter()
+        .find(|key| FOLLOWED_TARGETS.contains(key))
+        .copied()
+        .unwrap_or(transaction.fee_payer)
+}
+
+/// Every account key of the transaction, static keys first, then the addresses
+/// loaded from lookup tables, matching the indexes used by compiled instructions.
+pub fn transaction_account_keys(metadata: &InstructionMetadata) -> Vec<Pubkey> {
+    let meta = &metadata.transaction_metadata.meta;
+    let static_account_keys = metadata.transaction_metadata.message.static_account_keys();
+
+    let mut account_keys: Vec<Pubkey> = Vec::with_capacity(
+        static_account_keys.len()
+            + meta.loaded_addresses.writable.len()
+            + meta.loaded_addresses.readonly.len(),
+    );
+
+    account_keys.extend(static_account_keys);
+    account_keys.extend(&meta.loaded_addresses.writable);
+    account_keys.extend(&meta.loaded_addresses.readonly);
+
+    account_keys
+}
+
+/// Token balance of `token_account` right after the transaction, with its mint.
+/// Used to read pool reserves off the target's own trade.
+pub fn post_token_balance(
+    metadata: &InstructionMetadata,
+    account_keys: &[Pubkey],
+    token_account: &Pubkey,
+) -> Option<(Pubkey, u64)> {
+    find_token_balance(
+        metadata
+            .transaction_metadata
+            .meta
+            .post_token_balances
+            .as_deref()?,
+        account_keys,
+        token_account,
+    )
+}
+
+/// Token balance of `token_account` before the transaction, with its mint.
+pub fn pre_token_balance(
+    metadata: &InstructionMetadata,
+    account_keys: &[Pubkey],
+    token_account: &Pubkey,
+) -> Option<(Pubkey, u64)> {
+    find_token_balance(
+        metadata
+            .transaction_metadata
+            .meta
+            .pre_token_balances
+            .as_deref()?,
+        account_keys,
+        token_account,
+    )
+}
+
+fn find_token_balance(
+    balances: &[TransactionTokenBalance],
+    account_keys: &[Pubkey],
+    token_account: &Pubkey,
+) -> Option<(Pubkey, u64)> {
+    let index = account_keys.iter().position(|key| key == token_account)?;
+
+    balances
+        .iter()
+        .find(|balance| balance.account_index as usize == index)
+        .and_then(|balance| {
+            Some((
+                balance.mint.parse().ok()?,
+                balance.ui_token_amount.amount.parse().ok()?,
+            ))
+        })
+}
+
+/// Tokens of `mint` the `wallet` can sell, from the position store when the position is
+/// tracked, otherwise read from its `token_account` over RPC.
+pub async fn position_balance(
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+) -> Option<u64> {
+    match WALLET_POOL.position(wallet, mint) {
+        Some(amount) => Some(amount).filter(|amount| *amount > 0),
+        None => wallet_token_balance(token_account).await,
+    }
+}
+
+/// Current balance of one of our token accounts, `None` when it is empty or missing.
+pub async fn wallet_token_balance(token_account: &Pubkey) -> Option<u64> {
+    match RPC_CLIENT
+        .get_token_account_balance_with_commitment(token_account, CommitmentConfig::confirmed())
+        .await
+    {
+        Ok(response) => response
+            .value
+            .amount
+            .parse::<u64>()
+            .ok()
+            .filter(|amount| *amount > 0),
+        Err(e) => {
+            warn!("Failed to get token balance of {}: {:?}", token_account, e);
+            None
+        }
+    }
+}
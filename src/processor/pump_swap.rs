@@ -0,0 +1,306 @@
+use async_trait::async_trait;
+use carbon_core::{
+    deserialize::{ArrangeAccounts, CarbonDeserialize},
+    error::CarbonResult,
+    instruction::InstructionProcessorInputType,
+    metrics::MetricsCollection,
+    processor::Processor,
+};
+use carbon_pump_swap_decoder::{
+    PROGRAM_ID as PUMP_SWAP_PROGRAM_ID,
+    instructions::{
+        PumpSwapInstruction, buy::Buy as PumpSwapBuy, buy_event::BuyEvent,
+        sell::Sell as PumpSwapSell, sell_event::SellEvent,
+    },
+};
+use solana_sdk::signer::Signer;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::{
+    config::{BUY_SOL_AMOUNT, BUY_SPLIT_THRESHOLD, buy_slippage, sell_slippage},
+    instructions::{
+        pump_swap_buy_ix::PumpSwapBuyInstructionAccountsExt,
+        pump_swap_sell_ix::PumpSwapSellInstructionAccountsExt,
+        wsol::{close_wsol_account_ix, create_wsol_account_ix, wrap_sol_ixs},
+    },
+    processor::{
+        CopyOrder, ORDER_EXECUTOR, claim_orders, sell_positions, target_wallet,
+        transaction_account_keys,
+    },
+    utils::{
+        JOURNAL_SKIP, LatencyTrace, SEEN_INSTRUCTIONS, associated_token_address,
+        associated_token_address_with_program_id, decode_event_cpi, find_event_cpi, observe_slot,
+        pump_swap_buy_quote, pump_swap_sell_quote, record_target_trade, split_amount,
+        stale_event_latency,
+    },
+    wallet::WALLET_POOL,
+};
+
+pub struct PumpSwapProcess;
+
+#[async_trait]
+impl Processor for PumpSwapProcess {
+    type InputType = InstructionProcessorInputType<PumpSwapInstruction>;
+
+    async fn process(
+        &mut self,
+        (metadata, instruction, _nested_instructions, _instructions): Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let signature = metadata.transaction_metadata.signature;
+        let target = target_wallet(&metadata);
+
+        observe_slot(metadata.transaction_metadata.slot);
+
+        let account_keys = transaction_account_keys(&metadata);
+
+        let inner_instructions = metadata
+            .transaction_metadata
+            .meta
+            .inner_instructions
+            .as_deref()
+            .unwrap_or_default();
+
+        let start = LatencyTrace::start(&signature);
+
+        let orders = match instruction.data {
+            PumpSwapInstruction::Buy(_) | PumpSwapInstruction::Sell(_)
+                if !SEEN_INSTRUCTIONS.first_seen(signature, &metadata.absolute_path) =>
+            {
+                debug!("Signature {} already processed, skipping...", signature);
+                return Ok(());
+            }
+            PumpSwapInstruction::Buy(_buy_data) => {
+                info!("Received target's PumpSwap signature");
+
+                let Some(mut arranged) = PumpSwapBuy::arrange_accounts(&instruction.accounts)
+                else {
+                    warn!("Failed to arrange accounts");
+                    return Ok(());
+                };
+
+                // Only SOL-quoted pools (graduated pumpfun tokens) are copied
+                if arranged.quote_mint != spl_token::native_mint::id() {
+                    return Ok(());
+                }
+
+                let Some(event_ix) = find_event_cpi(
+                    inner_instructions,
+                    &account_keys,
+                    metadata.index,
+                    &metadata.absolute_path,
+                    &PUMP_SWAP_PROGRAM_ID,
+                    &arranged.event_authority,
+                    BuyEvent::DISCRIMINATOR,
+                ) else {
+                    info!(target: JOURNAL_SKIP, "No buy event found for {}, skipping...", signature);
+                    return Ok(());
+                };
+
+                let Some(buy_event) = decode_event_cpi::<BuyEvent>(&event_ix.instruction.data)
+                else {
+                    warn!(target: JOURNAL_SKIP, "Failed to parse buy event of {}, skipping...", signature);
+                    return Ok(());
+                };
+
+                record_target_trade(
+                    &arranged.base_mint,
+                    true,
+                    buy_event.quote_amount_in,
+                    buy_event.base_amount_out,
+                );
+
+                info!(
+                    "Target bought {} tokens by {} SOL on PumpSwap",
+                    buy_event.base_amount_out as f64 / 10f64.powf(6_f64),
+                    buy_event.quote_amount_in as f64 / 10f64.powf(9_f64)
+                );
+
+                if let Some(latency) =
+                    stale_event_latency(metadata.transaction_metadata.slot, buy_event.timestamp)
+                {
+                    info!(target: JOURNAL_SKIP,
+                        "Event is {}ms old, past the stale deadline, skipping...",
+                        latency
+                    );
+                    return Ok(());
+                }
+
+                let fee_bps = buy_event.lp_fee_basis_points
+                    + buy_event.protocol_fee_basis_points
+                    + buy_event.coin_creator_fee_basis_points;
+
+                let required_lamports = (*BUY_SOL_AMOUNT as f64
+                    * (1.0 + buy_slippage(&metadata.transaction_metadata.fee_payer)))
+                    as u64;
+
+                let Some(wallet) = WALLET_POOL.next_buy_wallet(required_lamports) else {
+                    info!(target: JOURNAL_SKIP, "No trading wallet has enough SOL for this buy, skipping...");
+                    return Ok(());
+                };
+
+                arranged.user = wallet.pubkey();
+                arranged.user_base_token_account = associated_token_address_with_program_id(
+                    &arranged.user,
+                    &arranged.base_mint,
+                    &arranged.base_token_program,
+                );
+                arranged.user_quote_token_account =
+                    associated_token_address(&arranged.user, &arranged.quote_mint);
+
+                let chunks = match *BUY_SPLIT_THRESHOLD {
+                    Some(max_chunk) => split_amount(*BUY_SOL_AMOUNT, max_chunk),
+                    None => vec![*BUY_SOL_AMOUNT],
+                };
+
+                let mut pool_base_reserves = buy_event.pool_base_token_reserves;
+                let mut pool_quote_reserves = buy_event.pool_quote_token_reserves;
+
+                chunks
+                    .into_iter()
+                    .map(|sol_amount| {
+                        let base_amount_out = pump_swap_buy_quote(
+                            sol_amount,
+                            pool_base_reserves,
+                            pool_quote_reserves,
+                            fee_bps,
+                        );
+
+                        pool_base_reserves = pool_base_reserves.saturating_sub(base_amount_out);
+                        pool_quote_reserves += sol_amount;
+
+                        let max_quote_amount_in = (sol_amount as f64
+                            * (1.0 + buy_slippage(&metadata.transaction_metadata.fee_payer)))
+                            as u64;
+
+                        let mut ixs = wrap_sol_ixs(&arranged.user, max_quote_amount_in);
+                        ixs.push(arranged.get_create_idempotent_ata_ix());
+                        ixs.push(arranged.get_buy_ix(PumpSwapBuy {
+                            base_amount_out,
+                            max_quote_amount_in,
+                        }));
+                        // Unwrap whatever SOL the slippage allowance left over
+                        ixs.push(close_wsol_account_ix(&arranged.user));
+
+                        CopyOrder {
+                            target,
+                            wallet,
+                            mint: arranged.base_mint,
+                            bought_tokens: Some(base_amount_out),
+                            quoted_sol: sol_amount,
+                            ixs,
+                        }
+                    })
+                    .collect()
+            }
+            PumpSwapInstruction::Sell(_sell_data) => {
+                info!("Received target's PumpSwap signature");
+
+                let Some(mut arranged) = PumpSwapSell::arrange_accounts(&instruction.accounts)
+                else {
+                    warn!("Failed to arrange accounts");
+                    return Ok(());
+                };
+
+                if arranged.quote_mint != spl_token::native_mint::id() {
+                    return Ok(());
+                }
+
+                let Some(event_ix) = find_event_cpi(
+                    inner_instructions,
+                    &account_keys,
+                    metadata.index,
+                    &metadata.absolute_path,
+                    &PUMP_SWAP_PROGRAM_ID,
+                    &arranged.event_authority,
+                    SellEvent::DISCRIMINATOR,
+                ) else {
+                    info!(target: JOURNAL_SKIP, "No sell event found for {}, skipping...", signature);
+                    return Ok(());
+                };
+
+                let Some(sell_event) = decode_event_cpi::<SellEvent>(&event_ix.instruction.data)
+                else {
+                    warn!(target: JOURNAL_SKIP, "Failed to parse sell event of {}, skipping...", signature);
+                    return Ok(());
+                };
+
+                record_target_trade(
+                    &arranged.base_mint,
+                    false,
+                    sell_event.user_quote_amount_out,
+                    sell_event.base_amount_in,
+                );
+
+                info!(
+                    "Target sold {} tokens for {} SOL on PumpSwap",
+                    sell_event.base_amount_in as f64 / 10f64.powf(6_f64),
+                    sell_event.user_quote_amount_out as f64 / 10f64.powf(9_f64)
+                );
+
+                let fee_bps = sell_event.lp_fee_basis_points
+                    + sell_event.protocol_fee_basis_points
+                    + sell_event.coin_creator_fee_basis_points;
+
+                let positions = sell_positions(&arranged.base_mint, |wallet| {
+                    associated_token_address_with_program_id(
+                        wallet,
+                        &arranged.base_mint,
+                        &arranged.base_token_program,
+                    )
+                })
+                .await;
+                let mut orders = Vec::with_capacity(positions.len());
+
+                for (wallet, token_amount) in positions {
+                    arranged.user = wallet.pubkey();
+                    arranged.user_base_token_account = associated_token_address_with_program_id(
+                        &arranged.user,
+                        &arranged.base_mint,
+                        &arranged.base_token_program,
+                    );
+                    arranged.user_quote_token_account =
+                        associated_token_address(&arranged.user, &arranged.quote_mint);
+
+                    let quote_amount_out = pump_swap_sell_quote(
+                        token_amount,
+                        sell_event.pool_base_token_reserves,
+                        sell_event.pool_quote_token_reserves,
+                        fee_bps,
+                    );
+
+                    let min_quote_amount_out = (quote_amount_out as f64
+                        * (1.0 - sell_slippage(&metadata.transaction_metadata.fee_payer)))
+                        as u64;
+
+                    orders.push(CopyOrder {
+                        target,
+                        wallet,
+                        mint: arranged.base_mint,
+                        bought_tokens: None,
+                        quoted_sol: quote_amount_out,
+                        ixs: vec![
+                            create_wsol_account_ix(&arranged.user),
+                            arranged.get_sell_ix(PumpSwapSell {
+                                base_amount_in: token_amount,
+                                min_quote_amount_out,
+                            }),
+                            arranged.get_close_ata_ix(),
+                            close_wsol_account_ix(&arranged.user),
+                        ],
+                    });
+                }
+
+                orders
+            }
+            _ => {
+                vec![]
+            }
+        };
+
+        ORDER_EXECUTOR.dispatch(claim_orders(signature, orders), &metrics, start);
+
+        Ok(())
+    }
+}
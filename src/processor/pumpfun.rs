@@ -0,0 +1,467 @@
+use async_trait::async_trait;
+use carbon_core::{
+    deserialize::ArrangeAccounts, error::CarbonResult, instruction::InstructionProcessorInputType,
+    metrics::MetricsCollection, processor::Processor,
+};
+use carbon_pumpfun_decoder::instructions::{
+    PumpfunInstruction, buy::Buy, create::Create, migrate::Migrate, sell::Sell,
+    trade_event::TradeEvent,
+};
+use solana_sdk::signer::Signer;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::{
+    config::{BUY_SOL_AMOUNT, BUY_SPLIT_THRESHOLD, WATCH_HOT_CURVES, buy_slippage, sell_slippage},
+    instructions::{
+        buy_ix::BuyExactInInstructionAccountsExt, sell_ix::SellExactInInstructionAccountsExt,
+    },
+    processor::{
+        CopyOrder, ORDER_EXECUTOR, claim_orders, created_in_transaction, sell_positions,
+        snipe_launch, target_wallet, transaction_account_keys,
+    },
+    utils::{
+        CURVE_STATES, CurveReserves, JOURNAL_SKIP, LatencyTrace, SEEN_INSTRUCTIONS,
+        associated_token_address, bonding_curve_sol_quote, bonding_curve_token_quote,
+        curve_slippage, find_trade_event, observe_slot, pumpfun_buy_cost, pumpfun_fee_rate,
+        pumpfun_fees, pumpfun_min_sol_output, pumpfun_sell_output, pumpfun_trade_event,
+        record_target_trade, router_name, set_pumpfun_fees, split_amount, stale_event_latency,
+        trade_event_from_logs,
+    },
+    wallet::WALLET_POOL,
+};
+
+pub struct PumpfunProcess;
+
+#[async_trait]
+impl Processor for PumpfunProcess {
+    type InputType = InstructionProcessorInputType<PumpfunInstruction>;
+
+    async fn process(
+        &mut self,
+        (metadata, instruction, _nested_instructions, _instructions): Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let signature = metadata.transaction_metadata.signature;
+        let target = target_wallet(&metadata);
+
+        observe_slot(metadata.transaction_metadata.slot);
+
+        let account_keys = transaction_account_keys(&metadata);
+
+        // Trades nested under an aggregator or bot router are decoded from its CPI, note which one routed it
+        if metadata.stack_height > 1 {
+            let router = metadata
+                .transaction_metadata
+                .message
+                .instructions()
+                .get(metadata.index as usize)
+                .and_then(|ix| account_keys.get(ix.program_id_index as usize));
+
+            if let Some(router) = router {
+                info!(
+                    "Target trade routed through {}",
+                    router_name(router).unwrap_or_else(|| router.to_string())
+                );
+            }
+        }
+
+        let start = LatencyTrace::start(&signature);
+
+        let orders = match instruction.data {
+            PumpfunInstruction::Buy(_buy_data) => {
+                // The same trade can arrive from several datasources, only copy it once
+                if !SEEN_INSTRUCTIONS.first_seen(signature, &metadata.absolute_path) {
+                    debug!("Signature {} already processed, skipping...", signature);
+                    return Ok(());
+                }
+                // Print siganure with timestamp
+                info!("Received target's signature");
+
+                if let Some(mut arranged) = Buy::arrange_accounts(&instruction.accounts) {
+                    // A dev buy is copied together with the create it launches
+                    if created_in_transaction(&metadata, &arranged.mint) {
+                        info!(target: JOURNAL_SKIP, "Dev buy is copied with its launch, skipping...");
+                        return Ok(());
+                    }
+
+                    // The curve no longer trades, the token moved to PumpSwap
+                    if CURVE_STATES.is_complete(&arranged.mint) {
+                        info!(target: JOURNAL_SKIP,
+                            "Bonding curve of {} is complete, skipping...",
+                            arranged.mint
+                        );
+                        return Ok(());
+                    }
+
+                    let required_lamports = (*BUY_SOL_AMOUNT as f64
+                        * (1.0 + pumpfun_fee_rate())
+                        * (1.0 + buy_slippage(&metadata.transaction_metadata.fee_payer)))
+                        as u64;
+
+                    let Some(wallet) = WALLET_POOL.next_buy_wallet(required_lamports) else {
+                        info!(target: JOURNAL_SKIP, "No trading wallet has enough SOL for this buy, skipping...");
+                        return Ok(());
+                    };
+
+                    arranged.user = wallet.pubkey();
+                    arranged.associated_user =
+                        associated_token_address(&arranged.user, &arranged.mint);
+
+                    let Some(trade_event) = find_trade_event(
+                        metadata
+                            .transaction_metadata
+                            .meta
+                            .inner_instructions
+                            .as_deref()
+                            .unwrap_or_default(),
+                        &account_keys,
+                        metadata.index,
+                        &metadata.absolute_path,
+                        &arranged.event_authority,
+                    )
+                    .and_then(|swap_cpi_ix| pumpfun_trade_event(&swap_cpi_ix.instruction.data))
+                    // Some datasources omit inner instructions, the event is in the logs too
+                    .or_else(|| {
+                        trade_event_from_logs(
+                            metadata
+                                .transaction_metadata
+                                .meta
+                                .log_messages
+                                .as_deref()
+                                .unwrap_or_default(),
+                            &arranged.mint,
+                            true,
+                        )
+                    }) else {
+                        info!(target: JOURNAL_SKIP, "No trade event found for {}, skipping...", signature);
+                        return Ok(());
+                    };
+
+                    record_target_trade(
+                        &arranged.mint,
+                        true,
+                        trade_event.sol_amount,
+                        trade_event.token_amount,
+                    );
+
+                    info!(
+                        "Target bought {} tokens by {} SOL",
+                        trade_event.token_amount as f64 / 10f64.powf(6_f64),
+                        trade_event.sol_amount as f64 / 10f64.powf(9_f64)
+                    );
+
+                    if *WATCH_HOT_CURVES {
+                        CURVE_STATES.watch_hot(arranged.mint, arranged.bonding_curve);
+                    }
+
+                    CURVE_STATES.update(
+                        arranged.bonding_curve,
+                        curve_reserves(&trade_event, metadata.transaction_metadata.slot),
+                    );
+
+                    // A late copy buys the top, so drop it; sells still go through
+                    if let Some(latency) = stale_event_latency(
+                        metadata.transaction_metadata.slot,
+                        trade_event.timestamp,
+                    ) {
+                        info!(target: JOURNAL_SKIP,
+                            "Event is {}ms old, past the stale deadline, skipping...",
+                            latency
+                        );
+                        return Ok(());
+                    }
+
+                    // Follow the curve of the token we're about to hold
+                    CURVE_STATES.watch(arranged.mint, arranged.bonding_curve);
+
+                    // Large buys are split into several transactions, each quoted
+                    // against the reserves left by the chunks before it
+                    let chunks = match *BUY_SPLIT_THRESHOLD {
+                        Some(max_chunk) => split_amount(*BUY_SOL_AMOUNT, max_chunk),
+                        None => vec![*BUY_SOL_AMOUNT],
+                    };
+
+                    // Our own earlier buys may not be in the event's reserves yet
+                    let (mut virtual_sol_reserves, mut virtual_token_reserves) = CURVE_STATES
+                        .with_own_buys(
+                            &arranged.mint,
+                            trade_event.virtual_sol_reserves,
+                            trade_event.virtual_token_reserves,
+                        );
+
+                    let fees = pumpfun_fees();
+
+                    chunks
+                        .into_iter()
+                        .map(|sol_amount| {
+                            let required_token_amount = bonding_curve_token_quote(
+                                sol_amount,
+                                virtual_sol_reserves,
+                                virtual_token_reserves,
+                                true,
+                            );
+
+                            // The exact cost of the tokens, fees included, before the curve moves
+                            let sol_cost = pumpfun_buy_cost(
+                                required_token_amount,
+                                virtual_sol_reserves,
+                                virtual_token_reserves,
+                                &fees,
+                            );
+
+                            let slippage = curve_slippage(
+                                buy_slippage(&metadata.transaction_metadata.fee_payer),
+                                sol_amount,
+                                virtual_sol_reserves,
+                                true,
+                            );
+
+                            virtual_sol_reserves += sol_amount;
+                            virtual_token_reserves =
+                                virtual_token_reserves.saturating_sub(required_token_amount);
+
+                            let lamports_with_slippage =
+                                (sol_cost as f64 * (1.0 + slippage)) as u64;
+
+                            let create_ata_ix = arranged.get_create_idempotent_ata_ix();
+
+                            let buy_ix = arranged.get_buy_ix(Buy {
+                                amount: required_token_amount,
+                                max_sol_cost: lamports_with_slippage,
+                            });
+
+                            CopyOrder {
+                                target,
+                                wallet,
+                                mint: arranged.mint,
+                                bought_tokens: Some(required_token_amount),
+                                quoted_sol: sol_cost,
+                                ixs: vec![create_ata_ix, buy_ix],
+                            }
+                        })
+                        .collect()
+                } else {
+                    warn!("Failed to arrange accounts");
+
+                    vec![]
+                }
+            }
+            PumpfunInstruction::Sell(_sell_data) => {
+                // The same trade can arrive from several datasources, only copy it once
+                if !SEEN_INSTRUCTIONS.first_seen(signature, &metadata.absolute_path) {
+                    debug!("Signature {} already processed, skipping...", signature);
+                    return Ok(());
+                }
+                // Print siganure with timestamp
+                info!("Received target's signature");
+
+                if let Some(mut arranged) = Sell::arrange_accounts(&instruction.accounts) {
+                    let Some(trade_event) = find_trade_event(
+                        metadata
+                            .transaction_metadata
+                            .meta
+                            .inner_instructions
+                            .as_deref()
+                            .unwrap_or_default(),
+                        &account_keys,
+                        metadata.index,
+                        &metadata.absolute_path,
+                        &arranged.event_authority,
+                    )
+                    .and_then(|swap_cpi_ix| pumpfun_trade_event(&swap_cpi_ix.instruction.data))
+                    // Some datasources omit inner instructions, the event is in the logs too
+                    .or_else(|| {
+                        trade_event_from_logs(
+                            metadata
+                                .transaction_metadata
+                                .meta
+                                .log_messages
+                                .as_deref()
+                                .unwrap_or_default(),
+                            &arranged.mint,
+                            false,
+                        )
+                    }) else {
+                        info!(target: JOURNAL_SKIP, "No trade event found for {}, skipping...", signature);
+                        return Ok(());
+                    };
+
+                    record_target_trade(
+                        &arranged.mint,
+                        false,
+                        trade_event.sol_amount,
+                        trade_event.token_amount,
+                    );
+
+                    info!(
+                        "Target sold {} tokens for {} SOL",
+                        trade_event.token_amount as f64 / 10f64.powf(6_f64),
+                        trade_event.sol_amount as f64 / 10f64.powf(9_f64)
+                    );
+
+                    CURVE_STATES.update(
+                        arranged.bonding_curve,
+                        curve_reserves(&trade_event, metadata.transaction_metadata.slot),
+                    );
+
+                    let positions = sell_positions(&arranged.mint, |wallet| {
+                        associated_token_address(wallet, &arranged.mint)
+                    })
+                    .await;
+
+                    // Each wallet's sell is quoted against the reserves left by the ones before it
+                    let (mut virtual_sol_reserves, mut virtual_token_reserves) = CURVE_STATES
+                        .with_own_buys(
+                            &arranged.mint,
+                            trade_event.virtual_sol_reserves,
+                            trade_event.virtual_token_reserves,
+                        );
+
+                    let fees = pumpfun_fees();
+
+                    let mut orders = Vec::with_capacity(positions.len());
+
+                    for (wallet, token_amount) in positions {
+                        arranged.user = wallet.pubkey();
+                        arranged.associated_user =
+                            associated_token_address(&arranged.user, &arranged.mint);
+
+                        let sol_output = bonding_curve_sol_quote(
+                            token_amount,
+                            virtual_sol_reserves,
+                            virtual_token_reserves,
+                            false,
+                        );
+
+                        let quoted_sol = pumpfun_sell_output(
+                            token_amount,
+                            virtual_sol_reserves,
+                            virtual_token_reserves,
+                            &fees,
+                        );
+
+                        let min_sol_output = pumpfun_min_sol_output(
+                            token_amount,
+                            virtual_sol_reserves,
+                            virtual_token_reserves,
+                            &fees,
+                            curve_slippage(
+                                sell_slippage(&metadata.transaction_metadata.fee_payer),
+                                sol_output,
+                                virtual_sol_reserves,
+                                false,
+                            ),
+                        );
+
+                        virtual_sol_reserves -= sol_output;
+                        virtual_token_reserves += token_amount;
+
+                        let sell_ix = arranged.get_sell_ix(Sell {
+                            amount: token_amount,
+                            min_sol_output,
+                        });
+
+                        let close_ata_ix = arranged.get_close_ata_ix();
+
+                        orders.push(CopyOrder {
+                            target,
+                            wallet,
+                            mint: arranged.mint,
+                            bought_tokens: None,
+                            quoted_sol,
+                            ixs: vec![sell_ix, close_ata_ix],
+                        });
+                    }
+
+                    orders
+                } else {
+                    warn!("Failed to arrange accounts");
+
+                    vec![]
+                }
+            }
+            PumpfunInstruction::Create(create_data) => {
+                if !SEEN_INSTRUCTIONS.first_seen(signature, &metadata.absolute_path) {
+                    debug!("Signature {} already processed, skipping...", signature);
+                    return Ok(());
+                }
+
+                info!("Received target's launch signature");
+
+                match Create::arrange_accounts(&instruction.accounts) {
+                    Some(arranged) => {
+                        snipe_launch(&metadata, &create_data, &arranged, &account_keys, &metrics)
+                            .await;
+                    }
+                    None => warn!("Failed to arrange accounts"),
+                }
+
+                vec![]
+            }
+            PumpfunInstruction::SetParams(params) => {
+                info!(
+                    "Pumpfun params changed, fee: {} bps, creator fee: {} bps",
+                    params.fee_basis_points, params.creator_fee_basis_points
+                );
+
+                set_pumpfun_fees(params.fee_basis_points, params.creator_fee_basis_points);
+
+                vec![]
+            }
+            PumpfunInstruction::SetParamsEvent(params) => {
+                set_pumpfun_fees(params.fee_basis_points, params.creator_fee_basis_points);
+
+                vec![]
+            }
+            PumpfunInstruction::Migrate(_) => {
+                if let Some(arranged) = Migrate::arrange_accounts(&instruction.accounts) {
+                    info!(
+                        "Bonding curve of {} withdrawn to PumpSwap pool {}",
+                        arranged.mint, arranged.pool
+                    );
+
+                    CURVE_STATES.mark_complete(arranged.mint);
+                }
+
+                vec![]
+            }
+            PumpfunInstruction::CompleteEvent(event) => {
+                info!("Bonding curve of {} completed", event.mint);
+
+                CURVE_STATES.mark_complete(event.mint);
+
+                vec![]
+            }
+            PumpfunInstruction::CompletePumpAmmMigrationEvent(event) => {
+                info!("{} migrated to PumpSwap pool {}", event.mint, event.pool);
+
+                CURVE_STATES.mark_complete(event.mint);
+
+                vec![]
+            }
+            PumpfunInstruction::ExtendAccount(_) => {
+                info!("Target extended a pumpfun account : {}", signature);
+
+                vec![]
+            }
+            _ => {
+                vec![]
+            }
+        };
+
+        ORDER_EXECUTOR.dispatch(claim_orders(signature, orders), &metrics, start);
+
+        Ok(())
+    }
+}
+
+/// Curve reserves right after the trade of `trade_event`, landed in `slot`.
+fn curve_reserves(trade_event: &TradeEvent, slot: u64) -> CurveReserves {
+    CurveReserves {
+        virtual_sol_reserves: trade_event.virtual_sol_reserves,
+        virtual_token_reserves: trade_event.virtual_token_reserves,
+        complete: false,
+        slot,
+    }
+}
@@ -0,0 +1,252 @@
+use async_trait::async_trait;
+use carbon_core::{
+    deserialize::ArrangeAccounts,
+    error::CarbonResult,
+    instruction::{InstructionMetadata, InstructionProcessorInputType},
+    metrics::MetricsCollection,
+    processor::Processor,
+};
+use carbon_raydium_amm_v4_decoder::instructions::{
+    RaydiumAmmV4Instruction,
+    swap_base_in::{SwapBaseIn, SwapBaseInInstructionAccounts},
+    swap_base_out::SwapBaseOut,
+};
+use chrono::Utc;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::{
+    config::{BUY_SOL_AMOUNT, BUY_SPLIT_THRESHOLD, buy_slippage, sell_slippage},
+    instructions::{
+        raydium_amm_ix::{RaydiumAmmSwapInstructionAccountsExt, swap_base_in_accounts},
+        token_account::close_token_account_ix,
+        wsol::{close_wsol_account_ix, create_wsol_account_ix, wrap_sol_ixs},
+    },
+    processor::{
+        CopyOrder, ORDER_EXECUTOR, claim_orders, post_token_balance, pre_token_balance,
+        sell_positions, target_wallet, transaction_account_keys,
+    },
+    utils::{
+        JOURNAL_SKIP, LatencyTrace, SEEN_INSTRUCTIONS, associated_token_address, observe_slot,
+        raydium_amm_quote, record_trade_mint, split_amount, stale_event_latency,
+    },
+    wallet::WALLET_POOL,
+};
+
+/// Copies the target's swaps on Raydium AMM v4 pools quoted in SOL, including
+/// pumpfun tokens migrated there.
+pub struct RaydiumAmmProcess;
+
+#[async_trait]
+impl Processor for RaydiumAmmProcess {
+    type InputType = InstructionProcessorInputType<RaydiumAmmV4Instruction>;
+
+    async fn process(
+        &mut self,
+        (metadata, instruction, _nested_instructions, _instructions): Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let accounts = match instruction.data {
+            RaydiumAmmV4Instruction::SwapBaseIn(_) => {
+                SwapBaseIn::arrange_accounts(&instruction.accounts)
+            }
+            RaydiumAmmV4Instruction::SwapBaseOut(_) => {
+                SwapBaseOut::arrange_accounts(&instruction.accounts).map(swap_base_in_accounts)
+            }
+            _ => return Ok(()),
+        };
+
+        let Some(accounts) = accounts else {
+            warn!("Failed to arrange accounts");
+            return Ok(());
+        };
+
+        let signature = metadata.transaction_metadata.signature;
+
+        observe_slot(metadata.transaction_metadata.slot);
+
+        if !SEEN_INSTRUCTIONS.first_seen(signature, &metadata.absolute_path) {
+            debug!("Signature {} already processed, skipping...", signature);
+            return Ok(());
+        }
+
+        info!("Received target's Raydium AMM signature");
+
+        let start = LatencyTrace::start(&signature);
+
+        let orders = copy_swap(&metadata, accounts).await;
+
+        ORDER_EXECUTOR.dispatch(claim_orders(signature, orders), &metrics, start);
+
+        Ok(())
+    }
+}
+
+/// Reserves of one side of the pool right after the target's swap.
+struct PoolSide {
+    mint: Pubkey,
+    reserve: u64,
+    /// Whether the target's swap paid into this side
+    is_input: bool,
+}
+
+fn pool_side(
+    metadata: &InstructionMetadata,
+    account_keys: &[Pubkey],
+    vault: Pubkey,
+) -> Option<PoolSide> {
+    let (mint, reserve) = post_token_balance(metadata, account_keys, &vault)?;
+    let (_, pre_reserve) = pre_token_balance(metadata, account_keys, &vault)?;
+
+    Some(PoolSide {
+        mint,
+        reserve,
+        is_input: reserve > pre_reserve,
+    })
+}
+
+async fn copy_swap(
+    metadata: &InstructionMetadata,
+    mut accounts: SwapBaseInInstructionAccounts,
+) -> Vec<CopyOrder> {
+    let account_keys = transaction_account_keys(metadata);
+    let target = target_wallet(metadata);
+
+    let (Some(coin), Some(pc)) = (
+        pool_side(metadata, &account_keys, accounts.pool_coin_token_account),
+        pool_side(metadata, &account_keys, accounts.pool_pc_token_account),
+    ) else {
+        info!(target: JOURNAL_SKIP, "Pool reserves not found in the transaction, skipping...");
+        return vec![];
+    };
+
+    let (input, output) = if coin.is_input {
+        (coin, pc)
+    } else {
+        (pc, coin)
+    };
+    let native_mint = spl_token::native_mint::id();
+
+    if input.mint == native_mint {
+        // Target bought `output.mint` with SOL
+        record_trade_mint(&output.mint);
+
+        let block_time = metadata
+            .transaction_metadata
+            .block_time
+            .unwrap_or_else(|| Utc::now().timestamp());
+
+        if let Some(latency) = stale_event_latency(metadata.transaction_metadata.slot, block_time) {
+            info!(target: JOURNAL_SKIP,
+                "Event is {}ms old, past the stale deadline, skipping...",
+                latency
+            );
+            return vec![];
+        }
+
+        let required_lamports = (*BUY_SOL_AMOUNT as f64
+            * (1.0 + buy_slippage(&metadata.transaction_metadata.fee_payer)))
+            as u64;
+
+        let Some(wallet) = WALLET_POOL.next_buy_wallet(required_lamports) else {
+            info!(target: JOURNAL_SKIP, "No trading wallet has enough SOL for this buy, skipping...");
+            return vec![];
+        };
+
+        accounts.user_source_owner = wallet.pubkey();
+        accounts.user_source_token_account =
+            associated_token_address(&wallet.pubkey(), &native_mint);
+        accounts.user_destination_token_account =
+            associated_token_address(&wallet.pubkey(), &output.mint);
+
+        let chunks = match *BUY_SPLIT_THRESHOLD {
+            Some(max_chunk) => split_amount(*BUY_SOL_AMOUNT, max_chunk),
+            None => vec![*BUY_SOL_AMOUNT],
+        };
+
+        let mut reserve_in = input.reserve;
+        let mut reserve_out = output.reserve;
+
+        chunks
+            .into_iter()
+            .map(|sol_amount| {
+                let amount_out = raydium_amm_quote(sol_amount, reserve_in, reserve_out);
+
+                reserve_in += sol_amount;
+                reserve_out = reserve_out.saturating_sub(amount_out);
+
+                let mut ixs = wrap_sol_ixs(&wallet.pubkey(), sol_amount);
+                ixs.push(
+                    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                        &wallet.pubkey(),
+                        &wallet.pubkey(),
+                        &output.mint,
+                        &spl_token::id(),
+                    ),
+                );
+                ixs.push(accounts.get_swap_base_in_ix(SwapBaseIn {
+                    amount_in: sol_amount,
+                    minimum_amount_out: (amount_out as f64 * (1.0 - sell_slippage(&metadata.transaction_metadata.fee_payer))) as u64,
+                }));
+                ixs.push(close_wsol_account_ix(&wallet.pubkey()));
+
+                CopyOrder {
+                    target,
+                    wallet,
+                    mint: output.mint,
+                    bought_tokens: Some(amount_out),
+                    quoted_sol: sol_amount,
+                    ixs,
+                }
+            })
+            .collect()
+    } else if output.mint == native_mint {
+        // Target sold `input.mint` for SOL
+        record_trade_mint(&input.mint);
+
+        let positions = sell_positions(&input.mint, |wallet| {
+            associated_token_address(wallet, &input.mint)
+        })
+        .await;
+        let mut orders = Vec::with_capacity(positions.len());
+
+        for (wallet, token_amount) in positions {
+            accounts.user_source_owner = wallet.pubkey();
+            accounts.user_source_token_account =
+                associated_token_address(&wallet.pubkey(), &input.mint);
+            accounts.user_destination_token_account =
+                associated_token_address(&wallet.pubkey(), &native_mint);
+
+            let sol_out = raydium_amm_quote(token_amount, input.reserve, output.reserve);
+
+            orders.push(CopyOrder {
+                target,
+                wallet,
+                mint: input.mint,
+                bought_tokens: None,
+                quoted_sol: sol_out,
+                ixs: vec![
+                    create_wsol_account_ix(&wallet.pubkey()),
+                    accounts.get_swap_base_in_ix(SwapBaseIn {
+                        amount_in: token_amount,
+                        minimum_amount_out: (sol_out as f64
+                            * (1.0 - sell_slippage(&metadata.transaction_metadata.fee_payer)))
+                            as u64,
+                    }),
+                    close_wsol_account_ix(&wallet.pubkey()),
+                    close_token_account_ix(
+                        &spl_token::id(),
+                        &accounts.user_source_token_account,
+                        &wallet.pubkey(),
+                    ),
+                ],
+            });
+        }
+
+        orders
+    } else {
+        // Neither side is SOL, nothing to copy
+        vec![]
+    }
+}
@@ -0,0 +1,242 @@
+use async_trait::async_trait;
+use carbon_core::{
+    deserialize::ArrangeAccounts,
+    error::CarbonResult,
+    instruction::{InstructionMetadata, InstructionProcessorInputType},
+    metrics::MetricsCollection,
+    processor::Processor,
+};
+use carbon_raydium_cpmm_decoder::instructions::{
+    RaydiumCpmmInstruction,
+    swap_base_input::{SwapBaseInput, SwapBaseInputInstructionAccounts},
+    swap_base_output::SwapBaseOutput,
+};
+use chrono::Utc;
+use solana_sdk::signer::Signer;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::{
+    config::{BUY_SOL_AMOUNT, BUY_SPLIT_THRESHOLD, buy_slippage, sell_slippage},
+    instructions::{
+        raydium_cpmm_ix::{RaydiumCpmmSwapInstructionAccountsExt, swap_base_input_accounts},
+        token_account::close_token_account_ix,
+        wsol::{close_wsol_account_ix, create_wsol_account_ix, wrap_sol_ixs},
+    },
+    processor::{
+        CopyOrder, ORDER_EXECUTOR, claim_orders, post_token_balance, sell_positions, target_wallet,
+        transaction_account_keys,
+    },
+    utils::{
+        JOURNAL_SKIP, LatencyTrace, SEEN_INSTRUCTIONS, associated_token_address_with_program_id,
+        observe_slot, raydium_cpmm_quote, record_trade_mint, split_amount, stale_event_latency,
+    },
+    wallet::WALLET_POOL,
+};
+
+/// Copies the target's swaps on Raydium CPMM pools quoted in SOL, including
+/// pumpfun tokens migrated there.
+pub struct RaydiumCpmmProcess;
+
+#[async_trait]
+impl Processor for RaydiumCpmmProcess {
+    type InputType = InstructionProcessorInputType<RaydiumCpmmInstruction>;
+
+    async fn process(
+        &mut self,
+        (metadata, instruction, _nested_instructions, _instructions): Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let accounts = match instruction.data {
+            RaydiumCpmmInstruction::SwapBaseInput(_) => {
+                SwapBaseInput::arrange_accounts(&instruction.accounts)
+            }
+            RaydiumCpmmInstruction::SwapBaseOutput(_) => {
+                SwapBaseOutput::arrange_accounts(&instruction.accounts)
+                    .map(swap_base_input_accounts)
+            }
+            _ => return Ok(()),
+        };
+
+        let Some(accounts) = accounts else {
+            warn!("Failed to arrange accounts");
+            return Ok(());
+        };
+
+        let signature = metadata.transaction_metadata.signature;
+
+        observe_slot(metadata.transaction_metadata.slot);
+
+        if !SEEN_INSTRUCTIONS.first_seen(signature, &metadata.absolute_path) {
+            debug!("Signature {} already processed, skipping...", signature);
+            return Ok(());
+        }
+
+        info!("Received target's Raydium CPMM signature");
+
+        let start = LatencyTrace::start(&signature);
+
+        let orders = copy_swap(&metadata, accounts).await;
+
+        ORDER_EXECUTOR.dispatch(claim_orders(signature, orders), &metrics, start);
+
+        Ok(())
+    }
+}
+
+async fn copy_swap(
+    metadata: &InstructionMetadata,
+    mut accounts: SwapBaseInputInstructionAccounts,
+) -> Vec<CopyOrder> {
+    let account_keys = transaction_account_keys(metadata);
+    let target = target_wallet(metadata);
+
+    let (Some((_, reserve_in)), Some((_, reserve_out))) = (
+        post_token_balance(metadata, &account_keys, &accounts.input_vault),
+        post_token_balance(metadata, &account_keys, &accounts.output_vault),
+    ) else {
+        info!(target: JOURNAL_SKIP, "Pool reserves not found in the transaction, skipping...");
+        return vec![];
+    };
+
+    let amm_config = accounts.amm_config.to_string();
+    let native_mint = spl_token::native_mint::id();
+
+    if accounts.input_token_mint == native_mint {
+        // Target bought the output token with SOL
+        record_trade_mint(&accounts.output_token_mint);
+
+        let block_time = metadata
+            .transaction_metadata
+            .block_time
+            .unwrap_or_else(|| Utc::now().timestamp());
+
+        if let Some(latency) = stale_event_latency(metadata.transaction_metadata.slot, block_time) {
+            info!(target: JOURNAL_SKIP,
+                "Event is {}ms old, past the stale deadline, skipping...",
+                latency
+            );
+            return vec![];
+        }
+
+        let required_lamports = (*BUY_SOL_AMOUNT as f64
+            * (1.0 + buy_slippage(&metadata.transaction_metadata.fee_payer)))
+            as u64;
+
+        let Some(wallet) = WALLET_POOL.next_buy_wallet(required_lamports) else {
+            info!(target: JOURNAL_SKIP, "No trading wallet has enough SOL for this buy, skipping...");
+            return vec![];
+        };
+
+        accounts.payer = wallet.pubkey();
+        accounts.input_token_account = associated_token_address_with_program_id(
+            &wallet.pubkey(),
+            &native_mint,
+            &accounts.input_token_program,
+        );
+        accounts.output_token_account = associated_token_address_with_program_id(
+            &wallet.pubkey(),
+            &accounts.output_token_mint,
+            &accounts.output_token_program,
+        );
+
+        let chunks = match *BUY_SPLIT_THRESHOLD {
+            Some(max_chunk) => split_amount(*BUY_SOL_AMOUNT, max_chunk),
+            None => vec![*BUY_SOL_AMOUNT],
+        };
+
+        let mut reserve_in = reserve_in;
+        let mut reserve_out = reserve_out;
+
+        chunks
+            .into_iter()
+            .map(|sol_amount| {
+                let amount_out = raydium_cpmm_quote(sol_amount, reserve_in, reserve_out, &amm_config);
+
+                reserve_in += sol_amount;
+                reserve_out = reserve_out.saturating_sub(amount_out);
+
+                let mut ixs = wrap_sol_ixs(&wallet.pubkey(), sol_amount);
+                ixs.push(
+                    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                        &wallet.pubkey(),
+                        &wallet.pubkey(),
+                        &accounts.output_token_mint,
+                        &accounts.output_token_program,
+                    ),
+                );
+                ixs.push(accounts.get_swap_base_input_ix(SwapBaseInput {
+                    amount_in: sol_amount,
+                    minimum_amount_out: (amount_out as f64 * (1.0 - sell_slippage(&metadata.transaction_metadata.fee_payer))) as u64,
+                }));
+                ixs.push(close_wsol_account_ix(&wallet.pubkey()));
+
+                CopyOrder {
+                    target,
+                    wallet,
+                    mint: accounts.output_token_mint,
+                    bought_tokens: Some(amount_out),
+                    quoted_sol: sol_amount,
+                    ixs,
+                }
+            })
+            .collect()
+    } else if accounts.output_token_mint == native_mint {
+        // Target sold the input token for SOL
+        record_trade_mint(&accounts.input_token_mint);
+
+        let positions = sell_positions(&accounts.input_token_mint, |wallet| {
+            associated_token_address_with_program_id(
+                wallet,
+                &accounts.input_token_mint,
+                &accounts.input_token_program,
+            )
+        })
+        .await;
+        let mut orders = Vec::with_capacity(positions.len());
+
+        for (wallet, token_amount) in positions {
+            accounts.payer = wallet.pubkey();
+            accounts.input_token_account = associated_token_address_with_program_id(
+                &wallet.pubkey(),
+                &accounts.input_token_mint,
+                &accounts.input_token_program,
+            );
+            accounts.output_token_account = associated_token_address_with_program_id(
+                &wallet.pubkey(),
+                &native_mint,
+                &accounts.output_token_program,
+            );
+
+            let sol_out = raydium_cpmm_quote(token_amount, reserve_in, reserve_out, &amm_config);
+
+            orders.push(CopyOrder {
+                target,
+                wallet,
+                mint: accounts.input_token_mint,
+                bought_tokens: None,
+                quoted_sol: sol_out,
+                ixs: vec![
+                    create_wsol_account_ix(&wallet.pubkey()),
+                    accounts.get_swap_base_input_ix(SwapBaseInput {
+                        amount_in: token_amount,
+                        minimum_amount_out: (sol_out as f64
+                            * (1.0 - sell_slippage(&metadata.transaction_metadata.fee_payer)))
+                            as u64,
+                    }),
+                    close_wsol_account_ix(&wallet.pubkey()),
+                    close_token_account_ix(
+                        &accounts.input_token_program,
+                        &accounts.input_token_account,
+                        &wallet.pubkey(),
+                    ),
+                ],
+            });
+        }
+
+        orders
+    } else {
+        // Neither side is SOL, nothing to copy
+        vec![]
+    }
+}
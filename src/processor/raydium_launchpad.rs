@@ -0,0 +1,321 @@
+use async_trait::async_trait;
+use carbon_core::{
+    deserialize::{ArrangeAccounts, CarbonDeserialize},
+    error::CarbonResult,
+    instruction::InstructionProcessorInputType,
+    metrics::MetricsCollection,
+    processor::Processor,
+};
+use carbon_raydium_launchpad_decoder::{
+    PROGRAM_ID as LAUNCHPAD_PROGRAM_ID,
+    instructions::{
+        RaydiumLaunchpadInstruction, buy_exact_in::BuyExactIn, trade_event::TradeEvent,
+    },
+    types::PoolStatus,
+};
+use chrono::Utc;
+use solana_sdk::signer::Signer;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::{
+    config::{BUY_SOL_AMOUNT, BUY_SPLIT_THRESHOLD, buy_slippage, sell_slippage},
+    instructions::{
+        raydium_launchpad_ix::RaydiumLaunchpadInstructionAccountsExt,
+        token_account::close_token_account_ix,
+        wsol::{close_wsol_account_ix, create_wsol_account_ix, wrap_sol_ixs},
+    },
+    processor::{
+        CopyOrder, ORDER_EXECUTOR, claim_orders, sell_positions, target_wallet,
+        transaction_account_keys,
+    },
+    utils::{
+        JOURNAL_SKIP, LatencyTrace, SEEN_INSTRUCTIONS, associated_token_address_with_program_id,
+        decode_event_cpi, find_event_cpi, is_copied_platform, launchpad_buy_quote,
+        launchpad_platform_name, launchpad_sell_quote, observe_slot, record_target_trade,
+        split_amount, stale_event_latency,
+    },
+    wallet::WALLET_POOL,
+};
+
+/// Number of accounts in the LaunchLab swap layout known to the decoder
+const LAUNCHPAD_SWAP_ACCOUNTS: usize = 15;
+
+/// Copies the target's trades on Raydium LaunchLab bonding curves quoted in SOL,
+/// including launch platforms built on it such as bonk.fun.
+pub struct RaydiumLaunchpadProcess;
+
+#[async_trait]
+impl Processor for RaydiumLaunchpadProcess {
+    type InputType = InstructionProcessorInputType<RaydiumLaunchpadInstruction>;
+
+    async fn process(
+        &mut self,
+        (metadata, instruction, _nested_instructions, _instructions): Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let is_buy = match instruction.data {
+            RaydiumLaunchpadInstruction::BuyExactIn(_)
+            | RaydiumLaunchpadInstruction::BuyExactOut(_) => true,
+            RaydiumLaunchpadInstruction::SellExactIn(_)
+            | RaydiumLaunchpadInstruction::SellExactOut(_) => false,
+            _ => return Ok(()),
+        };
+
+        // All four swap instructions share one account layout
+        let Some(mut accounts) = BuyExactIn::arrange_accounts(&instruction.accounts) else {
+            warn!("Failed to arrange accounts");
+            return Ok(());
+        };
+        let remaining_accounts = instruction
+            .accounts
+            .get(LAUNCHPAD_SWAP_ACCOUNTS..)
+            .unwrap_or_default();
+
+        let signature = metadata.transaction_metadata.signature;
+        let target = target_wallet(&metadata);
+
+        observe_slot(metadata.transaction_metadata.slot);
+
+        if !SEEN_INSTRUCTIONS.first_seen(signature, &metadata.absolute_path) {
+            debug!("Signature {} already processed, skipping...", signature);
+            return Ok(());
+        }
+
+        let platform = launchpad_platform_name(&accounts.platform_config);
+
+        info!("Received target's {} signature", platform);
+
+        if !is_copied_platform(platform) {
+            info!(target: JOURNAL_SKIP, "{} trades are not copied, skipping...", platform);
+            return Ok(());
+        }
+
+        let native_mint = spl_token::native_mint::id();
+
+        // Only SOL-quoted launches are copied
+        if accounts.quote_token_mint != native_mint {
+            return Ok(());
+        }
+
+        let account_keys = transaction_account_keys(&metadata);
+
+        let Some(event_ix) = find_event_cpi(
+            metadata
+                .transaction_metadata
+                .meta
+                .inner_instructions
+                .as_deref()
+                .unwrap_or_default(),
+            &account_keys,
+            metadata.index,
+            &metadata.absolute_path,
+            &LAUNCHPAD_PROGRAM_ID,
+            &accounts.event_authority,
+            TradeEvent::DISCRIMINATOR,
+        ) else {
+            info!(target: JOURNAL_SKIP, "No trade event found for {}, skipping...", signature);
+            return Ok(());
+        };
+
+        let Some(trade_event) = decode_event_cpi::<TradeEvent>(&event_ix.instruction.data) else {
+            warn!(target: JOURNAL_SKIP, "Failed to parse trade event of {}, skipping...", signature);
+            return Ok(());
+        };
+
+        // Once the curve completes, trading moves to the migrated AMM pool
+        if trade_event.pool_status != PoolStatus::Fund {
+            info!(target: JOURNAL_SKIP, "LaunchLab pool is no longer on its curve, skipping...");
+            return Ok(());
+        }
+
+        let fees = trade_event.protocol_fee + trade_event.platform_fee + trade_event.share_fee;
+
+        let start = LatencyTrace::start(&signature);
+
+        let orders = if is_buy {
+            record_target_trade(
+                &accounts.base_token_mint,
+                true,
+                trade_event.amount_in,
+                trade_event.amount_out,
+            );
+
+            info!(
+                "Target bought {} tokens by {} SOL on {}",
+                trade_event.amount_out as f64 / 10f64.powf(6_f64),
+                trade_event.amount_in as f64 / 10f64.powf(9_f64),
+                platform
+            );
+
+            let block_time = metadata
+                .transaction_metadata
+                .block_time
+                .unwrap_or_else(|| Utc::now().timestamp());
+
+            if let Some(latency) =
+                stale_event_latency(metadata.transaction_metadata.slot, block_time)
+            {
+                info!(target: JOURNAL_SKIP,
+                    "Event is {}ms old, past the stale deadline, skipping...",
+                    latency
+                );
+                return Ok(());
+            }
+
+            // Buy fees are taken out of the quote paid in
+            let fee_rate = fees as f64 / trade_event.amount_in.max(1) as f64;
+
+            let required_lamports = (*BUY_SOL_AMOUNT as f64
+                * (1.0 + buy_slippage(&metadata.transaction_metadata.fee_payer)))
+                as u64;
+
+            let Some(wallet) = WALLET_POOL.next_buy_wallet(required_lamports) else {
+                info!(target: JOURNAL_SKIP, "No trading wallet has enough SOL for this buy, skipping...");
+                return Ok(());
+            };
+
+            accounts.payer = wallet.pubkey();
+            accounts.user_base_token = associated_token_address_with_program_id(
+                &wallet.pubkey(),
+                &accounts.base_token_mint,
+                &accounts.base_token_program,
+            );
+            accounts.user_quote_token = associated_token_address_with_program_id(
+                &wallet.pubkey(),
+                &native_mint,
+                &accounts.quote_token_program,
+            );
+
+            let chunks = match *BUY_SPLIT_THRESHOLD {
+                Some(max_chunk) => split_amount(*BUY_SOL_AMOUNT, max_chunk),
+                None => vec![*BUY_SOL_AMOUNT],
+            };
+
+            let mut real_base = trade_event.real_base_after;
+            let mut real_quote = trade_event.real_quote_after;
+
+            chunks
+                .into_iter()
+                .map(|sol_amount| {
+                    let amount_out = launchpad_buy_quote(
+                        sol_amount,
+                        trade_event.virtual_base,
+                        trade_event.virtual_quote,
+                        real_base,
+                        real_quote,
+                        fee_rate,
+                    );
+
+                    real_base += amount_out;
+                    real_quote += (sol_amount as f64 * (1.0 - fee_rate)) as u64;
+
+                    let mut ixs = wrap_sol_ixs(&wallet.pubkey(), sol_amount);
+                    ixs.push(
+                        spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                            &wallet.pubkey(),
+                            &wallet.pubkey(),
+                            &accounts.base_token_mint,
+                            &accounts.base_token_program,
+                        ),
+                    );
+                    ixs.push(accounts.get_buy_exact_in_ix(
+                        sol_amount,
+                        (amount_out as f64 * (1.0 - sell_slippage(&metadata.transaction_metadata.fee_payer))) as u64,
+                        remaining_accounts,
+                    ));
+                    ixs.push(close_wsol_account_ix(&wallet.pubkey()));
+
+                    CopyOrder {
+                        target,
+                        wallet,
+                        mint: accounts.base_token_mint,
+                        bought_tokens: Some(amount_out),
+                        quoted_sol: sol_amount,
+                        ixs,
+                    }
+                })
+                .collect()
+        } else {
+            record_target_trade(
+                &accounts.base_token_mint,
+                false,
+                trade_event.amount_out,
+                trade_event.amount_in,
+            );
+
+            info!(
+                "Target sold {} tokens for {} SOL on {}",
+                trade_event.amount_in as f64 / 10f64.powf(6_f64),
+                trade_event.amount_out as f64 / 10f64.powf(9_f64),
+                platform
+            );
+
+            // Sell fees are taken out of the quote paid out
+            let fee_rate = fees as f64 / (trade_event.amount_out + fees).max(1) as f64;
+
+            let positions = sell_positions(&accounts.base_token_mint, |wallet| {
+                associated_token_address_with_program_id(
+                    wallet,
+                    &accounts.base_token_mint,
+                    &accounts.base_token_program,
+                )
+            })
+            .await;
+            let mut orders = Vec::with_capacity(positions.len());
+
+            for (wallet, token_amount) in positions {
+                accounts.payer = wallet.pubkey();
+                accounts.user_base_token = associated_token_address_with_program_id(
+                    &wallet.pubkey(),
+                    &accounts.base_token_mint,
+                    &accounts.base_token_program,
+                );
+                accounts.user_quote_token = associated_token_address_with_program_id(
+                    &wallet.pubkey(),
+                    &native_mint,
+                    &accounts.quote_token_program,
+                );
+
+                let sol_out = launchpad_sell_quote(
+                    token_amount,
+                    trade_event.virtual_base,
+                    trade_event.virtual_quote,
+                    trade_event.real_base_after,
+                    trade_event.real_quote_after,
+                    fee_rate,
+                );
+
+                orders.push(CopyOrder {
+                    target,
+                    wallet,
+                    mint: accounts.base_token_mint,
+                    bought_tokens: None,
+                    quoted_sol: sol_out,
+                    ixs: vec![
+                        create_wsol_account_ix(&wallet.pubkey()),
+                        accounts.get_sell_exact_in_ix(
+                            token_amount,
+                            (sol_out as f64
+                                * (1.0 - sell_slippage(&metadata.transaction_metadata.fee_payer)))
+                                as u64,
+                            remaining_accounts,
+                        ),
+                        close_wsol_account_ix(&wallet.pubkey()),
+                        close_token_account_ix(
+                            &accounts.base_token_program,
+                            &accounts.user_base_token,
+                            &wallet.pubkey(),
+                        ),
+                    ],
+                });
+            }
+
+            orders
+        };
+
+        ORDER_EXECUTOR.dispatch(claim_orders(signature, orders), &metrics, start);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,393 @@
+use carbon_core::{
+    deserialize::CarbonDeserialize, instruction::InstructionMetadata, metrics::MetricsCollection,
+};
+use carbon_pumpfun_decoder::{
+    PROGRAM_ID as PUMPFUN_PROGRAM_ID,
+    accounts::bonding_curve::BondingCurve,
+    instructions::{
+        buy::{Buy, BuyInstructionAccounts},
+        create::{Create, CreateInstructionAccounts},
+        create_event::CreateEvent,
+        sell::{Sell, SellInstructionAccounts},
+    },
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::Signer};
+use solana_transaction_status_client_types::InnerInstructions;
+use std::sync::Arc;
+use tokio::time::{Duration, sleep};
+use tracing::{Instrument, info, warn};
+
+use crate::{
+    config::{
+        LAUNCH_BUNDLE_SOL_AMOUNT, RPC_CLIENT, SELL_SLIPPAGE, SNIPE_MAX_HOLD_SEC, SNIPE_MODE,
+        SNIPE_SOL_AMOUNT, buy_slippage,
+    },
+    instructions::{
+        buy_ix::BuyExactInInstructionAccountsExt, sell_ix::SellExactInInstructionAccountsExt,
+    },
+    processor::{CopyOrder, ORDER_EXECUTOR, position_balance, submit_bundle_order, target_wallet},
+    utils::{
+        CURVE_STATES, CurveReserves, JOURNAL_SKIP, LatencyTrace, PENDING_ACTIONS,
+        REPLAYED_TRANSACTIONS, TradeEventView, append_trade_log, associated_token_address,
+        bonding_curve_sol_quote, bonding_curve_token_quote, curve_slippage, decode_event_cpi,
+        find_event_cpi, find_pda, pumpfun_buy_cost, pumpfun_fee_rate, pumpfun_fee_recipient,
+        pumpfun_fees, pumpfun_min_sol_output, pumpfun_sell_output, record_trade_mint,
+        stale_event_latency,
+    },
+    wallet::{WALLET_POOL, WalletSigner},
+};
+
+pub fn creator_vault_pda(creator: &Pubkey) -> Pubkey {
+    find_pda(&[b"creator-vault", creator.as_ref()], &PUMPFUN_PROGRAM_ID)
+}
+
+/// Whether the transaction also creates `mint`, i.e. a buy in it is the creator's dev buy.
+pub fn created_in_transaction(metadata: &InstructionMetadata, mint: &Pubkey) -> bool {
+    let message = &metadata.transaction_metadata.message;
+    let account_keys = message.static_account_keys();
+
+    message.instructions().iter().any(|ix| {
+        account_keys.get(ix.program_id_index as usize) == Some(&PUMPFUN_PROGRAM_ID)
+            && ix.data.starts_with(Create::DISCRIMINATOR)
+            && ix
+                .accounts
+                .first()
+                .and_then(|index| account_keys.get(*index as usize))
+                == Some(mint)
+    })
+}
+
+/// Virtual (sol, token) reserves after the last trade of `mint` in the transaction.
+fn latest_trade_reserves(
+    inner_instructions: &[InnerInstructions],
+    mint: &Pubkey,
+) -> Option<(u64, u64)> {
+    inner_instructions
+        .iter()
+        .flat_map(|group| &group.instructions)
+        .filter_map(|ix| TradeEventView::from_cpi(&ix.instruction.data))
+        .rfind(|event| event.is_mint(mint))
+        .map(|event| (event.virtual_sol_reserves(), event.virtual_token_reserves()))
+}
+
+/// Buys a token the target just created, and schedules its exit after
+/// `SNIPE_MAX_HOLD_SEC` when set. A create with a dev buy in the same transaction is
+/// copied as a Jito bundle of `LAUNCH_BUNDLE_SOL_AMOUNT`; a bare create is only bought
+/// in `SNIPE_MODE`, with `SNIPE_SOL_AMOUNT`.
+pub async fn snipe_launch(
+    metadata: &InstructionMetadata,
+    create: &Create,
+    accounts: &CreateInstructionAccounts,
+    account_keys: &[Pubkey],
+    metrics: &Arc<MetricsCollection>,
+) {
+    let start = LatencyTrace::start(&metadata.transaction_metadata.signature);
+    let target = target_wallet(metadata);
+
+    let inner_instructions = metadata
+        .transaction_metadata
+        .meta
+        .inner_instructions
+        .as_deref()
+        .unwrap_or_default();
+
+    let dev_buy_reserves = latest_trade_reserves(inner_instructions, &accounts.mint);
+
+    if dev_buy_reserves.is_none() && !*SNIPE_MODE {
+        return;
+    }
+
+    let Some(create_event) = find_event_cpi(
+        inner_instructions,
+        account_keys,
+        metadata.index,
+        &metadata.absolute_path,
+        &PUMPFUN_PROGRAM_ID,
+        &accounts.event_authority,
+        CreateEvent::DISCRIMINATOR,
+    )
+    .and_then(|event_ix| decode_event_cpi::<CreateEvent>(&event_ix.instruction.data)) else {
+        info!(target: JOURNAL_SKIP, "No create event found, skipping snipe...");
+        return;
+    };
+
+    info!(
+        "Target created {} ({}) : {}",
+        create_event.name, create_event.symbol, create_event.mint
+    );
+
+    record_trade_mint(&create_event.mint);
+
+    if let Some(latency) =
+        stale_event_latency(metadata.transaction_metadata.slot, create_event.timestamp)
+    {
+        info!(target: JOURNAL_SKIP,
+            "Launch is {}ms old, past the stale deadline, skipping snipe...",
+            latency
+        );
+        return;
+    }
+
+    // A dev buy in the same transaction has already moved the curve off its start
+    let (virtual_sol_reserves, virtual_token_reserves) = dev_buy_reserves.unwrap_or((
+        create_event.virtual_sol_reserves,
+        create_event.virtual_token_reserves,
+    ));
+
+    let sol_amount = match dev_buy_reserves {
+        Some(_) => *LAUNCH_BUNDLE_SOL_AMOUNT,
+        None => *SNIPE_SOL_AMOUNT,
+    };
+
+    let required_lamports = (sol_amount as f64
+        * (1.0 + pumpfun_fee_rate())
+        * (1.0 + buy_slippage(&metadata.transaction_metadata.fee_payer)))
+        as u64;
+
+    let Some(wallet) = WALLET_POOL.next_buy_wallet(required_lamports) else {
+        info!(target: JOURNAL_SKIP, "No trading wallet has enough SOL for this snipe, skipping...");
+        return;
+    };
+
+    let buy_accounts = BuyInstructionAccounts {
+        global: accounts.global,
+        fee_recipient: pumpfun_fee_recipient(),
+        mint: accounts.mint,
+        bonding_curve: accounts.bonding_curve,
+        associated_bonding_curve: accounts.associated_bonding_curve,
+        associated_user: associated_token_address(&wallet.pubkey(), &accounts.mint),
+        user: wallet.pubkey(),
+        system_program: accounts.system_program,
+        token_program: accounts.token_program,
+        creator_vault: creator_vault_pda(&create.creator),
+        event_authority: accounts.event_authority,
+        program: accounts.program,
+    };
+
+    let token_amount = bonding_curve_token_quote(
+        sol_amount,
+        virtual_sol_reserves,
+        virtual_token_reserves,
+        true,
+    );
+
+    let sol_cost = pumpfun_buy_cost(
+        token_amount,
+        virtual_sol_reserves,
+        virtual_token_reserves,
+        &pumpfun_fees(),
+    );
+
+    let max_sol_cost = (sol_cost as f64
+        * (1.0
+            + curve_slippage(
+                buy_slippage(&metadata.transaction_metadata.fee_payer),
+                sol_amount,
+                virtual_sol_reserves,
+                true,
+            ))) as u64;
+
+    let order = CopyOrder {
+        target,
+        wallet,
+        mint: accounts.mint,
+        bought_tokens: Some(token_amount),
+        quoted_sol: sol_cost,
+        ixs: vec![
+            buy_accounts.get_create_idempotent_ata_ix(),
+            buy_accounts.get_buy_ix(Buy {
+                amount: token_amount,
+                max_sol_cost,
+            }),
+        ],
+    };
+
+    if REPLAYED_TRANSACTIONS.is_replayed(&metadata.transaction_metadata.signature) {
+        info!(target: JOURNAL_SKIP,
+            "Launch of {} was replayed, skipping snipe...",
+            accounts.mint
+        );
+        return;
+    }
+
+    if !PENDING_ACTIONS.claim(metadata.transaction_metadata.signature, accounts.mint, true) {
+        info!(target: JOURNAL_SKIP,
+            "Launch of {} is already being copied, skipping...",
+            accounts.mint
+        );
+        return;
+    }
+
+    append_trade_log(
+        &metadata.transaction_metadata.signature,
+        &accounts.mint,
+        true,
+    );
+
+    CURVE_STATES.watch(accounts.mint, accounts.bonding_curve);
+
+    if dev_buy_reserves.is_some() {
+        submit_bundle_order(order, metrics, start.built()).await;
+    } else {
+        ORDER_EXECUTOR.dispatch(vec![order], metrics, start);
+    }
+
+    if let Some(max_hold) = *SNIPE_MAX_HOLD_SEC {
+        tokio::spawn(
+            exit_snipe(
+                target,
+                wallet,
+                buy_accounts,
+                metrics.clone(),
+                Duration::from_secs(max_hold),
+            )
+            .in_current_span(),
+        );
+    }
+}
+
+/// Sells a sniped position still held after `max_hold`, quoting against the live curve.
+async fn exit_snipe(
+    target: Pubkey,
+    wallet: &'static WalletSigner,
+    accounts: BuyInstructionAccounts,
+    metrics: Arc<MetricsCollection>,
+    max_hold: Duration,
+) {
+    sleep(max_hold).await;
+
+    // Already closed by copying the target's sell
+    if !WALLET_POOL
+        .holders(&accounts.mint)
+        .iter()
+        .any(|(holder, _)| holder.pubkey() == wallet.pubkey())
+    {
+        return;
+    }
+
+    let Some(token_amount) =
+        position_balance(&wallet.pubkey(), &accounts.mint, &accounts.associated_user).await
+    else {
+        return;
+    };
+
+    let Some(reserves) = curve_reserves(&accounts.bonding_curve)
+        .await
+        .filter(|reserves| !reserves.complete)
+    else {
+        info!(target: JOURNAL_SKIP,
+            "Bonding curve of {} is gone or complete, skipping snipe exit...",
+            accounts.mint
+        );
+        return;
+    };
+
+    info!(
+        "Held sniped {} for {}s, selling...",
+        accounts.mint,
+        max_hold.as_secs()
+    );
+
+    let sell_accounts = SellInstructionAccounts {
+        global: accounts.global,
+        fee_recipient: accounts.fee_recipient,
+        mint: accounts.mint,
+        bonding_curve: accounts.bonding_curve,
+        associated_bonding_curve: accounts.associated_bonding_curve,
+        associated_user: accounts.associated_user,
+        user: accounts.user,
+        system_program: accounts.system_program,
+        creator_vault: accounts.creator_vault,
+        token_program: accounts.token_program,
+        event_authority: accounts.event_authority,
+        program: accounts.program,
+    };
+
+    let (ixs, quoted_sol) = curve_sell_ixs(&sell_accounts, token_amount, &reserves);
+
+    let order = CopyOrder {
+        target,
+        wallet,
+        mint: accounts.mint,
+        bought_tokens: None,
+        quoted_sol,
+        ixs,
+    };
+
+    ORDER_EXECUTOR.dispatch(vec![order], &metrics, LatencyTrace::now());
+}
+
+/// Sells `token_amount` on the bonding curve at `SELL_SLIPPAGE` from `reserves`, and
+/// closes the emptied token account. Returns the instructions with the lamports the sell
+/// is quoted to pay out.
+pub fn curve_sell_ixs(
+    accounts: &SellInstructionAccounts,
+    token_amount: u64,
+    reserves: &CurveReserves,
+) -> (Vec<Instruction>, u64) {
+    let (virtual_sol_reserves, virtual_token_reserves) = CURVE_STATES.with_own_buys(
+        &accounts.mint,
+        reserves.virtual_sol_reserves,
+        reserves.virtual_token_reserves,
+    );
+
+    let fees = pumpfun_fees();
+
+    let min_sol_output = pumpfun_min_sol_output(
+        token_amount,
+        virtual_sol_reserves,
+        virtual_token_reserves,
+        &fees,
+        curve_slippage(
+            *SELL_SLIPPAGE,
+            bonding_curve_sol_quote(
+                token_amount,
+                virtual_sol_reserves,
+                virtual_token_reserves,
+                false,
+            ),
+            virtual_sol_reserves,
+            false,
+        ),
+    );
+
+    let quoted_sol = pumpfun_sell_output(
+        token_amount,
+        virtual_sol_reserves,
+        virtual_token_reserves,
+        &fees,
+    );
+
+    let ixs = vec![
+        accounts.get_sell_ix(Sell {
+            amount: token_amount,
+            min_sol_output,
+        }),
+        accounts.get_close_ata_ix(),
+    ];
+
+    (ixs, quoted_sol)
+}
+
+/// Reserves of `bonding_curve` from the streamed local state, fetched over RPC when the
+/// curve isn't streamed.
+async fn curve_reserves(bonding_curve: &Pubkey) -> Option<CurveReserves> {
+    if let Some(reserves) = CURVE_STATES.reserves(bonding_curve) {
+        return Some(reserves);
+    }
+
+    match RPC_CLIENT.get_account_data(bonding_curve).await {
+        Ok(data) => {
+            <BondingCurve as CarbonDeserialize>::deserialize(&data).map(|curve| CurveReserves {
+                virtual_sol_reserves: curve.virtual_sol_reserves,
+                virtual_token_reserves: curve.virtual_token_reserves,
+                complete: curve.complete,
+                slot: 0,
+            })
+        }
+        Err(e) => {
+            warn!("Failed to fetch bonding curve {}: {:?}", bonding_curve, e);
+            None
+        }
+    }
+}
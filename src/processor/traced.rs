@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use carbon_core::{
+    error::CarbonResult, instruction::InstructionProcessorInputType, metrics::MetricsCollection,
+    processor::Processor,
+};
+use std::sync::Arc;
+use tracing::Instrument;
+
+use crate::{
+    processor::target_wallet,
+    utils::{record_detection, trade_span},
+};
+
+/// Wraps an instruction processor so each call runs in the `trade` span of the target
+/// transaction, and counts the transaction as a detection for its target wallet.
+/// Orders dispatched from it carry the span to the execution workers.
+pub struct Traced<P> {
+    venue: &'static str,
+    inner: P,
+}
+
+impl<P> Traced<P> {
+    pub fn new(venue: &'static str, inner: P) -> Self {
+        Self { venue, inner }
+    }
+}
+
+#[async_trait]
+impl<P, T> Processor for Traced<P>
+where
+    P: Processor<InputType = InstructionProcessorInputType<T>> + Send + Sync,
+    T: Send + 'static,
+{
+    type InputType = InstructionProcessorInputType<T>;
+
+    async fn process(
+        &mut self,
+        data: Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let signature = data.0.transaction_metadata.signature;
+        let target = target_wallet(&data.0);
+
+        record_detection(&metrics, self.venue, &target, signature).await;
+
+        let span = trade_span(self.venue, &signature, &target);
+
+        self.inner.process(data, metrics).instrument(span).await
+    }
+}
@@ -3,13 +3,14 @@ use serde_json::json;
 use solana_program::example_mocks::solana_sdk::system_instruction;
 use solana_sdk::{
     compute_budget::ComputeBudgetInstruction, instruction::Instruction,
-    native_token::sol_to_lamports, pubkey::Pubkey,
+    native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
 };
 use std::time::{Duration, Instant};
 use tokio::time::sleep;
+use tracing::{info, warn};
 
 use crate::service::{
-    ping_all, ping_one, JitoEndpoint, JitoRegionsType, Tips, HEALTH_CHECK_SEC, JITO_MIN_TIP, JITO_REGIONS, JITO_TIP, PING_DURATION_SEC
+    ping_all, ping_one, JitoEndpoint, JitoRegionsType, Tips, JITO_MIN_TIP, JITO_REGIONS, JITO_TIP, PING_DURATION_SEC, sender_health, build_sender_client, endpoint_origin, spawn_keep_warm
 };
 
 #[derive(Debug)]
@@ -35,14 +36,11 @@ impl Jito {
         )
         .await
         {
-            println!("Ping failed during init: {}", err);
+            warn!("Ping failed during init: {}", err);
         }
 
         Self {
-            client: Client::builder()
-                .tcp_keepalive(Duration::from_secs(HEALTH_CHECK_SEC))
-                .build()
-                .expect("Failed to build Jito HTTP client"),
+            client: build_sender_client(),
             endpoint,
             auth_key,
         }
@@ -61,11 +59,11 @@ impl Jito {
         let endpoint = fastest_index
             .map(|i| JITO_REGIONS[i].clone())
             .unwrap_or_else(|| {
-                println!("All region pings failed, falling back to first region.");
+                warn!("All region pings failed, falling back to first region.");
                 JITO_REGIONS[0].clone()
             });
 
-        println!("Connecting with {} ...", endpoint.relayer_name);
+        info!("Connecting with {} ...", endpoint.relayer_name);
 
         // Optional: Ping chosen one again
         if let Err(err) = ping_one(
@@ -75,52 +73,52 @@ impl Jito {
         )
         .await
         {
-            println!("Ping failed during init: {}", err);
+            warn!("Ping failed during init: {}", err);
         }
 
         Self {
-            client: Client::builder()
-                .tcp_keepalive(Duration::from_secs(HEALTH_CHECK_SEC))
-                .build()
-                .expect("Failed to build HTTP client"),
+            client: build_sender_client(),
             endpoint,
             auth_key,
         }
     }
 
     pub fn health_check(&self, interval_sec: u64) {
-        // let client = self.client.clone();
-        // let endpoint = self.endpoint.clone();
-        // let relayer_name = endpoint.relayer_name.clone();
-        // let rpc_url = format!("https://{}", endpoint.ping_endpoint.clone());
-
-        // tokio::spawn(async move {
-        //     let payload = json!({
-        //         "jsonrpc": "2.0",
-        //         "id": 1,
-        //         "method": "getHealth"
-        //     });
-
-        //     loop {
-        //         match client.post(&rpc_url).json(&payload).send().await {
-        //             Ok(response) if response.status().is_success() => {
-        //                 println!("{} health check successful", relayer_name);
-        //             }
-        //             Ok(response) => {
-        //                 eprintln!(
-        //                     "{} health check failed with status: {}",
-        //                     relayer_name,
-        //                     response.status()
-        //                 );
-        //             }
-        //             Err(err) => {
-        //                 eprintln!("{} health check request error: {:?}", relayer_name, err);
-        //             }
-        //         }
-
-        //         sleep(Duration::from_secs(interval_sec)).await;
-        //     }
-        // });
+        let client = self.client.clone();
+        let endpoint = self.endpoint.clone();
+        let relayer_name = self.endpoint.relayer_name;
+        let health = sender_health("JITO").expect("Jito health not registered");
+
+        tokio::spawn(async move {
+            // Any HTTP response proves the relayer is reachable
+            let ping_url = format!("https://{}", endpoint.ping_endpoint);
+
+            loop {
+                match client.get(&ping_url).send().await {
+                    Ok(_) => {
+                        health.record_success(None);
+                    }
+                    Err(err) => {
+                        warn!("{} Health Check request error: {:?}", relayer_name, err);
+                        health.record_failure();
+                    }
+                }
+
+                sleep(Duration::from_secs(interval_sec)).await;
+            }
+        });
+    }
+
+    /// Keeps a pooled connection to the submit endpoint open between sends.
+    pub fn keep_warm(&self, interval_sec: u64) {
+        if let Some(origin) = endpoint_origin(self.endpoint.submit_endpoint) {
+            spawn_keep_warm(
+                self.client.clone(),
+                origin,
+                self.endpoint.relayer_name,
+                interval_sec,
+            );
+        }
     }
 
     pub fn add_tip_ix(&self, tip_config: Tips) -> Vec<Instruction> {
@@ -144,7 +142,7 @@ impl Jito {
         let transfer_ix = system_instruction::transfer(
             &tip_config.payer,
             &recipient,
-            sol_to_lamports(relayer_fee),
+            (relayer_fee * LAMPORTS_PER_SOL as f64) as u64,
         );
         ixs.push(transfer_ix);
 
@@ -154,7 +152,7 @@ impl Jito {
     pub async fn send_transaction(&self, encoded_tx: &str) -> anyhow::Result<serde_json::Value> {
         let start = Instant::now();
 
-        let url = format!("{}", self.endpoint.submit_endpoint);
+        let url = self.endpoint.submit_endpoint;
 
         let payload = json!({
             "jsonrpc": "2.0",
@@ -194,7 +192,28 @@ impl Jito {
             parts.push("0µs".to_string()); // fallback if literally nothing
         }
 
-        println!("Transaction submission took: {}", parts.join(" : "));
+        info!("Transaction submission took: {}", parts.join(" : "));
+
+        Ok(data)
+    }
+
+    /// Submits the encoded transactions as one atomic bundle through `sendBundle`.
+    pub async fn send_bundle(&self, encoded_txs: &[String]) -> anyhow::Result<serde_json::Value> {
+        let url = self
+            .endpoint
+            .submit_endpoint
+            .replace("/transactions", "/bundles");
+
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendBundle",
+            "params": [encoded_txs, {"encoding": "base64"}]
+        });
+
+        let response = self.client.post(url).json(&payload).send().await?;
+
+        let data: serde_json::Value = response.json().await?;
 
         Ok(data)
     }
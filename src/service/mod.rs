@@ -1,8 +1,12 @@
-pub mod nozomi;
-pub mod utils;
-pub mod zero_slot;
-pub mod jito;
-pub use nozomi::*;
-pub use utils::*;
-pub use zero_slot::*;
-pub use jito::*;
+pub mod nozomi;
+pub mod utils;
+pub mod zero_slot;
+pub mod jito;
+pub mod outcome;
+pub mod sender;
+pub use nozomi::*;
+pub use utils::*;
+pub use zero_slot::*;
+pub use jito::*;
+pub use outcome::*;
+pub use sender::*;
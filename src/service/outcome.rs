@@ -0,0 +1,128 @@
+use solana_sdk::signature::Signature;
+use std::{fmt, time::Duration};
+
+use crate::{service::STUB_SENDER, utils::TxCosts};
+
+/// Why a submission didn't go through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SendErrorKind {
+    /// The pre-send simulation failed, so the transaction was never sent
+    SimulationFailed,
+    /// A signer refused or failed to sign, e.g. a Ledger the trade was rejected on
+    SigningFailed(String),
+    /// The latest blockhash is older than `BLOCKHASH_MAX_AGE_MS`
+    StaleBlockhash,
+    /// Every configured sender has an open circuit
+    CircuitOpen,
+    UnknownService,
+    /// The sender didn't answer within `SENDER_TIMEOUT_MS`
+    Timeout,
+    /// The request never got a response, e.g. a connection error
+    Transport(String),
+    /// The sender answered with a JSON-RPC error
+    Rejected(String),
+}
+
+impl SendErrorKind {
+    /// Whether the send failed before reaching a sender, so no other sender would do better.
+    pub fn is_local(&self) -> bool {
+        matches!(
+            self,
+            Self::SimulationFailed | Self::SigningFailed(_) | Self::StaleBlockhash
+        )
+    }
+}
+
+impl fmt::Display for SendErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SimulationFailed => write!(f, "simulation failed"),
+            Self::SigningFailed(message) => write!(f, "signing failed: {}", message),
+            Self::StaleBlockhash => write!(f, "blockhash too old"),
+            Self::CircuitOpen => write!(f, "all sender circuits are open"),
+            Self::UnknownService => write!(f, "unknown confirmation service"),
+            Self::Timeout => write!(f, "send timed out"),
+            Self::Transport(message) => write!(f, "transport error: {}", message),
+            Self::Rejected(message) => write!(f, "rejected: {}", message),
+        }
+    }
+}
+
+/// Result of submitting a transaction (or bundle) through one sender.
+#[derive(Debug, Clone)]
+pub struct SendOutcome {
+    /// Sender the transaction went through, e.g. `NOZOMI` or `JITO_BUNDLE`
+    pub sender: String,
+    /// Signature of the signed transaction, `None` when it was never signed
+    pub signature: Option<Signature>,
+    /// Time from submission to the sender's response
+    pub latency: Duration,
+    pub error: Option<SendErrorKind>,
+    /// Tips and fees the transaction pays once it lands
+    pub costs: TxCosts,
+}
+
+impl SendOutcome {
+    /// An outcome of a send that failed before anything was submitted.
+    pub fn failed(sender: &str, error: SendErrorKind) -> Self {
+        Self {
+            sender: sender.to_string(),
+            signature: None,
+            latency: Duration::ZERO,
+            error: Some(error),
+            costs: TxCosts::default(),
+        }
+    }
+
+    /// An outcome of a send stubbed out by a replay, accepted but never signed.
+    pub fn stubbed() -> Self {
+        Self {
+            sender: STUB_SENDER.to_string(),
+            signature: None,
+            latency: Duration::ZERO,
+            error: None,
+            costs: TxCosts::default(),
+        }
+    }
+
+    /// An outcome of a send held back by `SHADOW_MODE`, signed and simulated but never
+    /// submitted.
+    pub fn shadowed(sender: &str, signature: Option<Signature>) -> Self {
+        Self {
+            sender: sender.to_string(),
+            signature,
+            latency: Duration::ZERO,
+            error: None,
+            costs: TxCosts::default(),
+        }
+    }
+
+    /// Whether the sender accepted the transaction.
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Whether the send failed before reaching a sender.
+    pub fn is_local_failure(&self) -> bool {
+        self.error.as_ref().is_some_and(SendErrorKind::is_local)
+    }
+
+    pub fn latency_ms(&self) -> u64 {
+        self.latency.as_millis() as u64
+    }
+}
+
+impl fmt::Display for SendOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.error {
+            None => write!(f, "{} accepted", self.sender)?,
+            Some(error) => write!(f, "{} failed: {}", self.sender, error)?,
+        }
+
+        if let Some(signature) = &self.signature {
+            write!(f, ", signature {}", signature)?;
+        }
+
+        write!(f, " ({}ms)", self.latency_ms())
+    }
+}
@@ -0,0 +1,293 @@
+use carbon_core::metrics::MetricsCollection;
+use solana_sdk::{
+    instruction::Instruction, signature::Signature, signer::Signer,
+    transaction::VersionedTransaction,
+};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use tokio::time::{error::Elapsed, timeout};
+use tracing::{info, warn};
+
+use crate::{
+    config::{
+        CONFIRM_SERVICE, FEE_PAYER, FEE_PAYER_PUBKEY, JITO_CLIENT, LAUNCH_BUNDLE_TIP_SOL,
+        NOZOMI_CLIENT, PRIORITY_FEE, SENDER_FAILOVER, SENDER_TIMEOUT_MS, SHADOW_MODE,
+        ZSLOT_CLIENT,
+    },
+    service::{
+        SendErrorKind, SendOutcome, Tips, record_send_metrics, sender_health, track_landing,
+    },
+    utils::{TxCosts, build_and_simulate, fresh_blockhash, record_stage},
+    wallet::{WalletSigner, paper_trading},
+};
+
+/// Set for a journal replay: every send is reported as accepted by `STUB_SENDER`
+/// without being signed or submitted.
+static SENDERS_STUBBED: AtomicBool = AtomicBool::new(false);
+
+/// Sender reported by the stubbed sends of a replay.
+pub const STUB_SENDER: &str = "STUB";
+
+/// Stubs every sender out for the rest of the run, see `SENDERS_STUBBED`.
+pub fn stub_senders() {
+    SENDERS_STUBBED.store(true, Ordering::Relaxed);
+}
+
+pub fn senders_stubbed() -> bool {
+    SENDERS_STUBBED.load(Ordering::Relaxed)
+}
+
+/// Tips, signs and submits `raw_instructions` through the configured `CONFIRM_SERVICE`,
+/// with `wallet` signing as the trading wallet. When the primary sender is unhealthy or
+/// errors, the services listed in `SENDER_FAILOVER` are tried in order. Submission
+/// latency and landing of every attempt are recorded per sender in `metrics`.
+pub async fn send_with_service(
+    raw_instructions: Vec<Instruction>,
+    wallet: &WalletSigner,
+    metrics: &Arc<MetricsCollection>,
+) -> SendOutcome {
+    if senders_stubbed() {
+        return SendOutcome::stubbed();
+    }
+
+    // Senders with an open circuit are skipped entirely
+    let services: Vec<&str> = std::iter::once(CONFIRM_SERVICE.as_str())
+        .chain(SENDER_FAILOVER.iter().map(String::as_str))
+        .filter(|service| sender_health(service).is_none_or(|health| !health.is_circuit_open()))
+        .collect();
+
+    // Prefer healthy senders but never drop the trade when all of them look unhealthy
+    let (mut ordered, unhealthy): (Vec<&str>, Vec<&str>) = services
+        .into_iter()
+        .partition(|service| sender_health(service).is_none_or(|health| health.is_healthy()));
+    ordered.extend(unhealthy);
+
+    let mut outcome = SendOutcome::failed(CONFIRM_SERVICE.as_str(), SendErrorKind::CircuitOpen);
+
+    for service in ordered {
+        outcome = send_via(service, raw_instructions.clone(), wallet, metrics).await;
+
+        // A shadowed send never reached the sender, it says nothing about its health
+        if !outcome.is_local_failure() && !*SHADOW_MODE && !paper_trading() {
+            if let Some(health) = sender_health(service) {
+                if outcome.is_success() {
+                    health.record_success(Some(outcome.latency_ms()));
+                } else {
+                    health.record_failure();
+                }
+            }
+
+            record_send_metrics(metrics, &outcome).await;
+
+            if outcome.is_success() {
+                track_landing(metrics.clone(), &outcome);
+            }
+        }
+
+        // A failed simulation or stale blockhash would fail on every sender, so don't fail over on it
+        if outcome.is_success() || outcome.is_local_failure() {
+            break;
+        }
+
+        warn!("{}, failing over", outcome);
+    }
+
+    outcome
+}
+
+/// Tips, signs and submits `raw_instructions` through a single sender `service`. The
+/// sign (including simulation, when enabled) and send stages are recorded in `metrics`.
+/// In `SHADOW_MODE` or `PAPER_TRADING` the signed transaction is reported as accepted
+/// without being sent.
+pub async fn send_via(
+    service: &str,
+    raw_instructions: Vec<Instruction>,
+    wallet: &WalletSigner,
+    metrics: &MetricsCollection,
+) -> SendOutcome {
+    let (cu, priority_fee_micro_lamport, third_party_fee) = *PRIORITY_FEE;
+
+    let tips = Tips {
+        cu: Some(cu),
+        priority_fee_micro_lamport: Some(priority_fee_micro_lamport),
+        payer: *FEE_PAYER_PUBKEY,
+        pure_ix: raw_instructions,
+        tip_addr_idx: 1,
+        tip_sol_amount: third_party_fee,
+    };
+
+    let signers: [&(dyn Signer + Sync); 2] = [&*FEE_PAYER, wallet];
+    let send_timeout = Duration::from_millis(*SENDER_TIMEOUT_MS);
+
+    let ixs = match service {
+        "NOZOMI" => NOZOMI_CLIENT
+            .get()
+            .expect("Nozomi client not initialized")
+            .add_tip_ix(tips),
+        "ZERO_SLOT" => ZSLOT_CLIENT
+            .get()
+            .expect("ZSlot client not initialized")
+            .add_tip_ix(tips),
+        "JITO" => JITO_CLIENT
+            .get()
+            .expect("Jito client not initialized")
+            .add_tip_ix(tips),
+        _ => return SendOutcome::failed(service, SendErrorKind::UnknownService),
+    };
+
+    let costs = TxCosts::of(&ixs, signatures(wallet), cu, priority_fee_micro_lamport);
+
+    let Some(recent_blockhash) = fresh_blockhash() else {
+        return SendOutcome::failed(service, SendErrorKind::StaleBlockhash);
+    };
+
+    let sign_start = Instant::now();
+
+    let encoded_tx = match build_and_simulate(ixs, recent_blockhash, &signers).await {
+        Ok(encoded_tx) => encoded_tx,
+        Err(kind) => return SendOutcome::failed(service, kind),
+    };
+
+    record_stage(metrics, "sign", sign_start.elapsed()).await;
+
+    if *SHADOW_MODE || paper_trading() {
+        info!("Not submitting through {}, shadow or paper trading", service);
+        return SendOutcome::shadowed(service, transaction_signature(&encoded_tx));
+    }
+
+    let send_start = Instant::now();
+
+    let result = match service {
+        "NOZOMI" => {
+            let nozomi = NOZOMI_CLIENT.get().expect("Nozomi client not initialized");
+            timeout(send_timeout, nozomi.send_transaction(&encoded_tx)).await
+        }
+        "ZERO_SLOT" => {
+            let zero_slot = ZSLOT_CLIENT.get().expect("ZSlot client not initialized");
+            timeout(send_timeout, zero_slot.send_transaction(&encoded_tx)).await
+        }
+        _ => {
+            let jito = JITO_CLIENT.get().expect("Jito client not initialized");
+            timeout(send_timeout, jito.send_transaction(&encoded_tx)).await
+        }
+    };
+
+    let latency = send_start.elapsed();
+
+    record_stage(metrics, "send", latency).await;
+
+    SendOutcome {
+        sender: service.to_string(),
+        signature: transaction_signature(&encoded_tx),
+        latency,
+        error: send_error(result),
+        costs,
+    }
+}
+
+/// Tips, signs and submits `raw_instructions` as a Jito bundle, so the copy lands
+/// atomically in the next slot the block engine can reach or not at all.
+pub async fn send_bundle_with_jito(
+    raw_instructions: Vec<Instruction>,
+    wallet: &WalletSigner,
+    metrics: &Arc<MetricsCollection>,
+) -> SendOutcome {
+    if senders_stubbed() {
+        return SendOutcome::stubbed();
+    }
+
+    let (cu, priority_fee_micro_lamport, third_party_fee) = *PRIORITY_FEE;
+
+    let jito = JITO_CLIENT.get().expect("Jito client not initialized");
+
+    let ixs = jito.add_tip_ix(Tips {
+        cu: Some(cu),
+        priority_fee_micro_lamport: Some(priority_fee_micro_lamport),
+        payer: *FEE_PAYER_PUBKEY,
+        pure_ix: raw_instructions,
+        tip_addr_idx: 1,
+        tip_sol_amount: LAUNCH_BUNDLE_TIP_SOL.unwrap_or(third_party_fee),
+    });
+
+    let costs = TxCosts::of(&ixs, signatures(wallet), cu, priority_fee_micro_lamport);
+
+    let signers: [&(dyn Signer + Sync); 2] = [&*FEE_PAYER, wallet];
+
+    let Some(recent_blockhash) = fresh_blockhash() else {
+        return SendOutcome::failed(JITO_BUNDLE, SendErrorKind::StaleBlockhash);
+    };
+
+    let sign_start = Instant::now();
+
+    let encoded_tx = match build_and_simulate(ixs, recent_blockhash, &signers).await {
+        Ok(encoded_tx) => encoded_tx,
+        Err(kind) => return SendOutcome::failed(JITO_BUNDLE, kind),
+    };
+
+    record_stage(metrics, "sign", sign_start.elapsed()).await;
+
+    if *SHADOW_MODE || paper_trading() {
+        info!("Not submitting the bundle, shadow or paper trading");
+        return SendOutcome::shadowed(JITO_BUNDLE, transaction_signature(&encoded_tx));
+    }
+
+    let start = Instant::now();
+
+    let result = timeout(
+        Duration::from_millis(*SENDER_TIMEOUT_MS),
+        jito.send_bundle(std::slice::from_ref(&encoded_tx)),
+    )
+    .await;
+
+    let latency = start.elapsed();
+
+    record_stage(metrics, "send", latency).await;
+
+    // The bundle response carries the bundle id, so track the transaction itself
+    let outcome = SendOutcome {
+        sender: JITO_BUNDLE.to_string(),
+        signature: transaction_signature(&encoded_tx),
+        latency,
+        error: send_error(result),
+        costs,
+    };
+
+    record_send_metrics(metrics, &outcome).await;
+
+    if outcome.is_success() {
+        track_landing(metrics.clone(), &outcome);
+    }
+
+    outcome
+}
+
+/// Sender name bundle submissions are recorded under
+const JITO_BUNDLE: &str = "JITO_BUNDLE";
+
+/// Signatures a transaction `wallet` trades in carries, one when it pays its own fees
+fn signatures(wallet: &WalletSigner) -> u64 {
+    if wallet.pubkey() == *FEE_PAYER_PUBKEY { 1 } else { 2 }
+}
+
+fn transaction_signature(encoded_tx: &str) -> Option<Signature> {
+    let bytes = base64_simd::STANDARD.decode_to_vec(encoded_tx).ok()?;
+    let transaction: VersionedTransaction = bincode::deserialize(&bytes).ok()?;
+
+    transaction.signatures.first().copied()
+}
+
+/// Classifies a sender's response, `None` when it accepted the transaction.
+fn send_error(result: Result<anyhow::Result<serde_json::Value>, Elapsed>) -> Option<SendErrorKind> {
+    match result {
+        Ok(Ok(data)) => data
+            .get("error")
+            .map(|error| SendErrorKind::Rejected(error.to_string())),
+        Ok(Err(err)) => Some(SendErrorKind::Transport(err.to_string())),
+        Err(_) => Some(SendErrorKind::Timeout),
+    }
+}
@@ -0,0 +1,89 @@
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU32, AtomicU64, Ordering},
+};
+
+use crate::config::{SENDER_CIRCUIT_COOLDOWN_SEC, SENDER_MAX_FAILURES, SENDER_SLOW_MS};
+
+pub const SENDER_SERVICES: [&str; 3] = ["NOZOMI", "ZERO_SLOT", "JITO"];
+
+/// Rolling health of a sender service, fed by both sends and periodic health checks.
+/// After `SENDER_MAX_FAILURES` consecutive failures its circuit opens and the sender is
+/// skipped for `SENDER_CIRCUIT_COOLDOWN_SEC` before being tried again.
+#[derive(Debug, Default)]
+pub struct SenderHealth {
+    consecutive_failures: AtomicU32,
+    last_latency_ms: AtomicU64,
+    circuit_open_until_ms: AtomicU64,
+    submitted: AtomicU64,
+    landed: AtomicU64,
+}
+
+pub static SENDER_HEALTH: Lazy<HashMap<&'static str, SenderHealth>> = Lazy::new(|| {
+    SENDER_SERVICES
+        .iter()
+        .map(|service| (*service, SenderHealth::default()))
+        .collect()
+});
+
+pub fn sender_health(service: &str) -> Option<&'static SenderHealth> {
+    SENDER_HEALTH.get(service)
+}
+
+impl SenderHealth {
+    pub fn record_success(&self, latency_ms: Option<u64>) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+
+        if let Some(latency_ms) = latency_ms {
+            self.last_latency_ms.store(latency_ms, Ordering::Relaxed);
+            self.submitted.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn record_landed(&self) {
+        self.landed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Share of accepted submissions that landed on chain.
+    pub fn land_rate(&self) -> f64 {
+        let submitted = self.submitted.load(Ordering::Relaxed);
+
+        if submitted == 0 {
+            return 0.0;
+        }
+
+        self.landed.load(Ordering::Relaxed) as f64 / submitted as f64
+    }
+
+    pub fn record_failure(&self) {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+
+        if failures >= *SENDER_MAX_FAILURES {
+            let open_until =
+                Utc::now().timestamp_millis() as u64 + *SENDER_CIRCUIT_COOLDOWN_SEC * 1000;
+            self.circuit_open_until_ms
+                .store(open_until, Ordering::Relaxed);
+        }
+    }
+
+    /// Whether the circuit is open; once the cooldown passes the sender gets a trial send.
+    pub fn is_circuit_open(&self) -> bool {
+        Utc::now().timestamp_millis() as u64 <= self.circuit_open_until_ms.load(Ordering::Relaxed)
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures.load(Ordering::Relaxed)
+    }
+
+    pub fn last_latency_ms(&self) -> u64 {
+        self.last_latency_ms.load(Ordering::Relaxed)
+    }
+
+    /// Healthy while it isn't erroring repeatedly and its last send wasn't slow.
+    pub fn is_healthy(&self) -> bool {
+        self.consecutive_failures() < *SENDER_MAX_FAILURES
+            && self.last_latency_ms() <= *SENDER_SLOW_MS
+    }
+}
@@ -0,0 +1,131 @@
+use carbon_core::metrics::MetricsCollection;
+use std::sync::Arc;
+use tokio::time::{Duration, Instant, sleep};
+
+use crate::{
+    config::{LAND_TIMEOUT_SEC, RPC_CLIENT},
+    service::{SendOutcome, sender_health},
+    utils::{FEE_LEDGER, current_slot},
+};
+
+/// Records the outcome of a single submission.
+pub async fn record_send_metrics(metrics: &MetricsCollection, outcome: &SendOutcome) {
+    let service = outcome.sender.to_lowercase();
+
+    metrics
+        .record_histogram(
+            &format!("sender_{}_submit_latency_ms", service),
+            outcome.latency_ms() as f64,
+        )
+        .await
+        .ok();
+
+    let counter = if outcome.is_success() {
+        "submitted"
+    } else {
+        "send_errors"
+    };
+    metrics
+        .increment_counter(&format!("sender_{}_{}", service, counter), 1)
+        .await
+        .ok();
+}
+
+/// Polls the signature status of a submitted transaction in the background and records
+/// whether (and in which slot) it landed for its sender, and the tips and fees it paid
+/// when it did.
+pub fn track_landing(metrics: Arc<MetricsCollection>, outcome: &SendOutcome) {
+    let Some(signature) = outcome.signature else {
+        return;
+    };
+    let service = outcome.sender.clone();
+    let costs = outcome.costs;
+
+    let sent_slot = current_slot();
+
+    tokio::spawn(async move {
+        let health = sender_health(&service);
+        let sender = service.clone();
+        let service = service.to_lowercase();
+        let deadline = Instant::now() + Duration::from_secs(*LAND_TIMEOUT_SEC);
+
+        while Instant::now() < deadline {
+            let status = RPC_CLIENT
+                .get_signature_statuses(&[signature])
+                .await
+                .ok()
+                .and_then(|response| response.value.into_iter().next().flatten());
+
+            if let Some(status) = status {
+                if let Some(health) = health {
+                    health.record_landed();
+                }
+
+                metrics
+                    .increment_counter(&format!("sender_{}_landed", service), 1)
+                    .await
+                    .ok();
+                metrics
+                    .update_gauge(
+                        &format!("sender_{}_landed_slot", service),
+                        status.slot as f64,
+                    )
+                    .await
+                    .ok();
+                metrics
+                    .record_histogram(
+                        &format!("sender_{}_slots_to_land", service),
+                        status.slot.saturating_sub(sent_slot) as f64,
+                    )
+                    .await
+                    .ok();
+
+                if status.err.is_some() {
+                    metrics
+                        .increment_counter(&format!("sender_{}_landed_failed", service), 1)
+                        .await
+                        .ok();
+                }
+
+                FEE_LEDGER.record(&sender, costs, status.err.is_some());
+
+                let tip = if status.err.is_some() { 0 } else { costs.tip };
+
+                metrics
+                    .increment_counter(&format!("sender_{}_tip_lamports", service), tip)
+                    .await
+                    .ok();
+                metrics
+                    .increment_counter(
+                        &format!("sender_{}_fee_lamports", service),
+                        costs.priority_fee + costs.base_fee,
+                    )
+                    .await
+                    .ok();
+
+                if let Some(health) = health {
+                    metrics
+                        .update_gauge(&format!("sender_{}_land_rate", service), health.land_rate())
+                        .await
+                        .ok();
+                }
+
+                return;
+            }
+
+            sleep(Duration::from_millis(400)).await;
+        }
+
+        metrics
+            .increment_counter(&format!("sender_{}_not_landed", service), 1)
+            .await
+            .ok();
+
+        if let Some(health) = health {
+            metrics
+                .update_gauge(&format!("sender_{}_land_rate", service), health.land_rate())
+                .await
+                .ok();
+        }
+    });
+}
@@ -1,6 +1,12 @@
-pub mod ping;
-pub mod services;
-pub mod tip;
-pub use ping::*;
-pub use services::*;
-pub use tip::*;
\ No newline at end of file
+pub mod health;
+pub mod metrics;
+pub mod ping;
+pub mod services;
+pub mod tip;
+pub mod warm;
+pub use health::*;
+pub use metrics::*;
+pub use ping::*;
+pub use services::*;
+pub use tip::*;
+pub use warm::*;
\ No newline at end of file
@@ -0,0 +1,45 @@
+use reqwest::{Client, Url};
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::warn;
+
+use crate::service::HEALTH_CHECK_SEC;
+
+/// HTTP client shared by the senders: idle connections are never dropped from the pool
+/// and HTTP/2 connections are pinged so the first send after a quiet period is warm.
+pub fn build_sender_client() -> Client {
+    Client::builder()
+        .tcp_keepalive(Duration::from_secs(HEALTH_CHECK_SEC))
+        .tcp_nodelay(true)
+        .pool_idle_timeout(None)
+        .pool_max_idle_per_host(4)
+        .http2_keep_alive_interval(Some(Duration::from_secs(HEALTH_CHECK_SEC)))
+        .http2_keep_alive_while_idle(true)
+        .build()
+        .expect("Failed to build HTTP client")
+}
+
+/// Scheme and host of a submit endpoint, e.g. `https://ny.0slot.trade`.
+pub fn endpoint_origin(submit_endpoint: &str) -> Option<String> {
+    Url::parse(submit_endpoint)
+        .ok()
+        .map(|url| url.origin().ascii_serialization())
+}
+
+/// Periodically issues a no-op request to `url` through `client`, keeping a pooled
+/// connection to the sender open. Response status is irrelevant.
+pub fn spawn_keep_warm(client: Client, url: String, relayer_name: &'static str, interval_sec: u64) {
+    if interval_sec == 0 {
+        return;
+    }
+
+    tokio::spawn(async move {
+        loop {
+            if let Err(err) = client.head(&url).send().await {
+                warn!("{} keep-warm request error: {:?}", relayer_name, err);
+            }
+
+            sleep(Duration::from_secs(interval_sec)).await;
+        }
+    });
+}
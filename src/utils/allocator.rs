@@ -0,0 +1,89 @@
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+#[cfg(all(feature = "mimalloc", feature = "jemalloc"))]
+compile_error!("The `mimalloc` and `jemalloc` features are mutually exclusive");
+
+/// Allocator the binary is built with: mimalloc or jemalloc when their feature is
+/// enabled, the system allocator otherwise.
+#[cfg(feature = "mimalloc")]
+pub type BaseAllocator = mimalloc::MiMalloc;
+#[cfg(feature = "mimalloc")]
+pub const BASE_ALLOCATOR: BaseAllocator = mimalloc::MiMalloc;
+
+#[cfg(feature = "jemalloc")]
+pub type BaseAllocator = tikv_jemallocator::Jemalloc;
+#[cfg(feature = "jemalloc")]
+pub const BASE_ALLOCATOR: BaseAllocator = tikv_jemallocator::Jemalloc;
+
+#[cfg(not(any(feature = "mimalloc", feature = "jemalloc")))]
+pub type BaseAllocator = std::alloc::System;
+#[cfg(not(any(feature = "mimalloc", feature = "jemalloc")))]
+pub const BASE_ALLOCATOR: BaseAllocator = std::alloc::System;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Global allocator wrapper counting every allocation and the bytes requested, for
+/// the `alloc-profiling` feature. Reallocations count as a new allocation.
+pub struct CountingAllocator<A> {
+    inner: A,
+}
+
+impl<A> CountingAllocator<A> {
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation(layout.size());
+        unsafe { self.inner.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation(layout.size());
+        unsafe { self.inner.alloc_zeroed(layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation(new_size);
+        unsafe { self.inner.realloc(ptr, layout, new_size) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { self.inner.dealloc(ptr, layout) }
+    }
+}
+
+fn count_allocation(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+}
+
+/// Allocations made through a `CountingAllocator` since the process started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocationStats {
+    pub allocations: u64,
+    pub bytes: u64,
+}
+
+impl AllocationStats {
+    pub fn now() -> Self {
+        Self {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Allocations made since `earlier`.
+    pub fn since(&self, earlier: &Self) -> Self {
+        Self {
+            allocations: self.allocations.saturating_sub(earlier.allocations),
+            bytes: self.bytes.saturating_sub(earlier.bytes),
+        }
+    }
+}
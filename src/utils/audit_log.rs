@@ -0,0 +1,185 @@
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use solana_sdk::signature::Signature;
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    sync::mpsc::{self, Sender},
+    thread,
+};
+use tracing::{info, warn};
+
+use crate::config::AUDIT_LOG_PATH;
+
+/// `prev_hash` of the first entry
+pub const AUDIT_GENESIS_HASH: &str =
+    "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Queue of the audit log writer thread, `None` with `AUDIT_LOG_PATH` unset. Records are
+/// the fields of an entry before it's chained.
+static AUDIT_LOG: Lazy<Option<Sender<Value>>> = Lazy::new(|| {
+    let path = AUDIT_LOG_PATH.clone()?;
+    let (sender, receiver) = mpsc::channel::<Value>();
+
+    thread::Builder::new()
+        .name("audit-log".to_string())
+        .spawn(move || {
+            let (mut seq, mut prev_hash) = audit_log_head(&path);
+
+            let mut file = match OpenOptions::new().create(true).append(true).open(&path) {
+                Ok(file) => file,
+                Err(e) => {
+                    warn!("ALERT: Failed to open audit log {}: {}", path, e);
+                    return;
+                }
+            };
+
+            info!(
+                "Appending signed transactions and control calls to audit log {}",
+                path
+            );
+
+            for record in receiver {
+                let entry = audit_entry(seq, &prev_hash, record);
+                let line = format!("{}\n", entry);
+
+                // Synced per entry, so what was written survives a crash
+                if let Err(e) = file
+                    .write_all(line.as_bytes())
+                    .and_then(|_| file.sync_data())
+                {
+                    warn!("ALERT: Failed to write audit log {}: {}", path, e);
+                    continue;
+                }
+
+                seq += 1;
+                prev_hash = entry["hash"].as_str().unwrap_or_default().to_string();
+            }
+        })
+        .expect("Failed to spawn the audit log thread");
+
+    Some(sender)
+});
+
+/// Appends a signed transaction to the audit log at `AUDIT_LOG_PATH`, if set. Entries
+/// are JSON lines with the wire transaction, each hashed together with the hash of the
+/// one before, so an entry edited or removed afterwards breaks the chain from there on,
+/// see `verify_audit_log`. Writes happen on a dedicated thread.
+pub fn audit_signed_transaction(signature: Signature, transaction: &str) {
+    append_audit_record(json!({
+        "timestamp_ms": Utc::now().timestamp_millis(),
+        "signature": signature.to_string(),
+        "transaction": transaction,
+    }));
+}
+
+/// Appends a call to a control surface to the audit log, if set: the `surface` it came
+/// through, the `caller`, the `action` asked for and its `outcome`.
+pub fn audit_control_call(surface: &str, caller: &str, action: &str, outcome: &str) {
+    append_audit_record(json!({
+        "timestamp_ms": Utc::now().timestamp_millis(),
+        "control": {
+            "surface": surface,
+            "caller": caller,
+            "action": action,
+            "outcome": outcome,
+        },
+    }));
+}
+
+fn append_audit_record(record: Value) {
+    if let Some(audit_log) = AUDIT_LOG.as_ref() {
+        let _ = audit_log.send(record);
+    }
+}
+
+fn audit_entry(seq: u64, prev_hash: &str, mut entry: Value) -> Value {
+    entry["seq"] = seq.into();
+    entry["prev_hash"] = prev_hash.into();
+    entry["hash"] = audit_hash(&entry).into();
+    entry
+}
+
+/// SHA-256 of an entry without its `hash`, as lowercase hex. The entry's `prev_hash`
+/// is part of it, which chains the entries.
+pub fn audit_hash(entry: &Value) -> String {
+    let mut entry = entry.clone();
+
+    if let Some(fields) = entry.as_object_mut() {
+        fields.remove("hash");
+    }
+
+    Sha256::digest(entry.to_string().as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Sequence number and hash the next entry of the audit log at `path` continues from.
+fn audit_log_head(path: &str) -> (u64, String) {
+    let last = fs::read_to_string(path).ok().and_then(|content| {
+        content
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .and_then(|line| serde_json::from_str::<Value>(line).ok())
+    });
+
+    match last {
+        Some(entry) => (
+            entry["seq"].as_u64().unwrap_or_default() + 1,
+            entry["hash"]
+                .as_str()
+                .unwrap_or(AUDIT_GENESIS_HASH)
+                .to_string(),
+        ),
+        None => (0, AUDIT_GENESIS_HASH.to_string()),
+    }
+}
+
+/// Checks the hash chain of an audit log's `content`. Returns the number of entries,
+/// or which one first breaks the chain and how.
+pub fn verify_audit_log(content: &str) -> Result<usize, String> {
+    let mut prev_hash = AUDIT_GENESIS_HASH.to_string();
+    let mut entries = 0;
+
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let line_number = index + 1;
+        let entry: Value = serde_json::from_str(line)
+            .map_err(|e| format!("line {} isn't a JSON entry: {}", line_number, e))?;
+
+        if entry["seq"].as_u64() != Some(entries as u64) {
+            return Err(format!(
+                "line {} has seq {}, expected {}",
+                line_number, entry["seq"], entries
+            ));
+        }
+
+        if entry["prev_hash"].as_str() != Some(prev_hash.as_str()) {
+            return Err(format!(
+                "line {} doesn't follow the entry before it",
+                line_number
+            ));
+        }
+
+        let hash = audit_hash(&entry);
+
+        if entry["hash"].as_str() != Some(hash.as_str()) {
+            return Err(format!(
+                "line {} was modified, its hash doesn't match",
+                line_number
+            ));
+        }
+
+        prev_hash = hash;
+        entries += 1;
+    }
+
+    Ok(entries)
+}
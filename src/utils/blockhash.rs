@@ -1,39 +1,156 @@
+use once_cell::sync::Lazy;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{commitment_config::CommitmentConfig, hash::Hash};
-use std::sync::Arc;
-use tokio::time::{Duration, sleep};
+use std::{
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Instant,
+};
+use tokio::{
+    sync::Notify,
+    time::{Duration, sleep, timeout},
+};
+use tracing::warn;
 
-use once_cell::sync::Lazy;
-use std::sync::Mutex;
+use crate::config::{BLOCKHASH_MAX_AGE_MS, BLOCKHASH_POLL_MS, BLOCKHASH_REFRESH_SLOTS};
+
+/// A blockhash along with the slot and time it was fetched at.
+#[derive(Debug, Clone, Copy)]
+pub struct RecentBlockhash {
+    pub hash: Hash,
+    pub slot: u64,
+    pub fetched_at: Instant,
+}
 
-static GLOBAL_CURRENT_HASH: Lazy<Mutex<Hash>> = Lazy::new(|| Mutex::new(Hash::default()));
+impl RecentBlockhash {
+    pub fn age(&self) -> Duration {
+        self.fetched_at.elapsed()
+    }
 
-fn set_slot(value: Hash) {
-    let mut slot = GLOBAL_CURRENT_HASH.lock().unwrap();
-    *slot = value;
+    /// Slots the chain tip has advanced since the hash was fetched.
+    pub fn slot_age(&self) -> u64 {
+        current_slot().saturating_sub(self.slot)
+    }
 }
 
-pub fn get_slot() -> Hash {
-    let slot = GLOBAL_CURRENT_HASH.lock().unwrap();
-    *slot
+/// Tracks the chain tip and keeps a recent blockhash, refetching it every
+/// `BLOCKHASH_REFRESH_SLOTS` slots as slots are observed from the datasources.
+pub struct BlockhashService {
+    latest: RwLock<Option<RecentBlockhash>>,
+    slot: AtomicU64,
+    refresh: Notify,
 }
 
-/// Continuously retries fetching the latest blockhash until successful.
-pub async fn recent_blockhash_handler(rpc_client: Arc<RpcClient>) {
-    loop {
+pub static BLOCKHASH_SERVICE: Lazy<BlockhashService> = Lazy::new(BlockhashService::new);
+
+impl Default for BlockhashService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlockhashService {
+    pub fn new() -> Self {
+        Self {
+            latest: RwLock::new(None),
+            slot: AtomicU64::new(0),
+            refresh: Notify::new(),
+        }
+    }
+
+    pub fn latest(&self) -> Option<RecentBlockhash> {
+        *self.latest.read().unwrap()
+    }
+
+    /// The latest blockhash, `None` when none was fetched yet or it is older than
+    /// `BLOCKHASH_MAX_AGE_MS`, in which case nothing should be signed with it.
+    pub fn fresh(&self) -> Option<Hash> {
+        self.latest()
+            .filter(|blockhash| blockhash.age() <= Duration::from_millis(*BLOCKHASH_MAX_AGE_MS))
+            .map(|blockhash| blockhash.hash)
+    }
+
+    /// Advances the tracked chain tip, waking the refresher once the blockhash is
+    /// `BLOCKHASH_REFRESH_SLOTS` behind it.
+    pub fn observe_slot(&self, slot: u64) {
+        if self.slot.fetch_max(slot, Ordering::Relaxed) >= slot {
+            return;
+        }
+
+        let due = self
+            .latest()
+            .is_none_or(|blockhash| slot >= blockhash.slot + *BLOCKHASH_REFRESH_SLOTS);
+
+        if due {
+            self.refresh.notify_one();
+        }
+    }
+
+    pub fn current_slot(&self) -> u64 {
+        self.slot.load(Ordering::Relaxed)
+    }
+
+    async fn refresh(&self, rpc_client: &RpcClient) {
         match rpc_client
             .get_latest_blockhash_with_commitment(CommitmentConfig::processed())
             .await
         {
-            Ok((latest_blockhash, _)) => {
-                set_slot(latest_blockhash);
-                break; // Exit the loop once we get a valid blockhash
+            Ok((hash, _)) => {
+                *self.latest.write().unwrap() = Some(RecentBlockhash {
+                    hash,
+                    slot: self.current_slot(),
+                    fetched_at: Instant::now(),
+                });
             }
             Err(e) => {
+                warn!("Failed to fetch the latest blockhash: {}", e);
                 sleep(Duration::from_millis(200)).await;
             }
         }
     }
+}
 
-    sleep(Duration::from_millis(500)).await;
-}
\ No newline at end of file
+/// The latest blockhash with its age, whether or not it is still fresh.
+pub fn latest_blockhash() -> Option<RecentBlockhash> {
+    BLOCKHASH_SERVICE.latest()
+}
+
+/// The blockhash to sign with, `None` while it is too old to send.
+pub fn fresh_blockhash() -> Option<Hash> {
+    BLOCKHASH_SERVICE.fresh()
+}
+
+/// Advances the tracked chain tip if `slot` is newer than what we've seen.
+pub fn observe_slot(slot: u64) {
+    BLOCKHASH_SERVICE.observe_slot(slot);
+}
+
+/// Highest slot seen from the RPC or any processed transaction.
+pub fn current_slot() -> u64 {
+    BLOCKHASH_SERVICE.current_slot()
+}
+
+/// Refreshes the blockhash as observed slots advance. When no slots arrive for
+/// `BLOCKHASH_POLL_MS`, the slot is polled from the RPC instead so the hash never
+/// goes stale just because the datasources are quiet.
+pub async fn blockhash_handler(rpc_client: Arc<RpcClient>) {
+    loop {
+        let notified = timeout(
+            Duration::from_millis(*BLOCKHASH_POLL_MS),
+            BLOCKHASH_SERVICE.refresh.notified(),
+        )
+        .await;
+
+        if notified.is_err()
+            && let Ok(slot) = rpc_client
+                .get_slot_with_commitment(CommitmentConfig::processed())
+                .await
+        {
+            BLOCKHASH_SERVICE.slot.fetch_max(slot, Ordering::Relaxed);
+        }
+
+        BLOCKHASH_SERVICE.refresh(&rpc_client).await;
+    }
+}
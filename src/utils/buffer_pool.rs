@@ -0,0 +1,48 @@
+use base64_simd::{Out, STANDARD};
+use solana_sdk::transaction::VersionedTransaction;
+use std::cell::RefCell;
+
+thread_local! {
+    /// Per-thread scratch buffers for serializing and base64 encoding transactions.
+    /// Signing never awaits, so a buffer is never held across a task switch.
+    static TX_BUFFERS: RefCell<TxBuffers> = RefCell::new(TxBuffers::default());
+}
+
+/// Buffers kept above this size are released after use, so one oversized
+/// transaction doesn't pin its memory for the rest of the run
+const TX_BUFFER_RETAIN: usize = 4 * 1024;
+
+#[derive(Default)]
+struct TxBuffers {
+    serialized: Vec<u8>,
+    encoded: Vec<u8>,
+}
+
+/// Serializes `transaction` with bincode and base64 encodes it, reusing this thread's
+/// scratch buffers so only the returned string is allocated.
+pub fn encode_transaction(transaction: &VersionedTransaction) -> bincode::Result<String> {
+    TX_BUFFERS.with(|buffers| {
+        let mut buffers = buffers.borrow_mut();
+        let TxBuffers {
+            serialized,
+            encoded,
+        } = &mut *buffers;
+
+        serialized.clear();
+        bincode::serialize_into(&mut *serialized, transaction)?;
+
+        // base64-simd picks AVX2, SSE4.1 or NEON at runtime, with a scalar fallback
+        encoded.resize(STANDARD.encoded_length(serialized.len()), 0);
+        let result = STANDARD
+            .encode_as_str(serialized, Out::from_slice(encoded))
+            .to_owned();
+
+        for buffer in [serialized, encoded] {
+            if buffer.capacity() > TX_BUFFER_RETAIN {
+                *buffer = Vec::new();
+            }
+        }
+
+        Ok(result)
+    })
+}
@@ -1,29 +1,54 @@
 use solana_sdk::{
     hash::Hash,
     instruction::Instruction,
-    message::{VersionedMessage, v0::Message},
+    message::VersionedMessage,
+    pubkey::Pubkey,
+    signer::{Signer, SignerError},
     transaction::VersionedTransaction,
 };
 
-use crate::config::{PRIVATE_KEY, PUBKEY};
+use crate::utils::{
+    audit_signed_transaction, compile_message, encode_transaction, guard_instructions,
+};
 
-pub fn build_and_sign(
+/// Compiles and signs a v0 transaction. The first signer pays the fees; the same
+/// key may appear more than once (e.g. when the fee payer is the trading wallet).
+/// Messages are compiled from the transaction template cache. Every transaction signed
+/// goes to the audit log. Fails when a signer can't sign, e.g. one on a device the
+/// transaction was rejected on, or the instructions don't pass `check_instructions`.
+pub fn build_and_sign<S: Signer + ?Sized>(
     mut ixs: Vec<Instruction>,
     recent_blockhash: Hash,
     nonce_ix: Option<Instruction>,
-) -> String {
+    signers: &[&S],
+) -> Result<String, SignerError> {
     // If there's a nonce instruction, insert it at the start of the instruction list
     if let Some(nonce_instruction) = nonce_ix {
         ixs.insert(0, nonce_instruction);
     }
 
-    let message = Message::try_compile(&PUBKEY, &ixs, &[], recent_blockhash)
-        .expect("Failed to compile message");
+    let payer = signers
+        .first()
+        .expect("At least one signer is required")
+        .pubkey();
+
+    let signer_pubkeys: Vec<Pubkey> = signers.iter().map(|signer| signer.pubkey()).collect();
+    guard_instructions(&ixs, &signer_pubkeys).map_err(SignerError::Custom)?;
+
+    let mut unique_signers: Vec<&S> = Vec::with_capacity(signers.len());
+    for signer in signers {
+        if !unique_signers.iter().any(|s| s.pubkey() == signer.pubkey()) {
+            unique_signers.push(signer);
+        }
+    }
+
+    let message = compile_message(&payer, &ixs, recent_blockhash);
     let versioned_message = VersionedMessage::V0(message);
-    let txn = VersionedTransaction::try_new(versioned_message, &[&PRIVATE_KEY])
-        .expect("Failed to create transaction");
+    let txn = VersionedTransaction::try_new(versioned_message, unique_signers.as_slice())?;
+
+    let encoded_tx = encode_transaction(&txn).expect("Failed to serialize transaction");
 
-    let serialized_tx = bincode::serialize(&txn).expect("Failed to serialize transaction");
+    audit_signed_transaction(txn.signatures[0], &encoded_tx);
 
-    bs64::encode(&serialized_tx)
+    Ok(encoded_tx)
 }
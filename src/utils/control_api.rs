@@ -0,0 +1,434 @@
+use axum::{
+    Json, Router,
+    extract::{
+        Request, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::{Method, StatusCode, header::AUTHORIZATION},
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Response},
+    routing::{get, post},
+};
+use carbon_core::metrics::MetricsCollection;
+use serde_json::{Value, json};
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signer::Signer};
+use std::{str::FromStr, sync::Arc};
+use tokio::{net::TcpListener, sync::broadcast::error::RecvError};
+use tracing::{info, warn};
+
+use crate::{
+    config::{
+        BUY_SLIPPAGE, BUY_SOL_AMOUNT, BUY_SPLIT_THRESHOLD, CONFIRM_SERVICE, CONTROL_API_READ_TOKEN,
+        CONTROL_API_TOKEN, DYNAMIC_SLIPPAGE, EXECUTION_BREAKER_FAILURES, FOLLOWED_VENUES,
+        PAUSE_ON_SLOT_LAG, RPC_CLIENT, SELL_SLIPPAGE, SENDER_FAILOVER, SIMULATE_BEFORE_SEND,
+        SNIPE_MODE, SNIPE_SOL_AMOUNT, TARGET_WALLET,
+    },
+    datasource::{DATASOURCE_STATUS, FOLLOWED_TARGETS},
+    processor::{buy_mint, manual_sell_mint},
+    service::{SENDER_SERVICES, sender_health},
+    utils::{
+        ControlDenied, ControlScope, EXECUTION_BREAKER, FEE_LEDGER, FeeTotals, TARGET_METRICS,
+        admit_control_call, recent_trade_events, subscribe_trade_events, target_label,
+        token_caller,
+    },
+    wallet::{LOW_BALANCE, RotationMode, WALLET_POOL, rotate_wallet},
+};
+
+/// The dashboard page, a single file with no dependencies
+const DASHBOARD_HTML: &str = include_str!("dashboard.html");
+
+type ApiResult = Result<Json<Value>, (StatusCode, Json<Value>)>;
+
+fn api_error(status: StatusCode, message: impl ToString) -> (StatusCode, Json<Value>) {
+    (status, Json(json!({ "error": message.to_string() })))
+}
+
+fn parse_pubkey(body: &Value, field: &str) -> Result<Pubkey, (StatusCode, Json<Value>)> {
+    body[field]
+        .as_str()
+        .and_then(|value| Pubkey::from_str(value).ok())
+        .ok_or_else(|| {
+            api_error(
+                StatusCode::BAD_REQUEST,
+                format!("`{}` must be a base58 public key", field),
+            )
+        })
+}
+
+/// Serves the JSON control API on `addr` for dashboards and scripts:
+///
+/// - `GET /` for the web dashboard, built on the endpoints below
+/// - `GET /positions`, `GET /pnl`, `GET /fees`, `GET /config`, `GET /status` and
+///   `GET /senders` to read the bot
+/// - `GET /events` for the latest copy, exit and error events, and a WebSocket on
+///   `GET /events/ws` streaming new ones
+/// - `POST /pause` and `POST /resume` to stop and restart buying
+/// - `POST /buy {"mint", "sol_amount"?, "confirm_code"?}` and
+///   `POST /sell {"mint", "confirm_code"?}` to trade a pump.fun curve by hand. One over
+///   `MANUAL_CONFIRM_ABOVE_SOL` fails with the code to send it again with
+/// - `GET /target` for the followed wallets and `PUT /target {"wallet"}` to follow only
+///   another one
+/// - `POST /wallets/rotate {"wallet", "new_wallet", "mode"?}` to rotate a trading wallet
+///   out for another key or signer, see `rotate_wallet`
+///
+/// Every request but the dashboard page needs `Authorization: Bearer <CONTROL_API_TOKEN>`
+/// when it is set, or a `token` query parameter since browsers can't set headers on a
+/// WebSocket. `CONTROL_API_READ_TOKEN` may only `GET`. Requests are rate limited and
+/// audited, see `admit_control_call`.
+pub async fn control_api(addr: String) {
+    // Manual trades aren't copies of a target trade, so their metrics aren't collected
+    let metrics = Arc::new(MetricsCollection::new(vec![]));
+
+    let app = Router::new()
+        .route("/positions", get(positions))
+        .route("/pnl", get(pnl))
+        .route("/fees", get(fees))
+        .route("/config", get(config))
+        .route("/status", get(status))
+        .route("/senders", get(senders))
+        .route("/events", get(events))
+        .route("/events/ws", get(events_ws))
+        .route("/pause", post(pause))
+        .route("/resume", post(resume))
+        .route("/buy", post(buy))
+        .route("/sell", post(sell))
+        .route("/target", get(target).put(set_target))
+        .route("/wallets/rotate", post(rotate))
+        .layer(middleware::from_fn(authorize))
+        // The page holds no data, it asks for the token to call the API
+        .route("/", get(|| async { Html(DASHBOARD_HTML) }))
+        .with_state(metrics);
+
+    let listener = match TcpListener::bind(&addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            warn!("Failed to start the control API on {}: {}", addr, e);
+            return;
+        }
+    };
+
+    if CONTROL_API_TOKEN.is_none() && CONTROL_API_READ_TOKEN.is_none() {
+        warn!("CONTROL_API_TOKEN is not set, the control API accepts any request");
+    }
+
+    info!("Control API listening on {}", addr);
+
+    if let Err(e) = axum::serve(listener, app).await {
+        warn!("Control API stopped: {}", e);
+    }
+}
+
+async fn authorize(request: Request, next: Next) -> Response {
+    let bearer = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or_else(|| {
+            request
+                .uri()
+                .query()?
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("token="))
+        });
+
+    let caller = token_caller(bearer);
+    let needs = match *request.method() {
+        Method::GET => ControlScope::Read,
+        _ => ControlScope::Trade,
+    };
+    let action = format!("{} {}", request.method(), request.uri().path());
+
+    match admit_control_call(
+        "api",
+        caller.map_or("unknown", |(caller, _)| caller),
+        caller.map(|(_, scope)| scope),
+        needs,
+        &action,
+    ) {
+        Ok(()) => next.run(request).await,
+        Err(denied) => {
+            let status = match denied {
+                ControlDenied::Unauthenticated => StatusCode::UNAUTHORIZED,
+                ControlDenied::Forbidden => StatusCode::FORBIDDEN,
+                ControlDenied::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            };
+
+            api_error(status, denied.as_str()).into_response()
+        }
+    }
+}
+
+/// Compares without returning early, so response times don't leak the token.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+async fn positions() -> Json<Value> {
+    let positions: Vec<Value> = WALLET_POOL
+        .positions()
+        .into_iter()
+        .map(|(wallet, mint, tokens)| {
+            json!({
+                "wallet": wallet.to_string(),
+                "mint": mint.to_string(),
+                "tokens": tokens,
+            })
+        })
+        .collect();
+
+    Json(json!(positions))
+}
+
+async fn pnl() -> Json<Value> {
+    let targets: Vec<Value> = TARGET_METRICS
+        .all()
+        .into_iter()
+        .map(|(target, stats)| {
+            json!({
+                "target": target.to_string(),
+                "label": target_label(&target),
+                "detections": stats.detections,
+                "copies_sent": stats.copies_sent,
+                "copies_failed": stats.copies_failed,
+                "landed": stats.landed,
+                "not_landed": stats.not_landed,
+                "land_rate": stats.land_rate(),
+                "sol_flow_lamports": stats.sol_flow,
+            })
+        })
+        .collect();
+
+    Json(json!(targets))
+}
+
+/// Tips and fees paid per sender, since the start and per UTC day.
+async fn fees() -> Json<Value> {
+    let totals = |totals: FeeTotals| {
+        json!({
+            "transactions": totals.transactions,
+            "tip_lamports": totals.tips,
+            "priority_fee_lamports": totals.priority_fees,
+            "base_fee_lamports": totals.base_fees,
+            "fee_payer_paid_lamports": totals.fee_payer_paid,
+        })
+    };
+
+    let senders: Vec<Value> = FEE_LEDGER
+        .senders()
+        .into_iter()
+        .map(|(sender, sender_totals)| json!({ "sender": sender, "totals": totals(sender_totals) }))
+        .collect();
+
+    let days: Vec<Value> = FEE_LEDGER
+        .days()
+        .into_iter()
+        .map(|(day, sender, day_totals)| {
+            json!({
+                "day": day.to_string(),
+                "sender": sender,
+                "totals": totals(day_totals),
+            })
+        })
+        .collect();
+
+    Json(json!({
+        "total_lamports": FEE_LEDGER.total(),
+        "fee_payer_paid_lamports": FEE_LEDGER.fee_payer_paid(),
+        "senders": senders,
+        "days": days,
+    }))
+}
+
+/// Trading settings, without any key or credential.
+async fn config() -> Json<Value> {
+    Json(json!({
+        "target_wallet": TARGET_WALLET.to_string(),
+        "followed_targets": followed_targets(),
+        "followed_venues": *FOLLOWED_VENUES,
+        "trading_wallets": WALLET_POOL
+            .wallets()
+            .iter()
+            .map(|wallet| wallet.pubkey().to_string())
+            .collect::<Vec<_>>(),
+        "confirm_service": *CONFIRM_SERVICE,
+        "sender_failover": *SENDER_FAILOVER,
+        "buy_sol_amount": *BUY_SOL_AMOUNT as f64 / LAMPORTS_PER_SOL as f64,
+        "buy_split_threshold_sol": BUY_SPLIT_THRESHOLD
+            .map(|threshold| threshold as f64 / LAMPORTS_PER_SOL as f64),
+        "buy_slippage": *BUY_SLIPPAGE,
+        "sell_slippage": *SELL_SLIPPAGE,
+        "dynamic_slippage": *DYNAMIC_SLIPPAGE,
+        "simulate_before_send": *SIMULATE_BEFORE_SEND,
+        "snipe_mode": *SNIPE_MODE,
+        "snipe_sol_amount": *SNIPE_SOL_AMOUNT as f64 / LAMPORTS_PER_SOL as f64,
+        "pause_on_slot_lag": *PAUSE_ON_SLOT_LAG,
+        "execution_breaker_failures": *EXECUTION_BREAKER_FAILURES,
+    }))
+}
+
+async fn status() -> Json<Value> {
+    Json(json!({
+        "buying_paused": EXECUTION_BREAKER.is_tripped(),
+        "low_balance_halt": LOW_BALANCE.is_halted(),
+        "land_failures": EXECUTION_BREAKER.land_failures(),
+        "simulation_failures": EXECUTION_BREAKER.simulation_failures(),
+        "followed_targets": followed_targets(),
+        "datasources": DATASOURCE_STATUS.connected(),
+    }))
+}
+
+async fn senders() -> Json<Value> {
+    let senders: Vec<Value> = SENDER_SERVICES
+        .iter()
+        .filter_map(|service| {
+            let health = sender_health(service)?;
+
+            Some(json!({
+                "service": service,
+                "healthy": health.is_healthy(),
+                "circuit_open": health.is_circuit_open(),
+                "consecutive_failures": health.consecutive_failures(),
+                "last_latency_ms": health.last_latency_ms(),
+                "land_rate": health.land_rate(),
+            }))
+        })
+        .collect();
+
+    Json(json!(senders))
+}
+
+async fn events() -> Json<Value> {
+    let events: Vec<Value> = recent_trade_events()
+        .iter()
+        .map(|event| event.to_json())
+        .collect();
+
+    Json(json!(events))
+}
+
+async fn events_ws(ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(stream_events)
+}
+
+/// Sends every new event as a JSON text message until the client goes away.
+async fn stream_events(mut socket: WebSocket) {
+    let mut events = subscribe_trade_events();
+
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            // A slow client skips what it missed rather than holding the others back
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => return,
+        };
+
+        if socket
+            .send(Message::Text(event.to_json().to_string()))
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+async fn pause() -> Json<Value> {
+    Json(json!({ "paused": EXECUTION_BREAKER.pause() }))
+}
+
+async fn resume() -> Json<Value> {
+    Json(json!({ "resumed": EXECUTION_BREAKER.resume() }))
+}
+
+async fn buy(State(metrics): State<Arc<MetricsCollection>>, Json(body): Json<Value>) -> ApiResult {
+    let mint = parse_pubkey(&body, "mint")?;
+
+    let sol_amount = match &body["sol_amount"] {
+        Value::Null => *BUY_SOL_AMOUNT,
+        value => value
+            .as_f64()
+            .filter(|sol| *sol > 0.0)
+            .map(|sol| (sol * LAMPORTS_PER_SOL as f64) as u64)
+            .ok_or_else(|| {
+                api_error(
+                    StatusCode::BAD_REQUEST,
+                    "`sol_amount` must be a positive number",
+                )
+            })?,
+    };
+
+    let wallet = buy_mint(mint, sol_amount, body["confirm_code"].as_str(), &metrics)
+        .await
+        .map_err(|reason| api_error(StatusCode::UNPROCESSABLE_ENTITY, reason))?;
+
+    Ok(Json(json!({
+        "mint": mint.to_string(),
+        "wallet": wallet.to_string(),
+        "sol_amount": sol_amount as f64 / LAMPORTS_PER_SOL as f64,
+    })))
+}
+
+async fn sell(State(metrics): State<Arc<MetricsCollection>>, Json(body): Json<Value>) -> ApiResult {
+    let mint = parse_pubkey(&body, "mint")?;
+
+    let wallets = manual_sell_mint(mint, body["confirm_code"].as_str(), &metrics)
+        .await
+        .map_err(|reason| api_error(StatusCode::UNPROCESSABLE_ENTITY, reason))?;
+
+    Ok(Json(
+        json!({ "mint": mint.to_string(), "wallets": wallets }),
+    ))
+}
+
+fn followed_targets() -> Vec<String> {
+    FOLLOWED_TARGETS
+        .all()
+        .iter()
+        .map(Pubkey::to_string)
+        .collect()
+}
+
+async fn target() -> Json<Value> {
+    Json(json!({ "wallets": followed_targets() }))
+}
+
+/// Follows only another wallet. Only the gRPC datasources pick it up live, WebSocket,
+/// RPC polling and backfill keep the targets followed at startup until a restart.
+async fn set_target(Json(body): Json<Value>) -> ApiResult {
+    let wallet = parse_pubkey(&body, "wallet")?;
+
+    Ok(Json(json!({
+        "wallet": wallet.to_string(),
+        "changed": FOLLOWED_TARGETS.set(wallet),
+    })))
+}
+
+/// Rotates `wallet` out for `new_wallet`, an entry as `TRADING_WALLETS` takes one.
+/// `mode` is `drain`, the default, or `transfer`.
+async fn rotate(Json(body): Json<Value>) -> ApiResult {
+    let wallet = parse_pubkey(&body, "wallet")?;
+
+    let new_entry = body["new_wallet"]
+        .as_str()
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .ok_or_else(|| api_error(StatusCode::BAD_REQUEST, "`new_wallet` must be set"))?;
+
+    let mode = match body["mode"].as_str() {
+        None => RotationMode::Drain,
+        Some(mode) => mode
+            .parse::<RotationMode>()
+            .map_err(|e| api_error(StatusCode::BAD_REQUEST, e))?,
+    };
+
+    let new_wallet = rotate_wallet(RPC_CLIENT.clone(), wallet, new_entry.to_string(), mode)
+        .await
+        .map_err(|reason| api_error(StatusCode::UNPROCESSABLE_ENTITY, reason))?;
+
+    Ok(Json(json!({
+        "wallet": wallet.to_string(),
+        "new_wallet": new_wallet.to_string(),
+        "mode": format!("{:?}", mode).to_lowercase(),
+    })))
+}
@@ -0,0 +1,149 @@
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tracing::{debug, info, warn};
+
+use crate::{
+    config::{CONTROL_API_READ_TOKEN, CONTROL_API_TOKEN, CONTROL_RATE_LIMIT_PER_MIN},
+    utils::{audit_control_call, control_api::constant_time_eq},
+};
+
+/// What a caller of the control API, gRPC service or Telegram bot may do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ControlScope {
+    /// Positions, PnL, status and events
+    Read,
+    /// Also pausing, trading, changing targets and rotating wallets
+    Trade,
+}
+
+/// Why a control call was turned down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlDenied {
+    /// No token, or one that matches none
+    Unauthenticated,
+    /// The caller may only read
+    Forbidden,
+    /// Over `CONTROL_RATE_LIMIT_PER_MIN`
+    RateLimited,
+}
+
+impl ControlDenied {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unauthenticated => "missing or invalid bearer token",
+            Self::Forbidden => "read-only callers can't make this call",
+            Self::RateLimited => "too many control calls, try again in a minute",
+        }
+    }
+}
+
+/// Calls per caller over fixed windows.
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    calls: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Counts a call of `caller`, false once it made `limit` in the current window.
+    pub fn allow(&self, caller: &str) -> bool {
+        let now = Instant::now();
+        let mut calls = self.calls.lock().unwrap();
+
+        calls.retain(|_, (started, _)| now.duration_since(*started) < self.window);
+
+        let (_, count) = calls.entry(caller.to_string()).or_insert((now, 0));
+
+        if *count >= self.limit {
+            return false;
+        }
+
+        *count += 1;
+        true
+    }
+}
+
+static CONTROL_RATE_LIMITER: Lazy<RateLimiter> =
+    Lazy::new(|| RateLimiter::new(*CONTROL_RATE_LIMIT_PER_MIN, Duration::from_secs(60)));
+
+/// The caller a bearer token stands for and its scope: `CONTROL_API_TOKEN` may trade,
+/// `CONTROL_API_READ_TOKEN` only read. Anyone may trade when neither is set, `None` for
+/// a token that matches neither.
+pub fn token_caller(bearer: Option<&str>) -> Option<(&'static str, ControlScope)> {
+    let tokens = [
+        (
+            CONTROL_API_TOKEN.as_deref(),
+            "trade token",
+            ControlScope::Trade,
+        ),
+        (
+            CONTROL_API_READ_TOKEN.as_deref(),
+            "read token",
+            ControlScope::Read,
+        ),
+    ];
+
+    if tokens.iter().all(|(token, _, _)| token.is_none()) {
+        return Some(("anonymous", ControlScope::Trade));
+    }
+
+    let bearer = bearer?;
+
+    tokens.into_iter().find_map(|(token, caller, scope)| {
+        token
+            .filter(|token| constant_time_eq(bearer.as_bytes(), token.as_bytes()))
+            .map(|_| (caller, scope))
+    })
+}
+
+/// Admits a control `action` that `needs` a scope, from `caller` holding `scope` (`None`
+/// when unauthenticated), within `CONTROL_RATE_LIMIT_PER_MIN`; unauthenticated calls
+/// share one limit. Every call is logged and goes to the audit log, admitted or not.
+pub fn admit_control_call(
+    surface: &str,
+    caller: &str,
+    scope: Option<ControlScope>,
+    needs: ControlScope,
+    action: &str,
+) -> Result<(), ControlDenied> {
+    let admitted = match scope {
+        _ if !CONTROL_RATE_LIMITER.allow(caller) => Err(ControlDenied::RateLimited),
+        None => Err(ControlDenied::Unauthenticated),
+        Some(scope) if scope < needs => Err(ControlDenied::Forbidden),
+        Some(_) => Ok(()),
+    };
+
+    let outcome = match admitted {
+        Ok(()) => "admitted",
+        Err(denied) => denied.as_str(),
+    };
+
+    match (admitted, needs) {
+        (Err(_), _) => warn!(
+            "Turned down {} control call {} from {}: {}",
+            surface, action, caller, outcome
+        ),
+        (Ok(()), ControlScope::Trade) => {
+            info!("{} control call {} from {}", surface, action, caller)
+        }
+        (Ok(()), ControlScope::Read) => {
+            debug!("{} control call {} from {}", surface, action, caller)
+        }
+    }
+
+    audit_control_call(surface, caller, action, outcome);
+
+    admitted
+}
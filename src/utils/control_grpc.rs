@@ -0,0 +1,288 @@
+use carbon_core::metrics::MetricsCollection;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+use std::{net::SocketAddr, pin::Pin, str::FromStr, sync::Arc};
+use tokio_stream::{
+    Stream, StreamExt,
+    wrappers::{BroadcastStream, errors::BroadcastStreamRecvError},
+};
+use tonic::{Request, Response, Status, transport::Server};
+use tracing::{info, warn};
+
+use crate::{
+    config::{BUY_SOL_AMOUNT, CONTROL_API_READ_TOKEN, CONTROL_API_TOKEN},
+    datasource::{DATASOURCE_STATUS, FOLLOWED_TARGETS},
+    processor::{buy_mint, manual_sell_mint},
+    utils::{
+        ControlDenied, ControlScope, EXECUTION_BREAKER, TradeEvent, admit_control_call,
+        subscribe_trade_events, token_caller,
+    },
+    wallet::WALLET_POOL,
+};
+
+#[allow(clippy::all)]
+pub mod control_proto {
+    tonic::include_proto!("control");
+}
+
+use control_proto::{
+    BuyReply, BuyRequest, Empty, Position, Positions, SellReply, SellRequest, SetTargetRequest,
+    StreamEventsRequest, Toggled,
+    control_server::{Control, ControlServer},
+};
+
+type EventStream = Pin<Box<dyn Stream<Item = Result<control_proto::TradeEvent, Status>> + Send>>;
+
+/// Serves the `Control` gRPC service of `proto/control.proto` on `addr`: a live stream
+/// of copy, exit and error events, plus the REST control API's methods. Every call
+/// needs `authorization: Bearer <CONTROL_API_TOKEN>` metadata when it is set;
+/// `CONTROL_API_READ_TOKEN` may only stream events and read. Calls are rate limited and
+/// audited, see `admit_control_call`.
+pub async fn control_grpc(addr: String) {
+    let socket_addr = match SocketAddr::from_str(&addr) {
+        Ok(socket_addr) => socket_addr,
+        Err(e) => {
+            warn!("Invalid CONTROL_GRPC_ADDR {}: {}", addr, e);
+            return;
+        }
+    };
+
+    if CONTROL_API_TOKEN.is_none() && CONTROL_API_READ_TOKEN.is_none() {
+        warn!("CONTROL_API_TOKEN is not set, the gRPC control service accepts any call");
+    }
+
+    let service = ControlService {
+        // Manual trades aren't copies of a target trade, so their metrics aren't collected
+        metrics: Arc::new(MetricsCollection::new(vec![])),
+    };
+
+    info!("gRPC control service listening on {}", addr);
+
+    if let Err(e) = Server::builder()
+        .add_service(ControlServer::with_interceptor(service, authorize))
+        .serve(socket_addr)
+        .await
+    {
+        warn!("gRPC control service stopped: {}", e);
+    }
+}
+
+/// The caller of a call's bearer token, `None` when it matches no token
+#[derive(Clone, Copy)]
+struct Caller(Option<(&'static str, ControlScope)>);
+
+// tonic's interceptor and handler signatures fix the `Status` error
+#[allow(clippy::result_large_err)]
+fn authorize(mut request: Request<()>) -> Result<Request<()>, Status> {
+    let bearer = request
+        .metadata()
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    let caller = Caller(token_caller(bearer));
+
+    // Checked by each method, which knows what the call does
+    request.extensions_mut().insert(caller);
+
+    Ok(request)
+}
+
+/// Admits the call `action` when its caller's scope covers `needs`.
+#[allow(clippy::result_large_err)]
+fn admit<T>(request: &Request<T>, action: &str, needs: ControlScope) -> Result<(), Status> {
+    let Caller(caller) = request
+        .extensions()
+        .get::<Caller>()
+        .copied()
+        .unwrap_or(Caller(None));
+
+    admit_control_call(
+        "grpc",
+        caller.map_or("unknown", |(caller, _)| caller),
+        caller.map(|(_, scope)| scope),
+        needs,
+        action,
+    )
+    .map_err(|denied| match denied {
+        ControlDenied::Unauthenticated => Status::unauthenticated(denied.as_str()),
+        ControlDenied::Forbidden => Status::permission_denied(denied.as_str()),
+        ControlDenied::RateLimited => Status::resource_exhausted(denied.as_str()),
+    })
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_pubkey(value: &str, field: &str) -> Result<Pubkey, Status> {
+    Pubkey::from_str(value)
+        .map_err(|_| Status::invalid_argument(format!("`{}` must be a base58 public key", field)))
+}
+
+impl From<TradeEvent> for control_proto::TradeEvent {
+    fn from(event: TradeEvent) -> Self {
+        Self {
+            kind: event.kind.as_str().to_string(),
+            timestamp_ms: event.timestamp_ms,
+            wallet: event.wallet.map(|wallet| wallet.to_string()),
+            mint: event.mint.map(|mint| mint.to_string()),
+            sol_lamports: event.sol_lamports,
+            tokens: event.tokens,
+            pnl_lamports: event.pnl_lamports,
+            signature: event.signature.map(|signature| signature.to_string()),
+            message: event.message,
+        }
+    }
+}
+
+struct ControlService {
+    metrics: Arc<MetricsCollection>,
+}
+
+#[tonic::async_trait]
+impl Control for ControlService {
+    type StreamEventsStream = EventStream;
+
+    async fn stream_events(
+        &self,
+        request: Request<StreamEventsRequest>,
+    ) -> Result<Response<EventStream>, Status> {
+        admit(&request, "StreamEvents", ControlScope::Read)?;
+
+        let kinds: Vec<String> = request
+            .into_inner()
+            .kinds
+            .iter()
+            .map(|kind| kind.trim().to_lowercase())
+            .collect();
+
+        let stream = BroadcastStream::new(subscribe_trade_events()).filter_map(move |event| {
+            match event {
+                Ok(event) if kinds.is_empty() || event.kind.is_enabled(&kinds) => {
+                    Some(Ok(event.into()))
+                }
+                Ok(_) => None,
+                // A slow client skips what it missed rather than holding the others back
+                Err(BroadcastStreamRecvError::Lagged(missed)) => {
+                    warn!(
+                        "gRPC event stream client fell behind, {} events dropped",
+                        missed
+                    );
+                    None
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn get_positions(&self, request: Request<Empty>) -> Result<Response<Positions>, Status> {
+        admit(&request, "GetPositions", ControlScope::Read)?;
+
+        let positions = WALLET_POOL
+            .positions()
+            .into_iter()
+            .map(|(wallet, mint, tokens)| Position {
+                wallet: wallet.to_string(),
+                mint: mint.to_string(),
+                tokens,
+            })
+            .collect();
+
+        Ok(Response::new(Positions { positions }))
+    }
+
+    async fn get_status(
+        &self,
+        request: Request<Empty>,
+    ) -> Result<Response<control_proto::Status>, Status> {
+        admit(&request, "GetStatus", ControlScope::Read)?;
+
+        Ok(Response::new(control_proto::Status {
+            buying_paused: EXECUTION_BREAKER.is_tripped(),
+            land_failures: EXECUTION_BREAKER.land_failures(),
+            simulation_failures: EXECUTION_BREAKER.simulation_failures(),
+            followed_targets: FOLLOWED_TARGETS
+                .all()
+                .iter()
+                .map(Pubkey::to_string)
+                .collect(),
+            datasources: DATASOURCE_STATUS.connected(),
+        }))
+    }
+
+    async fn pause(&self, request: Request<Empty>) -> Result<Response<Toggled>, Status> {
+        admit(&request, "Pause", ControlScope::Trade)?;
+
+        Ok(Response::new(Toggled {
+            changed: EXECUTION_BREAKER.pause(),
+        }))
+    }
+
+    async fn resume(&self, request: Request<Empty>) -> Result<Response<Toggled>, Status> {
+        admit(&request, "Resume", ControlScope::Trade)?;
+
+        Ok(Response::new(Toggled {
+            changed: EXECUTION_BREAKER.resume(),
+        }))
+    }
+
+    async fn buy(&self, request: Request<BuyRequest>) -> Result<Response<BuyReply>, Status> {
+        admit(&request, "Buy", ControlScope::Trade)?;
+
+        let request = request.into_inner();
+        let mint = parse_pubkey(&request.mint, "mint")?;
+
+        let sol_amount = match request.sol_amount {
+            None => *BUY_SOL_AMOUNT,
+            Some(sol) if sol > 0.0 => (sol * LAMPORTS_PER_SOL as f64) as u64,
+            Some(_) => {
+                return Err(Status::invalid_argument(
+                    "`sol_amount` must be a positive number",
+                ));
+            }
+        };
+
+        let wallet = buy_mint(
+            mint,
+            sol_amount,
+            request.confirm_code.as_deref(),
+            &self.metrics,
+        )
+        .await
+        .map_err(Status::failed_precondition)?;
+
+        Ok(Response::new(BuyReply {
+            wallet: wallet.to_string(),
+            sol_amount: sol_amount as f64 / LAMPORTS_PER_SOL as f64,
+        }))
+    }
+
+    async fn sell(&self, request: Request<SellRequest>) -> Result<Response<SellReply>, Status> {
+        admit(&request, "Sell", ControlScope::Trade)?;
+
+        let request = request.into_inner();
+        let mint = parse_pubkey(&request.mint, "mint")?;
+
+        let wallets = manual_sell_mint(mint, request.confirm_code.as_deref(), &self.metrics)
+            .await
+            .map_err(Status::failed_precondition)?;
+
+        Ok(Response::new(SellReply {
+            wallets: wallets as u32,
+        }))
+    }
+
+    /// Follows only the given wallet. Only the gRPC datasources pick it up live,
+    /// WebSocket, RPC polling and backfill keep the targets followed at startup until a
+    /// restart.
+    async fn set_target(
+        &self,
+        request: Request<SetTargetRequest>,
+    ) -> Result<Response<Toggled>, Status> {
+        admit(&request, "SetTarget", ControlScope::Trade)?;
+
+        let wallet = parse_pubkey(&request.into_inner().wallet, "wallet")?;
+
+        Ok(Response::new(Toggled {
+            changed: FOLLOWED_TARGETS.set(wallet),
+        }))
+    }
+}
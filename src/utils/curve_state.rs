@@ -0,0 +1,242 @@
+use once_cell::sync::Lazy;
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+use tokio::sync::watch;
+
+use crate::{
+    config::{HOT_CURVE_LIMIT, IN_FLIGHT_BUY_TTL_MS},
+    utils::bonding_curve_sol_quote,
+};
+
+/// Reserves of a pumpfun bonding curve as of `slot`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurveReserves {
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub complete: bool,
+    pub slot: u64,
+}
+
+/// One of our own buys that was sent but may not be reflected in observed reserves yet.
+#[derive(Debug, Clone, Copy)]
+struct InFlightBuy {
+    wallet: Pubkey,
+    token_amount: u64,
+    sent_at: Instant,
+}
+
+/// Local view of the bonding curves worth following: those of tokens the wallets hold,
+/// plus the most recent ones the target traded ("hot" curves) when enabled. The gRPC
+/// datasources subscribe to the watched curves and resubscribe whenever they change.
+pub struct CurveStates {
+    watched: RwLock<HashMap<Pubkey, Pubkey>>,
+    hot: RwLock<VecDeque<Pubkey>>,
+    reserves: RwLock<HashMap<Pubkey, CurveReserves>>,
+    completed: RwLock<HashSet<Pubkey>>,
+    in_flight: RwLock<HashMap<Pubkey, Vec<InFlightBuy>>>,
+    changes: watch::Sender<u64>,
+}
+
+pub static CURVE_STATES: Lazy<CurveStates> = Lazy::new(CurveStates::new);
+
+impl Default for CurveStates {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CurveStates {
+    pub fn new() -> Self {
+        Self {
+            watched: RwLock::new(HashMap::new()),
+            hot: RwLock::new(VecDeque::new()),
+            reserves: RwLock::new(HashMap::new()),
+            completed: RwLock::new(HashSet::new()),
+            in_flight: RwLock::new(HashMap::new()),
+            changes: watch::channel(0).0,
+        }
+    }
+
+    /// Follows the `bonding_curve` of a held `mint`.
+    pub fn watch(&self, mint: Pubkey, bonding_curve: Pubkey) {
+        self.hot
+            .write()
+            .unwrap()
+            .retain(|hot_mint| *hot_mint != mint);
+
+        if self
+            .watched
+            .write()
+            .unwrap()
+            .insert(mint, bonding_curve)
+            .is_none()
+        {
+            self.changes.send_modify(|version| *version += 1);
+        }
+    }
+
+    /// Follows the `bonding_curve` of a token the target traded, evicting the oldest
+    /// hot curve beyond `HOT_CURVE_LIMIT`. Held tokens are never evicted.
+    pub fn watch_hot(&self, mint: Pubkey, bonding_curve: Pubkey) {
+        if self.watched.read().unwrap().contains_key(&mint) {
+            return;
+        }
+
+        let evicted = {
+            let mut hot = self.hot.write().unwrap();
+            hot.push_back(mint);
+
+            if hot.len() > *HOT_CURVE_LIMIT {
+                hot.pop_front()
+            } else {
+                None
+            }
+        };
+
+        self.watched.write().unwrap().insert(mint, bonding_curve);
+
+        match evicted {
+            Some(evicted) => self.unwatch(&evicted),
+            None => self.changes.send_modify(|version| *version += 1),
+        }
+    }
+
+    /// Stops following the curve of `mint`, e.g. once the position is closed.
+    pub fn unwatch(&self, mint: &Pubkey) {
+        self.hot
+            .write()
+            .unwrap()
+            .retain(|hot_mint| hot_mint != mint);
+
+        self.in_flight.write().unwrap().remove(mint);
+
+        if let Some(bonding_curve) = self.watched.write().unwrap().remove(mint) {
+            self.reserves.write().unwrap().remove(&bonding_curve);
+            self.changes.send_modify(|version| *version += 1);
+        }
+    }
+
+    pub fn watched_curves(&self) -> Vec<Pubkey> {
+        self.watched.read().unwrap().values().copied().collect()
+    }
+
+    /// Receiver notified whenever the set of watched curves changes.
+    pub fn subscribe_changes(&self) -> watch::Receiver<u64> {
+        self.changes.subscribe()
+    }
+
+    /// Records the reserves of a watched curve, ignoring updates older than the known state.
+    pub fn update(&self, bonding_curve: Pubkey, reserves: CurveReserves) {
+        if !self
+            .watched
+            .read()
+            .unwrap()
+            .values()
+            .any(|watched| *watched == bonding_curve)
+        {
+            return;
+        }
+
+        let mut known = self.reserves.write().unwrap();
+
+        if known
+            .get(&bonding_curve)
+            .is_none_or(|current| current.slot <= reserves.slot)
+        {
+            known.insert(bonding_curve, reserves);
+        }
+    }
+
+    pub fn reserves(&self, bonding_curve: &Pubkey) -> Option<CurveReserves> {
+        self.reserves.read().unwrap().get(bonding_curve).copied()
+    }
+
+    /// Records that the curve of `mint` completed or was migrated, so it no longer trades.
+    pub fn mark_complete(&self, mint: Pubkey) {
+        self.completed.write().unwrap().insert(mint);
+
+        let Some(bonding_curve) = self.watched.read().unwrap().get(&mint).copied() else {
+            return;
+        };
+
+        if let Some(reserves) = self.reserves.write().unwrap().get_mut(&bonding_curve) {
+            reserves.complete = true;
+        }
+    }
+
+    pub fn is_complete(&self, mint: &Pubkey) -> bool {
+        self.completed.read().unwrap().contains(mint)
+    }
+
+    /// Records a buy of `token_amount` of a watched `mint` sent by `wallet`, so quotes
+    /// made before it shows up in the observed reserves account for it.
+    pub fn record_own_buy(&self, mint: Pubkey, wallet: Pubkey, token_amount: u64) {
+        if !self.watched.read().unwrap().contains_key(&mint) {
+            return;
+        }
+
+        self.in_flight
+            .write()
+            .unwrap()
+            .entry(mint)
+            .or_default()
+            .push(InFlightBuy {
+                wallet,
+                token_amount,
+                sent_at: Instant::now(),
+            });
+    }
+
+    /// Forgets an own buy that failed to send.
+    pub fn release_own_buy(&self, mint: &Pubkey, wallet: &Pubkey, token_amount: u64) {
+        let mut in_flight = self.in_flight.write().unwrap();
+
+        if let Some(buys) = in_flight.get_mut(mint)
+            && let Some(index) = buys
+                .iter()
+                .position(|buy| buy.wallet == *wallet && buy.token_amount == token_amount)
+        {
+            buys.remove(index);
+        }
+    }
+
+    /// Reserves of the `mint` curve after our own buys sent within the last
+    /// `IN_FLIGHT_BUY_TTL_MS`, applied on top of the observed `virtual_sol_reserves`
+    /// and `virtual_token_reserves`. Older buys are assumed to have landed or failed.
+    pub fn with_own_buys(
+        &self,
+        mint: &Pubkey,
+        virtual_sol_reserves: u64,
+        virtual_token_reserves: u64,
+    ) -> (u64, u64) {
+        let ttl = Duration::from_millis(*IN_FLIGHT_BUY_TTL_MS);
+        let mut in_flight = self.in_flight.write().unwrap();
+
+        let Some(buys) = in_flight.get_mut(mint) else {
+            return (virtual_sol_reserves, virtual_token_reserves);
+        };
+
+        buys.retain(|buy| buy.sent_at.elapsed() < ttl);
+
+        buys.iter().fold(
+            (virtual_sol_reserves, virtual_token_reserves),
+            |(sol_reserves, token_reserves), buy| {
+                let token_amount = buy.token_amount.min(token_reserves.saturating_sub(1));
+
+                (
+                    sol_reserves.saturating_add(bonding_curve_sol_quote(
+                        token_amount,
+                        sol_reserves,
+                        token_reserves,
+                        true,
+                    )),
+                    token_reserves - token_amount,
+                )
+            },
+        )
+    }
+}
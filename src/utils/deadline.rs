@@ -0,0 +1,31 @@
+use chrono::Utc;
+
+use crate::{config::STALE_EVENT_DEADLINE_MS, service::senders_stubbed, utils::current_slot};
+
+/// Approximate duration of a slot, used to turn slot lag into milliseconds.
+pub const SLOT_DURATION_MS: u64 = 400;
+
+/// Detection latency of an event in milliseconds, estimated from how far its slot
+/// trails the chain tip and how old its on-chain timestamp is.
+pub fn event_latency_ms(event_slot: u64, event_timestamp: i64) -> u64 {
+    let slot_lag_ms = current_slot().saturating_sub(event_slot) * SLOT_DURATION_MS;
+
+    // The on-chain timestamp only has second resolution, so allow a second of slack
+    let wall_lag_ms = (Utc::now().timestamp_millis() - event_timestamp * 1000 - 1000).max(0) as u64;
+
+    slot_lag_ms.max(wall_lag_ms)
+}
+
+/// Returns the latency when the event is older than `STALE_EVENT_DEADLINE_MS`. Never
+/// stale in a replay or backtest, those feed old events on purpose.
+pub fn stale_event_latency(event_slot: u64, event_timestamp: i64) -> Option<u64> {
+    let deadline = (*STALE_EVENT_DEADLINE_MS)?;
+
+    if senders_stubbed() {
+        return None;
+    }
+
+    let latency = event_latency_ms(event_slot, event_timestamp);
+
+    (latency > deadline).then_some(latency)
+}
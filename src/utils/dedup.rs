@@ -0,0 +1,39 @@
+use once_cell::sync::Lazy;
+use solana_sdk::signature::Signature;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::config::DEDUP_TTL_SEC;
+
+/// Remembers which instructions have already been handled so the same target trade
+/// delivered by several datasources is only copied once. Entries expire after `ttl`.
+pub struct DedupCache {
+    ttl: Duration,
+    seen: Mutex<HashMap<(Signature, Vec<u8>), Instant>>,
+}
+
+pub static SEEN_INSTRUCTIONS: Lazy<DedupCache> =
+    Lazy::new(|| DedupCache::new(Duration::from_secs(*DEDUP_TTL_SEC)));
+
+impl DedupCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records the instruction at `path` of transaction `signature` and returns whether
+    /// this is the first time it was seen. Checking and recording happen atomically.
+    pub fn first_seen(&self, signature: Signature, path: &[u8]) -> bool {
+        let now = Instant::now();
+        let mut seen = self.seen.lock().unwrap();
+
+        seen.retain(|_, seen_at| now.duration_since(*seen_at) < self.ttl);
+
+        seen.insert((signature, path.to_vec()), now).is_none()
+    }
+}
@@ -0,0 +1,167 @@
+use borsh::BorshDeserialize;
+use carbon_core::deserialize::CarbonDeserialize;
+use carbon_pumpfun_decoder::instructions::trade_event::TradeEvent;
+use solana_sdk::pubkey::Pubkey;
+
+/// Instruction tag anchor puts in front of every event self-CPI, before the event's
+/// own 8-byte discriminator. Carbon's event `DISCRIMINATOR`s include both.
+pub const ANCHOR_EVENT_CPI_TAG: [u8; 8] = [0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d];
+
+/// Body of an event self-CPI whose data starts with the carbon event `discriminator`.
+pub fn event_cpi_body<'a>(data: &'a [u8], discriminator: &[u8]) -> Option<&'a [u8]> {
+    data.strip_prefix(discriminator)
+}
+
+/// Decodes an event self-CPI, `None` when it isn't a `T` or doesn't parse. Bytes past
+/// the known fields are ignored, so events that grew new fields still decode.
+pub fn decode_event_cpi<T: BorshDeserialize + CarbonDeserialize>(data: &[u8]) -> Option<T> {
+    let mut body = event_cpi_body(data, T::DISCRIMINATOR)?;
+
+    <T as BorshDeserialize>::deserialize(&mut body).ok()
+}
+
+/// Borrowed view over the body of a pumpfun `TradeEvent`, reading fields in place.
+/// Events emitted before the creator fee fields existed are accepted as well.
+#[derive(Debug, Clone, Copy)]
+pub struct TradeEventView<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> TradeEventView<'a> {
+    const MINT: usize = 0;
+    const SOL_AMOUNT: usize = 32;
+    const TOKEN_AMOUNT: usize = 40;
+    const IS_BUY: usize = 48;
+    const USER: usize = 49;
+    const TIMESTAMP: usize = 81;
+    const VIRTUAL_SOL_RESERVES: usize = 89;
+    const VIRTUAL_TOKEN_RESERVES: usize = 97;
+    const REAL_SOL_RESERVES: usize = 105;
+    const REAL_TOKEN_RESERVES: usize = 113;
+    const LEGACY_LEN: usize = 121;
+    const FEE_RECIPIENT: usize = 121;
+    const FEE_BASIS_POINTS: usize = 153;
+    const FEE: usize = 161;
+    const CREATOR: usize = 169;
+    const CREATOR_FEE_BASIS_POINTS: usize = 201;
+    const CREATOR_FEE: usize = 209;
+    const LEN: usize = 217;
+
+    /// Wraps an event body, `None` when it is too short to be a `TradeEvent`.
+    pub fn new(data: &'a [u8]) -> Option<Self> {
+        (data.len() >= Self::LEGACY_LEN).then_some(Self { data })
+    }
+
+    /// Wraps the `TradeEvent` self-CPI in instruction `data`.
+    pub fn from_cpi(data: &'a [u8]) -> Option<Self> {
+        event_cpi_body(data, TradeEvent::DISCRIMINATOR).and_then(Self::new)
+    }
+
+    fn has_fees(&self) -> bool {
+        self.data.len() >= Self::LEN
+    }
+
+    fn u64_at(&self, offset: usize) -> u64 {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&self.data[offset..offset + 8]);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn pubkey_at(&self, offset: usize) -> Pubkey {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&self.data[offset..offset + 32]);
+        Pubkey::new_from_array(bytes)
+    }
+
+    /// Compares the mint in place, without copying it out.
+    pub fn is_mint(&self, mint: &Pubkey) -> bool {
+        self.data[Self::MINT..Self::MINT + 32] == mint.to_bytes()
+    }
+
+    pub fn mint(&self) -> Pubkey {
+        self.pubkey_at(Self::MINT)
+    }
+
+    pub fn sol_amount(&self) -> u64 {
+        self.u64_at(Self::SOL_AMOUNT)
+    }
+
+    pub fn token_amount(&self) -> u64 {
+        self.u64_at(Self::TOKEN_AMOUNT)
+    }
+
+    pub fn is_buy(&self) -> bool {
+        self.data[Self::IS_BUY] != 0
+    }
+
+    pub fn user(&self) -> Pubkey {
+        self.pubkey_at(Self::USER)
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        self.u64_at(Self::TIMESTAMP) as i64
+    }
+
+    pub fn virtual_sol_reserves(&self) -> u64 {
+        self.u64_at(Self::VIRTUAL_SOL_RESERVES)
+    }
+
+    pub fn virtual_token_reserves(&self) -> u64 {
+        self.u64_at(Self::VIRTUAL_TOKEN_RESERVES)
+    }
+
+    pub fn real_sol_reserves(&self) -> u64 {
+        self.u64_at(Self::REAL_SOL_RESERVES)
+    }
+
+    pub fn real_token_reserves(&self) -> u64 {
+        self.u64_at(Self::REAL_TOKEN_RESERVES)
+    }
+
+    /// The token creator, `None` on events without the creator fee fields.
+    pub fn creator(&self) -> Option<Pubkey> {
+        self.has_fees().then(|| self.pubkey_at(Self::CREATOR))
+    }
+
+    /// Copies the event out, with zeroed fee fields on legacy events.
+    pub fn to_event(&self) -> TradeEvent {
+        let fee_field = |offset| {
+            if self.has_fees() {
+                self.u64_at(offset)
+            } else {
+                0
+            }
+        };
+        let pubkey_field = |offset| {
+            if self.has_fees() {
+                self.pubkey_at(offset)
+            } else {
+                Pubkey::default()
+            }
+        };
+
+        TradeEvent {
+            mint: self.mint(),
+            sol_amount: self.sol_amount(),
+            token_amount: self.token_amount(),
+            is_buy: self.is_buy(),
+            user: self.user(),
+            timestamp: self.timestamp(),
+            virtual_sol_reserves: self.virtual_sol_reserves(),
+            virtual_token_reserves: self.virtual_token_reserves(),
+            real_sol_reserves: self.real_sol_reserves(),
+            real_token_reserves: self.real_token_reserves(),
+            fee_recipient: pubkey_field(Self::FEE_RECIPIENT),
+            fee_basis_points: fee_field(Self::FEE_BASIS_POINTS),
+            fee: fee_field(Self::FEE),
+            creator: pubkey_field(Self::CREATOR),
+            creator_fee_basis_points: fee_field(Self::CREATOR_FEE_BASIS_POINTS),
+            creator_fee: fee_field(Self::CREATOR_FEE),
+        }
+    }
+}
+
+/// Pumpfun `TradeEvent` of an event self-CPI, `None` when `data` isn't one.
+pub fn pumpfun_trade_event(data: &[u8]) -> Option<TradeEvent> {
+    TradeEventView::from_cpi(data).map(|view| view.to_event())
+}
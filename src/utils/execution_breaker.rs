@@ -0,0 +1,105 @@
+use carbon_core::metrics::MetricsCollection;
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use tracing::{info, warn};
+
+use crate::{config::EXECUTION_BREAKER_FAILURES, utils::notify_error};
+
+/// Stops buying after `EXECUTION_BREAKER_FAILURES` consecutive sent copies fail to land,
+/// or as many consecutive simulations fail, so a misconfiguration doesn't keep paying
+/// tips. Once tripped it stays open until resumed by hand, sells always go through.
+#[derive(Debug, Default)]
+pub struct ExecutionBreaker {
+    land_failures: AtomicU32,
+    simulation_failures: AtomicU32,
+    tripped: AtomicBool,
+}
+
+pub static EXECUTION_BREAKER: Lazy<ExecutionBreaker> = Lazy::new(ExecutionBreaker::default);
+
+impl ExecutionBreaker {
+    /// Records whether a sent copy landed.
+    pub async fn record_landing(&self, metrics: &MetricsCollection, landed: bool) {
+        if landed {
+            self.land_failures.store(0, Ordering::Relaxed);
+            return;
+        }
+
+        let failures = self.land_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        self.check(metrics, failures, "sent copies failed to land")
+            .await;
+    }
+
+    /// Records whether a copy passed its pre-send simulation.
+    pub async fn record_simulation(&self, metrics: &MetricsCollection, passed: bool) {
+        if passed {
+            self.simulation_failures.store(0, Ordering::Relaxed);
+            return;
+        }
+
+        let failures = self.simulation_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        self.check(metrics, failures, "simulations failed").await;
+    }
+
+    async fn check(&self, metrics: &MetricsCollection, failures: u32, reason: &str) {
+        if *EXECUTION_BREAKER_FAILURES == 0
+            || failures < *EXECUTION_BREAKER_FAILURES
+            || self.tripped.swap(true, Ordering::Relaxed)
+        {
+            return;
+        }
+
+        warn!(
+            "ALERT: {} consecutive {}, pausing buys until resumed",
+            failures, reason
+        );
+
+        notify_error(format!(
+            "{} consecutive {}, buying is paused until resumed",
+            failures, reason
+        ));
+
+        metrics
+            .increment_counter("execution_breaker_trips", 1)
+            .await
+            .ok();
+    }
+
+    /// Whether buys are paused until a manual resume.
+    pub fn is_tripped(&self) -> bool {
+        self.tripped.load(Ordering::Relaxed)
+    }
+
+    /// Trips the breaker by hand. Returns whether it was closed.
+    pub fn pause(&self) -> bool {
+        let was_closed = !self.tripped.swap(true, Ordering::Relaxed);
+
+        if was_closed {
+            info!("Execution breaker paused by hand, holding back buys");
+        }
+
+        was_closed
+    }
+
+    /// Closes the breaker again and clears the failure streaks. Returns whether it was tripped.
+    pub fn resume(&self) -> bool {
+        self.land_failures.store(0, Ordering::Relaxed);
+        self.simulation_failures.store(0, Ordering::Relaxed);
+
+        let was_tripped = self.tripped.swap(false, Ordering::Relaxed);
+
+        if was_tripped {
+            info!("Execution breaker resumed, buying again");
+        }
+
+        was_tripped
+    }
+
+    pub fn land_failures(&self) -> u32 {
+        self.land_failures.load(Ordering::Relaxed)
+    }
+
+    pub fn simulation_failures(&self) -> u32 {
+        self.simulation_failures.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,173 @@
+use chrono::{NaiveDate, Utc};
+use once_cell::sync::Lazy;
+use solana_sdk::{instruction::Instruction, system_instruction::SystemInstruction, system_program};
+use std::{collections::BTreeMap, sync::Mutex};
+
+use crate::utils::is_tip_account;
+
+/// Lamports a transaction pays to get through a sender, besides what it trades.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TxCosts {
+    /// Transferred to the sender's tip account
+    pub tip: u64,
+    /// Compute unit limit times the compute unit price
+    pub priority_fee: u64,
+    /// The network's fee per signature
+    pub base_fee: u64,
+    /// Paid by a separate fee payer rather than the trading wallet, so left out of the
+    /// wallet's SOL flow
+    pub fee_payer_pays: bool,
+}
+
+impl TxCosts {
+    /// Costs of a transaction of `ixs` with `signatures` signers, setting a `cu` limit
+    /// at `priority_fee_micro_lamport` per unit.
+    pub fn of(
+        ixs: &[Instruction],
+        signatures: u64,
+        cu: u64,
+        priority_fee_micro_lamport: u64,
+    ) -> Self {
+        Self {
+            tip: tip_lamports(ixs),
+            priority_fee: cu * priority_fee_micro_lamport / 1_000_000,
+            base_fee: signatures * SIGNATURE_FEE,
+            fee_payer_pays: signatures > 1,
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.tip + self.priority_fee + self.base_fee
+    }
+}
+
+/// Lamports the network charges per signature
+pub const SIGNATURE_FEE: u64 = 5_000;
+
+/// Lamports `ixs` transfer to a sender's tip account.
+pub fn tip_lamports(ixs: &[Instruction]) -> u64 {
+    ixs.iter()
+        .filter(|ix| ix.program_id == system_program::ID)
+        .filter_map(|ix| match bincode::deserialize(&ix.data).ok()? {
+            SystemInstruction::Transfer { lamports } => ix
+                .accounts
+                .get(1)
+                .filter(|recipient| is_tip_account(&recipient.pubkey))
+                .map(|_| lamports),
+            _ => None,
+        })
+        .sum()
+}
+
+/// Tips and fees paid through one sender.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeTotals {
+    /// Landed transactions that paid them
+    pub transactions: u64,
+    pub tips: u64,
+    pub priority_fees: u64,
+    pub base_fees: u64,
+    /// Of them, what a separate fee payer paid, left out of the targets' SOL flow
+    pub fee_payer_paid: u64,
+}
+
+impl FeeTotals {
+    pub fn total(&self) -> u64 {
+        self.tips + self.priority_fees + self.base_fees
+    }
+}
+
+/// Tips and fees paid by landed transactions, per UTC day and sender, so what speed
+/// costs shows up next to the PnL. A transaction that lands but fails still pays its
+/// fees, its tip transfer is rolled back with it.
+pub struct FeeLedger {
+    days: Mutex<BTreeMap<(NaiveDate, String), FeeTotals>>,
+}
+
+pub static FEE_LEDGER: Lazy<FeeLedger> = Lazy::new(FeeLedger::new);
+
+impl Default for FeeLedger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeeLedger {
+    pub fn new() -> Self {
+        Self {
+            days: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Records the `costs` of a transaction `sender` landed today, `failed` when it
+    /// landed with an error.
+    pub fn record(&self, sender: &str, costs: TxCosts, failed: bool) {
+        self.record_on(Utc::now().date_naive(), sender, costs, failed);
+    }
+
+    /// `record` on a given `day`.
+    pub fn record_on(&self, day: NaiveDate, sender: &str, costs: TxCosts, failed: bool) {
+        let mut days = self.days.lock().unwrap();
+        let totals = days.entry((day, sender.to_string())).or_default();
+
+        // A failed transaction's tip transfer is rolled back, its fees aren't
+        let tip = if failed { 0 } else { costs.tip };
+
+        totals.transactions += 1;
+        totals.tips += tip;
+        totals.priority_fees += costs.priority_fee;
+        totals.base_fees += costs.base_fee;
+
+        if costs.fee_payer_pays {
+            totals.fee_payer_paid += tip + costs.priority_fee + costs.base_fee;
+        }
+    }
+
+    /// Totals per day and sender, oldest day first.
+    pub fn days(&self) -> Vec<(NaiveDate, String, FeeTotals)> {
+        self.days
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|((day, sender), totals)| (*day, sender.clone(), *totals))
+            .collect()
+    }
+
+    /// Totals per sender since the start.
+    pub fn senders(&self) -> Vec<(String, FeeTotals)> {
+        let mut senders: BTreeMap<String, FeeTotals> = BTreeMap::new();
+
+        for ((_, sender), totals) in self.days.lock().unwrap().iter() {
+            let sender_totals = senders.entry(sender.clone()).or_default();
+
+            sender_totals.transactions += totals.transactions;
+            sender_totals.tips += totals.tips;
+            sender_totals.priority_fees += totals.priority_fees;
+            sender_totals.base_fees += totals.base_fees;
+            sender_totals.fee_payer_paid += totals.fee_payer_paid;
+        }
+
+        senders.into_iter().collect()
+    }
+
+    /// Lamports paid in tips and fees since the start.
+    pub fn total(&self) -> u64 {
+        self.days
+            .lock()
+            .unwrap()
+            .values()
+            .map(FeeTotals::total)
+            .sum()
+    }
+
+    /// Lamports a separate fee payer paid since the start, to take off the targets' SOL
+    /// flow for the PnL net of tips and fees.
+    pub fn fee_payer_paid(&self) -> u64 {
+        self.days
+            .lock()
+            .unwrap()
+            .values()
+            .map(|totals| totals.fee_payer_paid)
+            .sum()
+    }
+}
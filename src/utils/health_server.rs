@@ -0,0 +1,151 @@
+use serde_json::{Value, json};
+use solana_sdk::signer::Signer;
+use std::time::Duration;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    time::timeout,
+};
+use tracing::{info, warn};
+
+use crate::{
+    config::{BUY_SOL_AMOUNT, JITO_CLIENT, NOZOMI_CLIENT, ZSLOT_CLIENT},
+    datasource::DATASOURCE_STATUS,
+    utils::{EXECUTION_BREAKER, fresh_blockhash, latest_blockhash},
+    wallet::{LOW_BALANCE, WALLET_POOL},
+};
+
+/// Longest a client gets to send its request line
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Liveness: a datasource is connected and the blockhash is fresh enough to send with.
+pub fn health_status() -> (bool, Value) {
+    let datasources = DATASOURCE_STATUS.connected();
+    let blockhash = latest_blockhash();
+    let blockhash_fresh = fresh_blockhash().is_some();
+
+    let healthy = !datasources.is_empty() && blockhash_fresh;
+
+    (
+        healthy,
+        json!({
+            "healthy": healthy,
+            "datasources": datasources,
+            "blockhash_fresh": blockhash_fresh,
+            "blockhash_age_ms": blockhash.map(|blockhash| blockhash.age().as_millis() as u64),
+        }),
+    )
+}
+
+/// Readiness: every sender is initialized, a wallet can afford a `BUY_SOL_AMOUNT` buy
+/// and neither the execution breaker nor a low fee payer balance is holding buys back.
+pub fn ready_status() -> (bool, Value) {
+    let senders = json!({
+        "NOZOMI": NOZOMI_CLIENT.initialized(),
+        "ZERO_SLOT": ZSLOT_CLIENT.initialized(),
+        "JITO": JITO_CLIENT.initialized(),
+    });
+    let senders_ready =
+        NOZOMI_CLIENT.initialized() && ZSLOT_CLIENT.initialized() && JITO_CLIENT.initialized();
+
+    // Only fetched balances count here, unlike when picking a wallet to buy with
+    let funded_wallets = WALLET_POOL
+        .wallets()
+        .iter()
+        .filter(|wallet| {
+            WALLET_POOL
+                .balance(&wallet.pubkey())
+                .is_some_and(|balance| balance >= *BUY_SOL_AMOUNT)
+        })
+        .count();
+
+    let breaker_tripped = EXECUTION_BREAKER.is_tripped();
+
+    let low_balance_halt = LOW_BALANCE.is_halted();
+
+    let ready = senders_ready && funded_wallets > 0 && !breaker_tripped && !low_balance_halt;
+
+    (
+        ready,
+        json!({
+            "ready": ready,
+            "senders": senders,
+            "funded_wallets": funded_wallets,
+            "low_balance_halt": low_balance_halt,
+            "execution_breaker": {
+                "tripped": breaker_tripped,
+                "land_failures": EXECUTION_BREAKER.land_failures(),
+                "simulation_failures": EXECUTION_BREAKER.simulation_failures(),
+            },
+        }),
+    )
+}
+
+/// Serves `GET /healthz` and `GET /readyz` on `addr` for supervisors and load
+/// balancers, answering 200 when the check passes and 503 otherwise, with the
+/// details as JSON. `POST /resume` closes a tripped execution breaker.
+pub async fn health_server(addr: String) {
+    let listener = match TcpListener::bind(&addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            warn!("Failed to start the health server on {}: {}", addr, e);
+            return;
+        }
+    };
+
+    info!("Health server listening on {}", addr);
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(handle_connection(stream));
+            }
+            Err(e) => warn!("Health server failed to accept a connection: {}", e),
+        }
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream) {
+    let mut request = [0u8; 1024];
+
+    let Ok(Ok(read)) = timeout(REQUEST_TIMEOUT, stream.read(&mut request)).await else {
+        return;
+    };
+
+    let request = String::from_utf8_lossy(&request[..read]);
+    let mut request_line = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+
+    let (status, body) = match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some("/healthz")) => check_response(health_status()),
+        (Some("GET"), Some("/readyz")) => check_response(ready_status()),
+        (Some("POST"), Some("/resume")) => {
+            ("200 OK", json!({ "resumed": EXECUTION_BREAKER.resume() }))
+        }
+        _ => ("404 Not Found", json!({ "error": "not found" })),
+    };
+
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+fn check_response((passed, body): (bool, Value)) -> (&'static str, Value) {
+    let status = if passed {
+        "200 OK"
+    } else {
+        "503 Service Unavailable"
+    };
+
+    (status, body)
+}
@@ -0,0 +1,169 @@
+use chrono::Utc;
+use serde_json::{Map, Value};
+use std::{
+    fmt,
+    fs::OpenOptions,
+    io::{self, Write},
+};
+use tracing::{
+    Event, Metadata, Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+};
+use tracing_appender::non_blocking::{NonBlocking, WorkerGuard};
+use tracing_subscriber::{
+    Layer,
+    filter::{FilterFn, Filtered, filter_fn},
+    layer::Context,
+    registry::LookupSpan,
+};
+
+/// Log target of a trade, or part of one, the filters or a guard decided not to copy.
+pub const JOURNAL_SKIP: &str = "journal::skip";
+/// Log target of a copy about to be submitted, with what it was quoted at.
+pub const JOURNAL_QUOTE: &str = "journal::quote";
+/// Log target of a sender's response to a copy.
+pub const JOURNAL_SEND: &str = "journal::send";
+/// Log target of whether a sent copy landed.
+pub const JOURNAL_LANDING: &str = "journal::landing";
+
+/// Name of the span of one target transaction, see `trade_span`.
+const TRADE_SPAN: &str = "trade";
+
+/// Fields recorded so far on a trade span.
+struct TradeFields(Map<String, Value>);
+
+/// Appends one JSON line per journal entry: a `detection` when a trade span opens, one
+/// per event logged under a `journal::` target, named after it, and the trade's
+/// `outcome` once its span and every copy confirming under it are done. Entries carry
+/// the trade span's fields, so each can be traced back to its target transaction.
+pub struct JournalLayer {
+    writer: NonBlocking,
+}
+
+/// The journal layer appending to `path`, only seeing trade spans and journal events
+/// whatever `RUST_LOG` says. Writes happen on a background thread; the guard flushes
+/// them when dropped.
+pub fn journal_layer<S>(
+    path: &str,
+) -> io::Result<(Filtered<JournalLayer, FilterFn, S>, WorkerGuard)>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let (writer, guard) = tracing_appender::non_blocking(file);
+
+    Ok((
+        JournalLayer { writer }.with_filter(filter_fn(is_journaled)),
+        guard,
+    ))
+}
+
+fn is_journaled(metadata: &Metadata<'_>) -> bool {
+    if metadata.is_span() {
+        metadata.name() == TRADE_SPAN
+    } else {
+        metadata.target().starts_with("journal::")
+    }
+}
+
+impl JournalLayer {
+    fn append(&self, event: &str, mut fields: Map<String, Value>) {
+        fields.insert("timestamp_ms".into(), Utc::now().timestamp_millis().into());
+        fields.insert("event".into(), event.into());
+
+        let mut line = Value::Object(fields).to_string();
+        line.push('\n');
+
+        // The writer drops lines rather than block when its queue is full
+        let _ = self.writer.clone().write_all(line.as_bytes());
+    }
+}
+
+impl<S> Layer<S> for JournalLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut fields = FieldVisitor(Map::new());
+        attrs.record(&mut fields);
+
+        self.append("detection", fields.0.clone());
+        span.extensions_mut().insert(TradeFields(fields.0));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        if let Some(fields) = span.extensions_mut().get_mut::<TradeFields>() {
+            let mut visitor = FieldVisitor(std::mem::take(&mut fields.0));
+            values.record(&mut visitor);
+            fields.0 = visitor.0;
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = ctx
+            .event_scope(event)
+            .and_then(|mut scope| scope.find(|span| span.name() == TRADE_SPAN))
+            .and_then(|span| {
+                span.extensions()
+                    .get::<TradeFields>()
+                    .map(|fields| fields.0.clone())
+            })
+            .unwrap_or_default();
+
+        let mut visitor = FieldVisitor(Map::new());
+        event.record(&mut visitor);
+        fields.extend(visitor.0);
+
+        let target = event.metadata().target();
+        self.append(target.trim_start_matches("journal::"), fields);
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+
+        if let Some(fields) = span.extensions_mut().remove::<TradeFields>() {
+            self.append("outcome", fields.0);
+        }
+    }
+}
+
+/// Collects span or event fields as JSON values.
+struct FieldVisitor(Map<String, Value>);
+
+impl Visit for FieldVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().into(), format!("{:?}", value).into());
+    }
+}
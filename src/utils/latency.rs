@@ -0,0 +1,76 @@
+use carbon_core::metrics::MetricsCollection;
+use solana_sdk::signature::Signature;
+use std::time::{Duration, Instant};
+
+use crate::datasource::ARRIVALS;
+
+/// Timestamps of a copy trade along the hot path: when a datasource first delivered the
+/// target's transaction, when its processor started on it and when the copy orders were
+/// built. Signing and sending are timed by the sender.
+#[derive(Debug, Clone, Copy)]
+pub struct LatencyTrace {
+    pub detected_at: Option<Instant>,
+    pub decoded_at: Instant,
+    pub built_at: Option<Instant>,
+}
+
+impl LatencyTrace {
+    /// Starts the trace of target transaction `signature` as its processor picks it up.
+    pub fn start(signature: &Signature) -> Self {
+        Self {
+            detected_at: ARRIVALS.first_arrival(signature),
+            decoded_at: Instant::now(),
+            built_at: None,
+        }
+    }
+
+    /// Trace of an order not triggered by a target transaction, e.g. a timed exit.
+    pub fn now() -> Self {
+        Self {
+            detected_at: None,
+            decoded_at: Instant::now(),
+            built_at: None,
+        }
+    }
+
+    pub fn built(self) -> Self {
+        Self {
+            built_at: Some(Instant::now()),
+            ..self
+        }
+    }
+
+    /// Time since detection, or since decoding when the arrival wasn't tracked.
+    pub fn elapsed(&self) -> Duration {
+        self.detected_at.unwrap_or(self.decoded_at).elapsed()
+    }
+
+    /// Records the detection to decode and decode to build stages.
+    pub async fn record_build(&self, metrics: &MetricsCollection) {
+        if let Some(detected_at) = self.detected_at {
+            record_stage(metrics, "decode", self.decoded_at - detected_at).await;
+        }
+
+        if let Some(built_at) = self.built_at {
+            record_stage(metrics, "build", built_at - self.decoded_at).await;
+        }
+    }
+
+    /// Records how long the orders waited for an execution worker.
+    pub async fn record_queue(&self, metrics: &MetricsCollection) {
+        if let Some(built_at) = self.built_at {
+            record_stage(metrics, "queue", built_at.elapsed()).await;
+        }
+    }
+}
+
+/// Records the duration of a hot path `stage` as the `hot_path_{stage}_ms` histogram.
+pub async fn record_stage(metrics: &MetricsCollection, stage: &str, duration: Duration) {
+    metrics
+        .record_histogram(
+            &format!("hot_path_{}_ms", stage),
+            duration.as_secs_f64() * 1000.0,
+        )
+        .await
+        .ok();
+}
@@ -0,0 +1,28 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+
+use crate::config::LAUNCHPAD_PLATFORMS;
+
+/// Launch platforms built on Raydium LaunchLab, keyed by their platform config account.
+/// Their trades go through the LaunchLab program itself, so they share its decoder and
+/// instruction builders.
+pub const LAUNCHLAB_PLATFORMS: &[(&str, Pubkey)] = &[(
+    "bonk.fun",
+    pubkey!("FfYek5vEz23cMkWsdJwG2oa6EphsvXSHrGpdALN4g6W1"),
+)];
+
+/// Name of the launch platform behind a LaunchLab `platform_config`.
+pub fn launchpad_platform_name(platform_config: &Pubkey) -> &'static str {
+    LAUNCHLAB_PLATFORMS
+        .iter()
+        .find(|(_, config)| config == platform_config)
+        .map(|(name, _)| *name)
+        .unwrap_or("LaunchLab")
+}
+
+/// Whether trades on `platform` should be copied according to `LAUNCHPAD_PLATFORMS`.
+pub fn is_copied_platform(platform: &str) -> bool {
+    LAUNCHPAD_PLATFORMS.is_empty()
+        || LAUNCHPAD_PLATFORMS
+            .iter()
+            .any(|copied| copied.eq_ignore_ascii_case(platform))
+}
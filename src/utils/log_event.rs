@@ -0,0 +1,43 @@
+use carbon_core::deserialize::CarbonDeserialize;
+use carbon_pumpfun_decoder::instructions::trade_event::TradeEvent;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::utils::{ANCHOR_EVENT_CPI_TAG, TradeEventView};
+
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Bodies of the anchor events logged as "Program data:" lines whose 8-byte event
+/// discriminator is `discriminator`. Unlike event self-CPIs these survive datasources
+/// that omit inner instructions.
+pub fn program_data_events<'a>(
+    log_messages: &'a [String],
+    discriminator: &'a [u8],
+) -> impl Iterator<Item = Vec<u8>> + 'a {
+    log_messages
+        .iter()
+        .filter_map(|line| line.strip_prefix(PROGRAM_DATA_PREFIX))
+        .filter_map(|data| base64::decode(data).ok())
+        .filter(move |data| data.starts_with(discriminator))
+        .map(|mut data| {
+            data.drain(..discriminator.len());
+            data
+        })
+}
+
+/// Pumpfun `TradeEvent` of a `mint` buy or sell, parsed from the transaction logs.
+pub fn trade_event_from_logs(
+    log_messages: &[String],
+    mint: &Pubkey,
+    is_buy: bool,
+) -> Option<TradeEvent> {
+    // The logged event carries the event discriminator without the self-CPI tag
+    program_data_events(
+        log_messages,
+        &TradeEvent::DISCRIMINATOR[ANCHOR_EVENT_CPI_TAG.len()..],
+    )
+    .find_map(|body| {
+        TradeEventView::new(&body)
+            .filter(|event| event.is_mint(mint) && event.is_buy() == is_buy)
+            .map(|event| event.to_event())
+    })
+}
@@ -0,0 +1,129 @@
+use file_rotate::{
+    ContentLimit, FileRotate, TimeFrequency,
+    compression::Compression,
+    suffix::{AppendCount, AppendTimestamp, DateFrom, FileLimit},
+};
+use std::{io::Write, path::Path};
+use tracing::{info, warn};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::{EnvFilter, Layer, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+
+use crate::{
+    config::{JOURNAL_PATH, LOG_DIR, LOG_MAX_FILE_MB, LOG_MAX_FILES, LOG_ROTATION},
+    utils::journal_layer,
+};
+
+/// Name of the active log file in `LOG_DIR`, rotated files get a date or count suffix
+const LOG_FILE: &str = "pumpfun-monitor.log";
+
+/// When the log file in `LOG_DIR` is rotated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogRotation {
+    Daily,
+    /// Once the file reaches `LOG_MAX_FILE_MB`
+    Size,
+}
+
+impl LogRotation {
+    /// `None` for an unknown value, reported once logging is up.
+    pub fn from_config(value: &str) -> Option<Self> {
+        match value {
+            "daily" => Some(LogRotation::Daily),
+            "size" => Some(LogRotation::Size),
+            _ => None,
+        }
+    }
+}
+
+/// Installs the log subscriber, filtered by `RUST_LOG` and logging `info` and above
+/// when it isn't set. Logs go to stdout unless `console` is off, e.g. while the TUI owns
+/// the terminal, and with `LOG_DIR` set, as JSON lines to a rotating file there too. Records of the `log` crate (carbon, solana clients) go
+/// through it as well. With `JOURNAL_PATH` set, the decision journal is appended to
+/// there regardless of `RUST_LOG`.
+///
+/// File writes happen on background threads; the returned guards flush them when
+/// dropped, so keep them alive until the bot exits.
+pub fn init_tracing(console: bool) -> Vec<WorkerGuard> {
+    let rotation = LogRotation::from_config(&LOG_ROTATION);
+
+    let mut guards = Vec::new();
+
+    let file_writer = LOG_DIR.as_deref().map(|dir| {
+        let (writer, guard) = tracing_appender::non_blocking(log_file(
+            Path::new(dir),
+            rotation.unwrap_or(LogRotation::Daily),
+        ));
+        guards.push(guard);
+
+        writer
+    });
+
+    let (journal, journal_error) = match JOURNAL_PATH.as_deref().map(journal_layer) {
+        Some(Ok((layer, guard))) => {
+            guards.push(guard);
+
+            (Some(layer), None)
+        }
+        Some(Err(e)) => (None, Some(e)),
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(console.then(|| fmt::layer().with_target(false).with_filter(env_filter())))
+        .with(file_writer.map(|writer| {
+            fmt::layer()
+                .json()
+                .with_current_span(true)
+                .with_span_list(false)
+                .with_writer(writer)
+                .with_filter(env_filter())
+        }))
+        .with(journal)
+        .init();
+
+    if rotation.is_none() {
+        warn!("Unknown LOG_ROTATION {}, using daily", *LOG_ROTATION);
+    }
+
+    if let Some(dir) = LOG_DIR.as_deref() {
+        info!("Writing JSON logs to {}", dir);
+    }
+
+    if let Some(path) = JOURNAL_PATH.as_deref() {
+        match journal_error {
+            None => info!("Appending the decision journal to {}", path),
+            Some(e) => warn!("Failed to open journal {}, not journaling: {}", path, e),
+        }
+    }
+
+    guards
+}
+
+/// `RUST_LOG`, or `info` and above when it isn't set.
+fn env_filter() -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"))
+}
+
+/// The log file in `dir`, rotated per `rotation`, keeping the newest `LOG_MAX_FILES`
+/// rotated files.
+fn log_file(dir: &Path, rotation: LogRotation) -> Box<dyn Write + Send> {
+    let path = dir.join(LOG_FILE);
+    let file_limit = FileLimit::MaxFiles(*LOG_MAX_FILES);
+
+    match rotation {
+        LogRotation::Daily => Box::new(FileRotate::new(
+            path,
+            AppendTimestamp::with_format("%Y-%m-%d", file_limit, DateFrom::DateYesterday),
+            ContentLimit::Time(TimeFrequency::Daily),
+            Compression::None,
+            None,
+        )),
+        LogRotation::Size => Box::new(FileRotate::new(
+            path,
+            AppendCount::new(*LOG_MAX_FILES),
+            ContentLimit::BytesSurpassed(*LOG_MAX_FILE_MB as usize * 1024 * 1024),
+            Compression::None,
+            None,
+        )),
+    }
+}
@@ -0,0 +1,105 @@
+use once_cell::sync::Lazy;
+use rand::Rng;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tracing::info;
+
+use crate::config::{MANUAL_CONFIRM_ABOVE_SOL, MANUAL_CONFIRM_TTL_SEC};
+
+/// A trade made by hand through Telegram, the control API or gRPC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManualTrade {
+    Buy { mint: Pubkey, sol_amount: u64 },
+    Sell { mint: Pubkey },
+}
+
+impl fmt::Display for ManualTrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Buy { mint, sol_amount } => write!(
+                f,
+                "buying {} with {} SOL",
+                mint,
+                *sol_amount as f64 / LAMPORTS_PER_SOL as f64
+            ),
+            Self::Sell { mint } => write!(f, "selling {}", mint),
+        }
+    }
+}
+
+/// Manual trades over a size limit waiting to be sent again with their confirmation
+/// code, so a fat-fingered amount or mint is never signed on the first try.
+pub struct ManualConfirmations {
+    limit: Option<u64>,
+    ttl: Duration,
+    pending: Mutex<HashMap<String, (ManualTrade, Instant)>>,
+}
+
+impl ManualConfirmations {
+    /// Holds trades worth more than `limit` lamports, each for `ttl`; `None` holds none.
+    pub fn new(limit: Option<u64>, ttl: Duration) -> Self {
+        Self {
+            limit,
+            ttl,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether `trade`, worth `lamports`, may go ahead. One over the limit is held and
+    /// fails with the code to send it again with; sent with that code before it expires,
+    /// it goes ahead. A code only confirms the trade it was given for, and only once.
+    pub fn check(
+        &self,
+        trade: ManualTrade,
+        lamports: u64,
+        code: Option<&str>,
+    ) -> Result<(), String> {
+        if self.limit.is_none_or(|limit| lamports <= limit) {
+            return Ok(());
+        }
+
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|_, (_, expires)| *expires > Instant::now());
+
+        if let Some(code) = code {
+            return match pending.remove(code.trim()) {
+                Some((held, _)) if held == trade => {
+                    info!("Confirmed {} by hand", trade);
+                    Ok(())
+                }
+                _ => Err("the confirmation code is wrong or expired".to_string()),
+            };
+        }
+
+        let code = loop {
+            let code = format!("{:06}", rand::thread_rng().gen_range(0..1_000_000));
+
+            if !pending.contains_key(&code) {
+                break code;
+            }
+        };
+
+        pending.insert(code.clone(), (trade, Instant::now() + self.ttl));
+
+        Err(format!(
+            "{} is worth {} SOL, over the MANUAL_CONFIRM_ABOVE_SOL limit. Send it again \
+             with confirmation code {} within {}s to go ahead",
+            trade,
+            lamports as f64 / LAMPORTS_PER_SOL as f64,
+            code,
+            self.ttl.as_secs()
+        ))
+    }
+}
+
+pub static MANUAL_CONFIRMATIONS: Lazy<ManualConfirmations> = Lazy::new(|| {
+    ManualConfirmations::new(
+        MANUAL_CONFIRM_ABOVE_SOL.map(|sol| (sol * LAMPORTS_PER_SOL as f64) as u64),
+        Duration::from_secs(*MANUAL_CONFIRM_TTL_SEC),
+    )
+});
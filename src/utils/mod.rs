@@ -1,11 +1,96 @@
-pub mod blockhash;
-pub mod build_and_sign;
-pub mod parse;
-pub mod swap_quote;
-pub mod utils;
-
-pub use blockhash::*;
-pub use build_and_sign::*;
-pub use parse::*;
-pub use swap_quote::*;
-pub use utils::*;
+pub mod allocator;
+pub mod audit_log;
+pub mod blockhash;
+pub mod buffer_pool;
+pub mod build_and_sign;
+pub mod control_api;
+pub mod control_auth;
+pub mod control_grpc;
+pub mod curve_state;
+pub mod deadline;
+pub mod dedup;
+pub mod event;
+pub mod execution_breaker;
+pub mod fee_ledger;
+pub mod health_server;
+pub mod journal;
+pub mod latency;
+pub mod launchpad;
+pub mod log_event;
+pub mod logging;
+pub mod manual_confirm;
+pub mod notifier;
+pub mod parse;
+pub mod pda;
+pub mod pending;
+pub mod pumpfun_global;
+pub mod replay;
+pub mod route;
+pub mod rpc_batch;
+pub mod runtime;
+pub mod shutdown;
+pub mod simulate;
+pub mod slippage;
+pub mod slot_lag;
+pub mod spend_policy;
+pub mod swap_quote;
+pub mod target_metrics;
+pub mod telegram_bot;
+pub mod trade_events;
+pub mod trade_log;
+pub mod trade_span;
+pub mod tui;
+pub mod tx_guard;
+pub mod tx_template;
+#[allow(clippy::module_inception)]
+pub mod utils;
+pub mod watchdog;
+pub mod webhook;
+
+pub use allocator::*;
+pub use audit_log::*;
+pub use blockhash::*;
+pub use buffer_pool::*;
+pub use build_and_sign::*;
+pub use control_api::*;
+pub use control_auth::*;
+pub use control_grpc::*;
+pub use curve_state::*;
+pub use deadline::*;
+pub use dedup::*;
+pub use event::*;
+pub use execution_breaker::*;
+pub use fee_ledger::*;
+pub use health_server::*;
+pub use journal::*;
+pub use latency::*;
+pub use launchpad::*;
+pub use log_event::*;
+pub use logging::*;
+pub use manual_confirm::*;
+pub use notifier::*;
+pub use parse::*;
+pub use pda::*;
+pub use pending::*;
+pub use pumpfun_global::*;
+pub use replay::*;
+pub use route::*;
+pub use rpc_batch::*;
+pub use runtime::*;
+pub use shutdown::*;
+pub use simulate::*;
+pub use slippage::*;
+pub use slot_lag::*;
+pub use spend_policy::*;
+pub use swap_quote::*;
+pub use target_metrics::*;
+pub use telegram_bot::*;
+pub use trade_events::*;
+pub use trade_log::*;
+pub use trade_span::*;
+pub use tui::*;
+pub use tx_guard::*;
+pub use tx_template::*;
+pub use utils::*;
+pub use watchdog::*;
+pub use webhook::*;
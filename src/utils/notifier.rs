@@ -0,0 +1,269 @@
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde_json::{Value, json};
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Signature};
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+use tracing::warn;
+
+use crate::{
+    config::{
+        SLACK_EVENTS, SLACK_WEBHOOK_URL, TELEGRAM_BOT_TOKEN, TELEGRAM_CHAT_ID, TELEGRAM_EVENTS,
+    },
+    service::senders_stubbed,
+    utils::{TradeEvent, post_webhooks, publish_trade_event},
+};
+
+static NOTIFY_CLIENT: Lazy<Client> = Lazy::new(|| {
+    Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+        .expect("Failed to build HTTP client")
+});
+
+/// SOL each wallet spent on its open position of a mint, in lamports, for the PnL of
+/// its exit
+static POSITION_COSTS: Lazy<Mutex<HashMap<(Pubkey, Pubkey), u64>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Kinds of chat notifications, each enabled per chat through `TELEGRAM_EVENTS` and
+/// `SLACK_EVENTS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyEvent {
+    /// A copied buy landed
+    Copy,
+    /// A sell closing a position landed
+    Exit,
+    /// A copy failed to send or land, or buying was paused
+    Error,
+}
+
+impl NotifyEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Copy => "copy",
+            Self::Exit => "exit",
+            Self::Error => "error",
+        }
+    }
+
+    pub fn is_enabled(&self, events: &[String]) -> bool {
+        events.iter().any(|event| event == self.as_str())
+    }
+}
+
+/// Posts `message` in the background to `TELEGRAM_CHAT_ID` and the Slack incoming
+/// webhook, to each that is configured with `event` enabled. A failed post is only
+/// logged. A replay or backtest posts nothing.
+pub fn notify(event: NotifyEvent, message: String) {
+    if senders_stubbed() {
+        return;
+    }
+
+    if let (Some(token), Some(chat_id)) = (TELEGRAM_BOT_TOKEN.as_ref(), TELEGRAM_CHAT_ID.as_ref())
+        && event.is_enabled(&TELEGRAM_EVENTS)
+    {
+        post_chat(
+            "Telegram",
+            event,
+            format!("https://api.telegram.org/bot{}/sendMessage", token),
+            json!({
+                "chat_id": chat_id,
+                "text": message,
+                "disable_web_page_preview": true,
+            }),
+        );
+    }
+
+    if let Some(url) = SLACK_WEBHOOK_URL.as_ref()
+        && event.is_enabled(&SLACK_EVENTS)
+    {
+        post_chat(
+            "Slack",
+            event,
+            url.clone(),
+            json!({
+                "text": message,
+                "unfurl_links": false,
+            }),
+        );
+    }
+}
+
+fn post_chat(chat: &'static str, event: NotifyEvent, url: String, body: Value) {
+    tokio::spawn(async move {
+        match NOTIFY_CLIENT.post(&url).json(&body).send().await {
+            Ok(response) if response.status().is_success() => {}
+            Ok(response) => warn!(
+                "{} rejected {} notification: {}",
+                chat,
+                event.as_str(),
+                response.status()
+            ),
+            // The URL holds the bot token or webhook secret, keep it out of the logs
+            Err(e) => warn!(
+                "Failed to send {} notification to {}: {}",
+                event.as_str(),
+                chat,
+                e.without_url()
+            ),
+        }
+    });
+}
+
+/// Notifies a landed copy buy of `tokens` for `sol_spent` lamports on the chats and
+/// webhooks, and remembers its cost for the PnL of the exit.
+pub fn notify_fill(
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    sol_spent: Option<u64>,
+    tokens: Option<f64>,
+    signature: &Signature,
+) {
+    if let Some(sol_spent) = sol_spent {
+        *POSITION_COSTS
+            .lock()
+            .unwrap()
+            .entry((*wallet, *mint))
+            .or_default() += sol_spent;
+    }
+
+    notify(
+        NotifyEvent::Copy,
+        fill_message(mint, sol_spent, tokens, signature),
+    );
+    post_webhooks(
+        NotifyEvent::Copy.as_str(),
+        json!({
+            "wallet": wallet.to_string(),
+            "mint": mint.to_string(),
+            "sol_spent_lamports": sol_spent,
+            "tokens": tokens,
+            "signature": signature.to_string(),
+        }),
+    );
+    publish_trade_event(TradeEvent {
+        wallet: Some(*wallet),
+        mint: Some(*mint),
+        sol_lamports: sol_spent,
+        tokens,
+        signature: Some(*signature),
+        ..TradeEvent::new(NotifyEvent::Copy)
+    });
+}
+
+/// Notifies a landed sell of `tokens` for `sol_received` lamports on the chats and
+/// webhooks, with its PnL when the position's cost is known.
+pub fn notify_exit(
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    sol_received: Option<u64>,
+    tokens: Option<f64>,
+    signature: &Signature,
+) {
+    let cost = POSITION_COSTS.lock().unwrap().remove(&(*wallet, *mint));
+    let pnl = sol_received
+        .zip(cost)
+        .map(|(received, cost)| received as i64 - cost as i64);
+
+    notify(
+        NotifyEvent::Exit,
+        exit_message(mint, sol_received, tokens, pnl, signature),
+    );
+    post_webhooks(
+        NotifyEvent::Exit.as_str(),
+        json!({
+            "wallet": wallet.to_string(),
+            "mint": mint.to_string(),
+            "sol_received_lamports": sol_received,
+            "tokens": tokens,
+            "pnl_lamports": pnl,
+            "signature": signature.to_string(),
+        }),
+    );
+    publish_trade_event(TradeEvent {
+        wallet: Some(*wallet),
+        mint: Some(*mint),
+        sol_lamports: sol_received,
+        tokens,
+        pnl_lamports: pnl,
+        signature: Some(*signature),
+        ..TradeEvent::new(NotifyEvent::Exit)
+    });
+}
+
+pub fn notify_error(message: String) {
+    post_webhooks(NotifyEvent::Error.as_str(), json!({ "message": message }));
+    publish_trade_event(TradeEvent {
+        message: Some(message.clone()),
+        ..TradeEvent::new(NotifyEvent::Error)
+    });
+    notify(NotifyEvent::Error, format!("Error: {}", message));
+}
+
+pub fn fill_message(
+    mint: &Pubkey,
+    sol_spent: Option<u64>,
+    tokens: Option<f64>,
+    signature: &Signature,
+) -> String {
+    format!(
+        "Copied buy\nMint: {}\nSpent: {}\nTokens: {}\nPrice: {}\n{}",
+        mint,
+        format_sol(sol_spent),
+        format_tokens(tokens),
+        format_price(sol_spent, tokens),
+        tx_link(signature)
+    )
+}
+
+pub fn exit_message(
+    mint: &Pubkey,
+    sol_received: Option<u64>,
+    tokens: Option<f64>,
+    pnl: Option<i64>,
+    signature: &Signature,
+) -> String {
+    let pnl = match pnl {
+        Some(pnl) => format!("{:+.4} SOL", pnl as f64 / LAMPORTS_PER_SOL as f64),
+        None => "unknown".to_string(),
+    };
+
+    format!(
+        "Exited\nMint: {}\nReceived: {}\nTokens: {}\nPrice: {}\nPnL: {}\n{}",
+        mint,
+        format_sol(sol_received),
+        format_tokens(tokens),
+        format_price(sol_received, tokens),
+        pnl,
+        tx_link(signature)
+    )
+}
+
+pub fn tx_link(signature: &Signature) -> String {
+    format!("https://solscan.io/tx/{}", signature)
+}
+
+fn format_sol(lamports: Option<u64>) -> String {
+    match lamports {
+        Some(lamports) => format!("{:.4} SOL", lamports as f64 / LAMPORTS_PER_SOL as f64),
+        None => "unknown".to_string(),
+    }
+}
+
+fn format_tokens(tokens: Option<f64>) -> String {
+    match tokens {
+        Some(tokens) => format!("{:.2}", tokens),
+        None => "unknown".to_string(),
+    }
+}
+
+/// SOL per token.
+fn format_price(lamports: Option<u64>, tokens: Option<f64>) -> String {
+    match (lamports, tokens) {
+        (Some(lamports), Some(tokens)) if tokens > 0.0 => format!(
+            "{:.10} SOL",
+            lamports as f64 / LAMPORTS_PER_SOL as f64 / tokens
+        ),
+        _ => "unknown".to_string(),
+    }
+}
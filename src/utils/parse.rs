@@ -1,8 +1,6 @@
-use borsh::{BorshDeserialize, BorshSerialize};
-use carbon_core::{CarbonDeserialize, borsh};
 use solana_sdk::pubkey::Pubkey;
 use solana_transaction_status_client_types::TransactionTokenBalance;
-use yellowstone_grpc_proto::prelude::{Message, TransactionStatusMeta};
+use yellowstone_grpc_proto::prelude::Message;
 
 pub fn get_pre_post_token_balance(
     pre_token_balance: Vec<TransactionTokenBalance>,
@@ -14,7 +12,7 @@ pub fn get_pre_post_token_balance(
         balances
             .iter()
             .find(|tb| tb.owner == pool_addr && tb.mint == token_mint)
-            .and_then(|tb| Some(tb.ui_token_amount.clone()))
+            .map(|tb| tb.ui_token_amount.clone())
             .and_then(|ui| ui.amount.parse::<u64>().ok())
             .unwrap_or(0)
     };
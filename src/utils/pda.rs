@@ -0,0 +1,70 @@
+use once_cell::sync::Lazy;
+use solana_sdk::pubkey::Pubkey;
+use std::{collections::HashMap, sync::RwLock};
+
+/// Derivations kept before the cache is cleared, bounding memory on long runs
+const PDA_CACHE_LIMIT: usize = 50_000;
+
+/// Program derived addresses by program and seeds. Repeat trades on a mint derive the
+/// same ATAs, creator vault and volume accumulators, so each is hashed only once.
+pub struct PdaCache {
+    addresses: RwLock<HashMap<(Pubkey, Vec<u8>), Pubkey>>,
+}
+
+pub static PDA_CACHE: Lazy<PdaCache> = Lazy::new(PdaCache::new);
+
+impl Default for PdaCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdaCache {
+    pub fn new() -> Self {
+        Self {
+            addresses: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn find(&self, seeds: &[&[u8]], program_id: &Pubkey) -> Pubkey {
+        let key = (*program_id, seeds.concat());
+
+        if let Some(address) = self.addresses.read().unwrap().get(&key) {
+            return *address;
+        }
+
+        let (address, _bump) = Pubkey::find_program_address(seeds, program_id);
+
+        let mut addresses = self.addresses.write().unwrap();
+
+        if addresses.len() >= PDA_CACHE_LIMIT {
+            addresses.clear();
+        }
+
+        addresses.insert(key, address);
+
+        address
+    }
+}
+
+/// Cached `Pubkey::find_program_address`.
+pub fn find_pda(seeds: &[&[u8]], program_id: &Pubkey) -> Pubkey {
+    PDA_CACHE.find(seeds, program_id)
+}
+
+/// Cached `get_associated_token_address_with_program_id`.
+pub fn associated_token_address_with_program_id(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Pubkey {
+    find_pda(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &spl_associated_token_account::ID,
+    )
+}
+
+/// Cached `get_associated_token_address` for SPL Token mints.
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    associated_token_address_with_program_id(owner, mint, &spl_token::ID)
+}
@@ -0,0 +1,40 @@
+use once_cell::sync::Lazy;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::config::PENDING_ACTION_TTL_SEC;
+
+/// Copy actions recently started per target transaction, traded mint and side. Unlike
+/// `SEEN_INSTRUCTIONS`, which is keyed by instruction, this catches several instructions
+/// of one transaction (or retried deliveries) that amount to the same trade.
+pub struct PendingActions {
+    ttl: Duration,
+    actions: Mutex<HashMap<(Signature, Pubkey, bool), Instant>>,
+}
+
+pub static PENDING_ACTIONS: Lazy<PendingActions> =
+    Lazy::new(|| PendingActions::new(Duration::from_secs(*PENDING_ACTION_TTL_SEC)));
+
+impl PendingActions {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            actions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Claims the copy of the `mint` buy or sell in target transaction `signature`.
+    /// Returns false when another copy attempt for it started within the last `ttl`.
+    pub fn claim(&self, signature: Signature, mint: Pubkey, is_buy: bool) -> bool {
+        let now = Instant::now();
+        let mut actions = self.actions.lock().unwrap();
+
+        actions.retain(|_, started_at| now.duration_since(*started_at) < self.ttl);
+
+        actions.insert((signature, mint, is_buy), now).is_none()
+    }
+}
@@ -0,0 +1,116 @@
+use carbon_core::deserialize::CarbonDeserialize;
+use carbon_pumpfun_decoder::accounts::global::Global;
+use once_cell::sync::Lazy;
+use rand::seq::SliceRandom;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use std::sync::{
+    Arc, RwLock,
+    atomic::{AtomicU64, Ordering},
+};
+use tokio::time::{Duration, sleep};
+use tracing::warn;
+
+use crate::config::PUMPFUN_GLOBAL_REFRESH_SEC;
+
+/// Pumpfun Global config account holding the protocol and creator fees
+pub const PUMPFUN_GLOBAL: Pubkey = pubkey!("4wTV1YmiEkRvAtNtsSGPtUrqRYQMe5SKy2uB4Jjaxnjf");
+
+/// Primary pumpfun fee recipient, used until the Global account is first fetched
+pub const PUMPFUN_FEE_RECIPIENT: Pubkey = pubkey!("CebN5WGQ4jvEPvsVU4EoHEpgzq1VV7AbicfhtW4xC9iM");
+
+// Current on-chain values, used until the Global account is first fetched
+static FEE_BASIS_POINTS: AtomicU64 = AtomicU64::new(95);
+static CREATOR_FEE_BASIS_POINTS: AtomicU64 = AtomicU64::new(5);
+
+/// Fee recipients the program accepts: the primary one, then the extra `fee_recipients`
+static FEE_RECIPIENTS: Lazy<RwLock<Vec<Pubkey>>> =
+    Lazy::new(|| RwLock::new(vec![PUMPFUN_FEE_RECIPIENT]));
+
+/// Protocol and creator fees charged on pumpfun trades, in basis points of the SOL amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PumpfunFees {
+    pub fee_basis_points: u64,
+    pub creator_fee_basis_points: u64,
+}
+
+impl PumpfunFees {
+    /// Fees the program charges on a curve trade of `sol_amount`, each rounded up.
+    pub fn fee_on(&self, sol_amount: u64) -> u64 {
+        [self.fee_basis_points, self.creator_fee_basis_points]
+            .iter()
+            .map(|basis_points| {
+                (sol_amount as u128 * *basis_points as u128).div_ceil(10_000) as u64
+            })
+            .sum()
+    }
+}
+
+/// Current fees from the Global account.
+pub fn pumpfun_fees() -> PumpfunFees {
+    PumpfunFees {
+        fee_basis_points: FEE_BASIS_POINTS.load(Ordering::Relaxed),
+        creator_fee_basis_points: CREATOR_FEE_BASIS_POINTS.load(Ordering::Relaxed),
+    }
+}
+
+/// Protocol plus creator fee charged on pumpfun trades, as a fraction of the SOL amount.
+pub fn pumpfun_fee_rate() -> f64 {
+    (FEE_BASIS_POINTS.load(Ordering::Relaxed) + CREATOR_FEE_BASIS_POINTS.load(Ordering::Relaxed))
+        as f64
+        / 10_000.0
+}
+
+/// Applies the fees of a Global account update or `set_params` call.
+pub fn set_pumpfun_fees(fee_basis_points: u64, creator_fee_basis_points: u64) {
+    FEE_BASIS_POINTS.store(fee_basis_points, Ordering::Relaxed);
+    CREATOR_FEE_BASIS_POINTS.store(creator_fee_basis_points, Ordering::Relaxed);
+}
+
+/// Every fee recipient of the Global account.
+pub fn pumpfun_fee_recipients() -> Vec<Pubkey> {
+    FEE_RECIPIENTS.read().unwrap().clone()
+}
+
+/// A fee recipient for a trade we build ourselves, picked at random so our trades
+/// don't all write-lock the same account.
+pub fn pumpfun_fee_recipient() -> Pubkey {
+    FEE_RECIPIENTS
+        .read()
+        .unwrap()
+        .choose(&mut rand::thread_rng())
+        .copied()
+        .unwrap_or(PUMPFUN_FEE_RECIPIENT)
+}
+
+/// Applies a fetched or streamed Global account: fees and fee recipients.
+pub fn apply_pumpfun_global(global: &Global) {
+    set_pumpfun_fees(global.fee_basis_points, global.creator_fee_basis_points);
+
+    let recipients: Vec<Pubkey> = std::iter::once(global.fee_recipient)
+        .chain(global.fee_recipients)
+        .filter(|recipient| *recipient != Pubkey::default())
+        .collect();
+
+    if !recipients.is_empty() {
+        *FEE_RECIPIENTS.write().unwrap() = recipients;
+    }
+}
+
+/// Keeps the pumpfun fees and fee recipients in sync with the Global account. Updates
+/// streamed by the gRPC datasources apply in between refreshes.
+pub async fn pumpfun_global_handler(rpc_client: Arc<RpcClient>) {
+    loop {
+        match rpc_client.get_account_data(&PUMPFUN_GLOBAL).await {
+            Ok(data) => match <Global as CarbonDeserialize>::deserialize(&data) {
+                Some(global) => apply_pumpfun_global(&global),
+                None => warn!("Failed to parse the pumpfun Global account"),
+            },
+            Err(e) => {
+                warn!("Failed to fetch the pumpfun Global account: {}", e);
+            }
+        }
+
+        sleep(Duration::from_secs(*PUMPFUN_GLOBAL_REFRESH_SEC)).await;
+    }
+}
@@ -0,0 +1,40 @@
+use once_cell::sync::Lazy;
+use solana_sdk::signature::Signature;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::config::DEDUP_TTL_SEC;
+
+/// Target transactions delivered late by a replay after a reconnect. They still go
+/// through the processors to keep the local state current, but are never bought into.
+pub struct ReplayedTransactions {
+    ttl: Duration,
+    replayed: Mutex<HashMap<Signature, Instant>>,
+}
+
+pub static REPLAYED_TRANSACTIONS: Lazy<ReplayedTransactions> =
+    Lazy::new(|| ReplayedTransactions::new(Duration::from_secs(*DEDUP_TTL_SEC)));
+
+impl ReplayedTransactions {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            replayed: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn mark(&self, signature: Signature) {
+        let now = Instant::now();
+        let mut replayed = self.replayed.lock().unwrap();
+
+        replayed.retain(|_, marked_at| now.duration_since(*marked_at) < self.ttl);
+        replayed.insert(signature, now);
+    }
+
+    pub fn is_replayed(&self, signature: &Signature) -> bool {
+        self.replayed.lock().unwrap().contains_key(signature)
+    }
+}
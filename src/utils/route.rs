@@ -0,0 +1,155 @@
+use carbon_core::deserialize::CarbonDeserialize;
+use carbon_pumpfun_decoder::{
+    PROGRAM_ID as PUMPFUN_PROGRAM_ID, instructions::trade_event::TradeEvent,
+};
+use solana_sdk::{pubkey, pubkey::Pubkey};
+use solana_transaction_status_client_types::{InnerInstruction, InnerInstructions};
+
+use crate::config::EXTRA_ROUTER_PROGRAMS;
+
+/// Aggregators and trading-bot routers known to CPI into pumpfun on behalf of the signer.
+/// Bots that send plain pumpfun instructions (e.g. BullX, Trojan) need no entry here.
+pub const ROUTER_PROGRAMS: &[(&str, Pubkey)] = &[
+    (
+        "Jupiter v6",
+        pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4"),
+    ),
+    (
+        "Jupiter v4",
+        pubkey!("JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB"),
+    ),
+    (
+        "Photon",
+        pubkey!("BSfD6SHZigAfDWSjzD5Q41jw8LmKwtmjskPH9XW1mrRW"),
+    ),
+];
+
+/// Name of the aggregator or router behind `program_id`, including the ones added
+/// through `EXTRA_ROUTER_PROGRAMS`.
+pub fn router_name(program_id: &Pubkey) -> Option<String> {
+    ROUTER_PROGRAMS
+        .iter()
+        .map(|(name, router)| (*name, router))
+        .chain(
+            EXTRA_ROUTER_PROGRAMS
+                .iter()
+                .map(|(name, router)| (name.as_str(), router)),
+        )
+        .find(|(_, router)| *router == program_id)
+        .map(|(name, _)| name.to_string())
+}
+
+/// Finds the `TradeEvent` self-CPI emitted by the pumpfun instruction at
+/// `absolute_path` inside top-level instruction `index`. Works for direct trades as
+/// well as trades nested under aggregators, and picks the event of this exact
+/// instruction when one transaction contains several pumpfun trades.
+pub fn find_trade_event<'a>(
+    inner_instructions: &'a [InnerInstructions],
+    account_keys: &[Pubkey],
+    index: u32,
+    absolute_path: &[u8],
+    event_authority: &Pubkey,
+) -> Option<&'a InnerInstruction> {
+    find_event_cpi(
+        inner_instructions,
+        account_keys,
+        index,
+        absolute_path,
+        &PUMPFUN_PROGRAM_ID,
+        event_authority,
+        TradeEvent::DISCRIMINATOR,
+    )
+}
+
+/// Finds the anchor event self-CPI starting with `discriminator` that `program_id`
+/// emitted from the instruction at `absolute_path` inside top-level instruction `index`.
+pub fn find_event_cpi<'a>(
+    inner_instructions: &'a [InnerInstructions],
+    account_keys: &[Pubkey],
+    index: u32,
+    absolute_path: &[u8],
+    program_id: &Pubkey,
+    event_authority: &Pubkey,
+    discriminator: &[u8],
+) -> Option<&'a InnerInstruction> {
+    let group = inner_instructions
+        .iter()
+        .find(|group| group.index as u32 == index)?;
+
+    let depth = absolute_path.len().max(1);
+
+    let is_trade_event = |inner_ix: &&InnerInstruction| {
+        let cpi_program_id = account_keys.get(inner_ix.instruction.program_id_index as usize);
+        let first_account = inner_ix
+            .instruction
+            .accounts
+            .first()
+            .and_then(|index| account_keys.get(*index as usize));
+
+        cpi_program_id == Some(program_id)
+            && first_account == Some(event_authority)
+            && inner_ix.instruction.data.starts_with(discriminator)
+    };
+
+    // Without stack heights carbon numbers every inner instruction as if it were
+    // top-level, so `absolute_path` is its position in the group. When that position
+    // holds a call into the program, the event follows it; otherwise this is the
+    // top-level instruction itself and its first event is the one
+    if group
+        .instructions
+        .iter()
+        .all(|inner_ix| inner_ix.stack_height.is_none())
+    {
+        let position = absolute_path
+            .first()
+            .map_or(0, |position| *position as usize);
+
+        let is_program_call = group.instructions.get(position).is_some_and(|inner_ix| {
+            account_keys.get(inner_ix.instruction.program_id_index as usize) == Some(program_id)
+                && !inner_ix.instruction.data.starts_with(discriminator)
+        });
+
+        return if is_program_call {
+            group.instructions[position + 1..]
+                .iter()
+                .find(is_trade_event)
+        } else {
+            group.instructions.iter().find(is_trade_event)
+        };
+    }
+
+    // Rebuild the instruction paths the same way carbon numbers them, to find where
+    // this instruction sits in the group. A top-level instruction is the group owner
+    let start = if depth == 1 {
+        0
+    } else {
+        let mut path = vec![0u8; depth];
+        path[0] = group.index;
+        let mut prev_height = 0;
+
+        group.instructions.iter().position(|inner_ix| {
+            let height = inner_ix.stack_height.unwrap_or(1) as usize;
+
+            if height > path.len() {
+                path.resize(height, 0);
+            }
+
+            if height > prev_height {
+                path[height - 1] = 0;
+            } else {
+                path[height - 1] += 1;
+            }
+
+            prev_height = height;
+
+            height == depth && path[..depth] == absolute_path[..]
+        })? + 1
+    };
+
+    // The event is a direct child of this instruction, before control returns to its caller
+    group.instructions[start..]
+        .iter()
+        .take_while(|inner_ix| inner_ix.stack_height.unwrap_or(1) as usize > depth)
+        .filter(|inner_ix| inner_ix.stack_height.unwrap_or(1) as usize == depth + 1)
+        .find(is_trade_event)
+}
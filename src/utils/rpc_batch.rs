@@ -0,0 +1,61 @@
+use solana_client::{client_error::ClientError, nonblocking::rpc_client::RpcClient};
+use solana_sdk::{account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey};
+
+/// Most accounts a single `getMultipleAccounts` request may ask for
+pub const MULTIPLE_ACCOUNTS_LIMIT: usize = 100;
+
+/// Offset of the amount in an SPL token account, the same for Token-2022 accounts
+const TOKEN_ACCOUNT_AMOUNT: usize = 64;
+
+/// Fetches `addresses` with as few `getMultipleAccounts` requests as possible, in order,
+/// with `None` for accounts that don't exist.
+pub async fn get_multiple_accounts(
+    rpc_client: &RpcClient,
+    addresses: &[Pubkey],
+    commitment: CommitmentConfig,
+) -> Result<Vec<Option<Account>>, ClientError> {
+    let mut accounts = Vec::with_capacity(addresses.len());
+
+    for chunk in addresses.chunks(MULTIPLE_ACCOUNTS_LIMIT) {
+        let response = rpc_client
+            .get_multiple_accounts_with_commitment(chunk, commitment)
+            .await?;
+
+        accounts.extend(response.value);
+    }
+
+    Ok(accounts)
+}
+
+/// SOL balances of `addresses` in lamports, zero for accounts that don't exist.
+pub async fn get_balances(
+    rpc_client: &RpcClient,
+    addresses: &[Pubkey],
+) -> Result<Vec<u64>, ClientError> {
+    Ok(
+        get_multiple_accounts(rpc_client, addresses, CommitmentConfig::confirmed())
+            .await?
+            .into_iter()
+            .map(|account| account.map_or(0, |account| account.lamports))
+            .collect(),
+    )
+}
+
+/// Token amounts held by `token_accounts`, `None` for accounts that don't exist.
+pub async fn get_token_account_balances(
+    rpc_client: &RpcClient,
+    token_accounts: &[Pubkey],
+) -> Result<Vec<Option<u64>>, ClientError> {
+    Ok(
+        get_multiple_accounts(rpc_client, token_accounts, CommitmentConfig::confirmed())
+            .await?
+            .into_iter()
+            .map(|account| account.and_then(|account| token_account_amount(&account.data)))
+            .collect(),
+    )
+}
+
+fn token_account_amount(data: &[u8]) -> Option<u64> {
+    data.get(TOKEN_ACCOUNT_AMOUNT..TOKEN_ACCOUNT_AMOUNT + 8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+}
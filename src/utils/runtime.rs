@@ -0,0 +1,64 @@
+use once_cell::sync::Lazy;
+use std::{
+    future::Future,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use tokio::runtime::{Builder, Runtime};
+use tracing::warn;
+
+use crate::config::{EXECUTION_CORES, EXECUTION_RUNTIME_THREADS};
+
+/// Runtime the copy order workers run on, so sending never queues behind background
+/// work. Its threads are pinned to `EXECUTION_CORES` when cores are configured.
+pub static EXECUTION_RUNTIME: Lazy<Runtime> = Lazy::new(|| {
+    let next_core = AtomicUsize::new(0);
+
+    Builder::new_multi_thread()
+        .worker_threads(*EXECUTION_RUNTIME_THREADS)
+        .thread_name("execution")
+        .on_thread_start(move || {
+            if EXECUTION_CORES.is_empty() {
+                return;
+            }
+
+            let core =
+                EXECUTION_CORES[next_core.fetch_add(1, Ordering::Relaxed) % EXECUTION_CORES.len()];
+
+            if !core_affinity::set_for_current(core_affinity::CoreId { id: core }) {
+                warn!("Failed to pin an execution thread to core {}", core);
+            }
+        })
+        .enable_all()
+        .build()
+        .expect("Failed to build the execution runtime")
+});
+
+/// Single-threaded runtime for periodic background tasks (blockhash refresh, balance
+/// polling, stats), keeping them off the runtime that processes detections.
+pub static BACKGROUND_RUNTIME: Lazy<Runtime> = Lazy::new(|| {
+    Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("background")
+        .enable_all()
+        .build()
+        .expect("Failed to build the background runtime")
+});
+
+/// Runtime the requests to remote signers run on. Signing is synchronous, so callers
+/// block on it rather than on the runtime they're on.
+pub static SIGNER_RUNTIME: Lazy<Runtime> = Lazy::new(|| {
+    Builder::new_multi_thread()
+        .worker_threads(2)
+        .thread_name("signer")
+        .enable_all()
+        .build()
+        .expect("Failed to build the signer runtime")
+});
+
+/// Spawns a background task on `BACKGROUND_RUNTIME`.
+pub fn spawn_background<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    BACKGROUND_RUNTIME.spawn(future);
+}
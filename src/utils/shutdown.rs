@@ -0,0 +1,157 @@
+use carbon_core::metrics::MetricsCollection;
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    collections::HashSet,
+    future::Future,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+use tokio::{
+    signal::{
+        self,
+        unix::{SignalKind, signal as unix_signal},
+    },
+    time::{sleep, timeout},
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::{
+    config::{POSITIONS_PATH, SHADOW_MODE, SHUTDOWN_TIMEOUT_SEC},
+    processor::{ORDER_EXECUTOR, sell_mint},
+    wallet::{WALLET_POOL, paper_trading, save_positions},
+};
+
+/// Set once the bot starts shutting down, no buys go out after that
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Sent orders still waiting to confirm
+static PENDING_CONFIRMATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether the bot is shutting down, buys are skipped then.
+pub fn shutting_down() -> bool {
+    SHUTTING_DOWN.load(Ordering::Relaxed)
+}
+
+/// Runs the confirmation of a sent order, counted so a shutdown waits for it.
+pub async fn track_confirmation<F: Future>(confirmation: F) -> F::Output {
+    PENDING_CONFIRMATIONS.fetch_add(1, Ordering::SeqCst);
+    let output = confirmation.await;
+    PENDING_CONFIRMATIONS.fetch_sub(1, Ordering::SeqCst);
+
+    output
+}
+
+/// Cancels `shutdown` on SIGTERM. The pipeline handles SIGINT itself.
+pub async fn sigterm_handler(shutdown: CancellationToken) {
+    let mut sigterm = match unix_signal(SignalKind::terminate()) {
+        Ok(sigterm) => sigterm,
+        Err(e) => {
+            warn!("Failed to listen for SIGTERM: {}", e);
+            return;
+        }
+    };
+
+    sigterm.recv().await;
+
+    info!("Received SIGTERM, shutting down...");
+    shutdown.cancel();
+}
+
+/// Winds the bot down once the pipeline has stopped: stops buying, waits for the
+/// queued orders to go out and the sent ones to confirm, sells every open pump.fun
+/// curve position if `liquidate` is set, then saves the positions to
+/// `POSITIONS_PATH`. Waiting is bounded by `SHUTDOWN_TIMEOUT_SEC`, and another
+/// SIGINT or SIGTERM skips it.
+pub async fn graceful_shutdown(liquidate: bool) {
+    SHUTTING_DOWN.store(true, Ordering::Relaxed);
+
+    info!("Shutting down, no new buys, waiting for in-flight orders...");
+
+    let wind_down = async {
+        drain_orders().await;
+
+        if liquidate {
+            liquidate_positions().await;
+            drain_orders().await;
+        }
+    };
+
+    tokio::select! {
+        result = timeout(Duration::from_secs(*SHUTDOWN_TIMEOUT_SEC), wind_down) => {
+            if result.is_err() {
+                warn!(
+                    "ALERT: Orders still in flight after {}s, shutting down anyway",
+                    *SHUTDOWN_TIMEOUT_SEC
+                );
+            }
+        }
+        _ = second_signal() => {
+            warn!("ALERT: Interrupted again, shutting down without waiting for orders");
+        }
+    }
+
+    flush_positions();
+}
+
+/// Waits for another SIGINT or SIGTERM.
+async fn second_signal() {
+    match unix_signal(SignalKind::terminate()) {
+        Ok(mut sigterm) => {
+            tokio::select! {
+                _ = signal::ctrl_c() => {}
+                _ = sigterm.recv() => {}
+            }
+        }
+        Err(_) => {
+            let _ = signal::ctrl_c().await;
+        }
+    }
+}
+
+/// Waits until every queued order is submitted and every sent one confirmed.
+async fn drain_orders() {
+    while !ORDER_EXECUTOR.is_idle() || PENDING_CONFIRMATIONS.load(Ordering::SeqCst) > 0 {
+        sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Sells every mint a wallet holds, see `sell_mint`.
+async fn liquidate_positions() {
+    let metrics = Arc::new(MetricsCollection::new(vec![]));
+    let mints: HashSet<Pubkey> = WALLET_POOL
+        .positions()
+        .into_iter()
+        .map(|(_, mint, _)| mint)
+        .collect();
+
+    info!(
+        "Liquidating {} open position(s) before exiting...",
+        mints.len()
+    );
+
+    for mint in mints {
+        if let Err(reason) = sell_mint(mint, &metrics).await {
+            warn!("ALERT: Failed to liquidate {}: {}", mint, reason);
+        }
+    }
+}
+
+/// Saves the positions for the next start. Paper and shadow positions are virtual, they
+/// aren't saved.
+fn flush_positions() {
+    if paper_trading() || *SHADOW_MODE {
+        return;
+    }
+
+    match save_positions(&POSITIONS_PATH) {
+        Ok(saved) => info!("Saved {} open position(s) to {}", saved, *POSITIONS_PATH),
+        Err(e) => warn!(
+            "ALERT: Failed to save positions to {}: {}",
+            *POSITIONS_PATH, e
+        ),
+    }
+}
@@ -0,0 +1,151 @@
+use carbon_pumpfun_decoder::PROGRAM_ID as PUMPFUN_PROGRAM_ID;
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde_json::{Value, json};
+use solana_sdk::{hash::Hash, instruction::Instruction, signer::Signer};
+use tracing::{info, warn};
+
+use crate::{
+    config::{
+        SHADOW_MODE, SIMULATE_BEFORE_SEND, SIMULATION_RPC_ENDPOINT, SIMULATION_SLIPPAGE_BUMP,
+    },
+    error::{ClientError, ClientResult},
+    instructions::{buy_ix::BUY_DISCRIMINATOR, sell_ix::SELL_DISCRIMINATOR},
+    service::SendErrorKind,
+    utils::{JOURNAL_SKIP, build_and_sign},
+};
+
+/// Pumpfun custom error raised when a buy costs more than `max_sol_cost`
+pub const TOO_MUCH_SOL_REQUIRED: u32 = 6002;
+/// Pumpfun custom error raised when a sell returns less than `min_sol_output`
+pub const TOO_LITTLE_SOL_RECEIVED: u32 = 6003;
+
+static SIMULATION_CLIENT: Lazy<Client> = Lazy::new(Client::new);
+
+/// Runs `simulateTransaction` for an encoded transaction against the simulation RPC.
+pub async fn simulate_transaction(encoded_tx: &str) -> ClientResult<()> {
+    let payload = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "simulateTransaction",
+        "params": [
+            encoded_tx,
+            { "encoding": "base64", "commitment": "processed", "sigVerify": false }
+        ]
+    });
+
+    let response = SIMULATION_CLIENT
+        .post(SIMULATION_RPC_ENDPOINT.as_str())
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| {
+            ClientError::Send(
+                "simulateTransaction request failed".to_string(),
+                e.to_string(),
+            )
+        })?;
+
+    let data: Value = response.json().await.map_err(|e| {
+        ClientError::Parse("simulateTransaction response".to_string(), e.to_string())
+    })?;
+
+    if let Some(err) = data.get("error") {
+        return Err(ClientError::Solana(
+            "simulateTransaction rejected".to_string(),
+            err.to_string(),
+        ));
+    }
+
+    match &data["result"]["value"]["err"] {
+        Value::Null => Ok(()),
+        err => Err(ClientError::SimulationError(err.to_string())),
+    }
+}
+
+fn is_slippage_error(message: &str) -> bool {
+    [TOO_MUCH_SOL_REQUIRED, TOO_LITTLE_SOL_RECEIVED]
+        .iter()
+        .any(|code| message.contains(&format!("\"Custom\":{}", code)))
+}
+
+/// Widens the SOL limit of every pumpfun buy/sell instruction by `bump` (0.05 = 5%).
+pub fn widen_slippage(ixs: &mut [Instruction], bump: f64) {
+    for ix in ixs
+        .iter_mut()
+        .filter(|ix| ix.program_id == PUMPFUN_PROGRAM_ID)
+    {
+        if ix.data.len() < 24 {
+            continue;
+        }
+
+        let factor = if ix.data.starts_with(&BUY_DISCRIMINATOR) {
+            1.0 + bump
+        } else if ix.data.starts_with(&SELL_DISCRIMINATOR) {
+            (1.0 - bump).max(0.0)
+        } else {
+            continue;
+        };
+
+        let limit = u64::from_le_bytes(ix.data[16..24].try_into().unwrap());
+        let widened = (limit as f64 * factor) as u64;
+        ix.data[16..24].copy_from_slice(&widened.to_le_bytes());
+    }
+}
+
+/// Builds and signs the transaction, gating it on a successful simulation when
+/// `SIMULATE_BEFORE_SEND` or `SHADOW_MODE` is enabled. Fails when the trade should be
+/// skipped.
+pub async fn build_and_simulate(
+    mut ixs: Vec<Instruction>,
+    recent_blockhash: Hash,
+    signers: &[&(dyn Signer + Sync)],
+) -> Result<String, SendErrorKind> {
+    let sign = |ixs: Vec<Instruction>| {
+        build_and_sign(ixs, recent_blockhash, None, signers).map_err(|e| {
+            warn!(target: JOURNAL_SKIP, "Signing failed, skipping trade: {}", e);
+            SendErrorKind::SigningFailed(e.to_string())
+        })
+    };
+
+    if !*SIMULATE_BEFORE_SEND && !*SHADOW_MODE {
+        return sign(ixs);
+    }
+
+    let encoded_tx = sign(ixs.clone())?;
+
+    match simulate_transaction(&encoded_tx).await {
+        Ok(()) => Ok(encoded_tx),
+        Err(ClientError::SimulationError(msg)) => match *SIMULATION_SLIPPAGE_BUMP {
+            Some(bump) if is_slippage_error(&msg) => {
+                info!(
+                    "Simulation hit slippage limit, retrying with {:.2}% extra slippage",
+                    bump * 100.0
+                );
+
+                widen_slippage(&mut ixs, bump);
+                let encoded_tx = sign(ixs)?;
+
+                match simulate_transaction(&encoded_tx).await {
+                    Ok(()) => Ok(encoded_tx),
+                    Err(err) => {
+                        info!(target: JOURNAL_SKIP,
+                            "Simulation failed after slippage adjustment, skipping trade: {}",
+                            err
+                        );
+                        Err(SendErrorKind::SimulationFailed)
+                    }
+                }
+            }
+            _ => {
+                warn!(target: JOURNAL_SKIP, "Simulation failed, skipping trade: {}", msg);
+                Err(SendErrorKind::SimulationFailed)
+            }
+        },
+        Err(err) => {
+            // The simulation RPC itself is unavailable; don't let it block trading
+            warn!("Simulation unavailable, sending without it: {}", err);
+            Ok(encoded_tx)
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::{
+    config::{DYNAMIC_SLIPPAGE, MAX_SLIPPAGE, MIN_SLIPPAGE, SLIPPAGE_IMPACT_MULTIPLIER},
+    utils::bonding_curve_price_impact,
+};
+
+/// Slippage for a bonding curve trade moving `sol_amount` against `virtual_sol_reserves`.
+/// With `DYNAMIC_SLIPPAGE`, the trade's own price impact times `SLIPPAGE_IMPACT_MULTIPLIER`,
+/// kept between `MIN_SLIPPAGE` and `MAX_SLIPPAGE`; otherwise the configured `slippage`.
+pub fn curve_slippage(
+    slippage: f64,
+    sol_amount: u64,
+    virtual_sol_reserves: u64,
+    is_buy: bool,
+) -> f64 {
+    if !*DYNAMIC_SLIPPAGE {
+        return slippage;
+    }
+
+    let impact = bonding_curve_price_impact(sol_amount, virtual_sol_reserves, is_buy);
+
+    (impact * *SLIPPAGE_IMPACT_MULTIPLIER).clamp(*MIN_SLIPPAGE, MAX_SLIPPAGE.max(*MIN_SLIPPAGE))
+}
@@ -0,0 +1,98 @@
+use once_cell::sync::Lazy;
+use std::{collections::HashMap, sync::RwLock};
+use tracing::{info, warn};
+
+use crate::{
+    config::{PAUSE_ON_SLOT_LAG, SLOT_LAG_ALERT_SLOTS},
+    utils::{current_slot, observe_slot},
+};
+
+/// How far behind the chain tip each datasource is, in slots. The tip is the highest
+/// slot seen from any source, a datasource's position the newest slot or transaction
+/// it delivered.
+pub struct SlotLag {
+    datasources: RwLock<HashMap<String, DatasourceSlot>>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct DatasourceSlot {
+    slot: u64,
+    lagging: bool,
+}
+
+pub static SLOT_LAG: Lazy<SlotLag> = Lazy::new(SlotLag::new);
+
+impl Default for SlotLag {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SlotLag {
+    pub fn new() -> Self {
+        Self {
+            datasources: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Records a slot delivered by `datasource` and returns its current lag, alerting
+    /// when it crosses `SLOT_LAG_ALERT_SLOTS` and when it catches up again.
+    pub fn observe(&self, datasource: &str, slot: u64) -> u64 {
+        observe_slot(slot);
+
+        let mut datasources = self.datasources.write().unwrap();
+        let state = datasources.entry(datasource.to_string()).or_default();
+
+        state.slot = state.slot.max(slot);
+
+        let lag = current_slot().saturating_sub(state.slot);
+        let lagging = lag > *SLOT_LAG_ALERT_SLOTS;
+
+        if lagging && !state.lagging {
+            warn!(
+                "{} is {} slots behind the chain tip{}",
+                datasource,
+                lag,
+                if *PAUSE_ON_SLOT_LAG {
+                    ", pausing buys"
+                } else {
+                    ""
+                }
+            );
+        } else if !lagging && state.lagging {
+            info!("{} caught up with the chain tip", datasource);
+        }
+
+        state.lagging = lagging;
+
+        lag
+    }
+
+    /// Drops a datasource whose stream ended, it no longer delivers anything to lag behind.
+    pub fn forget(&self, datasource: &str) {
+        self.datasources.write().unwrap().remove(datasource);
+    }
+
+    /// Current lag of every tracked datasource.
+    pub fn lags(&self) -> Vec<(String, u64)> {
+        let tip = current_slot();
+
+        self.datasources
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, state)| (name.clone(), tip.saturating_sub(state.slot)))
+            .collect()
+    }
+
+    /// Whether buys should be held back because a datasource lags past the threshold.
+    pub fn is_trading_paused(&self) -> bool {
+        *PAUSE_ON_SLOT_LAG
+            && self
+                .datasources
+                .read()
+                .unwrap()
+                .values()
+                .any(|state| state.lagging)
+    }
+}
@@ -0,0 +1,213 @@
+use once_cell::sync::Lazy;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use tracing::warn;
+
+use crate::{
+    config::{MAX_SPEND_PER_DAY_SOL, MAX_SPEND_PER_HOUR_SOL, MAX_SPEND_PER_TRADE_SOL},
+    utils::notify_error,
+};
+
+const HOUR: Duration = Duration::from_secs(3600);
+const DAY: Duration = Duration::from_secs(24 * 3600);
+
+/// Spend ceilings in lamports, `None` for no limit.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SpendLimits {
+    pub per_trade: Option<u64>,
+    pub per_hour: Option<u64>,
+    pub per_day: Option<u64>,
+}
+
+impl SpendLimits {
+    /// The limits of `MAX_SPEND_PER_TRADE_SOL`, `MAX_SPEND_PER_HOUR_SOL` and
+    /// `MAX_SPEND_PER_DAY_SOL`.
+    pub fn from_config() -> Self {
+        let lamports = |sol: Option<f64>| sol.map(|sol| (sol * LAMPORTS_PER_SOL as f64) as u64);
+
+        Self {
+            per_trade: lamports(*MAX_SPEND_PER_TRADE_SOL),
+            per_hour: lamports(*MAX_SPEND_PER_HOUR_SOL),
+            per_day: lamports(*MAX_SPEND_PER_DAY_SOL),
+        }
+    }
+}
+
+/// A spend the policy refused, with the limit it would have gone over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpendViolation {
+    PerTrade {
+        lamports: u64,
+        limit: u64,
+    },
+    PerHour {
+        spent: u64,
+        lamports: u64,
+        limit: u64,
+    },
+    PerDay {
+        spent: u64,
+        lamports: u64,
+        limit: u64,
+    },
+}
+
+impl fmt::Display for SpendViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sol = |lamports: u64| lamports as f64 / LAMPORTS_PER_SOL as f64;
+
+        match *self {
+            Self::PerTrade { lamports, limit } => write!(
+                f,
+                "{} SOL is over the per-trade limit of {} SOL",
+                sol(lamports),
+                sol(limit)
+            ),
+            Self::PerHour {
+                spent,
+                lamports,
+                limit,
+            } => write!(
+                f,
+                "{} SOL on top of {} SOL spent in the last hour is over the limit of {} SOL",
+                sol(lamports),
+                sol(spent),
+                sol(limit)
+            ),
+            Self::PerDay {
+                spent,
+                lamports,
+                limit,
+            } => write!(
+                f,
+                "{} SOL on top of {} SOL spent in the last day is over the limit of {} SOL",
+                sol(lamports),
+                sol(spent),
+                sol(limit)
+            ),
+        }
+    }
+}
+
+/// Ceilings on the SOL buys spend, per trade and over a rolling hour and day. Every buy
+/// is authorized before it's signed; one over a limit is refused and alerted on rather
+/// than sent.
+#[derive(Debug)]
+pub struct SpendPolicy {
+    limits: SpendLimits,
+    /// Spends authorized over the last day, oldest first
+    spends: Mutex<VecDeque<(Instant, u64)>>,
+    /// Set once a refusal was alerted on, until a spend is authorized again
+    alerted: AtomicBool,
+}
+
+pub static SPEND_POLICY: Lazy<SpendPolicy> =
+    Lazy::new(|| SpendPolicy::new(SpendLimits::from_config()));
+
+impl SpendPolicy {
+    pub fn new(limits: SpendLimits) -> Self {
+        Self {
+            limits,
+            spends: Mutex::new(VecDeque::new()),
+            alerted: AtomicBool::new(false),
+        }
+    }
+
+    /// Records a spend of `lamports`, or refuses it when it would go over a limit.
+    pub fn authorize(&self, lamports: u64) -> Result<(), SpendViolation> {
+        let mut spends = self.spends.lock().unwrap();
+        let now = Instant::now();
+
+        while spends
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > DAY)
+        {
+            spends.pop_front();
+        }
+
+        let spent_since = |window: Duration| -> u64 {
+            spends
+                .iter()
+                .filter(|(at, _)| now.duration_since(*at) <= window)
+                .map(|(_, spent)| spent)
+                .sum()
+        };
+
+        if let Some(limit) = self.limits.per_trade
+            && lamports > limit
+        {
+            return Err(SpendViolation::PerTrade { lamports, limit });
+        }
+
+        if let Some(limit) = self.limits.per_hour {
+            let spent = spent_since(HOUR);
+
+            if spent + lamports > limit {
+                return Err(SpendViolation::PerHour {
+                    spent,
+                    lamports,
+                    limit,
+                });
+            }
+        }
+
+        if let Some(limit) = self.limits.per_day {
+            let spent = spent_since(DAY);
+
+            if spent + lamports > limit {
+                return Err(SpendViolation::PerDay {
+                    spent,
+                    lamports,
+                    limit,
+                });
+            }
+        }
+
+        spends.push_back((now, lamports));
+        self.alerted.store(false, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// Gives back the latest spend of `lamports`, for a buy that was never sent.
+    pub fn refund(&self, lamports: u64) {
+        let mut spends = self.spends.lock().unwrap();
+
+        if let Some(index) = spends.iter().rposition(|(_, spent)| *spent == lamports) {
+            spends.remove(index);
+        }
+    }
+
+    /// SOL spent over the last hour and the last day, in lamports.
+    pub fn spent(&self) -> (u64, u64) {
+        let spends = self.spends.lock().unwrap();
+        let now = Instant::now();
+
+        spends.iter().fold((0, 0), |(hour, day), (at, spent)| {
+            match now.duration_since(*at) {
+                age if age <= HOUR => (hour + spent, day + spent),
+                age if age <= DAY => (hour, day + spent),
+                _ => (hour, day),
+            }
+        })
+    }
+
+    /// Alerts on a refused spend, once until a spend goes through again so a limit
+    /// that's reached doesn't alert on every buy after it.
+    pub fn alert(&self, violation: &SpendViolation) {
+        if self.alerted.swap(true, Ordering::Relaxed) {
+            return;
+        }
+
+        warn!("ALERT: Spend limit reached, skipping buys: {}", violation);
+        notify_error(format!("Spend limit reached, skipping buys: {}", violation));
+    }
+}
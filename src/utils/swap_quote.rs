@@ -1,37 +1,356 @@
-pub fn sol_token_quote(
-    amount: u64,
+use crate::{
+    decoder::DbcPoolConfig,
+    utils::{FEE_RATE_DENOMINATOR_VALUE, PumpfunFees, ceil_div, get_trade_fee},
+};
+
+/// Q64.64 scale of Meteora DBC sqrt prices
+const DBC_SQRT_PRICE_SCALE: f64 = 18446744073709551616.0;
+
+/// Tokens a pumpfun bonding curve trades against `sol_amount`, with the program's
+/// constant product integer math. For a buy, the tokens received for spending
+/// `sol_amount` (rounded down); for a sell, the tokens to sell to receive `sol_amount`
+/// (rounded up). Fees are not included.
+pub fn bonding_curve_token_quote(
+    sol_amount: u64,
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+    is_buy: bool,
+) -> u64 {
+    let sol_amount = sol_amount as u128;
+    let virtual_sol_reserves = virtual_sol_reserves as u128;
+    let virtual_token_reserves = virtual_token_reserves as u128;
+
+    let token_amount = if is_buy {
+        virtual_token_reserves * sol_amount / (virtual_sol_reserves + sol_amount)
+    } else {
+        match virtual_sol_reserves.checked_sub(sol_amount) {
+            Some(remaining_sol) if remaining_sol > 0 => {
+                (virtual_token_reserves * sol_amount).div_ceil(remaining_sol)
+            }
+            _ => return u64::MAX,
+        }
+    };
+
+    u64::try_from(token_amount).unwrap_or(u64::MAX)
+}
+
+/// SOL a pumpfun bonding curve trades against `token_amount`, with the program's
+/// constant product integer math. For a buy, the SOL it costs to buy `token_amount`
+/// (rounded up, plus the lamport the program adds); for a sell, the SOL received for
+/// selling `token_amount` (rounded down). Fees are not included.
+pub fn bonding_curve_sol_quote(
+    token_amount: u64,
     virtual_sol_reserves: u64,
     virtual_token_reserves: u64,
     is_buy: bool,
 ) -> u64 {
-    let out_token_amount;
-    if is_buy {
-        out_token_amount = virtual_token_reserves as f64
-            / (amount as f64 + virtual_sol_reserves as f64)
-            * (amount as f64);
+    let token_amount = token_amount as u128;
+    let virtual_sol_reserves = virtual_sol_reserves as u128;
+    let virtual_token_reserves = virtual_token_reserves as u128;
+
+    let sol_amount = if is_buy {
+        match virtual_token_reserves.checked_sub(token_amount) {
+            Some(remaining_tokens) if remaining_tokens > 0 => {
+                virtual_sol_reserves * token_amount / remaining_tokens + 1
+            }
+            _ => return u64::MAX,
+        }
     } else {
-        out_token_amount = virtual_token_reserves as f64
-            / (amount as f64 + virtual_sol_reserves as f64 - 1.0)
-            * (amount as f64 + 1.0);
+        virtual_sol_reserves * token_amount / (virtual_token_reserves + token_amount)
+    };
+
+    u64::try_from(sol_amount).unwrap_or(u64::MAX)
+}
+
+/// Relative move of the curve price caused by a trade of `sol_amount`. The curve price is
+/// `virtual_sol / virtual_token` and their product is constant, so the price scales with
+/// the square of the virtual SOL reserves.
+pub fn bonding_curve_price_impact(sol_amount: u64, virtual_sol_reserves: u64, is_buy: bool) -> f64 {
+    if virtual_sol_reserves == 0 {
+        return 1.0;
     }
 
-    out_token_amount as u64
+    let ratio = if is_buy {
+        (virtual_sol_reserves as f64 + sol_amount as f64) / virtual_sol_reserves as f64
+    } else {
+        (virtual_sol_reserves as f64 - sol_amount as f64).max(0.0) / virtual_sol_reserves as f64
+    };
+
+    (ratio * ratio - 1.0).abs()
 }
 
+/// Total SOL a pumpfun buy of `token_amount` costs, the curve cost plus the protocol and
+/// creator fees charged on it. This is what `max_sol_cost` is checked against.
+pub fn pumpfun_buy_cost(
+    token_amount: u64,
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+    fees: &PumpfunFees,
+) -> u64 {
+    let sol_cost = bonding_curve_sol_quote(
+        token_amount,
+        virtual_sol_reserves,
+        virtual_token_reserves,
+        true,
+    );
+
+    sol_cost.saturating_add(fees.fee_on(sol_cost))
+}
+
+/// SOL a pumpfun sell of `token_amount` pays out after the protocol and creator fees.
+/// This is what `min_sol_output` is checked against.
+pub fn pumpfun_sell_output(
+    token_amount: u64,
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+    fees: &PumpfunFees,
+) -> u64 {
+    let sol_output = bonding_curve_sol_quote(
+        token_amount,
+        virtual_sol_reserves,
+        virtual_token_reserves,
+        false,
+    );
+
+    sol_output.saturating_sub(fees.fee_on(sol_output))
+}
+
+/// `min_sol_output` for selling `token_amount`: the SOL the sell pays out after fees at
+/// the given reserves, less `slippage` (0.1 = 10%).
+pub fn pumpfun_min_sol_output(
+    token_amount: u64,
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+    fees: &PumpfunFees,
+    slippage: f64,
+) -> u64 {
+    let sol_output = pumpfun_sell_output(
+        token_amount,
+        virtual_sol_reserves,
+        virtual_token_reserves,
+        fees,
+    );
+
+    (sol_output as f64 * (1.0 - slippage).clamp(0.0, 1.0)) as u64
+}
+
+#[deprecated(note = "use `bonding_curve_token_quote`, which matches the on-chain integer math")]
+pub fn sol_token_quote(
+    amount: u64,
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+    is_buy: bool,
+) -> u64 {
+    bonding_curve_token_quote(amount, virtual_sol_reserves, virtual_token_reserves, is_buy)
+}
+
+#[deprecated(note = "use `bonding_curve_sol_quote`, which matches the on-chain integer math")]
 pub fn token_sol_quote(
     amount: u64,
     virtual_sol_reserves: u64,
     virtual_token_reserves: u64,
     is_buy: bool,
 ) -> u64 {
-    let out_sol_amount;
-    if is_buy {
-        out_sol_amount = amount as f64 / (virtual_token_reserves as f64 - amount as f64)
-            * virtual_sol_reserves as f64;
-    } else {
-        out_sol_amount = amount as f64 / (virtual_token_reserves as f64 + amount as f64)
-            * virtual_sol_reserves as f64;
+    bonding_curve_sol_quote(amount, virtual_sol_reserves, virtual_token_reserves, is_buy)
+}
+
+/// Splits `amount` into the fewest near-equal chunks that are each at most `max_chunk`.
+pub fn split_amount(amount: u64, max_chunk: u64) -> Vec<u64> {
+    if max_chunk == 0 || amount <= max_chunk {
+        return vec![amount];
+    }
+
+    let count = amount.div_ceil(max_chunk);
+    let base = amount / count;
+    let remainder = amount % count;
+
+    (0..count)
+        .map(|i| if i < remainder { base + 1 } else { base })
+        .collect()
+}
+
+/// Base tokens received for spending `quote_amount_in` (fees included) on a PumpSwap pool.
+/// `fee_bps` is the total of the lp, protocol and creator fees.
+pub fn pump_swap_buy_quote(
+    quote_amount_in: u64,
+    pool_base_reserves: u64,
+    pool_quote_reserves: u64,
+    fee_bps: u64,
+) -> u64 {
+    // Fees are charged on top of the amount that actually enters the pool
+    let effective_quote_in = quote_amount_in as f64 * 10_000.0 / (10_000.0 + fee_bps as f64);
+
+    let base_amount_out = pool_base_reserves as f64 * effective_quote_in
+        / (pool_quote_reserves as f64 + effective_quote_in);
+
+    base_amount_out as u64
+}
+
+/// Quote received, after fees, for selling `base_amount_in` into a PumpSwap pool.
+pub fn pump_swap_sell_quote(
+    base_amount_in: u64,
+    pool_base_reserves: u64,
+    pool_quote_reserves: u64,
+    fee_bps: u64,
+) -> u64 {
+    let quote_amount_out = pool_quote_reserves as f64 * base_amount_in as f64
+        / (pool_base_reserves as f64 + base_amount_in as f64);
+
+    (quote_amount_out * (10_000.0 - fee_bps as f64) / 10_000.0) as u64
+}
+
+/// Raydium AMM v4 swap fee, 25 / 10_000 of the input
+pub const RAYDIUM_AMM_FEE_NUMERATOR: u128 = 25;
+pub const RAYDIUM_AMM_FEE_DENOMINATOR: u128 = 10_000;
+
+/// Output of an exact-in swap on a Raydium AMM v4 pool.
+pub fn raydium_amm_quote(amount_in: u64, reserve_in: u64, reserve_out: u64) -> u64 {
+    let amount_in = amount_in as u128;
+    let fee = amount_in * RAYDIUM_AMM_FEE_NUMERATOR / RAYDIUM_AMM_FEE_DENOMINATOR;
+    let amount_in_after_fee = amount_in - fee;
+
+    (reserve_out as u128 * amount_in_after_fee / (reserve_in as u128 + amount_in_after_fee)) as u64
+}
+
+/// Output of an exact-in swap on a Raydium CPMM pool, charging the trade fee of the
+/// pool's `amm_config` like the program does (rounded up, in favour of the pool).
+pub fn raydium_cpmm_quote(
+    amount_in: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    amm_config: &str,
+) -> u64 {
+    let amount_in = amount_in as u128;
+    let fee = ceil_div(
+        amount_in,
+        get_trade_fee(amm_config),
+        FEE_RATE_DENOMINATOR_VALUE as u128,
+    )
+    .unwrap_or(amount_in);
+    let amount_in_after_fee = amount_in.saturating_sub(fee);
+
+    (reserve_out as u128 * amount_in_after_fee / (reserve_in as u128 + amount_in_after_fee)) as u64
+}
+
+/// Base tokens received for `quote_amount_in` on a Raydium LaunchLab constant product
+/// curve, from the virtual and real reserves of its trade event. `fee_rate` is the
+/// combined protocol, platform and share fee as a fraction of the quote amount.
+pub fn launchpad_buy_quote(
+    quote_amount_in: u64,
+    virtual_base: u64,
+    virtual_quote: u64,
+    real_base: u64,
+    real_quote: u64,
+    fee_rate: f64,
+) -> u64 {
+    let base_reserve = virtual_base.saturating_sub(real_base) as f64;
+    let quote_reserve = (virtual_quote + real_quote) as f64;
+    let quote_in_after_fee = quote_amount_in as f64 * (1.0 - fee_rate);
+
+    (base_reserve * quote_in_after_fee / (quote_reserve + quote_in_after_fee)) as u64
+}
+
+/// Quote received, after fees, for selling `base_amount_in` into a Raydium LaunchLab
+/// constant product curve.
+pub fn launchpad_sell_quote(
+    base_amount_in: u64,
+    virtual_base: u64,
+    virtual_quote: u64,
+    real_base: u64,
+    real_quote: u64,
+    fee_rate: f64,
+) -> u64 {
+    let base_reserve = virtual_base.saturating_sub(real_base) as f64;
+    let quote_reserve = (virtual_quote + real_quote) as f64;
+
+    let quote_out = quote_reserve * base_amount_in as f64 / (base_reserve + base_amount_in as f64);
+
+    (quote_out * (1.0 - fee_rate)) as u64
+}
+
+/// Base tokens bought with `quote_in` from a Meteora DBC pool at `sqrt_price` (Q64.64),
+/// walking the liquidity distribution of its config upwards. `fee_rate` is a fraction
+/// of the side the config collects fees on.
+pub fn meteora_dbc_buy_quote(
+    quote_in: u64,
+    sqrt_price: u128,
+    config: &DbcPoolConfig,
+    fee_rate: f64,
+) -> u64 {
+    let fee_on_input = config.collect_fee_mode == 0;
+
+    let mut remaining = quote_in as f64;
+    if fee_on_input {
+        remaining *= 1.0 - fee_rate;
+    }
+
+    let mut current = sqrt_price as f64;
+    let mut base_out = 0.0;
+
+    for &(upper, liquidity) in &config.curve {
+        let (upper, liquidity) = (upper as f64, liquidity as f64);
+
+        if upper <= current || liquidity == 0.0 {
+            continue;
+        }
+
+        let max_quote_in = liquidity * (upper - current) / DBC_SQRT_PRICE_SCALE.powi(2);
+
+        if remaining < max_quote_in {
+            let next = current + remaining * DBC_SQRT_PRICE_SCALE.powi(2) / liquidity;
+            base_out += liquidity * (next - current) / (current * next);
+            break;
+        }
+
+        base_out += liquidity * (upper - current) / (current * upper);
+        remaining -= max_quote_in;
+        current = upper;
+    }
+
+    if !fee_on_input {
+        base_out *= 1.0 - fee_rate;
     }
 
-    out_sol_amount as u64
-}
\ No newline at end of file
+    base_out as u64
+}
+
+/// Quote tokens received, after fees, for selling `base_in` into a Meteora DBC pool at
+/// `sqrt_price`, walking the liquidity distribution downwards.
+pub fn meteora_dbc_sell_quote(
+    base_in: u64,
+    sqrt_price: u128,
+    config: &DbcPoolConfig,
+    fee_rate: f64,
+) -> u64 {
+    let mut remaining = base_in as f64;
+    let mut current = sqrt_price as f64;
+    let mut quote_out = 0.0;
+
+    for (point, &(upper, liquidity)) in config.curve.iter().enumerate().rev() {
+        let lower = match point {
+            0 => config.sqrt_start_price,
+            _ => config.curve[point - 1].0,
+        } as f64;
+        let liquidity = liquidity as f64;
+
+        if current <= lower || upper as f64 <= lower || liquidity == 0.0 {
+            continue;
+        }
+
+        current = current.min(upper as f64);
+
+        let max_base_in = liquidity * (current - lower) / (lower * current);
+
+        if remaining < max_base_in {
+            let next = liquidity * current / (liquidity + remaining * current);
+            quote_out += liquidity * (current - next) / DBC_SQRT_PRICE_SCALE.powi(2);
+            break;
+        }
+
+        quote_out += liquidity * (current - lower) / DBC_SQRT_PRICE_SCALE.powi(2);
+        remaining -= max_base_in;
+        current = lower;
+    }
+
+    (quote_out * (1.0 - fee_rate)) as u64
+}
@@ -0,0 +1,200 @@
+use carbon_core::metrics::MetricsCollection;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Signature};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::Duration,
+};
+
+use crate::{config::DEDUP_TTL_SEC, utils::DedupCache};
+
+/// Running totals of one followed wallet, behind its `target_{label}_*` metrics.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TargetStats {
+    pub detections: u64,
+    pub copies_sent: u64,
+    pub copies_failed: u64,
+    pub landed: u64,
+    pub not_landed: u64,
+    /// SOL our trading wallets received minus what they spent copying this target, in
+    /// lamports, over the copies that landed
+    pub sol_flow: i64,
+}
+
+impl TargetStats {
+    pub fn land_rate(&self) -> f64 {
+        self.landed as f64 / (self.landed + self.not_landed).max(1) as f64
+    }
+}
+
+/// Latest detections kept for `recent_detections`
+const RECENT_DETECTIONS: usize = 100;
+
+/// A target transaction seen on a followed venue.
+#[derive(Debug, Clone, Copy)]
+pub struct Detection {
+    pub venue: &'static str,
+    pub target: Pubkey,
+    pub signature: Signature,
+    pub at: DateTime<Utc>,
+}
+
+/// Per target wallet breakdown of detections, copies, landings and realized PnL.
+/// Carbon metrics have no tags, so every metric carries the target in its name, e.g.
+/// `target_7xKXtg2C_copies_sent`.
+pub struct TargetMetrics {
+    stats: Mutex<HashMap<Pubkey, TargetStats>>,
+}
+
+pub static TARGET_METRICS: Lazy<TargetMetrics> = Lazy::new(TargetMetrics::new);
+
+/// Target transactions already counted, so one delivered by several datasources or
+/// holding several followed instructions is a single detection
+static SEEN_DETECTIONS: Lazy<DedupCache> =
+    Lazy::new(|| DedupCache::new(Duration::from_secs(*DEDUP_TTL_SEC)));
+
+static DETECTIONS: Lazy<Mutex<VecDeque<Detection>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(RECENT_DETECTIONS)));
+
+impl Default for TargetMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TargetMetrics {
+    pub fn new() -> Self {
+        Self {
+            stats: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn stats(&self, target: &Pubkey) -> TargetStats {
+        self.stats
+            .lock()
+            .unwrap()
+            .get(target)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Stats of every target seen so far.
+    pub fn all(&self) -> Vec<(Pubkey, TargetStats)> {
+        self.stats
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(target, stats)| (*target, *stats))
+            .collect()
+    }
+
+    fn update(&self, target: &Pubkey, update: impl FnOnce(&mut TargetStats)) -> TargetStats {
+        let mut stats = self.stats.lock().unwrap();
+        let target_stats = stats.entry(*target).or_default();
+
+        update(target_stats);
+
+        *target_stats
+    }
+}
+
+/// The latest detections, newest first.
+pub fn recent_detections() -> Vec<Detection> {
+    DETECTIONS.lock().unwrap().iter().rev().copied().collect()
+}
+
+/// Short form of a target wallet used in metric names, its first 8 base58 characters.
+pub fn target_label(target: &Pubkey) -> String {
+    target.to_string().chars().take(8).collect()
+}
+
+/// Counts target transaction `signature` on `venue` as a detection, once however often
+/// it arrives.
+pub async fn record_detection(
+    metrics: &MetricsCollection,
+    venue: &'static str,
+    target: &Pubkey,
+    signature: Signature,
+) {
+    if !SEEN_DETECTIONS.first_seen(signature, &[]) {
+        return;
+    }
+
+    TARGET_METRICS.update(target, |stats| stats.detections += 1);
+
+    {
+        let mut detections = DETECTIONS.lock().unwrap();
+
+        if detections.len() == RECENT_DETECTIONS {
+            detections.pop_front();
+        }
+        detections.push_back(Detection {
+            venue,
+            target: *target,
+            signature,
+            at: Utc::now(),
+        });
+    }
+
+    metrics
+        .increment_counter(&format!("target_{}_detections", target_label(target)), 1)
+        .await
+        .ok();
+}
+
+/// Counts a copy of `target`'s trade that was submitted, or failed to be.
+pub async fn record_copy(metrics: &MetricsCollection, target: &Pubkey, sent: bool) {
+    TARGET_METRICS.update(target, |stats| {
+        if sent {
+            stats.copies_sent += 1;
+        } else {
+            stats.copies_failed += 1;
+        }
+    });
+
+    let counter = if sent { "copies_sent" } else { "copies_failed" };
+    metrics
+        .increment_counter(&format!("target_{}_{}", target_label(target), counter), 1)
+        .await
+        .ok();
+}
+
+/// Records whether a sent copy of `target`'s trade landed, with the SOL it moved in
+/// or out of our wallet when it did, and updates the target's land rate and PnL.
+pub async fn record_target_landing(
+    metrics: &MetricsCollection,
+    target: &Pubkey,
+    landed: bool,
+    sol_flow: Option<i64>,
+) {
+    let stats = TARGET_METRICS.update(target, |stats| {
+        if landed {
+            stats.landed += 1;
+        } else {
+            stats.not_landed += 1;
+        }
+
+        stats.sol_flow += sol_flow.unwrap_or_default();
+    });
+
+    let label = target_label(target);
+    let counter = if landed { "landed" } else { "not_landed" };
+
+    metrics
+        .increment_counter(&format!("target_{}_{}", label, counter), 1)
+        .await
+        .ok();
+    metrics
+        .update_gauge(&format!("target_{}_land_rate", label), stats.land_rate())
+        .await
+        .ok();
+    metrics
+        .update_gauge(
+            &format!("target_{}_pnl_sol", label),
+            stats.sol_flow as f64 / LAMPORTS_PER_SOL as f64,
+        )
+        .await
+        .ok();
+}
@@ -0,0 +1,267 @@
+use carbon_core::metrics::MetricsCollection;
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde_json::{Value, json};
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+use std::{str::FromStr, sync::Arc, time::Duration};
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+use crate::{
+    config::{TELEGRAM_ALLOWED_CHAT_IDS, TELEGRAM_BOT_TOKEN, TELEGRAM_READ_CHAT_IDS},
+    datasource::FOLLOWED_TARGETS,
+    processor::manual_sell_mint,
+    utils::{
+        ControlDenied, ControlScope, EXECUTION_BREAKER, FEE_LEDGER, TARGET_METRICS,
+        admit_control_call, target_label,
+    },
+    wallet::WALLET_POOL,
+};
+
+/// Seconds Telegram holds a `getUpdates` request open waiting for a message
+const POLL_TIMEOUT_SEC: u64 = 30;
+
+static BOT_CLIENT: Lazy<Client> = Lazy::new(|| {
+    Client::builder()
+        .timeout(Duration::from_secs(POLL_TIMEOUT_SEC + 10))
+        .build()
+        .expect("Failed to build HTTP client")
+});
+
+const HELP: &str = "/positions - open positions\n\
+/pnl - SOL received minus spent per target, and the tips and fees per sender\n\
+/pause - stop buying\n\
+/resume - buy again\n\
+/sell <mint> [code] - sell a pump.fun curve position from every wallet, with the\n\
+  confirmation code asked for a large one\n\
+/settarget <wallet> - follow only another wallet";
+
+/// Long-polls the bot's updates and answers commands from `TELEGRAM_ALLOWED_CHAT_IDS`,
+/// and the read-only ones from `TELEGRAM_READ_CHAT_IDS`, ignoring every other chat.
+pub async fn telegram_bot() {
+    let Some(token) = TELEGRAM_BOT_TOKEN.clone() else {
+        warn!("TELEGRAM_COMMANDS is enabled but TELEGRAM_BOT_TOKEN is not set");
+        return;
+    };
+
+    if TELEGRAM_ALLOWED_CHAT_IDS.is_empty() && TELEGRAM_READ_CHAT_IDS.is_empty() {
+        warn!("TELEGRAM_COMMANDS is enabled but no chat is allowed to send commands");
+        return;
+    }
+
+    info!("Telegram command bot started");
+
+    // Manual sells aren't copies of a target trade, so their metrics aren't collected
+    let metrics = Arc::new(MetricsCollection::new(vec![]));
+    let mut offset = 0;
+
+    loop {
+        let updates = match get_updates(&token, offset).await {
+            Ok(updates) => updates,
+            Err(e) => {
+                warn!("Failed to get Telegram updates: {}", e.without_url());
+                sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        for update in updates {
+            if let Some(update_id) = update["update_id"].as_i64() {
+                offset = offset.max(update_id + 1);
+            }
+
+            let message = &update["message"];
+            let (Some(chat_id), Some(text)) =
+                (message["chat"]["id"].as_i64(), message["text"].as_str())
+            else {
+                continue;
+            };
+
+            let Some(reply) = handle_command(chat_id, text, &metrics).await else {
+                continue;
+            };
+
+            if let Err(e) = send_reply(&token, chat_id, reply).await {
+                warn!("Failed to answer Telegram command: {}", e.without_url());
+            }
+        }
+    }
+}
+
+async fn get_updates(token: &str, offset: i64) -> reqwest::Result<Vec<Value>> {
+    let response: Value = BOT_CLIENT
+        .get(format!("https://api.telegram.org/bot{}/getUpdates", token))
+        .query(&[
+            ("offset", offset.to_string()),
+            ("timeout", POLL_TIMEOUT_SEC.to_string()),
+            ("allowed_updates", "[\"message\"]".to_string()),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(response["result"].as_array().cloned().unwrap_or_default())
+}
+
+async fn send_reply(token: &str, chat_id: i64, text: String) -> reqwest::Result<()> {
+    BOT_CLIENT
+        .post(format!("https://api.telegram.org/bot{}/sendMessage", token))
+        .json(&json!({ "chat_id": chat_id, "text": text }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
+
+/// The scope of commands from `chat_id`, `None` for a chat that isn't allowed any
+fn chat_scope(chat_id: i64) -> Option<ControlScope> {
+    if TELEGRAM_ALLOWED_CHAT_IDS.contains(&chat_id) {
+        Some(ControlScope::Trade)
+    } else if TELEGRAM_READ_CHAT_IDS.contains(&chat_id) {
+        Some(ControlScope::Read)
+    } else {
+        None
+    }
+}
+
+/// The reply to a command message from `chat_id`, `None` for anything that isn't a
+/// command or comes from a chat that isn't allowed.
+async fn handle_command(
+    chat_id: i64,
+    text: &str,
+    metrics: &Arc<MetricsCollection>,
+) -> Option<String> {
+    let mut words = text.split_whitespace();
+    // Commands in groups carry the bot's name, e.g. /pnl@copy_bot
+    let command = words.next()?.strip_prefix('/')?.split('@').next()?;
+    let argument = words.next();
+    let confirm_code = words.next();
+
+    let needs = match command {
+        "pause" | "resume" | "sell" | "settarget" => ControlScope::Trade,
+        _ => ControlScope::Read,
+    };
+
+    match admit_control_call(
+        "telegram",
+        &format!("chat {}", chat_id),
+        chat_scope(chat_id),
+        needs,
+        &format!("/{}", command),
+    ) {
+        Ok(()) => {}
+        Err(ControlDenied::Unauthenticated) => return None,
+        Err(denied) => return Some(format!("Can't /{}: {}", command, denied.as_str())),
+    }
+
+    let reply = match (command, argument) {
+        ("positions", _) => positions_reply(),
+        ("pnl", _) => pnl_reply(),
+        ("pause", _) => {
+            if EXECUTION_BREAKER.pause() {
+                "Buying paused, sells still go through".to_string()
+            } else {
+                "Buying is already paused".to_string()
+            }
+        }
+        ("resume", _) => {
+            if EXECUTION_BREAKER.resume() {
+                "Buying resumed".to_string()
+            } else {
+                "Buying wasn't paused".to_string()
+            }
+        }
+        ("sell", Some(mint)) => match Pubkey::from_str(mint) {
+            Ok(mint) => match manual_sell_mint(mint, confirm_code, metrics).await {
+                Ok(wallets) => format!("Selling {} from {} wallet(s)", mint, wallets),
+                Err(reason) => format!("Can't sell {}: {}", mint, reason),
+            },
+            Err(_) => format!("{} isn't a valid mint", mint),
+        },
+        ("settarget", Some(wallet)) => match Pubkey::from_str(wallet) {
+            Ok(wallet) => {
+                if FOLLOWED_TARGETS.set(wallet) {
+                    format!(
+                        "Following only {} on the gRPC datasources. WebSocket, RPC polling \
+                         and backfill keep the targets followed at startup until a restart",
+                        wallet
+                    )
+                } else {
+                    format!("Already following {}", wallet)
+                }
+            }
+            Err(_) => format!("{} isn't a valid wallet", wallet),
+        },
+        _ => HELP.to_string(),
+    };
+
+    Some(reply)
+}
+
+fn positions_reply() -> String {
+    let mut positions = WALLET_POOL.positions();
+
+    if positions.is_empty() {
+        return "No open positions".to_string();
+    }
+
+    positions.sort_by_key(|(wallet, mint, _)| (*mint, *wallet));
+
+    positions
+        .iter()
+        .map(|(wallet, mint, amount)| format!("{}\n  {} tokens in {}", mint, amount, wallet))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn pnl_reply() -> String {
+    let targets = TARGET_METRICS.all();
+
+    if targets.is_empty() {
+        return "No copies landed yet".to_string();
+    }
+
+    let total: i64 = targets.iter().map(|(_, stats)| stats.sol_flow).sum();
+
+    let mut lines: Vec<String> = targets
+        .iter()
+        .map(|(target, stats)| {
+            format!(
+                "{}: {:+.4} SOL, {}/{} landed ({:.0}%)",
+                target_label(target),
+                stats.sol_flow as f64 / LAMPORTS_PER_SOL as f64,
+                stats.landed,
+                stats.landed + stats.not_landed,
+                stats.land_rate() * 100.0
+            )
+        })
+        .collect();
+
+    lines.push(format!(
+        "Total: {:+.4} SOL",
+        total as f64 / LAMPORTS_PER_SOL as f64
+    ));
+
+    for (sender, totals) in FEE_LEDGER.senders() {
+        lines.push(format!(
+            "{}: {:.4} SOL in tips, {:.4} SOL in fees over {} transaction(s)",
+            sender,
+            totals.tips as f64 / LAMPORTS_PER_SOL as f64,
+            (totals.priority_fees + totals.base_fees) as f64 / LAMPORTS_PER_SOL as f64,
+            totals.transactions
+        ));
+    }
+
+    // What the trading wallets paid themselves is already in their SOL flow
+    let net = total - FEE_LEDGER.fee_payer_paid() as i64;
+
+    lines.push(format!(
+        "Net of tips and fees: {:+.4} SOL",
+        net as f64 / LAMPORTS_PER_SOL as f64
+    ));
+
+    lines.join("\n")
+}
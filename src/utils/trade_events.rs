@@ -0,0 +1,90 @@
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde_json::{Value, json};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::{collections::VecDeque, sync::Mutex};
+use tokio::sync::broadcast;
+
+use crate::utils::NotifyEvent;
+
+/// Events kept for a subscriber that falls behind before it starts missing some
+const TRADE_EVENTS_CAPACITY: usize = 1024;
+
+/// Latest events kept for clients that connect later, e.g. the dashboard
+const RECENT_TRADE_EVENTS: usize = 500;
+
+/// A copy, exit or error as it happens, for live subscribers like the gRPC control
+/// service.
+#[derive(Debug, Clone)]
+pub struct TradeEvent {
+    pub kind: NotifyEvent,
+    pub timestamp_ms: i64,
+    pub wallet: Option<Pubkey>,
+    pub mint: Option<Pubkey>,
+    /// Spent by a copy, received by an exit
+    pub sol_lamports: Option<u64>,
+    pub tokens: Option<f64>,
+    pub pnl_lamports: Option<i64>,
+    pub signature: Option<Signature>,
+    pub message: Option<String>,
+}
+
+impl TradeEvent {
+    pub fn new(kind: NotifyEvent) -> Self {
+        Self {
+            kind,
+            timestamp_ms: Utc::now().timestamp_millis(),
+            wallet: None,
+            mint: None,
+            sol_lamports: None,
+            tokens: None,
+            pnl_lamports: None,
+            signature: None,
+            message: None,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "kind": self.kind.as_str(),
+            "timestamp_ms": self.timestamp_ms,
+            "wallet": self.wallet.map(|wallet| wallet.to_string()),
+            "mint": self.mint.map(|mint| mint.to_string()),
+            "sol_lamports": self.sol_lamports,
+            "tokens": self.tokens,
+            "pnl_lamports": self.pnl_lamports,
+            "signature": self.signature.map(|signature| signature.to_string()),
+            "message": self.message,
+        })
+    }
+}
+
+static TRADE_EVENTS: Lazy<broadcast::Sender<TradeEvent>> =
+    Lazy::new(|| broadcast::channel(TRADE_EVENTS_CAPACITY).0);
+
+static RECENT: Lazy<Mutex<VecDeque<TradeEvent>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(RECENT_TRADE_EVENTS)));
+
+/// Sends `event` to every current subscriber, dropped when there's none, and keeps it
+/// among the recent events.
+pub fn publish_trade_event(event: TradeEvent) {
+    {
+        let mut recent = RECENT.lock().unwrap();
+
+        if recent.len() == RECENT_TRADE_EVENTS {
+            recent.pop_front();
+        }
+        recent.push_back(event.clone());
+    }
+
+    let _ = TRADE_EVENTS.send(event);
+}
+
+/// The latest events, oldest first.
+pub fn recent_trade_events() -> Vec<TradeEvent> {
+    RECENT.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn subscribe_trade_events() -> broadcast::Receiver<TradeEvent> {
+    TRADE_EVENTS.subscribe()
+}
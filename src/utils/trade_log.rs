@@ -0,0 +1,63 @@
+use chrono::Utc;
+use serde_json::{Value, json};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::{
+    collections::HashSet,
+    fs::{self, OpenOptions},
+    io::Write,
+    str::FromStr,
+};
+use tracing::warn;
+
+use crate::{config::TRADE_LOG_PATH, service::senders_stubbed};
+
+/// Appends a handled target trade to the JSON lines trade log at `TRADE_LOG_PATH`, so
+/// trades missed during downtime can be told apart after a restart. A replay leaves it
+/// alone.
+pub fn append_trade_log(signature: &Signature, mint: &Pubkey, is_buy: bool) {
+    if senders_stubbed() {
+        return;
+    }
+
+    let entry = json!({
+        "signature": signature.to_string(),
+        "mint": mint.to_string(),
+        "side": if is_buy { "buy" } else { "sell" },
+        "timestamp": Utc::now().timestamp(),
+    });
+
+    let result = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(TRADE_LOG_PATH.as_str())
+        .and_then(|mut file| writeln!(file, "{}", entry));
+
+    if let Err(e) = result {
+        warn!("Failed to write the trade log: {}", e);
+    }
+}
+
+/// Target signatures in the trade log, with the timestamp of the newest entry.
+/// `None` when there is no trade log yet.
+pub fn load_trade_log() -> Option<(HashSet<Signature>, i64)> {
+    let content = fs::read_to_string(TRADE_LOG_PATH.as_str()).ok()?;
+
+    let mut signatures = HashSet::new();
+    let mut last_timestamp = 0;
+
+    for entry in content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+    {
+        if let Some(signature) = entry["signature"]
+            .as_str()
+            .and_then(|signature| Signature::from_str(signature).ok())
+        {
+            signatures.insert(signature);
+        }
+
+        last_timestamp = last_timestamp.max(entry["timestamp"].as_i64().unwrap_or_default());
+    }
+
+    Some((signatures, last_timestamp))
+}
@@ -0,0 +1,39 @@
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use tracing::{Span, field, info_span};
+
+/// Span of everything done for one target transaction, from detection to the copies'
+/// outcome, so the logs of trades handled concurrently can be told apart. The mint,
+/// amounts and outcome are recorded once known.
+pub fn trade_span(venue: &'static str, signature: &Signature, target: &Pubkey) -> Span {
+    info_span!(
+        "trade",
+        venue,
+        %signature,
+        %target,
+        mint = field::Empty,
+        side = field::Empty,
+        sol_amount = field::Empty,
+        token_amount = field::Empty,
+        outcome = field::Empty,
+    )
+}
+
+/// Records the target's trade on the current trade span, amounts in base units.
+pub fn record_target_trade(mint: &Pubkey, is_buy: bool, sol_amount: u64, token_amount: u64) {
+    let span = Span::current();
+
+    record_trade_mint(mint);
+    span.record("side", if is_buy { "buy" } else { "sell" });
+    span.record("sol_amount", sol_amount);
+    span.record("token_amount", token_amount);
+}
+
+/// Records the mint the current trade is on, for trades without amounts, e.g. a launch.
+pub fn record_trade_mint(mint: &Pubkey) {
+    Span::current().record("mint", field::display(mint));
+}
+
+/// Records how the copies of the current trade went, e.g. `2/2 sent`.
+pub fn record_trade_outcome(outcome: &str) {
+    Span::current().record("outcome", outcome);
+}
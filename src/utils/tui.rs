@@ -0,0 +1,220 @@
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Row, Table},
+};
+use solana_sdk::pubkey::Pubkey;
+use std::{io, thread, time::Duration};
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use crate::{
+    datasource::{DATASOURCE_STATUS, FOLLOWED_TARGETS},
+    service::{SENDER_SERVICES, sender_health},
+    utils::{EXECUTION_BREAKER, current_slot, latest_blockhash, recent_detections, target_label},
+    wallet::WALLET_POOL,
+};
+
+/// How often the screen is redrawn while no key is pressed
+const REFRESH: Duration = Duration::from_millis(500);
+
+const KEYS: &str = " q quit | p pause buying | r resume buying ";
+
+/// Takes over the terminal with a live view of the bot on its own thread: buying state,
+/// blockhash age, sender health, open positions and the latest detections. Quitting
+/// restores the terminal and cancels `shutdown` to stop the pipeline.
+pub fn start_tui(shutdown: CancellationToken) {
+    thread::Builder::new()
+        .name("tui".to_string())
+        .spawn(move || {
+            let mut terminal = ratatui::init();
+            let result = run(&mut terminal);
+            ratatui::restore();
+
+            if let Err(e) = result {
+                warn!("TUI stopped: {}", e);
+            }
+
+            shutdown.cancel();
+        })
+        .expect("Failed to spawn the TUI thread");
+}
+
+fn run(terminal: &mut DefaultTerminal) -> io::Result<()> {
+    loop {
+        terminal.draw(draw)?;
+
+        if !event::poll(REFRESH)? {
+            continue;
+        }
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            // Raw mode turns Ctrl-C into a key press instead of a signal
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+            KeyCode::Char('p') => {
+                EXECUTION_BREAKER.pause();
+            }
+            KeyCode::Char('r') => {
+                EXECUTION_BREAKER.resume();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn short(key: &Pubkey) -> String {
+    let key = key.to_string();
+    format!("{}..{}", &key[..4], &key[key.len() - 4..])
+}
+
+fn draw(frame: &mut Frame) {
+    let [status_area, middle_area, detections_area, keys_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(8),
+        Constraint::Min(8),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [senders_area, positions_area] =
+        Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .areas(middle_area);
+
+    frame.render_widget(status_line(), status_area);
+    frame.render_widget(senders_table(), senders_area);
+    frame.render_widget(positions_table(), positions_area);
+    frame.render_widget(detections_table(), detections_area);
+    frame.render_widget(Line::from(KEYS).reversed(), keys_area);
+}
+
+fn status_line() -> Line<'static> {
+    let buying = if EXECUTION_BREAKER.is_tripped() {
+        "PAUSED".red().bold()
+    } else {
+        "buying".green()
+    };
+
+    let blockhash = match latest_blockhash() {
+        Some(blockhash) => format!(
+            "{} ms / {} slots",
+            blockhash.age().as_millis(),
+            blockhash.slot_age()
+        ),
+        None => "none yet".to_string(),
+    };
+
+    let datasources = DATASOURCE_STATUS.connected();
+
+    Line::from(vec![
+        buying,
+        format!(
+            " | following {} | slot {} | blockhash {} | datasources {}",
+            FOLLOWED_TARGETS
+                .all()
+                .iter()
+                .map(short)
+                .collect::<Vec<_>>()
+                .join(", "),
+            current_slot(),
+            blockhash,
+            if datasources.is_empty() {
+                "none".to_string()
+            } else {
+                datasources.join(", ")
+            }
+        )
+        .into(),
+    ])
+}
+
+fn senders_table() -> Table<'static> {
+    let rows = SENDER_SERVICES.iter().filter_map(|service| {
+        let health = sender_health(service)?;
+
+        let (state, color) = if health.is_circuit_open() {
+            ("circuit open", Color::Red)
+        } else if health.is_healthy() {
+            ("healthy", Color::Green)
+        } else {
+            ("degraded", Color::Yellow)
+        };
+
+        Some(
+            Row::new(vec![
+                service.to_string(),
+                state.to_string(),
+                health.last_latency_ms().to_string(),
+                format!("{:.0}%", health.land_rate() * 100.0),
+                health.consecutive_failures().to_string(),
+            ])
+            .style(Style::new().fg(color)),
+        )
+    });
+
+    Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(13),
+            Constraint::Length(8),
+            Constraint::Length(6),
+            Constraint::Length(8),
+        ],
+    )
+    .header(Row::new(["sender", "state", "ms", "landed", "failures"]).bold())
+    .block(Block::bordered().title(" Senders "))
+}
+
+fn positions_table() -> Table<'static> {
+    let mut positions = WALLET_POOL.positions();
+    positions.sort_by_key(|(wallet, mint, _)| (*mint, *wallet));
+
+    let rows = positions.iter().map(|(wallet, mint, tokens)| {
+        Row::new(vec![short(mint), short(wallet), tokens.to_string()])
+    });
+
+    Table::new(
+        rows,
+        [
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Min(10),
+        ],
+    )
+    .header(Row::new(["mint", "wallet", "tokens"]).bold())
+    .block(Block::bordered().title(format!(" Positions ({}) ", positions.len())))
+}
+
+fn detections_table() -> Table<'static> {
+    let rows = recent_detections().into_iter().map(|detection| {
+        Row::new(vec![
+            detection.at.format("%H:%M:%S%.3f").to_string(),
+            detection.venue.to_string(),
+            target_label(&detection.target),
+            detection.signature.to_string(),
+        ])
+    });
+
+    Table::new(
+        rows,
+        [
+            Constraint::Length(12),
+            Constraint::Length(17),
+            Constraint::Length(8),
+            Constraint::Min(20),
+        ],
+    )
+    .header(Row::new(["time (UTC)", "venue", "target", "signature"]).bold())
+    .block(Block::bordered().title(" Last detections "))
+}
@@ -0,0 +1,213 @@
+use carbon_pump_swap_decoder::PROGRAM_ID as PUMP_SWAP_PROGRAM_ID;
+use carbon_pumpfun_decoder::PROGRAM_ID as PUMPFUN_PROGRAM_ID;
+use carbon_raydium_amm_v4_decoder::PROGRAM_ID as RAYDIUM_AMM_V4_PROGRAM_ID;
+use carbon_raydium_cpmm_decoder::PROGRAM_ID as RAYDIUM_CPMM_PROGRAM_ID;
+use carbon_raydium_launchpad_decoder::PROGRAM_ID as RAYDIUM_LAUNCHPAD_PROGRAM_ID;
+use once_cell::sync::Lazy;
+use solana_sdk::{
+    compute_budget, instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+    signer::Signer, system_instruction::SystemInstruction, system_program, sysvar,
+};
+use std::collections::HashSet;
+use tracing::warn;
+
+use crate::{
+    config::{
+        EXTRA_ALLOWED_PROGRAMS, FEE_PAYER_PUBKEY, LAUNCH_BUNDLE_TIP_SOL, PRIORITY_FEE,
+        PROFIT_SWEEP_ADDRESS,
+    },
+    decoder::METEORA_DBC_PROGRAM_ID,
+    instructions::token_account::TOKEN_2022_PROGRAM_ID,
+    service::{JITO_MIN_TIP, JITO_TIP, NOZOMI_MIN_TIP, NOZOMI_TIP, ZSLOT_MIN_TIP, ZSLOT_TIP},
+    utils::{associated_token_address, notify_error},
+    wallet::WALLET_POOL,
+};
+
+/// Programs the bot's own instructions call. Tips are system transfers and durable
+/// nonces system instructions, so they need no entry of their own.
+pub const ALLOWED_PROGRAMS: &[Pubkey] = &[
+    PUMPFUN_PROGRAM_ID,
+    PUMP_SWAP_PROGRAM_ID,
+    RAYDIUM_AMM_V4_PROGRAM_ID,
+    RAYDIUM_CPMM_PROGRAM_ID,
+    RAYDIUM_LAUNCHPAD_PROGRAM_ID,
+    METEORA_DBC_PROGRAM_ID,
+    spl_token::ID,
+    TOKEN_2022_PROGRAM_ID,
+    spl_associated_token_account::ID,
+    system_program::ID,
+    compute_budget::ID,
+];
+
+/// `ALLOWED_PROGRAMS` with the ones added through `EXTRA_ALLOWED_PROGRAMS`
+static ALLOWED: Lazy<HashSet<Pubkey>> = Lazy::new(|| {
+    ALLOWED_PROGRAMS
+        .iter()
+        .chain(EXTRA_ALLOWED_PROGRAMS.iter())
+        .copied()
+        .collect()
+});
+
+/// Sysvars an instruction may read, e.g. rent for an account it opens or recent
+/// blockhashes for a nonce advance
+const SYSVARS: &[Pubkey] = &[
+    sysvar::clock::ID,
+    sysvar::epoch_schedule::ID,
+    sysvar::instructions::ID,
+    sysvar::recent_blockhashes::ID,
+    sysvar::rent::ID,
+    sysvar::slot_hashes::ID,
+    sysvar::slot_history::ID,
+    sysvar::stake_history::ID,
+];
+
+/// Accounts no instruction of ours writes to: the allowed programs and the sysvars
+static NEVER_WRITABLE: Lazy<HashSet<Pubkey>> =
+    Lazy::new(|| ALLOWED.iter().chain(SYSVARS).copied().collect());
+
+/// Accounts of the senders' tips
+static TIP_ACCOUNTS: Lazy<HashSet<Pubkey>> = Lazy::new(|| {
+    JITO_TIP
+        .iter()
+        .chain(NOZOMI_TIP.iter())
+        .chain(ZSLOT_TIP.iter())
+        .map(|address| Pubkey::from_str_const(address))
+        .collect()
+});
+
+/// Whether `address` is one of the senders' tip accounts.
+pub fn is_tip_account(address: &Pubkey) -> bool {
+    TIP_ACCOUNTS.contains(address)
+}
+
+/// Largest tip a sender is paid: `THIRD_PARTY_FEE`, `LAUNCH_BUNDLE_TIP_SOL` or the least
+/// a sender accepts, rounded the way the senders round theirs.
+pub fn max_tip_lamports() -> u64 {
+    let (_, _, third_party_fee) = *PRIORITY_FEE;
+
+    [
+        third_party_fee,
+        LAUNCH_BUNDLE_TIP_SOL.unwrap_or_default(),
+        JITO_MIN_TIP,
+        NOZOMI_MIN_TIP,
+        ZSLOT_MIN_TIP,
+    ]
+    .into_iter()
+    .map(|tip| (tip * LAMPORTS_PER_SOL as f64) as u64)
+    .max()
+    .unwrap_or_default()
+}
+
+/// Checks `ixs` before `signers` sign them: every instruction must call an allowed
+/// program, none may make a program or sysvar writable, and a signer may only be made
+/// writable to one of the built-in `ALLOWED_PROGRAMS`. System instructions may only
+/// move lamports to the signers, their WSOL accounts, the bot's other wallets, the
+/// profit sweep address, or a sender's tip account up to `max_tip_lamports`. A last
+/// line of defense against a builder bug, or a program or account copied from a
+/// target's transaction, that would have the wallet sign something it never meant to.
+pub fn check_instructions(ixs: &[Instruction], signers: &[Pubkey]) -> Result<(), String> {
+    for (index, ix) in ixs.iter().enumerate() {
+        if !ALLOWED.contains(&ix.program_id) {
+            return Err(format!(
+                "instruction {} calls {}, which isn't an allowed program",
+                index, ix.program_id
+            ));
+        }
+
+        if let Some(account) = ix
+            .accounts
+            .iter()
+            .find(|account| account.is_writable && NEVER_WRITABLE.contains(&account.pubkey))
+        {
+            return Err(format!(
+                "instruction {} to {} makes {} writable",
+                index, ix.program_id, account.pubkey
+            ));
+        }
+
+        // Extra programs are trusted to be called, not with the signers' SOL
+        if !ALLOWED_PROGRAMS.contains(&ix.program_id)
+            && let Some(account) = ix
+                .accounts
+                .iter()
+                .find(|account| account.is_writable && signers.contains(&account.pubkey))
+        {
+            return Err(format!(
+                "instruction {} makes signer {} writable to {}",
+                index, account.pubkey, ix.program_id
+            ));
+        }
+
+        if ix.program_id == system_program::ID {
+            check_system_instruction(ix, signers)
+                .map_err(|e| format!("instruction {} {}", index, e))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn check_system_instruction(ix: &Instruction, signers: &[Pubkey]) -> Result<(), String> {
+    let instruction: SystemInstruction =
+        bincode::deserialize(&ix.data).map_err(|_| "isn't a system instruction".to_string())?;
+
+    let (lamports, recipient) = match instruction {
+        SystemInstruction::Transfer { lamports } => (lamports, 1),
+        SystemInstruction::TransferWithSeed { lamports, .. } => (lamports, 2),
+        SystemInstruction::CreateAccount { lamports, .. }
+        | SystemInstruction::CreateAccountWithSeed { lamports, .. } => (lamports, 1),
+        SystemInstruction::AdvanceNonceAccount => return Ok(()),
+        instruction => return Err(format!("is an unexpected {:?}", instruction)),
+    };
+
+    let recipient = ix
+        .accounts
+        .get(recipient)
+        .map(|account| account.pubkey)
+        .ok_or("has no recipient")?;
+
+    if TIP_ACCOUNTS.contains(&recipient) {
+        let max_tip = max_tip_lamports();
+
+        return if lamports <= max_tip {
+            Ok(())
+        } else {
+            Err(format!(
+                "tips {} lamports to {}, more than the configured {}",
+                lamports, recipient, max_tip
+            ))
+        };
+    }
+
+    if is_own_account(&recipient, signers) {
+        return Ok(());
+    }
+
+    Err(format!(
+        "sends {} lamports to {}, which isn't an allowed address",
+        lamports, recipient
+    ))
+}
+
+/// Whether lamports sent to `address` stay with the bot or go to its sweep address.
+fn is_own_account(address: &Pubkey, signers: &[Pubkey]) -> bool {
+    signers.iter().any(|signer| {
+        signer == address
+            || associated_token_address(signer, &spl_token::native_mint::ID) == *address
+    }) || *PROFIT_SWEEP_ADDRESS == Some(*address)
+        || *address == *FEE_PAYER_PUBKEY
+        || WALLET_POOL
+            .wallets()
+            .iter()
+            .any(|wallet| wallet.pubkey() == *address)
+}
+
+/// `check_instructions`, alerting when the transaction is refused.
+pub fn guard_instructions(ixs: &[Instruction], signers: &[Pubkey]) -> Result<(), String> {
+    check_instructions(ixs, signers).map_err(|e| {
+        let message = format!("Refused to sign a transaction: {}", e);
+        warn!("ALERT: {}", message);
+        notify_error(message.clone());
+        message
+    })
+}
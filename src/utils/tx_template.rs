@@ -0,0 +1,113 @@
+use once_cell::sync::Lazy;
+use solana_sdk::{hash::Hash, instruction::Instruction, message::v0::Message, pubkey::Pubkey};
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash as _, Hasher},
+    sync::RwLock,
+};
+
+/// Templates kept before the cache is cleared, bounding memory on long runs
+const TX_TEMPLATE_LIMIT: usize = 10_000;
+
+/// Compiled messages by transaction shape: the payer, and each instruction's program,
+/// accounts and data length. Repeat trades on a mint from the same wallet only differ
+/// in their amounts and blockhash, so the account ordering and header are compiled
+/// once and later transactions just patch instruction data and the blockhash in.
+pub struct TxTemplateCache {
+    templates: RwLock<HashMap<u64, Message>>,
+}
+
+pub static TX_TEMPLATES: Lazy<TxTemplateCache> = Lazy::new(TxTemplateCache::new);
+
+impl Default for TxTemplateCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxTemplateCache {
+    pub fn new() -> Self {
+        Self {
+            templates: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Compiles `ixs` into a v0 message, from the cached template when one of the same
+    /// shape was compiled before.
+    pub fn compile(&self, payer: &Pubkey, ixs: &[Instruction], recent_blockhash: Hash) -> Message {
+        let key = shape_key(payer, ixs);
+
+        if let Some(template) = self.templates.read().unwrap().get(&key) {
+            return patch(template, ixs, recent_blockhash);
+        }
+
+        let message = Message::try_compile(payer, ixs, &[], recent_blockhash)
+            .expect("Failed to compile message");
+
+        self.insert(key, message.clone());
+
+        message
+    }
+
+    /// Compiles the template for `ixs` ahead of time, so the first send of this shape
+    /// takes the patching path as well.
+    pub fn prewarm(&self, payer: &Pubkey, ixs: &[Instruction]) {
+        let key = shape_key(payer, ixs);
+
+        if self.templates.read().unwrap().contains_key(&key) {
+            return;
+        }
+
+        if let Ok(message) = Message::try_compile(payer, ixs, &[], Hash::default()) {
+            self.insert(key, message);
+        }
+    }
+
+    fn insert(&self, key: u64, message: Message) {
+        let mut templates = self.templates.write().unwrap();
+
+        if templates.len() >= TX_TEMPLATE_LIMIT {
+            templates.clear();
+        }
+
+        templates.insert(key, message);
+    }
+}
+
+/// Compiles `ixs` through the shared template cache.
+pub fn compile_message(payer: &Pubkey, ixs: &[Instruction], recent_blockhash: Hash) -> Message {
+    TX_TEMPLATES.compile(payer, ixs, recent_blockhash)
+}
+
+fn shape_key(payer: &Pubkey, ixs: &[Instruction]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    payer.hash(&mut hasher);
+
+    for ix in ixs {
+        ix.program_id.hash(&mut hasher);
+        ix.data.len().hash(&mut hasher);
+
+        for meta in &ix.accounts {
+            meta.pubkey.hash(&mut hasher);
+            meta.is_signer.hash(&mut hasher);
+            meta.is_writable.hash(&mut hasher);
+        }
+    }
+
+    hasher.finish()
+}
+
+/// Compilation keeps the instruction order, so the i-th compiled instruction takes
+/// the data of the i-th instruction.
+fn patch(template: &Message, ixs: &[Instruction], recent_blockhash: Hash) -> Message {
+    let mut message = template.clone();
+
+    message.recent_blockhash = recent_blockhash;
+
+    for (compiled, ix) in message.instructions.iter_mut().zip(ixs) {
+        compiled.data.copy_from_slice(&ix.data);
+    }
+
+    message
+}
@@ -1,6 +1,6 @@
 pub fn ceil_div(token_amount: u128, fee_numerator: u128, fee_denominator: u128) -> Option<u128> {
     token_amount
-        .checked_mul(u128::from(fee_numerator))
+        .checked_mul(fee_numerator)
         .unwrap()
         .checked_add(fee_denominator)?
         .checked_sub(1)?
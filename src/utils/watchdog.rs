@@ -0,0 +1,78 @@
+use once_cell::sync::Lazy;
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tokio::time::sleep;
+use tracing::warn;
+
+use crate::{
+    config::WATCHDOG_STALL_SEC,
+    datasource::DATASOURCE_STATUS,
+    utils::{latest_blockhash, notify_error},
+};
+
+/// How often the watchdog looks at the heartbeat
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Last time the pipeline processed an update or flushed its metrics
+static PIPELINE_HEARTBEAT: Lazy<Mutex<Instant>> = Lazy::new(|| Mutex::new(Instant::now()));
+
+/// Records that the pipeline loop made progress, see `PipelineHeartbeat`.
+pub fn record_pipeline_heartbeat() {
+    *PIPELINE_HEARTBEAT.lock().unwrap() = Instant::now();
+}
+
+/// Time since the pipeline loop last made progress.
+pub fn pipeline_heartbeat_age() -> Duration {
+    PIPELINE_HEARTBEAT.lock().unwrap().elapsed()
+}
+
+/// Why the bot looks stalled after `stall` without progress, `None` while it doesn't.
+/// Only counts while a datasource claims to be connected, reconnecting ones aren't
+/// stalled.
+fn stall_reason(stall: Duration) -> Option<String> {
+    if DATASOURCE_STATUS.connected().is_empty() {
+        return None;
+    }
+
+    let heartbeat_age = pipeline_heartbeat_age();
+
+    if heartbeat_age > stall {
+        return Some(format!("no pipeline progress for {:?}", heartbeat_age));
+    }
+
+    latest_blockhash()
+        .map(|blockhash| blockhash.age())
+        .filter(|age| *age > stall)
+        .map(|age| format!("blockhash not refreshed for {:?}", age))
+}
+
+/// Returns once the running pipeline stalls: no progress for `WATCHDOG_STALL_SEC`
+/// while datasources are connected, see `stall_reason`. The caller tears it down and
+/// builds a new one. A pipeline gets the full `WATCHDOG_STALL_SEC` from its start
+/// before it's judged, so a restart can't loop. Never returns with the watchdog off.
+pub async fn pipeline_watchdog() {
+    if *WATCHDOG_STALL_SEC == 0 {
+        return std::future::pending().await;
+    }
+
+    let stall = Duration::from_secs(*WATCHDOG_STALL_SEC);
+    let started = Instant::now();
+
+    record_pipeline_heartbeat();
+
+    loop {
+        sleep(WATCHDOG_INTERVAL).await;
+
+        if started.elapsed() <= stall {
+            continue;
+        }
+
+        if let Some(reason) = stall_reason(stall) {
+            warn!("ALERT: Pipeline stalled, {}, restarting it", reason);
+            notify_error(format!("Pipeline stalled, {}, restarting it", reason));
+            return;
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde_json::{Value, json};
+use sha2::Sha256;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::{
+    config::{WEBHOOK_SECRET, WEBHOOK_URLS},
+    service::senders_stubbed,
+};
+
+static WEBHOOK_CLIENT: Lazy<Client> = Lazy::new(|| {
+    Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+        .expect("Failed to build HTTP client")
+});
+
+/// POSTs `{"event", "timestamp_ms", "data"}` to every `WEBHOOK_URLS` entry in the
+/// background. With `WEBHOOK_SECRET` set, the body's HMAC-SHA256 goes in the
+/// `X-Signature-256` header as `sha256=<hex>`. A failed post is only logged. A replay
+/// or backtest posts nothing.
+pub fn post_webhooks(event: &'static str, data: Value) {
+    if WEBHOOK_URLS.is_empty() || senders_stubbed() {
+        return;
+    }
+
+    let body = json!({
+        "event": event,
+        "timestamp_ms": Utc::now().timestamp_millis(),
+        "data": data,
+    })
+    .to_string();
+
+    let signature = WEBHOOK_SECRET
+        .as_deref()
+        .map(|secret| webhook_signature(secret, body.as_bytes()));
+
+    for url in WEBHOOK_URLS.iter() {
+        let mut request = WEBHOOK_CLIENT
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header("X-Event", event)
+            .body(body.clone());
+
+        if let Some(signature) = &signature {
+            request = request.header("X-Signature-256", signature);
+        }
+
+        tokio::spawn(async move {
+            match request.send().await {
+                Ok(response) if response.status().is_success() => {}
+                Ok(response) => warn!(
+                    "Webhook {} rejected {} event: {}",
+                    response.url(),
+                    event,
+                    response.status()
+                ),
+                Err(e) => warn!("Failed to post {} event to webhook: {}", event, e),
+            }
+        });
+    }
+}
+
+/// `sha256=` followed by the hex HMAC-SHA256 of `body` keyed with `secret`, for
+/// receivers to check a payload came from this bot.
+pub fn webhook_signature(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+
+    let digest: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    format!("sha256={}", digest)
+}
@@ -0,0 +1,364 @@
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    config::{AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY, AWS_SESSION_TOKEN, GCP_ACCESS_TOKEN},
+    wallet::remote_call,
+};
+
+/// DER prefix of an Ed25519 `SubjectPublicKeyInfo`, the key follows it
+const ED25519_SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+
+const GCP_KMS_ENDPOINT: &str = "https://cloudkms.googleapis.com/v1";
+const GCP_METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+static KMS_CLIENT: Lazy<Client> = Lazy::new(Client::new);
+
+/// Access token from the GCP metadata server and when it expires
+static GCP_TOKEN: Lazy<Mutex<Option<(String, Instant)>>> = Lazy::new(|| Mutex::new(None));
+
+/// Credentials requests to AWS are signed with.
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+/// An Ed25519 key held in a cloud KMS, which signs without the key ever leaving it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KmsKey {
+    /// AWS KMS key by ARN, an `ECC_NIST_EDWARDS25519` signing key
+    Aws { region: String, key_id: String },
+    /// GCP Cloud KMS key version, an `EC_SIGN_ED25519` key, as
+    /// `projects/../locations/../keyRings/../cryptoKeys/../cryptoKeyVersions/..`
+    Gcp { name: String },
+}
+
+impl KmsKey {
+    /// `aws-kms:<key ARN>` or `gcp-kms:<key version name>`.
+    pub fn from_entry(entry: &str) -> Result<Self, String> {
+        if let Some(arn) = entry.strip_prefix("aws-kms:") {
+            // arn:aws:kms:<region>:<account>:key/<id>
+            let region = arn
+                .split(':')
+                .nth(3)
+                .filter(|region| arn.starts_with("arn:") && !region.is_empty())
+                .ok_or_else(|| format!("{} isn't a KMS key ARN", arn))?;
+
+            return Ok(Self::Aws {
+                region: region.to_string(),
+                key_id: arn.to_string(),
+            });
+        }
+
+        match entry.strip_prefix("gcp-kms:") {
+            Some(name) if name.starts_with("projects/") && name.contains("/cryptoKeyVersions/") => {
+                Ok(Self::Gcp {
+                    name: name.to_string(),
+                })
+            }
+            _ => Err(format!("{} isn't a KMS key", entry)),
+        }
+    }
+}
+
+/// A wallet whose key is in a cloud KMS. Signatures are checked against the key before
+/// they go into a transaction.
+#[derive(Debug)]
+pub struct KmsSigner {
+    key: KmsKey,
+    pubkey: Pubkey,
+}
+
+impl KmsSigner {
+    /// The key of `key`, read from the KMS.
+    pub fn connect(key: KmsKey) -> Result<Self, String> {
+        let pubkey = remote_call(kms_pubkey(key.clone()))?;
+
+        Ok(Self { key, pubkey })
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Result<Signature, String> {
+        let signature = remote_call(kms_sign(self.key.clone(), message.to_vec()))?;
+
+        if !signature.verify(self.pubkey.as_ref(), message) {
+            return Err("the KMS answered with an invalid signature".to_string());
+        }
+
+        Ok(signature)
+    }
+}
+
+/// The Ed25519 key of a DER `SubjectPublicKeyInfo`.
+pub fn ed25519_spki_pubkey(der: &[u8]) -> Option<Pubkey> {
+    let key = der.strip_prefix(&ED25519_SPKI_PREFIX)?;
+
+    Pubkey::try_from(key).ok()
+}
+
+async fn kms_pubkey(key: KmsKey) -> Result<Pubkey, String> {
+    let der = match &key {
+        KmsKey::Aws { region, key_id } => {
+            let response =
+                aws_kms_request(region, "GetPublicKey", json!({ "KeyId": key_id })).await?;
+
+            response["PublicKey"]
+                .as_str()
+                .and_then(|der| base64::decode(der).ok())
+        }
+        KmsKey::Gcp { name } => {
+            let request = KMS_CLIENT.get(format!("{}/{}/publicKey", GCP_KMS_ENDPOINT, name));
+            let response = gcp_kms_request(request).await?;
+
+            response["pem"].as_str().and_then(|pem| {
+                let body: String = pem
+                    .lines()
+                    .filter(|line| !line.starts_with("-----"))
+                    .collect();
+                base64::decode(body).ok()
+            })
+        }
+    };
+
+    der.as_deref()
+        .and_then(ed25519_spki_pubkey)
+        .ok_or_else(|| "the KMS key isn't an Ed25519 key".to_string())
+}
+
+async fn kms_sign(key: KmsKey, message: Vec<u8>) -> Result<Signature, String> {
+    let signature = match &key {
+        KmsKey::Aws { region, key_id } => {
+            let body = json!({
+                "KeyId": key_id,
+                "Message": base64::encode(&message),
+                "MessageType": "RAW",
+                "SigningAlgorithm": "ED25519_SHA_512",
+            });
+            let response = aws_kms_request(region, "Sign", body).await?;
+
+            response["Signature"].as_str().map(base64::decode)
+        }
+        KmsKey::Gcp { name } => {
+            let request = KMS_CLIENT
+                .post(format!("{}/{}:asymmetricSign", GCP_KMS_ENDPOINT, name))
+                .json(&json!({ "data": base64::encode(&message) }));
+            let response = gcp_kms_request(request).await?;
+
+            response["signature"].as_str().map(base64::decode)
+        }
+    };
+
+    signature
+        .and_then(Result::ok)
+        .and_then(|signature| Signature::try_from(signature.as_slice()).ok())
+        .ok_or_else(|| "the KMS answered with no signature".to_string())
+}
+
+/// Calls the AWS KMS JSON API `action`, e.g. `Sign`, signed with SigV4.
+async fn aws_kms_request(region: &str, action: &str, body: Value) -> Result<Value, String> {
+    aws_json_request(region, "kms", &format!("TrentService.{}", action), body).await
+}
+
+/// Calls an AWS JSON API of `service` in `region`, `target` naming the action, e.g.
+/// `secretsmanager.GetSecretValue`, signed with SigV4.
+pub async fn aws_json_request(
+    region: &str,
+    service: &str,
+    target: &str,
+    body: Value,
+) -> Result<Value, String> {
+    let credentials = AwsCredentials {
+        access_key_id: AWS_ACCESS_KEY_ID
+            .clone()
+            .ok_or("AWS_ACCESS_KEY_ID isn't set")?,
+        secret_access_key: AWS_SECRET_ACCESS_KEY
+            .clone()
+            .ok_or("AWS_SECRET_ACCESS_KEY isn't set")?,
+        session_token: AWS_SESSION_TOKEN.clone(),
+    };
+
+    let host = format!("{}.{}.amazonaws.com", service, region);
+    let body = body.to_string();
+    let amz_date = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut headers = vec![
+        ("content-type", "application/x-amz-json-1.1"),
+        ("host", host.as_str()),
+        ("x-amz-date", amz_date.as_str()),
+        ("x-amz-target", target),
+    ];
+
+    if let Some(session_token) = credentials.session_token.as_deref() {
+        headers.push(("x-amz-security-token", session_token));
+    }
+
+    let authorization = aws_sigv4_authorization(
+        &credentials,
+        region,
+        service,
+        "POST",
+        &headers,
+        body.as_bytes(),
+    );
+
+    let mut request = KMS_CLIENT
+        .post(format!("https://{}/", host))
+        .header("authorization", authorization)
+        .body(body.clone());
+
+    for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+        request = request.header(*name, *value);
+    }
+
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    let status = response.status();
+    let response: Value = response.json().await.map_err(|e| e.to_string())?;
+
+    if !status.is_success() {
+        return Err(format!("AWS {} failed: {} {}", target, status, response));
+    }
+
+    Ok(response)
+}
+
+/// `Authorization` header of an AWS request to path `/` with no query, signed with
+/// SigV4. `headers` are the ones signed, with lowercase names, `host` and `x-amz-date`
+/// among them.
+pub fn aws_sigv4_authorization(
+    credentials: &AwsCredentials,
+    region: &str,
+    service: &str,
+    method: &str,
+    headers: &[(&str, &str)],
+    body: &[u8],
+) -> String {
+    let mut headers = headers.to_vec();
+    headers.sort_by_key(|(name, _)| *name);
+
+    let amz_date = headers
+        .iter()
+        .find(|(name, _)| *name == "x-amz-date")
+        .map(|(_, value)| *value)
+        .unwrap_or_default();
+    let date = amz_date.get(..8).unwrap_or_default();
+
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let canonical_request = format!(
+        "{}\n/\n\n{}\n{}\n{}",
+        method,
+        canonical_headers,
+        signed_headers,
+        hex(&Sha256::digest(body))
+    );
+
+    let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex(&Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let signing_key = [date, region, service, "aws4_request"].iter().fold(
+        format!("AWS4{}", credentials.secret_access_key).into_bytes(),
+        |key, part| hmac_sha256(&key, part.as_bytes()),
+    );
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id,
+        scope,
+        signed_headers,
+        hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()))
+    )
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Calls the GCP Cloud KMS API with `GCP_ACCESS_TOKEN`, or else a token of the
+/// instance's service account from the metadata server.
+async fn gcp_kms_request(request: reqwest::RequestBuilder) -> Result<Value, String> {
+    let token = match GCP_ACCESS_TOKEN.clone() {
+        Some(token) => token,
+        None => gcp_metadata_token().await?,
+    };
+
+    let response = request
+        .bearer_auth(token)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let status = response.status();
+    let response: Value = response.json().await.map_err(|e| e.to_string())?;
+
+    if !status.is_success() {
+        return Err(format!("GCP KMS request failed: {} {}", status, response));
+    }
+
+    Ok(response)
+}
+
+/// Service account token from the GCP metadata server, reused until a minute before it
+/// expires.
+async fn gcp_metadata_token() -> Result<String, String> {
+    if let Some((token, expires)) = GCP_TOKEN.lock().unwrap().clone()
+        && Instant::now() < expires
+    {
+        return Ok(token);
+    }
+
+    let response: Value = KMS_CLIENT
+        .get(GCP_METADATA_TOKEN_URL)
+        .header("Metadata-Flavor", "Google")
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("no GCP_ACCESS_TOKEN and no metadata server: {}", e))?
+        .json()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let token = response["access_token"]
+        .as_str()
+        .ok_or("the metadata server answered with no token")?
+        .to_string();
+    let expires_in = response["expires_in"].as_u64().unwrap_or_default();
+    let expires = Instant::now() + Duration::from_secs(expires_in.saturating_sub(60));
+
+    *GCP_TOKEN.lock().unwrap() = Some((token.clone(), expires));
+
+    Ok(token)
+}
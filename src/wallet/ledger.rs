@@ -0,0 +1,338 @@
+use once_cell::sync::Lazy;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::{
+    collections::VecDeque,
+    fs::{self, File, OpenOptions},
+    io::{Read, Write},
+    path::PathBuf,
+    sync::{
+        Mutex,
+        mpsc::{self, Sender},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+use crate::config::{LEDGER_DEVICE, LEDGER_MAX_SIGNATURES_PER_HOUR, LEDGER_SIGN_TIMEOUT_SEC};
+
+/// Ledger's USB vendor id as `HID_ID` shows it in sysfs
+const LEDGER_VENDOR_ID: &str = "00002C97";
+
+/// Derivation path of a `ledger` wallet that doesn't give one
+pub const DEFAULT_LEDGER_PATH: &str = "44'/501'/0'/0'";
+
+const HID_PACKET_SIZE: usize = 64;
+const TRANSPORT_HEADER_LEN: usize = 5;
+const APDU_TAG: u8 = 0x05;
+const APDU_CLA: u8 = 0xe0;
+const INS_GET_PUBKEY: u8 = 0x05;
+const INS_SIGN_MESSAGE: u8 = 0x06;
+const P1_NON_CONFIRM: u8 = 0x00;
+const P1_CONFIRM: u8 = 0x01;
+const P2_EXTEND: u8 = 0x01;
+const P2_MORE: u8 = 0x02;
+/// Largest APDU payload
+const MAX_CHUNK_SIZE: usize = 255;
+const STATUS_OK: u16 = 0x9000;
+const STATUS_REJECTED: u16 = 0x6985;
+
+/// One command for the Solana app
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Apdu {
+    pub ins: u8,
+    pub p1: u8,
+    pub p2: u8,
+    pub data: Vec<u8>,
+}
+
+/// APDUs sent in a row, answered with the last one's response.
+struct LedgerRequest {
+    apdus: Vec<Apdu>,
+    reply: Sender<Result<Vec<u8>, String>>,
+}
+
+/// Queue of the thread owning the device, which answers one request at a time. `None`
+/// when no Ledger was found.
+static LEDGER: Lazy<Option<Sender<LedgerRequest>>> = Lazy::new(|| {
+    let path = LEDGER_DEVICE
+        .clone()
+        .map(PathBuf::from)
+        .or_else(find_ledger)?;
+
+    let mut device = match OpenOptions::new().read(true).write(true).open(&path) {
+        Ok(device) => device,
+        Err(e) => {
+            warn!(
+                "ALERT: Failed to open the Ledger at {}: {}",
+                path.display(),
+                e
+            );
+            return None;
+        }
+    };
+
+    info!("Signing with the Ledger at {}", path.display());
+
+    let (sender, receiver) = mpsc::channel::<LedgerRequest>();
+
+    thread::Builder::new()
+        .name("ledger".to_string())
+        .spawn(move || {
+            for request in receiver {
+                let response = request
+                    .apdus
+                    .iter()
+                    .try_fold(Vec::new(), |_, apdu| exchange(&mut device, apdu));
+
+                let _ = request.reply.send(response);
+            }
+        })
+        .expect("Failed to spawn the Ledger thread");
+
+    Some(sender)
+});
+
+/// Signatures asked of the device over the last hour, for
+/// `LEDGER_MAX_SIGNATURES_PER_HOUR`
+static RECENT_SIGNATURES: Lazy<Mutex<VecDeque<Instant>>> =
+    Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// A key on a Ledger running the Solana app. Every signature has to be approved on the
+/// device, which needs blind signing enabled for the swap programs.
+#[derive(Debug)]
+pub struct LedgerSigner {
+    pubkey: Pubkey,
+    path: Vec<u32>,
+}
+
+impl LedgerSigner {
+    /// The key at `path`, e.g. `44'/501'/0'/0'`, read from the device.
+    pub fn connect(path: &str) -> Result<Self, String> {
+        let path = parse_derivation_path(path)?;
+
+        let response = ledger_request(vec![Apdu {
+            ins: INS_GET_PUBKEY,
+            p1: P1_NON_CONFIRM,
+            p2: 0,
+            data: serialize_derivation_path(&path),
+        }])?;
+
+        let pubkey = Pubkey::try_from(response.as_slice())
+            .map_err(|_| "the Ledger answered with no public key".to_string())?;
+
+        Ok(Self { pubkey, path })
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    /// Has the device sign `message`, refused once `LEDGER_MAX_SIGNATURES_PER_HOUR`
+    /// signatures were asked for in the last hour, so a runaway bot can't flood the
+    /// device with prompts to approve.
+    pub fn sign(&self, message: &[u8]) -> Result<Signature, String> {
+        {
+            let mut recent = RECENT_SIGNATURES.lock().unwrap();
+
+            while recent
+                .front()
+                .is_some_and(|asked| asked.elapsed() > Duration::from_secs(3600))
+            {
+                recent.pop_front();
+            }
+
+            if recent.len() >= *LEDGER_MAX_SIGNATURES_PER_HOUR {
+                return Err(format!(
+                    "{} Ledger signatures asked for in the last hour, the limit",
+                    recent.len()
+                ));
+            }
+
+            recent.push_back(Instant::now());
+        }
+
+        let response = ledger_request(sign_apdus(&self.path, message))?;
+
+        Signature::try_from(response.as_slice())
+            .map_err(|_| "the Ledger answered with no signature".to_string())
+    }
+}
+
+/// Sends `apdus` to the device thread and waits for the answer, up to
+/// `LEDGER_SIGN_TIMEOUT_SEC` for someone to approve on the device.
+fn ledger_request(apdus: Vec<Apdu>) -> Result<Vec<u8>, String> {
+    let ledger = LEDGER.as_ref().ok_or("no Ledger found")?;
+    let (reply, response) = mpsc::channel();
+
+    ledger
+        .send(LedgerRequest { apdus, reply })
+        .map_err(|_| "the Ledger thread stopped".to_string())?;
+
+    response
+        .recv_timeout(Duration::from_secs(*LEDGER_SIGN_TIMEOUT_SEC))
+        .map_err(|_| "timed out waiting for the Ledger".to_string())?
+}
+
+/// First hidraw device with Ledger's vendor id.
+fn find_ledger() -> Option<PathBuf> {
+    let mut devices: Vec<_> = fs::read_dir("/sys/class/hidraw").ok()?.flatten().collect();
+    devices.sort_by_key(|device| device.file_name());
+
+    devices.into_iter().find_map(|device| {
+        let uevent = fs::read_to_string(device.path().join("device/uevent")).ok()?;
+
+        uevent
+            .lines()
+            .any(|line| line.starts_with("HID_ID=") && line.contains(LEDGER_VENDOR_ID))
+            .then(|| PathBuf::from("/dev").join(device.file_name()))
+    })
+}
+
+/// BIP32 path like `44'/501'/0'/0'`. Every index is hardened, as ed25519 needs.
+pub fn parse_derivation_path(path: &str) -> Result<Vec<u32>, String> {
+    let path = path.trim().trim_start_matches("m/");
+
+    let indexes = path
+        .split('/')
+        .map(|index| {
+            index
+                .trim_end_matches('\'')
+                .parse::<u32>()
+                .ok()
+                .filter(|index| *index < 0x8000_0000)
+                .ok_or_else(|| format!("invalid derivation path {}", path))
+        })
+        .collect::<Result<Vec<u32>, String>>()?;
+
+    if indexes.is_empty() || indexes.len() > 4 {
+        return Err(format!("invalid derivation path {}", path));
+    }
+
+    Ok(indexes)
+}
+
+fn serialize_derivation_path(path: &[u32]) -> Vec<u8> {
+    let mut serialized = vec![path.len() as u8];
+
+    for index in path {
+        serialized.extend_from_slice(&(index | 0x8000_0000).to_be_bytes());
+    }
+
+    serialized
+}
+
+/// The APDUs signing `message` with the key at `path`, split into chunks the app
+/// accepts: the first carries the signer count and path, the rest extend it.
+pub fn sign_apdus(path: &[u32], message: &[u8]) -> Vec<Apdu> {
+    let mut first = vec![1];
+    first.extend(serialize_derivation_path(path));
+
+    let (head, rest) = message.split_at(message.len().min(MAX_CHUNK_SIZE - first.len()));
+    first.extend_from_slice(head);
+
+    let mut apdus = vec![Apdu {
+        ins: INS_SIGN_MESSAGE,
+        p1: P1_CONFIRM,
+        p2: if rest.is_empty() { 0 } else { P2_MORE },
+        data: first,
+    }];
+
+    apdus.extend(rest.chunks(MAX_CHUNK_SIZE).map(|chunk| Apdu {
+        ins: INS_SIGN_MESSAGE,
+        p1: P1_CONFIRM,
+        p2: P2_EXTEND | P2_MORE,
+        data: chunk.to_vec(),
+    }));
+
+    // The last chunk says it's the last
+    if let Some(last) = apdus.last_mut().filter(|_| !rest.is_empty()) {
+        last.p2 &= !P2_MORE;
+    }
+
+    apdus
+}
+
+/// HID packets carrying `apdu`, each led by the channel, tag and sequence number, the
+/// first also by the APDU's length.
+pub fn hid_packets(apdu: &Apdu) -> Vec<[u8; HID_PACKET_SIZE]> {
+    let mut payload = vec![APDU_CLA, apdu.ins, apdu.p1, apdu.p2, apdu.data.len() as u8];
+    payload.extend_from_slice(&apdu.data);
+
+    let mut framed = (payload.len() as u16).to_be_bytes().to_vec();
+    framed.extend(payload);
+
+    framed
+        .chunks(HID_PACKET_SIZE - TRANSPORT_HEADER_LEN)
+        .enumerate()
+        .map(|(sequence, chunk)| {
+            let mut packet = [0; HID_PACKET_SIZE];
+            packet[..3].copy_from_slice(&[0x01, 0x01, APDU_TAG]);
+            packet[3..5].copy_from_slice(&(sequence as u16).to_be_bytes());
+            packet[TRANSPORT_HEADER_LEN..TRANSPORT_HEADER_LEN + chunk.len()].copy_from_slice(chunk);
+            packet
+        })
+        .collect()
+}
+
+/// Sends one APDU and reads its response, without the status word.
+fn exchange(device: &mut File, apdu: &Apdu) -> Result<Vec<u8>, String> {
+    for packet in hid_packets(apdu) {
+        // hidraw takes a leading report id, 0 for devices without numbered reports
+        let mut report = [0; HID_PACKET_SIZE + 1];
+        report[1..].copy_from_slice(&packet);
+
+        device
+            .write_all(&report)
+            .map_err(|e| format!("failed to write to the Ledger: {}", e))?;
+    }
+
+    let mut response = Vec::new();
+    let mut length = None;
+
+    for sequence in 0..=u16::MAX {
+        let mut packet = [0; HID_PACKET_SIZE];
+        let read = device
+            .read(&mut packet)
+            .map_err(|e| format!("failed to read from the Ledger: {}", e))?;
+
+        if read < TRANSPORT_HEADER_LEN
+            || packet[..3] != [0x01, 0x01, APDU_TAG]
+            || packet[3..5] != sequence.to_be_bytes()
+        {
+            return Err("unexpected response from the Ledger".to_string());
+        }
+
+        let mut offset = TRANSPORT_HEADER_LEN;
+
+        if length.is_none() {
+            length = Some(u16::from_be_bytes([packet[5], packet[6]]) as usize);
+            offset += 2;
+        }
+
+        let length = length.unwrap_or_default();
+
+        response.extend_from_slice(&packet[offset..read]);
+        response.truncate(length);
+
+        if response.len() == length {
+            break;
+        }
+    }
+
+    let Some(split) = response.len().checked_sub(2) else {
+        return Err("the Ledger answered with no status".to_string());
+    };
+
+    match u16::from_be_bytes([response[split], response[split + 1]]) {
+        STATUS_OK => {
+            response.truncate(split);
+            Ok(response)
+        }
+        STATUS_REJECTED => Err("rejected on the Ledger".to_string()),
+        status => Err(format!(
+            "the Ledger answered 0x{:04x}, is the Solana app open with blind signing on?",
+            status
+        )),
+    }
+}
@@ -0,0 +1,126 @@
+use once_cell::sync::Lazy;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use std::sync::atomic::{AtomicU8, Ordering};
+use tokio::time::{Duration, sleep};
+use tracing::{info, warn};
+
+use crate::{
+    config::{
+        FEE_PAYER_PUBKEY, LOW_BALANCE_HALT_SOL, LOW_BALANCE_WARN_SOL, WALLET_BALANCE_REFRESH_SEC,
+    },
+    utils::notify_error,
+    wallet::WALLET_POOL,
+};
+
+/// How low the fee payer's SOL is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum BalanceLevel {
+    Funded,
+    /// Under `LOW_BALANCE_WARN_SOL`
+    Low,
+    /// Under `LOW_BALANCE_HALT_SOL`, buys are held back
+    Critical,
+}
+
+impl BalanceLevel {
+    fn from_u8(level: u8) -> Self {
+        match level {
+            2 => Self::Critical,
+            1 => Self::Low,
+            _ => Self::Funded,
+        }
+    }
+}
+
+/// Watches the SOL of the wallet paying the tips and fees. Under the warning threshold
+/// it's alerted on, under the critical one buys stop until it's funded again, keeping
+/// what's left for the tips and fees of exits. Either threshold may be unset.
+pub struct LowBalanceMonitor {
+    warn_below: Option<u64>,
+    halt_below: Option<u64>,
+    level: AtomicU8,
+}
+
+pub static LOW_BALANCE: Lazy<LowBalanceMonitor> = Lazy::new(|| {
+    let lamports = |sol: Option<f64>| sol.map(|sol| (sol * LAMPORTS_PER_SOL as f64) as u64);
+
+    LowBalanceMonitor::new(
+        lamports(*LOW_BALANCE_WARN_SOL),
+        lamports(*LOW_BALANCE_HALT_SOL),
+    )
+});
+
+impl LowBalanceMonitor {
+    pub fn new(warn_below: Option<u64>, halt_below: Option<u64>) -> Self {
+        Self {
+            warn_below,
+            halt_below,
+            level: AtomicU8::new(BalanceLevel::Funded as u8),
+        }
+    }
+
+    /// Records the payer's `lamports` and returns its level, alerting when it drops to
+    /// a lower level and logging when it recovers.
+    pub fn observe(&self, lamports: u64) -> BalanceLevel {
+        let below =
+            |threshold: Option<u64>| threshold.is_some_and(|threshold| lamports < threshold);
+
+        let level = if below(self.halt_below) {
+            BalanceLevel::Critical
+        } else if below(self.warn_below) {
+            BalanceLevel::Low
+        } else {
+            BalanceLevel::Funded
+        };
+
+        let previous = BalanceLevel::from_u8(self.level.swap(level as u8, Ordering::Relaxed));
+        let sol = lamports as f64 / LAMPORTS_PER_SOL as f64;
+
+        if level > previous {
+            let message = match level {
+                BalanceLevel::Critical => format!(
+                    "Fee payer is down to {} SOL, under LOW_BALANCE_HALT_SOL: buying is paused \
+                     until it's funded, sells still go through",
+                    sol
+                ),
+                _ => format!(
+                    "Fee payer is down to {} SOL, under LOW_BALANCE_WARN_SOL",
+                    sol
+                ),
+            };
+
+            warn!("ALERT: {}", message);
+            notify_error(message);
+        } else if level < previous {
+            if previous == BalanceLevel::Critical {
+                info!("Fee payer is back to {} SOL, buying again", sol);
+            } else {
+                info!("Fee payer is back to {} SOL", sol);
+            }
+        }
+
+        level
+    }
+
+    /// Whether buys are held back for the payer's balance.
+    pub fn is_halted(&self) -> bool {
+        BalanceLevel::from_u8(self.level.load(Ordering::Relaxed)) == BalanceLevel::Critical
+    }
+}
+
+/// Checks the fee payer's balance, as last refreshed by `wallet_balance_handler`, every
+/// `WALLET_BALANCE_REFRESH_SEC` against `LOW_BALANCE_WARN_SOL` and `LOW_BALANCE_HALT_SOL`.
+pub async fn low_balance_handler() {
+    if LOW_BALANCE_WARN_SOL.is_none() && LOW_BALANCE_HALT_SOL.is_none() {
+        return;
+    }
+
+    loop {
+        if let Some(balance) = WALLET_POOL.balance(&FEE_PAYER_PUBKEY) {
+            LOW_BALANCE.observe(balance);
+        }
+
+        sleep(Duration::from_secs(*WALLET_BALANCE_REFRESH_SEC)).await;
+    }
+}
@@ -0,0 +1,27 @@
+pub mod kms;
+pub mod ledger;
+pub mod low_balance;
+pub mod paper;
+pub mod pool;
+pub mod position_store;
+pub mod profit_sweep;
+pub mod remote_signer;
+pub mod rotation;
+pub mod signer;
+pub mod token_accounts;
+pub mod top_up;
+pub mod transfer;
+
+pub use kms::*;
+pub use ledger::*;
+pub use low_balance::*;
+pub use paper::*;
+pub use pool::*;
+pub use position_store::*;
+pub use profit_sweep::*;
+pub use remote_signer::*;
+pub use rotation::*;
+pub use signer::*;
+pub use token_accounts::*;
+pub use top_up::*;
+pub use transfer::*;
@@ -0,0 +1,106 @@
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signer::Signer};
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+use tracing::info;
+
+use crate::{
+    config::{
+        PAPER_LANDING_MS, PAPER_SLIPPAGE_BPS_PER_100MS, PAPER_SOL_BALANCE, PAPER_TRADING, READ_ONLY,
+    },
+    wallet::WALLET_POOL,
+};
+
+/// Slippage is capped here, however slow the modeled fill
+const MAX_PAPER_SLIPPAGE: f64 = 0.5;
+
+/// Set by a backtest, which always paper trades whatever `PAPER_TRADING` says
+static PAPER_TRADING_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Paper trades for the rest of the run.
+pub fn enable_paper_trading() {
+    PAPER_TRADING_ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether orders fill against the virtual balances, through `PAPER_TRADING`,
+/// `READ_ONLY` or a backtest.
+pub fn paper_trading() -> bool {
+    *PAPER_TRADING || *READ_ONLY || PAPER_TRADING_ENABLED.load(Ordering::Relaxed)
+}
+
+/// What a paper order did to its wallet's virtual balances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaperFill {
+    /// Lamports the wallet gained, negative for a buy
+    pub sol_flow: i64,
+    /// Tokens of the mint the wallet holds after the fill
+    pub position: u64,
+}
+
+/// Starts every trading wallet at `PAPER_SOL_BALANCE` virtual SOL.
+pub fn seed_paper_balances() {
+    let lamports = (*PAPER_SOL_BALANCE * LAMPORTS_PER_SOL as f64) as u64;
+
+    for wallet in WALLET_POOL.wallets() {
+        WALLET_POOL.set_balance(&wallet.pubkey(), lamports);
+    }
+
+    info!("Paper trading with {} SOL per wallet", *PAPER_SOL_BALANCE);
+}
+
+/// Adverse price move of a fill landing `elapsed` after the target's trade, plus the
+/// modeled `PAPER_LANDING_MS`.
+pub fn paper_slippage(elapsed: Duration) -> f64 {
+    let latency_ms = elapsed.as_millis() as f64 + *PAPER_LANDING_MS as f64;
+    let slippage = latency_ms / 100.0 * *PAPER_SLIPPAGE_BPS_PER_100MS as f64 / 10_000.0;
+
+    slippage.min(MAX_PAPER_SLIPPAGE)
+}
+
+/// Fills an order of `wallet` at its quote, less `paper_slippage`, against the virtual
+/// balances. A buy keeps the SOL it's quoted to spend but gets fewer tokens, a sell of
+/// the whole position gets less SOL. `None` for a buy the wallet can't afford, it's
+/// treated as one that didn't land. Network fees and tips aren't charged.
+///
+/// The position was already updated from the quote when the order went out, a buy's
+/// `position` is the one it's corrected to once recorded as landed.
+pub fn paper_fill(
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    bought_tokens: Option<u64>,
+    quoted_sol: u64,
+    elapsed: Duration,
+) -> Option<PaperFill> {
+    let slippage = paper_slippage(elapsed);
+    let balance = WALLET_POOL.balance(wallet).unwrap_or_default();
+
+    match bought_tokens {
+        Some(quoted_tokens) => {
+            let balance = balance.checked_sub(quoted_sol)?;
+            let tokens = (quoted_tokens as f64 * (1.0 - slippage)) as u64;
+            let position = WALLET_POOL
+                .position(wallet, mint)
+                .unwrap_or_default()
+                .saturating_sub(quoted_tokens)
+                + tokens;
+
+            WALLET_POOL.set_balance(wallet, balance);
+
+            Some(PaperFill {
+                sol_flow: -(quoted_sol as i64),
+                position,
+            })
+        }
+        None => {
+            let received = (quoted_sol as f64 * (1.0 - slippage)) as u64;
+
+            WALLET_POOL.set_balance(wallet, balance + received);
+
+            Some(PaperFill {
+                sol_flow: received as i64,
+                position: 0,
+            })
+        }
+    }
+}
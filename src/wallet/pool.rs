@@ -0,0 +1,407 @@
+use once_cell::sync::Lazy;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signer::Signer};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        Arc, Mutex, RwLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+};
+use tokio::time::{Duration, sleep};
+use tracing::warn;
+
+use crate::{
+    config::{
+        FEE_PAYER_PUBKEY, PRIORITY_FEE, TRADING_WALLETS, WALLET_BALANCE_REFRESH_SEC,
+        WALLET_MAX_PENDING,
+    },
+    utils::{SIGNATURE_FEE, get_balances, notify_error},
+    wallet::{WalletSigner, paper_trading, seed_paper_balances},
+};
+
+/// Rent of the token account a buy opens
+const TOKEN_ACCOUNT_RENT: u64 = 2_039_280;
+
+/// Pool of trading wallets that buys are spread across, with the token positions each
+/// wallet holds, its last known SOL balance and its transactions in flight. Wallets can
+/// be added and retired while the bot runs, see `rotate_wallet`.
+pub struct WalletPool {
+    wallets: RwLock<Vec<&'static WalletSigner>>,
+    /// Wallets being rotated out, which get no new buys but still sell what they hold
+    retiring: RwLock<HashSet<Pubkey>>,
+    next: AtomicUsize,
+    balances: RwLock<HashMap<Pubkey, u64>>,
+    positions: RwLock<HashMap<(Pubkey, Pubkey), u64>>,
+    in_flight: Mutex<HashMap<Pubkey, InFlight>>,
+    /// Pays the copies' tips and network fees, each wallet its own when `None`
+    fee_payer: Option<Pubkey>,
+    /// Set once a buy no wallet could afford was alerted on, until one can again
+    underfunded: AtomicBool,
+}
+
+/// Transactions of a wallet sent but not yet landed or failed, and the lamports its
+/// buys among them spend.
+#[derive(Debug, Default, Clone, Copy)]
+struct InFlight {
+    transactions: usize,
+    lamports: u64,
+}
+
+/// A transaction of a pool wallet in flight, from its submission until it lands or
+/// fails, which dropping it marks.
+pub struct PendingTransaction {
+    pool: &'static WalletPool,
+    wallet: Pubkey,
+    lamports: u64,
+}
+
+impl Drop for PendingTransaction {
+    fn drop(&mut self) {
+        let mut in_flight = self.pool.in_flight.lock().unwrap();
+
+        if let Some(pending) = in_flight.get_mut(&self.wallet) {
+            pending.transactions = pending.transactions.saturating_sub(1);
+            pending.lamports = pending.lamports.saturating_sub(self.lamports);
+
+            if pending.transactions == 0 {
+                in_flight.remove(&self.wallet);
+            }
+        }
+    }
+}
+
+pub static WALLET_POOL: Lazy<WalletPool> =
+    Lazy::new(|| WalletPool::new(TRADING_WALLETS.iter()).with_fee_payer(*FEE_PAYER_PUBKEY));
+
+impl WalletPool {
+    pub fn new(wallets: impl IntoIterator<Item = &'static WalletSigner>) -> Self {
+        let wallets: Vec<&'static WalletSigner> = wallets.into_iter().collect();
+
+        assert!(
+            !wallets.is_empty(),
+            "At least one trading wallet is required"
+        );
+
+        Self {
+            wallets: RwLock::new(wallets),
+            retiring: RwLock::new(HashSet::new()),
+            next: AtomicUsize::new(0),
+            balances: RwLock::new(HashMap::new()),
+            positions: RwLock::new(HashMap::new()),
+            in_flight: Mutex::new(HashMap::new()),
+            fee_payer: None,
+            underfunded: AtomicBool::new(false),
+        }
+    }
+
+    /// Has `fee_payer` pay the tips and network fees, its balance tracked with the
+    /// wallets'.
+    pub fn with_fee_payer(mut self, fee_payer: Pubkey) -> Self {
+        self.fee_payer = Some(fee_payer);
+        self
+    }
+
+    fn pays_own_fees(&self, wallet: &Pubkey) -> bool {
+        self.fee_payer.is_none_or(|fee_payer| fee_payer == *wallet)
+    }
+
+    /// Lamports a buy of `wallet` needs besides the SOL it swaps: the rent of the
+    /// token account it opens, and the tip and network fees when it pays its own.
+    pub fn buy_overhead(&self, wallet: &Pubkey) -> u64 {
+        if self.pays_own_fees(wallet) {
+            TOKEN_ACCOUNT_RENT + copy_fees(1)
+        } else {
+            TOKEN_ACCOUNT_RENT
+        }
+    }
+
+    pub fn wallets(&self) -> Vec<&'static WalletSigner> {
+        self.wallets.read().unwrap().clone()
+    }
+
+    /// The pool's wallet of `pubkey`, retiring ones included.
+    pub fn wallet(&self, pubkey: &Pubkey) -> Option<&'static WalletSigner> {
+        self.wallets
+            .read()
+            .unwrap()
+            .iter()
+            .find(|wallet| wallet.pubkey() == *pubkey)
+            .copied()
+    }
+
+    /// Adds a wallet to spread buys across. `false` when it's already in the pool.
+    pub fn add_wallet(&self, wallet: &'static WalletSigner) -> bool {
+        let mut wallets = self.wallets.write().unwrap();
+
+        if wallets
+            .iter()
+            .any(|other| other.pubkey() == wallet.pubkey())
+        {
+            return false;
+        }
+
+        wallets.push(wallet);
+        true
+    }
+
+    /// Stops giving `wallet` buys, it keeps selling its positions until removed.
+    /// `false` when it isn't in the pool or is the only wallet taking buys.
+    pub fn retire_wallet(&self, wallet: &Pubkey) -> bool {
+        let wallets = self.wallets.read().unwrap();
+        let mut retiring = self.retiring.write().unwrap();
+
+        let active = wallets
+            .iter()
+            .filter(|other| !retiring.contains(&other.pubkey()))
+            .count();
+
+        let in_pool = wallets.iter().any(|other| other.pubkey() == *wallet);
+
+        if !in_pool || retiring.contains(wallet) || active < 2 {
+            return false;
+        }
+
+        retiring.insert(*wallet)
+    }
+
+    pub fn is_retiring(&self, wallet: &Pubkey) -> bool {
+        self.retiring.read().unwrap().contains(wallet)
+    }
+
+    /// Takes a retired wallet out of the pool, with its balance.
+    pub fn remove_wallet(&self, wallet: &Pubkey) {
+        self.wallets
+            .write()
+            .unwrap()
+            .retain(|other| other.pubkey() != *wallet);
+        self.retiring.write().unwrap().remove(wallet);
+        self.balances.write().unwrap().remove(wallet);
+    }
+
+    /// Picks the wallet for a buy of `required_lamports`: of those that can afford it
+    /// with its `buy_overhead`, besides their buys in flight, and are under
+    /// `WALLET_MAX_PENDING`, the one with the fewest transactions in flight, in
+    /// round-robin order among ties. A separate fee payer must afford the tip and fees.
+    /// Wallets whose balance hasn't been fetched yet are assumed to be funded. When none
+    /// can afford the buy it's alerted on, once until one can again.
+    pub fn next_buy_wallet(&self, required_lamports: u64) -> Option<&'static WalletSigner> {
+        let wallet = self.affordable_buy_wallet(required_lamports);
+
+        if wallet.is_some() {
+            self.underfunded.store(false, Ordering::Relaxed);
+        } else if !self.underfunded.swap(true, Ordering::Relaxed) {
+            let message = format!(
+                "No trading wallet can afford a buy of {} SOL with its fees and rent",
+                required_lamports as f64 / LAMPORTS_PER_SOL as f64
+            );
+
+            warn!("ALERT: {}", message);
+            notify_error(message);
+        }
+
+        wallet
+    }
+
+    fn affordable_buy_wallet(&self, required_lamports: u64) -> Option<&'static WalletSigner> {
+        let wallets = self.wallets.read().unwrap();
+        let retiring = self.retiring.read().unwrap();
+        let balances = self.balances.read().unwrap();
+        let in_flight = self.in_flight.lock().unwrap();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+
+        let pending =
+            |wallet: &WalletSigner| in_flight.get(&wallet.pubkey()).copied().unwrap_or_default();
+
+        (0..wallets.len())
+            .map(|offset| wallets[(start + offset) % wallets.len()])
+            .filter(|wallet| !retiring.contains(&wallet.pubkey()))
+            .filter(|wallet| {
+                let pending = pending(wallet);
+
+                let needed = required_lamports + self.buy_overhead(&wallet.pubkey());
+                let fee_payer_affords = self
+                    .fee_payer
+                    .filter(|fee_payer| *fee_payer != wallet.pubkey())
+                    .and_then(|fee_payer| balances.get(&fee_payer))
+                    .is_none_or(|balance| *balance >= copy_fees(2));
+
+                (*WALLET_MAX_PENDING == 0 || pending.transactions < *WALLET_MAX_PENDING)
+                    && fee_payer_affords
+                    && balances
+                        .get(&wallet.pubkey())
+                        .is_none_or(|balance| balance.saturating_sub(pending.lamports) >= needed)
+            })
+            .min_by_key(|wallet| pending(wallet).transactions)
+    }
+
+    /// Counts a transaction of `wallet` in flight until the returned guard is dropped,
+    /// with the `lamports` it spends when it's a buy.
+    pub fn track_pending(&'static self, wallet: Pubkey, lamports: u64) -> PendingTransaction {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        let pending = in_flight.entry(wallet).or_default();
+
+        pending.transactions += 1;
+        pending.lamports += lamports;
+
+        PendingTransaction {
+            pool: self,
+            wallet,
+            lamports,
+        }
+    }
+
+    /// Transactions of `wallet` in flight.
+    pub fn pending_transactions(&self, wallet: &Pubkey) -> usize {
+        self.in_flight
+            .lock()
+            .unwrap()
+            .get(wallet)
+            .map_or(0, |pending| pending.transactions)
+    }
+
+    /// Wallets currently holding a position in `mint`, with their token amounts.
+    pub fn holders(&self, mint: &Pubkey) -> Vec<(&'static WalletSigner, u64)> {
+        let positions = self.positions.read().unwrap();
+
+        self.wallets
+            .read()
+            .unwrap()
+            .iter()
+            .filter_map(|wallet| {
+                positions
+                    .get(&(wallet.pubkey(), *mint))
+                    .map(|amount| (*wallet, *amount))
+            })
+            .collect()
+    }
+
+    /// Mints `wallet` holds a tracked position in, with their token amounts.
+    pub fn wallet_positions(&self, wallet: &Pubkey) -> Vec<(Pubkey, u64)> {
+        self.positions
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|((owner, _), _)| owner == wallet)
+            .map(|((_, mint), amount)| (*mint, *amount))
+            .collect()
+    }
+
+    /// Every tracked position as (wallet, mint, token amount).
+    pub fn positions(&self) -> Vec<(Pubkey, Pubkey, u64)> {
+        self.positions
+            .read()
+            .unwrap()
+            .iter()
+            .map(|((wallet, mint), amount)| (*wallet, *mint, *amount))
+            .collect()
+    }
+
+    pub fn record_buy(&self, wallet: &Pubkey, mint: &Pubkey, token_amount: u64) {
+        *self
+            .positions
+            .write()
+            .unwrap()
+            .entry((*wallet, *mint))
+            .or_default() += token_amount;
+    }
+
+    pub fn record_sell(&self, wallet: &Pubkey, mint: &Pubkey) {
+        self.positions.write().unwrap().remove(&(*wallet, *mint));
+    }
+
+    /// Tokens of `mint` held by `wallet`, `None` when no position is tracked.
+    pub fn position(&self, wallet: &Pubkey, mint: &Pubkey) -> Option<u64> {
+        self.positions
+            .read()
+            .unwrap()
+            .get(&(*wallet, *mint))
+            .copied()
+    }
+
+    /// Sets the position to the balance seen after a confirmed fill.
+    pub fn set_position(&self, wallet: &Pubkey, mint: &Pubkey, token_amount: u64) {
+        let mut positions = self.positions.write().unwrap();
+
+        if token_amount == 0 {
+            positions.remove(&(*wallet, *mint));
+        } else {
+            positions.insert((*wallet, *mint), token_amount);
+        }
+    }
+
+    /// Takes back the tokens of a buy that never landed.
+    pub fn revert_buy(&self, wallet: &Pubkey, mint: &Pubkey, token_amount: u64) {
+        let mut positions = self.positions.write().unwrap();
+
+        if let Some(amount) = positions.get_mut(&(*wallet, *mint)) {
+            *amount = amount.saturating_sub(token_amount);
+
+            if *amount == 0 {
+                positions.remove(&(*wallet, *mint));
+            }
+        }
+    }
+
+    pub fn balance(&self, wallet: &Pubkey) -> Option<u64> {
+        self.balances.read().unwrap().get(wallet).copied()
+    }
+
+    /// Sets a wallet's balance, for the virtual balances of paper trading.
+    pub fn set_balance(&self, wallet: &Pubkey, lamports: u64) {
+        self.balances.write().unwrap().insert(*wallet, lamports);
+    }
+
+    /// Refreshes the SOL balance of every wallet in the pool and the fee payer, in one
+    /// batched request.
+    pub async fn refresh_balances(&self, rpc_client: &RpcClient) {
+        let mut addresses: Vec<Pubkey> = self
+            .wallets()
+            .iter()
+            .map(|wallet| wallet.pubkey())
+            .collect();
+
+        if let Some(fee_payer) = self.fee_payer
+            && !addresses.contains(&fee_payer)
+        {
+            addresses.push(fee_payer);
+        }
+
+        match get_balances(rpc_client, &addresses).await {
+            Ok(balances) => {
+                self.balances
+                    .write()
+                    .unwrap()
+                    .extend(addresses.into_iter().zip(balances));
+            }
+            Err(e) => {
+                warn!("Failed to fetch wallet balances: {}", e);
+            }
+        }
+    }
+}
+
+/// Lamports the fee payer spends on a copy with `signatures` signers: the tip, the
+/// priority fee and the signature fees.
+fn copy_fees(signatures: u64) -> u64 {
+    let (cu, priority_fee_micro_lamport, third_party_fee) = *PRIORITY_FEE;
+
+    (third_party_fee * LAMPORTS_PER_SOL as f64) as u64
+        + cu * priority_fee_micro_lamport / 1_000_000
+        + signatures * SIGNATURE_FEE
+}
+
+/// Keeps the wallet pool balances fresh in the background. Paper trading starts them at
+/// their virtual balance instead and leaves them to the paper fills.
+pub async fn wallet_balance_handler(rpc_client: Arc<RpcClient>) {
+    if paper_trading() {
+        seed_paper_balances();
+        return;
+    }
+
+    loop {
+        WALLET_POOL.refresh_balances(&rpc_client).await;
+
+        sleep(Duration::from_secs(*WALLET_BALANCE_REFRESH_SEC)).await;
+    }
+}
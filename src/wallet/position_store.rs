@@ -0,0 +1,54 @@
+use serde_json::{Value, json};
+use solana_sdk::pubkey::Pubkey;
+use std::{fs, io, str::FromStr};
+
+use crate::wallet::WALLET_POOL;
+
+/// Writes every tracked position to `path` as a JSON array, through a temporary file so
+/// a crash mid-write leaves the previous store intact. Returns the number written.
+pub fn save_positions(path: &str) -> io::Result<usize> {
+    let positions = WALLET_POOL.positions();
+
+    let entries: Vec<Value> = positions
+        .iter()
+        .map(|(wallet, mint, tokens)| {
+            json!({
+                "wallet": wallet.to_string(),
+                "mint": mint.to_string(),
+                "tokens": tokens,
+            })
+        })
+        .collect();
+
+    let temp_path = format!("{}.tmp", path);
+    fs::write(&temp_path, Value::from(entries).to_string())?;
+    fs::rename(&temp_path, path)?;
+
+    Ok(positions.len())
+}
+
+/// Restores the positions saved by `save_positions` into the wallet pool. A missing
+/// store restores nothing. Returns the number restored.
+pub fn load_positions(path: &str) -> io::Result<usize> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+
+    let entries: Vec<Value> = serde_json::from_str(&content)?;
+    let mut restored = 0;
+
+    for entry in entries {
+        let pubkey = |field: &str| Pubkey::from_str(entry[field].as_str()?).ok();
+
+        if let (Some(wallet), Some(mint), Some(tokens)) =
+            (pubkey("wallet"), pubkey("mint"), entry["tokens"].as_u64())
+        {
+            WALLET_POOL.set_position(&wallet, &mint, tokens);
+            restored += 1;
+        }
+    }
+
+    Ok(restored)
+}
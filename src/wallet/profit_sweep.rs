@@ -0,0 +1,90 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signer::Signer};
+use std::sync::Arc;
+use tokio::time::{Duration, sleep};
+use tracing::{info, warn};
+
+use crate::{
+    config::{
+        PROFIT_SWEEP_ADDRESS, PROFIT_SWEEP_CHECK_SEC, PROFIT_SWEEP_FLOOR_SOL, PROFIT_SWEEP_MIN_SOL,
+        SHADOW_MODE,
+    },
+    utils::get_balances,
+    wallet::{TRANSFER_FEE, WALLET_POOL, paper_trading, transfer_sol},
+};
+
+/// Lamports of `balance` above `floor` to sweep, less the transfer's fee, once that's at
+/// least `min`.
+pub fn sweep_amount(balance: u64, floor: u64, min: u64) -> Option<u64> {
+    let excess = balance.checked_sub(floor)?.checked_sub(TRANSFER_FEE)?;
+
+    (excess >= min.max(1)).then_some(excess)
+}
+
+/// Sends the realized profits of the trading wallets to the cold `PROFIT_SWEEP_ADDRESS`,
+/// checked every `PROFIT_SWEEP_CHECK_SEC`. A wallet keeps `PROFIT_SWEEP_FLOOR_SOL` as
+/// working capital and is swept once it holds `PROFIT_SWEEP_MIN_SOL` more. Wallets with
+/// a transaction in flight are left for the next check.
+pub async fn profit_sweep_handler(rpc_client: Arc<RpcClient>) {
+    let Some(destination) = *PROFIT_SWEEP_ADDRESS else {
+        return;
+    };
+
+    // Paper and shadow runs have no profits to move
+    if paper_trading() || *SHADOW_MODE {
+        return;
+    }
+
+    let floor = (*PROFIT_SWEEP_FLOOR_SOL * LAMPORTS_PER_SOL as f64) as u64;
+    let min = (*PROFIT_SWEEP_MIN_SOL * LAMPORTS_PER_SOL as f64) as u64;
+
+    info!(
+        "Sweeping profits above {} SOL per wallet to {}",
+        *PROFIT_SWEEP_FLOOR_SOL, destination
+    );
+
+    loop {
+        sleep(Duration::from_secs(*PROFIT_SWEEP_CHECK_SEC)).await;
+
+        // Read each time, a rotation changes them and empties a wallet rotated out
+        let wallets: Vec<Pubkey> = WALLET_POOL
+            .wallets()
+            .iter()
+            .map(|wallet| wallet.pubkey())
+            .filter(|wallet| *wallet != destination && !WALLET_POOL.is_retiring(wallet))
+            .collect();
+
+        let balances = match get_balances(&rpc_client, &wallets).await {
+            Ok(balances) => balances,
+            Err(e) => {
+                warn!("Failed to fetch balances to sweep: {}", e);
+                continue;
+            }
+        };
+
+        for (pubkey, balance) in wallets.iter().zip(balances) {
+            let Some(amount) = sweep_amount(balance, floor, min) else {
+                continue;
+            };
+
+            // A buy in flight may still spend what looks like profit
+            if WALLET_POOL.pending_transactions(pubkey) > 0 {
+                continue;
+            }
+
+            let Some(wallet) = WALLET_POOL.wallet(pubkey) else {
+                continue;
+            };
+
+            let sol = amount as f64 / LAMPORTS_PER_SOL as f64;
+
+            match transfer_sol(&rpc_client, wallet, &destination, amount).await {
+                Ok(signature) => info!(
+                    "Swept {} SOL of profit from {} to {}: {}",
+                    sol, pubkey, destination, signature
+                ),
+                Err(e) => warn!("Failed to sweep {} SOL from {}: {}", sol, pubkey, e),
+            }
+        }
+    }
+}
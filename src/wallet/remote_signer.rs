@@ -0,0 +1,221 @@
+use reqwest::{Client, RequestBuilder};
+use serde_json::{Value, json};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::{fmt, future::Future, str::FromStr, sync::mpsc, time::Duration};
+use tokio::time::timeout;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+
+use crate::{
+    config::{REMOTE_SIGNER_TIMEOUT_MS, REMOTE_SIGNER_TOKEN},
+    utils::SIGNER_RUNTIME,
+};
+
+#[allow(clippy::all)]
+pub mod signer_proto {
+    tonic::include_proto!("signer");
+}
+
+use signer_proto::{
+    GetPubkeyRequest, SignMessageRequest, remote_signer_client::RemoteSignerClient,
+};
+
+/// URL schemes a remote signer is reached with
+pub const REMOTE_SIGNER_SCHEMES: [&str; 4] = ["http", "https", "grpc", "grpcs"];
+
+#[derive(Clone)]
+enum Transport {
+    Http { client: Client, url: String },
+    Grpc(RemoteSignerClient<Channel>),
+}
+
+/// A key kept on a separate signing host. The bot only sends it the messages of the
+/// transactions it built, and checks the signatures it gets back.
+pub struct RemoteSigner {
+    url: String,
+    pubkey: Pubkey,
+    transport: Transport,
+}
+
+impl fmt::Debug for RemoteSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteSigner")
+            .field("url", &self.url)
+            .field("pubkey", &self.pubkey)
+            .finish()
+    }
+}
+
+impl RemoteSigner {
+    /// The signer at `url`, asked for its key. `http(s)://` speaks JSON, `POST /sign`
+    /// and `GET /pubkey`; `grpc(s)://` the `RemoteSigner` service of
+    /// `proto/signer.proto`.
+    pub fn connect(url: &str) -> Result<Self, String> {
+        let transport = match url.split_once("://") {
+            Some(("http" | "https", _)) => Transport::Http {
+                client: Client::new(),
+                url: url.trim_end_matches('/').to_string(),
+            },
+            Some(("grpc", address)) => grpc_transport(format!("http://{}", address))?,
+            Some(("grpcs", address)) => grpc_transport(format!("https://{}", address))?,
+            _ => return Err(format!("unsupported remote signer URL {}", url)),
+        };
+
+        let pubkey = remote_call(get_pubkey(transport.clone()))?;
+
+        Ok(Self {
+            url: url.to_string(),
+            pubkey,
+            transport,
+        })
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    /// Has the remote sign `message`. A signature that doesn't verify against the key
+    /// is refused rather than put in a transaction.
+    pub fn sign(&self, message: &[u8]) -> Result<Signature, String> {
+        let signature = remote_call(sign_message(
+            self.transport.clone(),
+            self.pubkey,
+            message.to_vec(),
+        ))?;
+
+        if !signature.verify(self.pubkey.as_ref(), message) {
+            return Err(format!("{} answered with an invalid signature", self.url));
+        }
+
+        Ok(signature)
+    }
+}
+
+fn grpc_transport(endpoint: String) -> Result<Transport, String> {
+    let mut endpoint = Endpoint::from_shared(endpoint).map_err(|e| e.to_string())?;
+
+    if endpoint.uri().scheme_str() == Some("https") {
+        endpoint = endpoint
+            .tls_config(ClientTlsConfig::new().with_native_roots())
+            .map_err(|e| e.to_string())?;
+    }
+
+    // The channel's connection runs on the signer runtime
+    let _runtime = SIGNER_RUNTIME.enter();
+
+    Ok(Transport::Grpc(RemoteSignerClient::new(
+        endpoint.connect_lazy(),
+    )))
+}
+
+/// Runs `request` on `SIGNER_RUNTIME` and waits for it, up to `REMOTE_SIGNER_TIMEOUT_MS`.
+/// Remote and KMS signers make their requests through it.
+pub fn remote_call<T: Send + 'static>(
+    request: impl Future<Output = Result<T, String>> + Send + 'static,
+) -> Result<T, String> {
+    let (reply, response) = mpsc::channel();
+    let limit = Duration::from_millis(*REMOTE_SIGNER_TIMEOUT_MS);
+
+    SIGNER_RUNTIME.spawn(async move {
+        let result = timeout(limit, request)
+            .await
+            .unwrap_or_else(|_| Err("timed out waiting for the remote signer".to_string()));
+
+        let _ = reply.send(result);
+    });
+
+    response
+        .recv()
+        .map_err(|_| "the signer runtime stopped".to_string())?
+}
+
+fn authorized(request: RequestBuilder) -> RequestBuilder {
+    match REMOTE_SIGNER_TOKEN.as_deref() {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
+fn grpc_request<T>(message: T) -> Result<tonic::Request<T>, String> {
+    let mut request = tonic::Request::new(message);
+
+    if let Some(token) = REMOTE_SIGNER_TOKEN.as_deref() {
+        let authorization = format!("Bearer {}", token)
+            .parse()
+            .map_err(|_| "REMOTE_SIGNER_TOKEN isn't valid metadata".to_string())?;
+
+        request
+            .metadata_mut()
+            .insert("authorization", authorization);
+    }
+
+    Ok(request)
+}
+
+async fn http_json(request: RequestBuilder) -> Result<Value, String> {
+    authorized(request)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| e.to_string())?
+        .json()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+async fn get_pubkey(transport: Transport) -> Result<Pubkey, String> {
+    let pubkey = match transport {
+        Transport::Http { client, url } => {
+            let response = http_json(client.get(format!("{}/pubkey", url))).await?;
+
+            response["pubkey"]
+                .as_str()
+                .and_then(|pubkey| Pubkey::from_str(pubkey).ok())
+        }
+        Transport::Grpc(mut client) => {
+            let reply = client
+                .get_pubkey(grpc_request(GetPubkeyRequest {})?)
+                .await
+                .map_err(|status| status.message().to_string())?
+                .into_inner();
+
+            Pubkey::try_from(reply.pubkey.as_slice()).ok()
+        }
+    };
+
+    pubkey.ok_or_else(|| "the remote signer answered with no public key".to_string())
+}
+
+async fn sign_message(
+    transport: Transport,
+    pubkey: Pubkey,
+    message: Vec<u8>,
+) -> Result<Signature, String> {
+    let signature = match transport {
+        Transport::Http { client, url } => {
+            let body = json!({
+                "pubkey": pubkey.to_string(),
+                "message": base64::encode(&message),
+            });
+            let response = http_json(client.post(format!("{}/sign", url)).json(&body)).await?;
+
+            response["signature"]
+                .as_str()
+                .and_then(|signature| Signature::from_str(signature).ok())
+        }
+        Transport::Grpc(mut client) => {
+            let request = SignMessageRequest {
+                pubkey: pubkey.to_bytes().to_vec(),
+                message,
+            };
+            let reply = client
+                .sign_message(grpc_request(request)?)
+                .await
+                .map_err(|status| status.message().to_string())?
+                .into_inner();
+
+            Signature::try_from(reply.signature.as_slice()).ok()
+        }
+    };
+
+    signature.ok_or_else(|| "the remote signer answered with no signature".to_string())
+}
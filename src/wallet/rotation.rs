@@ -0,0 +1,253 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::Signer};
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+use std::{str::FromStr, sync::Arc};
+use tokio::time::{Duration, sleep};
+use tracing::{info, warn};
+
+use crate::{
+    config::{FEE_PAYER_PUBKEY, SHADOW_MODE},
+    instructions::token_account::{close_token_account_ix, transfer_checked_ix},
+    utils::{notify_error, spawn_background},
+    wallet::{
+        TRANSFER_FEE, WALLET_POOL, WalletSigner, fetch_token_accounts, paper_trading,
+        send_instructions, transfer_sol,
+    },
+};
+
+/// How often a rotation checks whether the old wallet is drained
+const ROTATION_CHECK: Duration = Duration::from_secs(5);
+
+/// How a wallet being rotated out hands its positions over to the new one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationMode {
+    /// Its positions are sold as the targets exit, it just gets no new buys
+    Drain,
+    /// Its tokens move to the new wallet's token accounts right away
+    Transfer,
+}
+
+impl FromStr for RotationMode {
+    type Err = String;
+
+    fn from_str(mode: &str) -> Result<Self, Self::Err> {
+        match mode {
+            "drain" => Ok(Self::Drain),
+            "transfer" => Ok(Self::Transfer),
+            _ => Err(format!("{} isn't drain or transfer", mode)),
+        }
+    }
+}
+
+/// Rotates the trading wallet `old` out for the wallet of `new_entry`, a key or signer
+/// as `TRADING_WALLETS` takes one, while the pipeline keeps running. Buys go to the new
+/// wallet from now on. In the background, the old one hands its positions over as
+/// `mode` says, then its empty token accounts are closed, what SOL it has left moves to
+/// the new wallet and it leaves the pool. Returns the new wallet's public key.
+///
+/// The rotation lasts until the next restart, `TRADING_WALLETS` has to be updated by
+/// hand.
+pub async fn rotate_wallet(
+    rpc_client: Arc<RpcClient>,
+    old: Pubkey,
+    new_entry: String,
+    mode: RotationMode,
+) -> Result<Pubkey, String> {
+    if paper_trading() || *SHADOW_MODE {
+        return Err("Paper and shadow runs don't rotate wallets".to_string());
+    }
+
+    let old_wallet = WALLET_POOL
+        .wallet(&old)
+        .ok_or_else(|| format!("{} isn't a trading wallet", old))?;
+
+    if WALLET_POOL.is_retiring(&old) {
+        return Err(format!("{} is already being rotated out", old));
+    }
+
+    // It would keep paying the fees of every copy with nothing left to pay them with
+    if old == *FEE_PAYER_PUBKEY {
+        return Err(format!(
+            "{} is the fee payer, set FEE_PAYER_PRIVATE_KEY to another wallet to rotate it",
+            old
+        ));
+    }
+
+    // Connecting to a Ledger, remote signer or KMS blocks
+    let new_wallet = tokio::task::spawn_blocking(move || WalletSigner::try_from_entry(&new_entry))
+        .await
+        .map_err(|e| e.to_string())??;
+    let new = new_wallet.pubkey();
+
+    if WALLET_POOL.wallet(&new).is_some() {
+        return Err(format!("{} is already a trading wallet", new));
+    }
+
+    // Lives as long as the pool, like the wallets it started with
+    let new_wallet: &'static WalletSigner = Box::leak(Box::new(new_wallet));
+
+    WALLET_POOL.add_wallet(new_wallet);
+
+    if !WALLET_POOL.retire_wallet(&old) {
+        WALLET_POOL.remove_wallet(&new);
+        return Err(format!("{} can't be rotated out", old));
+    }
+
+    WALLET_POOL.refresh_balances(&rpc_client).await;
+
+    info!(
+        "Rotating {} out for {}, buys now go to the new wallet",
+        old, new
+    );
+
+    spawn_background(complete_rotation(rpc_client, old_wallet, new_wallet, mode));
+
+    Ok(new)
+}
+
+/// Waits for the old wallet to have no transaction in flight and no position left,
+/// moving them over first with `RotationMode::Transfer`, then empties it into the new
+/// wallet and takes it out of the pool.
+async fn complete_rotation(
+    rpc_client: Arc<RpcClient>,
+    old: &'static WalletSigner,
+    new: &'static WalletSigner,
+    mode: RotationMode,
+) {
+    let (old_pubkey, new_pubkey) = (old.pubkey(), new.pubkey());
+    let mut transferred = mode == RotationMode::Drain;
+
+    loop {
+        sleep(ROTATION_CHECK).await;
+
+        if WALLET_POOL.pending_transactions(&old_pubkey) > 0 {
+            continue;
+        }
+
+        if !transferred {
+            match hand_over_token_accounts(&rpc_client, old, &new_pubkey).await {
+                Ok(moved) => {
+                    info!(
+                        "Moved {} position(s) of {} to {}",
+                        moved, old_pubkey, new_pubkey
+                    );
+                    transferred = true;
+                }
+                Err(e) => {
+                    warn!("Failed to read the token accounts of {}: {}", old_pubkey, e);
+                    continue;
+                }
+            }
+        }
+
+        if WALLET_POOL.wallet_positions(&old_pubkey).is_empty() {
+            break;
+        }
+    }
+
+    // Leftover tokens no position tracked, and the rent of the drained accounts
+    if let Err(e) = hand_over_token_accounts(&rpc_client, old, &new_pubkey).await {
+        warn!("Failed to read the token accounts of {}: {}", old_pubkey, e);
+    }
+
+    match rpc_client.get_balance(&old_pubkey).await {
+        Ok(balance) if balance > TRANSFER_FEE => {
+            let lamports = balance - TRANSFER_FEE;
+
+            if let Err(e) = transfer_sol(&rpc_client, old, &new_pubkey, lamports).await {
+                let message = format!(
+                    "Failed to move the SOL of {} to {} while rotating it out: {}",
+                    old_pubkey, new_pubkey, e
+                );
+                warn!("ALERT: {}", message);
+                notify_error(message);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => warn!("Failed to fetch the balance of {}: {}", old_pubkey, e),
+    }
+
+    WALLET_POOL.remove_wallet(&old_pubkey);
+
+    info!(
+        "Rotated {} out for {}, replace it in TRADING_WALLETS before the next restart",
+        old_pubkey, new_pubkey
+    );
+}
+
+/// Moves every token `old` holds to the associated token accounts of `new`, opened as
+/// needed, and closes the accounts of `old` for their rent, one transaction per
+/// account. The positions follow the tokens. Returns how many accounts had tokens to
+/// move; one that fails is left to drain.
+async fn hand_over_token_accounts(
+    rpc_client: &RpcClient,
+    old: &WalletSigner,
+    new: &Pubkey,
+) -> anyhow::Result<usize> {
+    let old_pubkey = old.pubkey();
+    let mut moved = 0;
+
+    for account in fetch_token_accounts(rpc_client, &[old_pubkey]).await? {
+        let mut ixs: Vec<Instruction> = Vec::new();
+
+        if account.amount > 0 {
+            let destination = get_associated_token_address_with_program_id(
+                new,
+                &account.mint,
+                &account.token_program,
+            );
+
+            ixs.push(create_associated_token_account_idempotent(
+                &old_pubkey,
+                new,
+                &account.mint,
+                &account.token_program,
+            ));
+            ixs.push(transfer_checked_ix(
+                &account.token_program,
+                &account.address,
+                &account.mint,
+                &destination,
+                &old_pubkey,
+                account.amount,
+                account.decimals,
+            ));
+        }
+
+        ixs.push(close_token_account_ix(
+            &account.token_program,
+            &account.address,
+            &old_pubkey,
+        ));
+
+        match send_instructions(rpc_client, old, &ixs).await {
+            Ok(signature) if account.amount > 0 => {
+                let held = WALLET_POOL.position(new, &account.mint).unwrap_or_default();
+
+                WALLET_POOL.set_position(&old_pubkey, &account.mint, 0);
+                WALLET_POOL.set_position(new, &account.mint, held + account.amount);
+                moved += 1;
+
+                info!(
+                    "Moved {} of {} from {} to {}: {}",
+                    account.ui_amount(),
+                    account.mint,
+                    old_pubkey,
+                    new,
+                    signature
+                );
+            }
+            Ok(_) => {}
+            // E.g. a Token-2022 account holding withheld fees can't be closed
+            Err(e) => warn!(
+                "Failed to hand over {} of {}: {}",
+                account.address, old_pubkey, e
+            ),
+        }
+    }
+
+    Ok(moved)
+}
@@ -0,0 +1,106 @@
+use solana_sdk::{
+    bs58,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::{Signer, SignerError, keypair::Keypair},
+};
+
+use crate::{
+    config::LEDGER_MAX_TRADE_SOL,
+    wallet::{DEFAULT_LEDGER_PATH, KmsKey, KmsSigner, LedgerSigner, RemoteSigner},
+};
+
+/// A wallet's key, held in memory, or on a device, remote host or cloud KMS that signs
+/// for it.
+#[derive(Debug)]
+pub enum WalletSigner {
+    Keypair(Keypair),
+    Ledger(LedgerSigner),
+    Remote(RemoteSigner),
+    Kms(KmsSigner),
+}
+
+impl WalletSigner {
+    /// A wallet entry: `ledger`, `ledger:<derivation path>`, `remote:<url>`,
+    /// `aws-kms:<key ARN>`, `gcp-kms:<key version>` or a base58 key. Panics when the key
+    /// is invalid or its signer can't be reached.
+    pub fn from_entry(entry: &str) -> Self {
+        Self::try_from_entry(entry).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// `from_entry` failing with why instead of panicking, for a wallet added while the
+    /// bot runs.
+    pub fn try_from_entry(entry: &str) -> Result<Self, String> {
+        if entry.starts_with("aws-kms:") || entry.starts_with("gcp-kms:") {
+            let kms = KmsKey::from_entry(entry)
+                .and_then(KmsSigner::connect)
+                .map_err(|e| format!("Failed to use the KMS key {}: {}", entry, e))?;
+
+            return Ok(Self::Kms(kms));
+        }
+
+        if let Some(url) = entry.strip_prefix("remote:") {
+            let remote = RemoteSigner::connect(url)
+                .map_err(|e| format!("Failed to use the remote signer {}: {}", url, e))?;
+
+            return Ok(Self::Remote(remote));
+        }
+
+        match entry.strip_prefix("ledger") {
+            Some(path) => {
+                let path = path.strip_prefix(':').unwrap_or(DEFAULT_LEDGER_PATH);
+
+                let ledger = LedgerSigner::connect(path)
+                    .map_err(|e| format!("Failed to use the Ledger at {}: {}", path, e))?;
+
+                Ok(Self::Ledger(ledger))
+            }
+            None => bs58::decode(entry)
+                .into_vec()
+                .ok()
+                .and_then(|bytes| Keypair::from_bytes(&bytes).ok())
+                .map(Self::Keypair)
+                .ok_or_else(|| "Not a valid base58 private key".to_string()),
+        }
+    }
+
+    /// Largest buy, in lamports, the wallet may sign: `LEDGER_MAX_TRADE_SOL` for a
+    /// Ledger, unlimited for a key in memory.
+    pub fn trade_cap(&self) -> Option<u64> {
+        match self {
+            Self::Keypair(_) | Self::Remote(_) | Self::Kms(_) => None,
+            Self::Ledger(_) => {
+                LEDGER_MAX_TRADE_SOL.map(|sol| (sol * LAMPORTS_PER_SOL as f64) as u64)
+            }
+        }
+    }
+
+    pub fn exceeds_trade_cap(&self, lamports: u64) -> bool {
+        self.trade_cap().is_some_and(|cap| lamports > cap)
+    }
+}
+
+impl Signer for WalletSigner {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        match self {
+            Self::Keypair(keypair) => keypair.try_pubkey(),
+            Self::Ledger(ledger) => Ok(ledger.pubkey()),
+            Self::Remote(remote) => Ok(remote.pubkey()),
+            Self::Kms(kms) => Ok(kms.pubkey()),
+        }
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        match self {
+            Self::Keypair(keypair) => keypair.try_sign_message(message),
+            Self::Ledger(ledger) => ledger.sign(message).map_err(SignerError::Custom),
+            Self::Remote(remote) => remote.sign(message).map_err(SignerError::Custom),
+            Self::Kms(kms) => kms.sign(message).map_err(SignerError::Custom),
+        }
+    }
+
+    fn is_interactive(&self) -> bool {
+        matches!(self, Self::Ledger(_))
+    }
+}
@@ -0,0 +1,71 @@
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::TokenAccountsFilter};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+use crate::instructions::token_account::TOKEN_2022_PROGRAM_ID;
+
+/// A token account of a trading wallet as found on chain.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenAccount {
+    pub owner: Pubkey,
+    pub address: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub decimals: u8,
+    pub token_program: Pubkey,
+    /// Rent held by the account, returned when it is closed
+    pub lamports: u64,
+}
+
+impl TokenAccount {
+    pub fn ui_amount(&self) -> f64 {
+        self.amount as f64 / 10f64.powi(self.decimals as i32)
+    }
+}
+
+/// Every Token and Token-2022 account owned by `owners`, empty ones included.
+pub async fn fetch_token_accounts(
+    rpc_client: &RpcClient,
+    owners: &[Pubkey],
+) -> anyhow::Result<Vec<TokenAccount>> {
+    let mut token_accounts = Vec::new();
+
+    for owner in owners {
+        for token_program in [spl_token::id(), TOKEN_2022_PROGRAM_ID] {
+            let keyed_accounts = rpc_client
+                .get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(token_program))
+                .await?;
+
+            for keyed_account in keyed_accounts {
+                // The RPC returns them jsonParsed
+                let account = serde_json::to_value(&keyed_account.account)?;
+                let info = &account["data"]["parsed"]["info"];
+
+                let (Ok(address), Some(mint), Some(amount), Some(decimals)) = (
+                    Pubkey::from_str(&keyed_account.pubkey),
+                    info["mint"]
+                        .as_str()
+                        .and_then(|mint| Pubkey::from_str(mint).ok()),
+                    info["tokenAmount"]["amount"]
+                        .as_str()
+                        .and_then(|amount| amount.parse::<u64>().ok()),
+                    info["tokenAmount"]["decimals"].as_u64(),
+                ) else {
+                    continue;
+                };
+
+                token_accounts.push(TokenAccount {
+                    owner: *owner,
+                    address,
+                    mint,
+                    amount,
+                    decimals: decimals as u8,
+                    token_program,
+                    lamports: keyed_account.account.lamports,
+                });
+            }
+        }
+    }
+
+    Ok(token_accounts)
+}
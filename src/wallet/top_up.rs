@@ -0,0 +1,123 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signer::Signer};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Arc,
+    time::Instant,
+};
+use tokio::time::{Duration, sleep};
+use tracing::{info, warn};
+
+use crate::{
+    config::{
+        FEE_PAYER_PUBKEY, RESERVE_ADDRESS, RESERVE_PRIVATE_KEY, SHADOW_MODE, TOP_UP_AMOUNT_SOL,
+        TOP_UP_CHECK_SEC, TOP_UP_MAX_PER_DAY, TOP_UP_THRESHOLD_SOL,
+    },
+    utils::{get_balances, notify_error},
+    wallet::{WALLET_POOL, paper_trading, transfer_sol},
+};
+
+const DAY: Duration = Duration::from_secs(24 * 3600);
+
+/// Keeps the fee payer and trading wallets above `TOP_UP_THRESHOLD_SOL`, checked every
+/// `TOP_UP_CHECK_SEC`. A wallet below it gets `TOP_UP_AMOUNT_SOL` from the
+/// `RESERVE_PRIVATE_KEY` wallet, at most `TOP_UP_MAX_PER_DAY` times a day. With only a
+/// cold `RESERVE_ADDRESS`, or none, it's alerted on to be funded by hand, once until
+/// its balance recovers.
+pub async fn top_up_handler(rpc_client: Arc<RpcClient>) {
+    let Some(threshold) = *TOP_UP_THRESHOLD_SOL else {
+        return;
+    };
+
+    // Paper and shadow runs spend nothing to top up
+    if paper_trading() || *SHADOW_MODE {
+        return;
+    }
+
+    let threshold = (threshold * LAMPORTS_PER_SOL as f64) as u64;
+    let amount = (*TOP_UP_AMOUNT_SOL * LAMPORTS_PER_SOL as f64) as u64;
+
+    let mut alerted: HashSet<Pubkey> = HashSet::new();
+    let mut top_ups: HashMap<Pubkey, VecDeque<Instant>> = HashMap::new();
+
+    loop {
+        sleep(Duration::from_secs(*TOP_UP_CHECK_SEC)).await;
+
+        // Read each time, a rotation changes them; wallets rotated out are left to drain
+        let mut seen = HashSet::new();
+        let wallets: Vec<Pubkey> = std::iter::once(*FEE_PAYER_PUBKEY)
+            .chain(WALLET_POOL.wallets().iter().map(|wallet| wallet.pubkey()))
+            .filter(|wallet| !WALLET_POOL.is_retiring(wallet))
+            .filter(|wallet| seen.insert(*wallet))
+            .collect();
+
+        let balances = match get_balances(&rpc_client, &wallets).await {
+            Ok(balances) => balances,
+            Err(e) => {
+                warn!("Failed to fetch balances to top up: {}", e);
+                continue;
+            }
+        };
+
+        for (wallet, balance) in wallets.iter().zip(balances) {
+            if balance >= threshold {
+                alerted.remove(wallet);
+                continue;
+            }
+
+            let sol = balance as f64 / LAMPORTS_PER_SOL as f64;
+
+            let Some(reserve) = RESERVE_PRIVATE_KEY.as_ref() else {
+                if alerted.insert(*wallet) {
+                    let source = RESERVE_ADDRESS.map_or(String::new(), |reserve| {
+                        format!(", fund it from the cold reserve {}", reserve)
+                    });
+
+                    alert(format!("{} is down to {} SOL{}", wallet, sol, source));
+                }
+
+                continue;
+            };
+
+            let recent = top_ups.entry(*wallet).or_default();
+            recent.retain(|at| at.elapsed() < DAY);
+
+            if recent.len() >= *TOP_UP_MAX_PER_DAY {
+                if alerted.insert(*wallet) {
+                    alert(format!(
+                        "{} is down to {} SOL after {} top-up(s) today, the most allowed",
+                        wallet,
+                        sol,
+                        recent.len()
+                    ));
+                }
+
+                continue;
+            }
+
+            match transfer_sol(&rpc_client, reserve, wallet, amount).await {
+                Ok(signature) => {
+                    info!(
+                        "Topped up {} with {} SOL from the reserve, it was down to {} SOL: {}",
+                        wallet, *TOP_UP_AMOUNT_SOL, sol, signature
+                    );
+
+                    recent.push_back(Instant::now());
+                }
+                Err(e) => {
+                    if alerted.insert(*wallet) {
+                        alert(format!(
+                            "{} is down to {} SOL and couldn't be topped up: {}",
+                            wallet, sol, e
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn alert(message: String) {
+    warn!("ALERT: {}", message);
+    notify_error(message);
+}
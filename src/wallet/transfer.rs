@@ -0,0 +1,76 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::Signer,
+    system_instruction,
+    transaction::{Transaction, VersionedTransaction},
+};
+
+use crate::{
+    utils::{audit_signed_transaction, encode_transaction, guard_instructions},
+    wallet::WalletSigner,
+};
+
+/// Lamports a transfer's fee needs on top of what it moves
+pub const TRANSFER_FEE: u64 = 5_000;
+
+/// Transfers `lamports` of SOL from `from` to `to` and waits for it to confirm. The
+/// signed transaction goes to the audit log.
+pub async fn transfer_sol(
+    rpc_client: &RpcClient,
+    from: &WalletSigner,
+    to: &Pubkey,
+    lamports: u64,
+) -> Result<Signature, String> {
+    let balance = rpc_client
+        .get_balance(&from.pubkey())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if balance < lamports + TRANSFER_FEE {
+        return Err(format!(
+            "{} only has {} SOL",
+            from.pubkey(),
+            balance as f64 / LAMPORTS_PER_SOL as f64
+        ));
+    }
+
+    let ix = system_instruction::transfer(&from.pubkey(), to, lamports);
+
+    send_instructions(rpc_client, from, &[ix]).await
+}
+
+/// Sends `ixs` in a transaction `signer` pays for and signs, and waits for it to
+/// confirm. Refused unless they pass `check_instructions`; the signed transaction goes
+/// to the audit log.
+pub async fn send_instructions(
+    rpc_client: &RpcClient,
+    signer: &WalletSigner,
+    ixs: &[Instruction],
+) -> Result<Signature, String> {
+    guard_instructions(ixs, &[signer.pubkey()])?;
+
+    let blockhash = rpc_client
+        .get_latest_blockhash()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut transaction = Transaction::new_with_payer(ixs, Some(&signer.pubkey()));
+    transaction
+        .try_sign(&[signer], blockhash)
+        .map_err(|e| e.to_string())?;
+
+    let transaction = VersionedTransaction::from(transaction);
+
+    if let Ok(encoded_tx) = encode_transaction(&transaction) {
+        audit_signed_transaction(transaction.signatures[0], &encoded_tx);
+    }
+
+    rpc_client
+        .send_and_confirm_transaction(&transaction)
+        .await
+        .map_err(|e| e.to_string())
+}
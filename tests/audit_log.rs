@@ -0,0 +1,56 @@
+use pumpfun_monitor::utils::{AUDIT_GENESIS_HASH, audit_hash, verify_audit_log};
+use serde_json::{Value, json};
+
+fn chain(transactions: &[&str]) -> Vec<Value> {
+    let mut prev_hash = AUDIT_GENESIS_HASH.to_string();
+
+    transactions
+        .iter()
+        .enumerate()
+        .map(|(seq, transaction)| {
+            let mut entry = json!({
+                "seq": seq,
+                "timestamp_ms": 1_700_000_000_000i64 + seq as i64,
+                "signature": format!("sig{}", seq),
+                "transaction": transaction,
+                "prev_hash": prev_hash,
+            });
+            entry["hash"] = audit_hash(&entry).into();
+            prev_hash = entry["hash"].as_str().unwrap().to_string();
+            entry
+        })
+        .collect()
+}
+
+fn lines(entries: &[Value]) -> String {
+    entries.iter().map(|entry| format!("{}\n", entry)).collect()
+}
+
+#[test]
+fn audit_log_chain_detects_edits_and_removals() {
+    let entries = chain(&["AQID", "BAUG", "BwgJ"]);
+    assert_eq!(verify_audit_log(&lines(&entries)), Ok(3));
+
+    let mut edited = entries.clone();
+    edited[1]["transaction"] = "BAUH".into();
+    assert!(
+        verify_audit_log(&lines(&edited))
+            .unwrap_err()
+            .starts_with("line 2")
+    );
+
+    // Re-hashing the edited entry still breaks the one after it
+    edited[1]["hash"] = audit_hash(&edited[1]).into();
+    assert!(
+        verify_audit_log(&lines(&edited))
+            .unwrap_err()
+            .starts_with("line 3")
+    );
+
+    let removed = [entries[0].clone(), entries[2].clone()];
+    assert!(
+        verify_audit_log(&lines(&removed))
+            .unwrap_err()
+            .starts_with("line 2")
+    );
+}
@@ -0,0 +1,13 @@
+use chrono::NaiveDate;
+use pumpfun_monitor::cli::backtest_window;
+
+#[test]
+fn backtest_window_covers_whole_utc_days() {
+    let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+
+    let window = backtest_window(day(1), day(2)).unwrap();
+
+    assert_eq!(window.start, 1_735_689_600);
+    assert_eq!(window.end - window.start, 2 * 86_400);
+    assert!(backtest_window(day(2), day(1)).is_err());
+}
@@ -0,0 +1,20 @@
+use pumpfun_monitor::utils::RateLimiter;
+use std::time::Duration;
+
+#[test]
+fn limits_each_caller_separately() {
+    let limiter = RateLimiter::new(2, Duration::from_secs(60));
+
+    assert!(limiter.allow("trade token"));
+    assert!(limiter.allow("trade token"));
+    assert!(!limiter.allow("trade token"));
+    assert!(limiter.allow("read token"));
+}
+
+#[test]
+fn starts_over_in_a_new_window() {
+    let limiter = RateLimiter::new(1, Duration::ZERO);
+
+    assert!(limiter.allow("chat 1"));
+    assert!(limiter.allow("chat 1"));
+}
@@ -0,0 +1,54 @@
+use chrono::NaiveDate;
+use pumpfun_monitor::{
+    service::JITO_TIP,
+    utils::{FeeLedger, TxCosts},
+};
+use solana_sdk::{pubkey::Pubkey, system_instruction};
+
+#[test]
+fn costs_count_only_transfers_to_tip_accounts() {
+    let payer = Pubkey::new_unique();
+    let tip_account = Pubkey::from_str_const(JITO_TIP[0]);
+    let ixs = vec![
+        system_instruction::transfer(&payer, &tip_account, 100_000),
+        system_instruction::transfer(&payer, &Pubkey::new_unique(), 1_000_000),
+    ];
+
+    let costs = TxCosts::of(&ixs, 2, 200_000, 50_000);
+
+    assert_eq!(costs.tip, 100_000);
+    assert_eq!(costs.priority_fee, 10_000);
+    assert_eq!(costs.base_fee, 10_000);
+    assert!(costs.fee_payer_pays);
+}
+
+#[test]
+fn ledger_breaks_costs_down_per_day_and_sender() {
+    let ledger = FeeLedger::new();
+    let monday = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+    let tuesday = monday.succ_opt().unwrap();
+    let costs = TxCosts {
+        tip: 100_000,
+        priority_fee: 10_000,
+        base_fee: 5_000,
+        fee_payer_pays: false,
+    };
+
+    ledger.record_on(monday, "JITO", costs, false);
+    ledger.record_on(tuesday, "JITO", costs, false);
+    ledger.record_on(tuesday, "NOZOMI", costs, true);
+
+    assert_eq!(ledger.days().len(), 3);
+
+    let senders = ledger.senders();
+    assert_eq!(senders[0].0, "JITO");
+    assert_eq!(senders[0].1.tips, 200_000);
+    assert_eq!(senders[0].1.transactions, 2);
+
+    // The failed transaction's tip was rolled back, its fees weren't
+    assert_eq!(senders[1].1.tips, 0);
+    assert_eq!(senders[1].1.total(), 15_000);
+
+    assert_eq!(ledger.total(), 245_000);
+    assert_eq!(ledger.fee_payer_paid(), 0);
+}
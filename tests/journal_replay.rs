@@ -0,0 +1,28 @@
+use pumpfun_monitor::datasource::load_replay_signatures;
+use solana_sdk::signature::Signature;
+use std::fs;
+
+#[test]
+fn replay_reads_journal_detections_and_bare_signatures_once() {
+    let first = Signature::from([1; 64]);
+    let second = Signature::from([2; 64]);
+    let third = Signature::from([3; 64]);
+
+    let path = std::env::temp_dir().join(format!("journal-replay-{}.jsonl", std::process::id()));
+    fs::write(
+        &path,
+        format!(
+            "{{\"event\":\"detection\",\"signature\":\"{first}\",\"venue\":\"pumpfun\"}}\n\
+             {{\"event\":\"skip\",\"signature\":\"{second}\",\"message\":\"lagging\"}}\n\
+             {third}\n\
+             {{\"event\":\"detection\",\"signature\":\"{first}\"}}\n\
+             not a signature\n"
+        ),
+    )
+    .unwrap();
+
+    let signatures = load_replay_signatures(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(signatures, vec![first, third]);
+}
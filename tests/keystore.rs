@@ -0,0 +1,19 @@
+use pumpfun_monitor::config::{decrypt_keystore, encrypt_keystore, keystore_pubkey};
+use solana_sdk::signer::{Signer, keypair::Keypair};
+
+#[test]
+fn keystore_decrypts_only_with_its_passphrase() {
+    let keypair = Keypair::new();
+    let keystore = encrypt_keystore(&keypair, "correct horse");
+
+    assert!(!keystore.contains(&keypair.to_base58_string()));
+    assert_eq!(keystore_pubkey(&keystore), Ok(keypair.pubkey()));
+
+    let decrypted = decrypt_keystore(&keystore, "correct horse").unwrap();
+    assert_eq!(decrypted.to_bytes(), keypair.to_bytes());
+
+    assert_eq!(
+        decrypt_keystore(&keystore, "wrong horse").err().as_deref(),
+        Some("wrong passphrase")
+    );
+}
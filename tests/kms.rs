@@ -0,0 +1,66 @@
+use pumpfun_monitor::wallet::{
+    AwsCredentials, KmsKey, aws_sigv4_authorization, ed25519_spki_pubkey,
+};
+use solana_sdk::pubkey::Pubkey;
+
+#[test]
+fn sigv4_matches_the_aws_test_suite() {
+    // get-vanilla of the AWS Signature Version 4 test suite
+    let credentials = AwsCredentials {
+        access_key_id: "AKIDEXAMPLE".to_string(),
+        secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+        session_token: None,
+    };
+
+    let authorization = aws_sigv4_authorization(
+        &credentials,
+        "us-east-1",
+        "service",
+        "GET",
+        &[
+            ("x-amz-date", "20150830T123600Z"),
+            ("host", "example.amazonaws.com"),
+        ],
+        b"",
+    );
+
+    assert_eq!(
+        authorization,
+        "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+         SignedHeaders=host;x-amz-date, \
+         Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+    );
+}
+
+#[test]
+fn kms_entries_parse() {
+    assert_eq!(
+        KmsKey::from_entry("aws-kms:arn:aws:kms:eu-west-1:111122223333:key/1234abcd").unwrap(),
+        KmsKey::Aws {
+            region: "eu-west-1".to_string(),
+            key_id: "arn:aws:kms:eu-west-1:111122223333:key/1234abcd".to_string(),
+        }
+    );
+    assert!(KmsKey::from_entry("aws-kms:1234abcd").is_err());
+
+    let name = "projects/p/locations/global/keyRings/r/cryptoKeys/k/cryptoKeyVersions/1";
+    assert_eq!(
+        KmsKey::from_entry(&format!("gcp-kms:{}", name)).unwrap(),
+        KmsKey::Gcp {
+            name: name.to_string()
+        }
+    );
+    assert!(KmsKey::from_entry("gcp-kms:projects/p/cryptoKeys/k").is_err());
+}
+
+#[test]
+fn ed25519_keys_are_read_from_spki() {
+    let pubkey = Pubkey::new_unique();
+    let mut der = vec![
+        0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+    ];
+    der.extend_from_slice(pubkey.as_ref());
+
+    assert_eq!(ed25519_spki_pubkey(&der), Some(pubkey));
+    assert_eq!(ed25519_spki_pubkey(&der[1..]), None);
+}
@@ -0,0 +1,53 @@
+use pumpfun_monitor::wallet::{hid_packets, parse_derivation_path, sign_apdus};
+
+#[test]
+fn derivation_paths_parse_hardened_or_not() {
+    assert_eq!(
+        parse_derivation_path("44'/501'/0'/0'").unwrap(),
+        vec![44, 501, 0, 0]
+    );
+    assert_eq!(
+        parse_derivation_path("m/44/501/2").unwrap(),
+        vec![44, 501, 2]
+    );
+    assert!(parse_derivation_path("44'/abc").is_err());
+    assert!(parse_derivation_path("44'/501'/0'/0'/0'").is_err());
+}
+
+#[test]
+fn long_messages_are_signed_in_chunks() {
+    let path = parse_derivation_path("44'/501'/0'/0'").unwrap();
+    let message = vec![7; 600];
+
+    let apdus = sign_apdus(&path, &message);
+
+    // Signer count, path length and four indexes lead the first chunk
+    assert_eq!(apdus.len(), 3);
+    assert_eq!(apdus[0].data.len(), 255);
+    assert_eq!(&apdus[0].data[..6], &[1, 4, 0x80, 0, 0, 44]);
+    assert_eq!(apdus[0].p2, 0x02);
+    assert_eq!(apdus[1].p2, 0x03);
+    assert_eq!(apdus[2].p2, 0x01);
+
+    let signed: usize = apdus.iter().map(|apdu| apdu.data.len()).sum();
+    assert_eq!(signed, 18 + message.len());
+
+    let short = sign_apdus(&path, &[7; 10]);
+    assert_eq!(short.len(), 1);
+    assert_eq!(short[0].p2, 0);
+}
+
+#[test]
+fn apdus_are_split_into_sequenced_hid_packets() {
+    let path = parse_derivation_path("44'/501'/0'/0'").unwrap();
+    let apdu = sign_apdus(&path, &[7; 100]).remove(0);
+
+    let packets = hid_packets(&apdu);
+
+    // 2 length bytes, 5 header bytes and 118 data bytes over 59-byte packet payloads
+    assert_eq!(packets.len(), 3);
+    assert_eq!(&packets[0][..5], &[0x01, 0x01, 0x05, 0, 0]);
+    assert_eq!(&packets[2][..5], &[0x01, 0x01, 0x05, 0, 2]);
+    assert_eq!(u16::from_be_bytes([packets[0][5], packets[0][6]]), 123);
+    assert_eq!(&packets[0][7..12], &[0xe0, 0x06, 0x01, 0x00, 118]);
+}
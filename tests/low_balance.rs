@@ -0,0 +1,24 @@
+use pumpfun_monitor::wallet::{BalanceLevel, LowBalanceMonitor};
+
+#[test]
+fn halts_buys_under_the_critical_balance_until_funded() {
+    let monitor = LowBalanceMonitor::new(Some(500_000_000), Some(100_000_000));
+
+    assert_eq!(monitor.observe(1_000_000_000), BalanceLevel::Funded);
+    assert_eq!(monitor.observe(300_000_000), BalanceLevel::Low);
+    assert!(!monitor.is_halted());
+
+    assert_eq!(monitor.observe(50_000_000), BalanceLevel::Critical);
+    assert!(monitor.is_halted());
+
+    assert_eq!(monitor.observe(200_000_000), BalanceLevel::Low);
+    assert!(!monitor.is_halted());
+}
+
+#[test]
+fn unset_thresholds_never_alert_or_halt() {
+    let monitor = LowBalanceMonitor::new(None, None);
+
+    assert_eq!(monitor.observe(0), BalanceLevel::Funded);
+    assert!(!monitor.is_halted());
+}
@@ -0,0 +1,74 @@
+use pumpfun_monitor::utils::{ManualConfirmations, ManualTrade};
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+use std::time::Duration;
+
+/// The code a held trade's error asks for
+fn asked_code(error: &str) -> String {
+    error
+        .split_whitespace()
+        .find(|word| word.len() == 6 && word.chars().all(|c| c.is_ascii_digit()))
+        .expect("no confirmation code asked for")
+        .to_string()
+}
+
+#[test]
+fn large_trades_need_their_own_code_once() {
+    let confirmations = ManualConfirmations::new(Some(LAMPORTS_PER_SOL), Duration::from_secs(60));
+    let sell = ManualTrade::Sell {
+        mint: Pubkey::new_unique(),
+    };
+    let other = ManualTrade::Sell {
+        mint: Pubkey::new_unique(),
+    };
+
+    assert_eq!(confirmations.check(sell, LAMPORTS_PER_SOL, None), Ok(()));
+
+    let code = asked_code(
+        &confirmations
+            .check(sell, 2 * LAMPORTS_PER_SOL, None)
+            .unwrap_err(),
+    );
+
+    assert!(
+        confirmations
+            .check(other, 2 * LAMPORTS_PER_SOL, Some(&code))
+            .is_err()
+    );
+
+    let code = asked_code(
+        &confirmations
+            .check(sell, 2 * LAMPORTS_PER_SOL, None)
+            .unwrap_err(),
+    );
+
+    assert_eq!(
+        confirmations.check(sell, 2 * LAMPORTS_PER_SOL, Some(&code)),
+        Ok(())
+    );
+    assert!(
+        confirmations
+            .check(sell, 2 * LAMPORTS_PER_SOL, Some(&code))
+            .is_err()
+    );
+}
+
+#[test]
+fn codes_expire() {
+    let confirmations = ManualConfirmations::new(Some(0), Duration::ZERO);
+    let buy = ManualTrade::Buy {
+        mint: Pubkey::new_unique(),
+        sol_amount: LAMPORTS_PER_SOL,
+    };
+
+    let code = asked_code(
+        &confirmations
+            .check(buy, LAMPORTS_PER_SOL, None)
+            .unwrap_err(),
+    );
+
+    assert!(
+        confirmations
+            .check(buy, LAMPORTS_PER_SOL, Some(&code))
+            .is_err()
+    );
+}
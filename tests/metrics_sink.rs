@@ -0,0 +1,42 @@
+use carbon_core::metrics::Metrics;
+use pumpfun_monitor::metrics::{StatsdMetrics, influx_line, statsd_line};
+use std::{net::UdpSocket, time::Duration};
+
+#[test]
+fn statsd_lines_escape_reserved_characters() {
+    assert_eq!(
+        statsd_line("bot", "hot_path_send_ms", "1.5", "ms"),
+        "bot.hot_path_send_ms:1.5|ms"
+    );
+    assert_eq!(statsd_line("", "a:b|c@d e", "2", "c"), "a_b_c_d_e:2|c");
+}
+
+#[test]
+fn influx_lines_escape_the_measurement() {
+    assert_eq!(
+        influx_line("sends, total", "counter", "3i", 42),
+        "sends\\,\\ total,kind=counter value=3i 42"
+    );
+}
+
+#[tokio::test]
+async fn statsd_flush_sends_buffered_lines() {
+    let daemon = UdpSocket::bind("127.0.0.1:0").unwrap();
+    daemon
+        .set_read_timeout(Some(Duration::from_secs(2)))
+        .unwrap();
+
+    let statsd = StatsdMetrics::new(&daemon.local_addr().unwrap().to_string(), "bot").unwrap();
+
+    statsd.increment_counter("sends", 2).await.unwrap();
+    statsd.update_gauge("wallets", 3.0).await.unwrap();
+    statsd.flush().await.unwrap();
+
+    let mut packet = [0u8; 1500];
+    let read = daemon.recv(&mut packet).unwrap();
+
+    assert_eq!(
+        std::str::from_utf8(&packet[..read]).unwrap(),
+        "bot.sends:2|c\nbot.wallets:3|g"
+    );
+}
@@ -0,0 +1,36 @@
+use pumpfun_monitor::utils::{exit_message, fill_message, webhook_signature};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+#[test]
+fn fill_message_shows_price_and_link() {
+    let mint = Pubkey::new_unique();
+    let signature = Signature::default();
+
+    let message = fill_message(&mint, Some(500_000_000), Some(1_000_000.0), &signature);
+
+    assert!(message.contains(&format!("Mint: {}", mint)));
+    assert!(message.contains("Spent: 0.5000 SOL"));
+    assert!(message.contains("Price: 0.0000005000 SOL"));
+    assert!(message.ends_with(&format!("https://solscan.io/tx/{}", signature)));
+}
+
+#[test]
+fn exit_message_signs_pnl_and_tolerates_missing_amounts() {
+    let mint = Pubkey::new_unique();
+    let signature = Signature::default();
+
+    let message = exit_message(&mint, None, None, Some(-250_000_000), &signature);
+
+    assert!(message.contains("Received: unknown"));
+    assert!(message.contains("Price: unknown"));
+    assert!(message.contains("PnL: -0.2500 SOL"));
+}
+
+#[test]
+fn webhook_signature_is_hex_hmac_sha256() {
+    // RFC 4231 test case 2
+    assert_eq!(
+        webhook_signature("Jefe", b"what do ya want for nothing?"),
+        "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+}
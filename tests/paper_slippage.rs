@@ -0,0 +1,12 @@
+use pumpfun_monitor::wallet::paper_slippage;
+use std::time::Duration;
+
+#[test]
+fn paper_slippage_grows_with_latency_and_is_capped() {
+    let fast = paper_slippage(Duration::from_millis(100));
+    let slow = paper_slippage(Duration::from_millis(1_000));
+
+    assert!(fast > 0.0);
+    assert!(slow > fast);
+    assert_eq!(paper_slippage(Duration::from_secs(3_600)), 0.5);
+}
@@ -0,0 +1,15 @@
+use pumpfun_monitor::wallet::{TRANSFER_FEE, sweep_amount};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+#[test]
+fn sweep_keeps_the_floor_and_skips_small_profits() {
+    let floor = LAMPORTS_PER_SOL;
+    let min = LAMPORTS_PER_SOL / 10;
+
+    assert_eq!(sweep_amount(floor / 2, floor, min), None);
+    assert_eq!(sweep_amount(floor + min / 2, floor, min), None);
+    assert_eq!(
+        sweep_amount(3 * floor, floor, min),
+        Some(2 * floor - TRANSFER_FEE)
+    );
+}
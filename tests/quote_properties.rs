@@ -0,0 +1,241 @@
+#![allow(deprecated)]
+
+use proptest::prelude::*;
+use pumpfun_monitor::utils::{
+    PumpfunFees, bonding_curve_sol_quote, bonding_curve_token_quote, pumpfun_buy_cost,
+    pumpfun_sell_output, sol_token_quote, token_sol_quote,
+};
+
+/// Reference model of the pumpfun bonding curve, following the program's `buy` and
+/// `sell` handlers: trades are sized in tokens, the buy price is rounded up by one
+/// lamport, the sell price is rounded down, and each fee is rounded up.
+#[derive(Debug, Clone, Copy)]
+struct ReferenceCurve {
+    virtual_sol_reserves: u128,
+    virtual_token_reserves: u128,
+}
+
+/// What the program emits in its `TradeEvent`: the traded amounts and the reserves
+/// after the trade.
+#[derive(Debug, Clone, Copy)]
+struct ReferenceTradeEvent {
+    sol_amount: u64,
+    token_amount: u64,
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+}
+
+impl ReferenceCurve {
+    fn new(virtual_sol_reserves: u64, virtual_token_reserves: u64) -> Self {
+        Self {
+            virtual_sol_reserves: virtual_sol_reserves as u128,
+            virtual_token_reserves: virtual_token_reserves as u128,
+        }
+    }
+
+    fn buy_price(&self, token_amount: u64) -> u64 {
+        let token_amount = token_amount as u128;
+
+        (self.virtual_sol_reserves * token_amount / (self.virtual_token_reserves - token_amount)
+            + 1) as u64
+    }
+
+    fn sell_price(&self, token_amount: u64) -> u64 {
+        let token_amount = token_amount as u128;
+
+        (self.virtual_sol_reserves * token_amount / (self.virtual_token_reserves + token_amount))
+            as u64
+    }
+
+    fn fee(sol_amount: u64, basis_points: u64) -> u64 {
+        (sol_amount as u128 * basis_points as u128).div_ceil(10_000) as u64
+    }
+
+    fn buy(&mut self, token_amount: u64) -> ReferenceTradeEvent {
+        let sol_amount = self.buy_price(token_amount);
+
+        self.virtual_sol_reserves += sol_amount as u128;
+        self.virtual_token_reserves -= token_amount as u128;
+
+        self.event(sol_amount, token_amount)
+    }
+
+    fn sell(&mut self, token_amount: u64) -> ReferenceTradeEvent {
+        let sol_amount = self.sell_price(token_amount);
+
+        self.virtual_sol_reserves -= sol_amount as u128;
+        self.virtual_token_reserves += token_amount as u128;
+
+        self.event(sol_amount, token_amount)
+    }
+
+    fn event(&self, sol_amount: u64, token_amount: u64) -> ReferenceTradeEvent {
+        ReferenceTradeEvent {
+            sol_amount,
+            token_amount,
+            virtual_sol_reserves: self.virtual_sol_reserves as u64,
+            virtual_token_reserves: self.virtual_token_reserves as u64,
+        }
+    }
+}
+
+/// Reserves from a fresh curve (30 SOL / 1.073B tokens) up to close to completion.
+fn reserves() -> impl Strategy<Value = (u64, u64)> {
+    (
+        30_000_000_000u64..=120_000_000_000,
+        250_000_000_000_000u64..=1_073_000_000_000_000,
+    )
+}
+
+fn fees() -> impl Strategy<Value = PumpfunFees> {
+    (0u64..=500, 0u64..=100).prop_map(|(fee_basis_points, creator_fee_basis_points)| PumpfunFees {
+        fee_basis_points,
+        creator_fee_basis_points,
+    })
+}
+
+proptest! {
+    #[test]
+    fn sol_quote_matches_the_program(
+        (virtual_sol_reserves, virtual_token_reserves) in reserves(),
+        share in 1u64..=9_000,
+    ) {
+        let curve = ReferenceCurve::new(virtual_sol_reserves, virtual_token_reserves);
+        let token_amount = (virtual_token_reserves as u128 * share as u128 / 10_000) as u64;
+
+        prop_assert_eq!(
+            token_sol_quote(token_amount, virtual_sol_reserves, virtual_token_reserves, true),
+            curve.buy_price(token_amount)
+        );
+        prop_assert_eq!(
+            token_sol_quote(token_amount, virtual_sol_reserves, virtual_token_reserves, false),
+            curve.sell_price(token_amount)
+        );
+    }
+
+    #[test]
+    fn buy_token_quote_is_the_most_the_sol_affords(
+        (virtual_sol_reserves, virtual_token_reserves) in reserves(),
+        sol_amount in 1_000u64..=50_000_000_000,
+    ) {
+        let curve = ReferenceCurve::new(virtual_sol_reserves, virtual_token_reserves);
+        let token_amount =
+            sol_token_quote(sol_amount, virtual_sol_reserves, virtual_token_reserves, true);
+
+        // The program adds a lamport to every buy price, so the quote may cost one more
+        prop_assert!(curve.buy_price(token_amount) <= sol_amount + 1);
+        prop_assert!(curve.buy_price(token_amount + 1) > sol_amount);
+    }
+
+    #[test]
+    fn sell_token_quote_is_the_fewest_that_return_the_sol(
+        (virtual_sol_reserves, virtual_token_reserves) in reserves(),
+        share in 1u64..=9_000,
+    ) {
+        let curve = ReferenceCurve::new(virtual_sol_reserves, virtual_token_reserves);
+        let sol_amount = (virtual_sol_reserves as u128 * share as u128 / 10_000) as u64;
+        let token_amount =
+            sol_token_quote(sol_amount, virtual_sol_reserves, virtual_token_reserves, false);
+
+        prop_assert!(curve.sell_price(token_amount) >= sol_amount);
+        prop_assert!(curve.sell_price(token_amount - 1) < sol_amount);
+    }
+
+    #[test]
+    fn quotes_with_fees_match_the_program(
+        (virtual_sol_reserves, virtual_token_reserves) in reserves(),
+        share in 1u64..=9_000,
+        fees in fees(),
+    ) {
+        let curve = ReferenceCurve::new(virtual_sol_reserves, virtual_token_reserves);
+        let token_amount = (virtual_token_reserves as u128 * share as u128 / 10_000) as u64;
+
+        let buy_price = curve.buy_price(token_amount);
+        let sell_price = curve.sell_price(token_amount);
+
+        prop_assert_eq!(
+            pumpfun_buy_cost(token_amount, virtual_sol_reserves, virtual_token_reserves, &fees),
+            buy_price
+                + ReferenceCurve::fee(buy_price, fees.fee_basis_points)
+                + ReferenceCurve::fee(buy_price, fees.creator_fee_basis_points)
+        );
+        prop_assert_eq!(
+            pumpfun_sell_output(token_amount, virtual_sol_reserves, virtual_token_reserves, &fees),
+            sell_price
+                - ReferenceCurve::fee(sell_price, fees.fee_basis_points)
+                - ReferenceCurve::fee(sell_price, fees.creator_fee_basis_points)
+        );
+    }
+
+    /// The processors quote from the post-trade reserves of the target's `TradeEvent`,
+    /// so a quote against the reserves before an event must reproduce its amounts.
+    #[test]
+    fn quotes_reproduce_trade_events(
+        (virtual_sol_reserves, virtual_token_reserves) in reserves(),
+        trades in prop::collection::vec((any::<bool>(), 1u64..=500), 1..20),
+    ) {
+        let mut curve = ReferenceCurve::new(virtual_sol_reserves, virtual_token_reserves);
+        let mut bought_tokens = 0u64;
+
+        for (is_buy, share) in trades {
+            let before = curve;
+            let token_amount = (curve.virtual_token_reserves * share as u128 / 10_000) as u64;
+
+            // Only sell back tokens that were bought, like a real curve
+            let event = if is_buy || token_amount > bought_tokens {
+                bought_tokens += token_amount;
+                curve.buy(token_amount)
+            } else {
+                bought_tokens -= token_amount;
+                curve.sell(token_amount)
+            };
+
+            let pre_sol_reserves = before.virtual_sol_reserves as u64;
+            let pre_token_reserves = before.virtual_token_reserves as u64;
+            let is_buy = event.virtual_sol_reserves > pre_sol_reserves;
+
+            let sol_amount =
+                bonding_curve_sol_quote(event.token_amount, pre_sol_reserves, pre_token_reserves, is_buy);
+            prop_assert_eq!(sol_amount, event.sol_amount);
+
+            // Applying the quote to the reserves lands on the event's post-trade reserves
+            let (post_sol_reserves, post_token_reserves) = if is_buy {
+                (pre_sol_reserves + sol_amount, pre_token_reserves - event.token_amount)
+            } else {
+                (pre_sol_reserves - sol_amount, pre_token_reserves + event.token_amount)
+            };
+            prop_assert_eq!(post_sol_reserves, event.virtual_sol_reserves);
+            prop_assert_eq!(post_token_reserves, event.virtual_token_reserves);
+
+            if is_buy {
+                // The quoted tokens for the event's SOL are at least what the target got
+                prop_assert!(
+                    bonding_curve_token_quote(event.sol_amount, pre_sol_reserves, pre_token_reserves, true)
+                        >= event.token_amount
+                );
+            }
+        }
+    }
+
+    /// Neither direction lets a trade take value out of the curve.
+    #[test]
+    fn trades_never_shrink_the_curve_product(
+        (virtual_sol_reserves, virtual_token_reserves) in reserves(),
+        share in 1u64..=9_000,
+    ) {
+        let mut curve = ReferenceCurve::new(virtual_sol_reserves, virtual_token_reserves);
+        let product = curve.virtual_sol_reserves * curve.virtual_token_reserves;
+        let token_amount = (virtual_token_reserves as u128 * share as u128 / 10_000) as u64;
+
+        let event = curve.buy(token_amount);
+        prop_assert_eq!(
+            event.sol_amount,
+            bonding_curve_sol_quote(token_amount, virtual_sol_reserves, virtual_token_reserves, true)
+        );
+        prop_assert!(curve.virtual_sol_reserves * curve.virtual_token_reserves >= product);
+
+        let product = curve.virtual_sol_reserves * curve.virtual_token_reserves;
+        curve.sell(token_amount);
+        prop_assert!(curve.virtual_sol_reserves * curve.virtual_token_reserves >= product);
+    }
+}
@@ -0,0 +1,53 @@
+use axum::{Json, Router, routing::get, routing::post};
+use pumpfun_monitor::wallet::RemoteSigner;
+use serde_json::{Value, json};
+use solana_sdk::signer::{Signer, keypair::Keypair};
+use std::{sync::Arc, thread};
+
+/// Serves the HTTP remote signer protocol for `keypair` on a loopback port.
+fn serve(keypair: Arc<Keypair>) -> String {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let listener = runtime
+        .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+        .unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let pubkey = keypair.pubkey();
+    let app = Router::new()
+        .route(
+            "/pubkey",
+            get(move || async move { Json(json!({ "pubkey": pubkey.to_string() })) }),
+        )
+        .route(
+            "/sign",
+            post(move |Json(body): Json<Value>| async move {
+                let message = base64::decode(body["message"].as_str().unwrap()).unwrap();
+                let signature = keypair.sign_message(&message);
+
+                Json(json!({ "signature": signature.to_string() }))
+            }),
+        );
+
+    thread::spawn(move || runtime.block_on(async { axum::serve(listener, app).await.unwrap() }));
+
+    format!("http://{}", addr)
+}
+
+#[test]
+fn remote_signer_signs_over_http() {
+    let keypair = Arc::new(Keypair::new());
+    let url = serve(keypair.clone());
+
+    let remote = RemoteSigner::connect(&url).unwrap();
+    assert_eq!(remote.pubkey(), keypair.pubkey());
+
+    let message = b"transaction message";
+    let signature = remote.sign(message).unwrap();
+
+    assert!(signature.verify(keypair.pubkey().as_ref(), message));
+}
+
+#[test]
+fn unsupported_remote_signer_urls_are_refused() {
+    assert!(RemoteSigner::connect("ftp://signer.internal").is_err());
+}
@@ -0,0 +1,63 @@
+use axum::{Json, Router, http::HeaderMap, routing::get};
+use pumpfun_monitor::config::{SecretsSource, fetch_vault_secrets};
+use serde_json::json;
+
+#[test]
+fn secrets_sources_parse() {
+    assert_eq!(
+        SecretsSource::from_entry("vault:/secret/data/pumpfun/"),
+        Ok(SecretsSource::Vault {
+            path: "secret/data/pumpfun".to_string()
+        })
+    );
+
+    let arn = "arn:aws:secretsmanager:eu-west-1:123456789012:secret:pumpfun-AbCdEf";
+    assert_eq!(
+        SecretsSource::from_entry(&format!("aws-secrets:{}", arn)),
+        Ok(SecretsSource::Aws {
+            region: "eu-west-1".to_string(),
+            secret_id: arn.to_string()
+        })
+    );
+
+    assert!(SecretsSource::from_entry("vault:").is_err());
+    assert!(SecretsSource::from_entry("aws-secrets:pumpfun").is_err());
+    assert!(SecretsSource::from_entry("file:.env").is_err());
+}
+
+#[tokio::test]
+async fn vault_kv2_secret_is_read_with_the_token() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let app = Router::new().route(
+        "/v1/secret/data/pumpfun",
+        get(|headers: HeaderMap| async move {
+            assert_eq!(headers["x-vault-token"], "s.token");
+
+            Json(json!({
+                "lease_id": "",
+                "renewable": false,
+                "data": {
+                    "data": { "PRIVATE_KEY": "base58key", "CU": 120000 },
+                    "metadata": { "version": 3 }
+                }
+            }))
+        }),
+    );
+
+    tokio::spawn(async { axum::serve(listener, app).await.unwrap() });
+
+    let (secrets, lease_id) = fetch_vault_secrets(
+        &format!("http://{}", addr),
+        "s.token",
+        "secret/data/pumpfun",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(secrets.len(), 2);
+    assert_eq!(secrets["PRIVATE_KEY"], "base58key");
+    assert_eq!(secrets["CU"], "120000");
+    assert_eq!(lease_id, None);
+}
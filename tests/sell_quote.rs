@@ -0,0 +1,105 @@
+use pumpfun_monitor::utils::{
+    PumpfunFees, bonding_curve_sol_quote, pumpfun_min_sol_output, pumpfun_sell_output,
+};
+
+const VIRTUAL_SOL_RESERVES: u64 = 30_000_000_000;
+const VIRTUAL_TOKEN_RESERVES: u64 = 1_073_000_000_000_000;
+
+const FEES: PumpfunFees = PumpfunFees {
+    fee_basis_points: 95,
+    creator_fee_basis_points: 5,
+};
+
+#[test]
+fn min_sol_output_is_the_quote_after_fees_less_slippage() {
+    let token_amount = 10_000_000_000_000;
+
+    // 277_008_310 lamports out of the curve, less 2_631_579 protocol and 138_505 creator fees
+    assert_eq!(
+        bonding_curve_sol_quote(
+            token_amount,
+            VIRTUAL_SOL_RESERVES,
+            VIRTUAL_TOKEN_RESERVES,
+            false
+        ),
+        277_008_310
+    );
+    assert_eq!(
+        pumpfun_sell_output(
+            token_amount,
+            VIRTUAL_SOL_RESERVES,
+            VIRTUAL_TOKEN_RESERVES,
+            &FEES
+        ),
+        274_238_226
+    );
+    assert_eq!(
+        pumpfun_min_sol_output(
+            token_amount,
+            VIRTUAL_SOL_RESERVES,
+            VIRTUAL_TOKEN_RESERVES,
+            &FEES,
+            0.1
+        ),
+        246_814_403
+    );
+}
+
+#[test]
+fn min_sol_output_follows_the_position_size() {
+    let small = pumpfun_min_sol_output(
+        1_000_000_000_000,
+        VIRTUAL_SOL_RESERVES,
+        VIRTUAL_TOKEN_RESERVES,
+        &FEES,
+        0.1,
+    );
+    let large = pumpfun_min_sol_output(
+        50_000_000_000_000,
+        VIRTUAL_SOL_RESERVES,
+        VIRTUAL_TOKEN_RESERVES,
+        &FEES,
+        0.1,
+    );
+
+    assert!(small > 0);
+    assert!(large > small * 40, "{} vs {}", large, small);
+}
+
+#[test]
+fn min_sol_output_never_exceeds_what_the_sell_pays() {
+    for token_amount in [
+        1,
+        1_000,
+        1_000_000_000,
+        10_000_000_000_000,
+        500_000_000_000_000,
+    ] {
+        let paid = pumpfun_sell_output(
+            token_amount,
+            VIRTUAL_SOL_RESERVES,
+            VIRTUAL_TOKEN_RESERVES,
+            &FEES,
+        );
+
+        for slippage in [0.0, 0.01, 0.25, 1.0] {
+            assert!(
+                pumpfun_min_sol_output(
+                    token_amount,
+                    VIRTUAL_SOL_RESERVES,
+                    VIRTUAL_TOKEN_RESERVES,
+                    &FEES,
+                    slippage
+                ) <= paid
+            );
+        }
+    }
+}
+
+#[test]
+fn min_sol_output_is_zero_for_an_empty_position() {
+    assert_eq!(
+        pumpfun_min_sol_output(0, VIRTUAL_SOL_RESERVES, VIRTUAL_TOKEN_RESERVES, &FEES, 0.1),
+        0
+    );
+}
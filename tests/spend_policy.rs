@@ -0,0 +1,48 @@
+use pumpfun_monitor::utils::{SpendLimits, SpendPolicy, SpendViolation};
+
+#[test]
+fn spends_over_a_limit_are_refused() {
+    let policy = SpendPolicy::new(SpendLimits {
+        per_trade: Some(500),
+        per_hour: Some(1_000),
+        per_day: None,
+    });
+
+    assert_eq!(
+        policy.authorize(600),
+        Err(SpendViolation::PerTrade {
+            lamports: 600,
+            limit: 500
+        })
+    );
+
+    assert!(policy.authorize(400).is_ok());
+    assert!(policy.authorize(400).is_ok());
+    assert_eq!(
+        policy.authorize(400),
+        Err(SpendViolation::PerHour {
+            spent: 800,
+            lamports: 400,
+            limit: 1_000
+        })
+    );
+    assert_eq!(policy.spent(), (800, 800));
+
+    // A buy that was never sent gives its spend back
+    policy.refund(400);
+    assert!(policy.authorize(400).is_ok());
+}
+
+#[test]
+fn the_daily_limit_counts_every_spend() {
+    let policy = SpendPolicy::new(SpendLimits {
+        per_day: Some(1_000),
+        ..SpendLimits::default()
+    });
+
+    assert!(policy.authorize(1_000).is_ok());
+    assert!(matches!(
+        policy.authorize(1),
+        Err(SpendViolation::PerDay { spent: 1_000, .. })
+    ));
+}
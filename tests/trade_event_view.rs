@@ -0,0 +1,46 @@
+use borsh::BorshDeserialize;
+use carbon_core::deserialize::CarbonDeserialize;
+use carbon_pumpfun_decoder::instructions::trade_event::TradeEvent;
+use pumpfun_monitor::utils::{TradeEventView, pumpfun_trade_event};
+
+/// An event self-CPI with distinct bytes in every field, so misplaced offsets show.
+fn trade_event_cpi() -> Vec<u8> {
+    let mut body: Vec<u8> = (0..217u32).map(|i| (i * 7 % 251) as u8).collect();
+    body[48] = 1; // is_buy
+
+    [TradeEvent::DISCRIMINATOR, &body].concat()
+}
+
+#[test]
+fn view_reads_the_same_fields_as_borsh() {
+    let data = trade_event_cpi();
+    let expected = TradeEvent::try_from_slice(&data[TradeEvent::DISCRIMINATOR.len()..]).unwrap();
+
+    assert_eq!(pumpfun_trade_event(&data), Some(expected.clone()));
+
+    let view = TradeEventView::from_cpi(&data).unwrap();
+    assert!(view.is_mint(&expected.mint));
+    assert_eq!(view.creator(), Some(expected.creator));
+}
+
+#[test]
+fn legacy_events_without_fee_fields_parse() {
+    let data = trade_event_cpi();
+    let legacy = &data[..TradeEvent::DISCRIMINATOR.len() + 121];
+
+    let event = pumpfun_trade_event(legacy).unwrap();
+    let view = TradeEventView::from_cpi(legacy).unwrap();
+
+    assert_eq!(event.virtual_token_reserves, view.virtual_token_reserves());
+    assert_eq!(event.creator_fee, 0);
+    assert_eq!(view.creator(), None);
+}
+
+#[test]
+fn truncated_or_foreign_data_is_rejected() {
+    let data = trade_event_cpi();
+
+    assert!(pumpfun_trade_event(&data[..TradeEvent::DISCRIMINATOR.len() + 100]).is_none());
+    assert!(pumpfun_trade_event(&data[8..]).is_none());
+    assert!(pumpfun_trade_event(&[]).is_none());
+}
@@ -0,0 +1,66 @@
+use carbon_pumpfun_decoder::PROGRAM_ID as PUMPFUN_PROGRAM_ID;
+use pumpfun_monitor::{
+    service::JITO_TIP,
+    utils::{associated_token_address, check_instructions, max_tip_lamports},
+};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_instruction, sysvar,
+};
+
+#[test]
+fn allows_the_bots_own_programs() {
+    let payer = Pubkey::new_unique();
+    let wsol_account = associated_token_address(&payer, &spl_token::native_mint::ID);
+    let ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(100_000),
+        system_instruction::transfer(&payer, &wsol_account, 1_000),
+        Instruction::new_with_bytes(
+            PUMPFUN_PROGRAM_ID,
+            &[0; 24],
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(sysvar::rent::ID, false),
+            ],
+        ),
+    ];
+
+    assert_eq!(check_instructions(&ixs, &[payer]), Ok(()));
+}
+
+#[test]
+fn refuses_unknown_programs_and_writable_sysvars() {
+    let payer = Pubkey::new_unique();
+
+    let unknown = Instruction::new_with_bytes(
+        Pubkey::new_unique(),
+        &[],
+        vec![AccountMeta::new(payer, true)],
+    );
+    assert!(check_instructions(&[unknown], &[payer]).is_err());
+
+    let writable_sysvar = Instruction::new_with_bytes(
+        PUMPFUN_PROGRAM_ID,
+        &[],
+        vec![AccountMeta::new(sysvar::rent::ID, false)],
+    );
+    assert!(check_instructions(&[writable_sysvar], &[payer]).is_err());
+}
+
+#[test]
+fn caps_tips_at_the_configured_tip() {
+    let payer = Pubkey::new_unique();
+    let tip_account = Pubkey::from_str_const(JITO_TIP[0]);
+    let max_tip = max_tip_lamports();
+
+    let tip = system_instruction::transfer(&payer, &tip_account, max_tip);
+    assert_eq!(check_instructions(&[tip], &[payer]), Ok(()));
+
+    let drain = system_instruction::transfer(&payer, &tip_account, max_tip + 1);
+    assert!(check_instructions(&[drain], &[payer]).is_err());
+
+    let assign = system_instruction::assign(&payer, &PUMPFUN_PROGRAM_ID);
+    assert!(check_instructions(&[assign], &[payer]).is_err());
+}
@@ -0,0 +1,89 @@
+use pumpfun_monitor::utils::{TxTemplateCache, encode_transaction};
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::{VersionedMessage, v0::Message},
+    pubkey::Pubkey,
+    signer::{Signer, keypair::Keypair},
+    transaction::VersionedTransaction,
+};
+
+fn trade_ixs(payer: Pubkey, accounts: &[Pubkey], amount: u64, max_cost: u64) -> Vec<Instruction> {
+    let program_id = Pubkey::new_from_array([9; 32]);
+
+    let mut data = vec![0x66, 0x06, 0x3d, 0x12, 0x01, 0xda, 0xeb, 0xea];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&max_cost.to_le_bytes());
+
+    let mut metas = vec![AccountMeta::new(payer, true)];
+    metas.extend(
+        accounts
+            .iter()
+            .map(|account| AccountMeta::new(*account, false)),
+    );
+
+    vec![
+        Instruction::new_with_bytes(Pubkey::new_from_array([3; 32]), &[2, 0, 1], vec![]),
+        Instruction {
+            program_id,
+            accounts: metas,
+            data,
+        },
+    ]
+}
+
+#[test]
+fn patched_templates_match_a_fresh_compile() {
+    let cache = TxTemplateCache::new();
+    let payer = Pubkey::new_unique();
+    let accounts: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+
+    cache.prewarm(&payer, &trade_ixs(payer, &accounts, 1, 2));
+
+    for (amount, max_cost) in [(1_000_000, 5_000_000), (42, u64::MAX)] {
+        let ixs = trade_ixs(payer, &accounts, amount, max_cost);
+        let blockhash = Hash::new_unique();
+
+        assert_eq!(
+            cache.compile(&payer, &ixs, blockhash),
+            Message::try_compile(&payer, &ixs, &[], blockhash).unwrap()
+        );
+    }
+}
+
+#[test]
+fn different_accounts_get_their_own_template() {
+    let cache = TxTemplateCache::new();
+    let payer = Pubkey::new_unique();
+    let accounts: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+    let other_accounts: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+
+    let blockhash = Hash::new_unique();
+    cache.compile(&payer, &trade_ixs(payer, &accounts, 1, 2), blockhash);
+
+    let ixs = trade_ixs(payer, &other_accounts, 1, 2);
+
+    assert_eq!(
+        cache.compile(&payer, &ixs, blockhash),
+        Message::try_compile(&payer, &ixs, &[], blockhash).unwrap()
+    );
+}
+
+#[test]
+fn pooled_encoding_matches_bincode_and_base64() {
+    let payer = Keypair::new();
+    let accounts: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+
+    // Transactions of different sizes in a row, so the buffers are reused at every size
+    for account_count in [2, 6, 1] {
+        let ixs = trade_ixs(payer.pubkey(), &accounts[..account_count], 7, 8);
+        let message = Message::try_compile(&payer.pubkey(), &ixs, &[], Hash::new_unique()).unwrap();
+        let transaction =
+            VersionedTransaction::try_new(VersionedMessage::V0(message), &[&payer]).unwrap();
+
+        assert_eq!(
+            encode_transaction(&transaction).unwrap(),
+            base64::encode(bincode::serialize(&transaction).unwrap())
+        );
+    }
+}
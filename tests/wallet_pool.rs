@@ -0,0 +1,102 @@
+use pumpfun_monitor::wallet::{WalletPool, WalletSigner};
+use solana_sdk::signer::{Signer, keypair::Keypair};
+
+fn pool(wallets: usize) -> &'static WalletPool {
+    let wallets: Vec<WalletSigner> = (0..wallets)
+        .map(|_| WalletSigner::Keypair(Keypair::new()))
+        .collect();
+
+    Box::leak(Box::new(WalletPool::new(&*Vec::leak(wallets))))
+}
+
+#[test]
+fn buys_go_to_the_wallet_with_the_fewest_transactions_in_flight() {
+    let pool = pool(3);
+    let [first, second, third] = [0, 1, 2].map(|index| pool.wallets()[index].pubkey());
+
+    let _pending = [
+        pool.track_pending(first, 0),
+        pool.track_pending(first, 0),
+        pool.track_pending(second, 0),
+    ];
+
+    for _ in 0..3 {
+        assert_eq!(pool.next_buy_wallet(1).unwrap().pubkey(), third);
+    }
+
+    assert_eq!(pool.pending_transactions(&first), 2);
+}
+
+#[test]
+fn buys_in_flight_count_against_the_balance() {
+    let pool = pool(2);
+    let [first, second] = [0, 1].map(|index| pool.wallets()[index].pubkey());
+
+    pool.set_balance(&first, pool.buy_overhead(&first) + 1_000);
+    pool.set_balance(&second, pool.buy_overhead(&second) + 500);
+
+    let pending = pool.track_pending(first, 800);
+    let _other = pool.track_pending(second, 0);
+
+    // 200 left on the first, which has as many in flight as the second
+    assert_eq!(pool.next_buy_wallet(400).unwrap().pubkey(), second);
+    assert!(pool.next_buy_wallet(600).is_none());
+
+    drop(pending);
+
+    assert_eq!(pool.pending_transactions(&first), 0);
+    assert_eq!(pool.next_buy_wallet(600).unwrap().pubkey(), first);
+}
+
+#[test]
+fn buys_need_their_rent_and_fees_on_top() {
+    let pool = pool(1);
+    let wallet = pool.wallets()[0].pubkey();
+
+    pool.set_balance(&wallet, 1_000);
+    assert!(pool.next_buy_wallet(1_000).is_none());
+
+    pool.set_balance(&wallet, pool.buy_overhead(&wallet) + 1_000);
+    assert_eq!(pool.next_buy_wallet(1_000).unwrap().pubkey(), wallet);
+
+    // A separate fee payer pays the fees, the wallet only the rent
+    let fee_payer = Keypair::new().pubkey();
+    let pool = Box::leak(Box::new(
+        WalletPool::new(pool.wallets()).with_fee_payer(fee_payer),
+    ));
+
+    assert!(pool.buy_overhead(&wallet) < pool.buy_overhead(&fee_payer));
+
+    pool.set_balance(&fee_payer, 0);
+    assert!(pool.next_buy_wallet(1).is_none());
+}
+
+#[test]
+fn retired_wallets_get_no_buys_but_keep_selling_until_removed() {
+    let pool = pool(1);
+    let old = pool.wallets()[0].pubkey();
+    let mint = Keypair::new().pubkey();
+
+    // The only wallet taking buys can't retire
+    assert!(!pool.retire_wallet(&old));
+
+    let new: &'static WalletSigner = Box::leak(Box::new(WalletSigner::Keypair(Keypair::new())));
+    assert!(pool.add_wallet(new));
+    assert!(!pool.add_wallet(new));
+
+    pool.record_buy(&old, &mint, 1_000);
+    assert!(pool.retire_wallet(&old));
+
+    for _ in 0..3 {
+        assert_eq!(pool.next_buy_wallet(1).unwrap().pubkey(), new.pubkey());
+    }
+
+    assert_eq!(pool.holders(&mint)[0].0.pubkey(), old);
+    assert_eq!(pool.wallet_positions(&old), vec![(mint, 1_000)]);
+
+    pool.record_sell(&old, &mint);
+    pool.remove_wallet(&old);
+
+    assert!(pool.wallet(&old).is_none());
+    assert_eq!(pool.wallets().len(), 1);
+}